@@ -0,0 +1,207 @@
+use serde_json::{Map, Value};
+
+use asciidork_ast::prelude::*;
+
+use crate::loc::*;
+
+pub struct Asg<'src> {
+  pub src: &'src [u8],
+}
+
+impl<'src> Asg<'src> {
+  pub fn new(src: &'src str) -> Self {
+    Self { src: src.trim_end().as_bytes() }
+  }
+
+  pub fn gen_document(&self, document: &Document) -> Value {
+    let mut doc = Map::new();
+    doc.set("name", "document");
+    doc.set("type", "block");
+    if let Some(header) = &document.header {
+      self.gen_doc_header(document, header, &mut doc);
+    }
+
+    match &document.content {
+      DocContent::Blocks(blocks) => {
+        if !blocks.is_empty() {
+          doc.set_val("blocks", Value::Array(self.gen_blocks(blocks)));
+        }
+      }
+      DocContent::Sections(Sectioned { preamble, sections }) => {
+        let mut blocks = preamble
+          .as_ref()
+          .map(|preamble| self.gen_blocks(preamble))
+          .unwrap_or_default();
+        blocks.extend(sections.iter().map(|s| self.gen_section(s)));
+        doc.set_val("blocks", Value::Array(blocks));
+      }
+      DocContent::Parts(book) => {
+        let mut blocks = book
+          .preamble
+          .as_ref()
+          .map(|preamble| self.gen_blocks(preamble))
+          .unwrap_or_default();
+        blocks.extend(
+          book
+            .opening_special_sects
+            .iter()
+            .map(|s| self.gen_section(s)),
+        );
+        blocks.extend(book.parts.iter().map(|part| self.gen_part(part)));
+        blocks.extend(
+          book
+            .closing_special_sects
+            .iter()
+            .map(|s| self.gen_section(s)),
+        );
+        doc.set_val("blocks", Value::Array(blocks));
+      }
+    }
+    let end = match self.src.len() as u32 {
+      0 => Loc::new(1, 1),
+      len => self.loc_from_pos(len - 1).incr_column(),
+    };
+    self.push_locspan(Some(LocSpan::new(Loc::new(1, 1), end)), &mut doc);
+    Value::Object(doc)
+  }
+
+  fn gen_doc_header(&self, document: &Document, header: &DocHeader, doc: &mut Map<String, Value>) {
+    let mut attrs = Map::new();
+    for (key, value) in document.meta.header_attrs().iter() {
+      if !key.starts_with("_asciidork_derived_") {
+        attrs.set(key, value.str().unwrap_or(""));
+      }
+    }
+    doc.set_val("attributes", Value::Object(attrs));
+    let mut ast_header = Map::new();
+    if let Some(doc_title) = &header.title {
+      ast_header.set_val("title", Value::Array(self.gen_inlines(&doc_title.main)));
+    }
+    self.push_srcloc(header.loc, &mut ast_header);
+    doc.set_val("header", Value::Object(ast_header));
+  }
+
+  pub fn gen_section(&self, section: &Section) -> Value {
+    let mut map = Map::new();
+    map.set("name", "section");
+    map.set("type", "block");
+    self.push_chunk_meta(&section.meta, Map::new(), &mut map);
+    map.set_val("level", Value::Number(section.level.into()));
+    map.set_val("title", Value::Array(self.gen_inlines(&section.heading)));
+    map.set_val("blocks", Value::Array(self.gen_blocks(&section.blocks)));
+    self.push_multiloc(&section.loc, &mut map);
+    Value::Object(map)
+  }
+
+  // parts are represented as level 0 sections, per asciidoctor
+  fn gen_part(&self, part: &Part) -> Value {
+    let mut map = Map::new();
+    map.set("name", "section");
+    map.set("type", "block");
+    self.push_chunk_meta(&part.title.meta, Map::new(), &mut map);
+    map.set_val("level", Value::Number(0.into()));
+    map.set_val("title", Value::Array(self.gen_inlines(&part.title.text)));
+    let mut blocks = part
+      .intro
+      .as_ref()
+      .map(|intro| self.gen_blocks(intro))
+      .unwrap_or_default();
+    blocks.extend(part.sections.iter().map(|s| self.gen_section(s)));
+    map.set_val("blocks", Value::Array(blocks));
+    let last = part
+      .sections
+      .last()
+      .and_then(|sect| sect.last_loc())
+      .or_else(|| part.title.text.last_loc());
+    if let Some(last) = last {
+      let first = part.title.meta.start_loc;
+      self.push_locspan(self.locspan_from_pair(first, last), &mut map);
+    }
+    Value::Object(map)
+  }
+
+  /// adds the optional `id`, `title`, and `metadata` properties
+  pub fn push_chunk_meta(
+    &self,
+    meta: &ChunkMeta,
+    mut attributes: Map<String, Value>,
+    map: &mut Map<String, Value>,
+  ) {
+    if let Some(id) = meta.attrs.id() {
+      map.set("id", id);
+    }
+    if let Some(title) = meta.title() {
+      map.set_val("title", Value::Array(self.gen_inlines(title)));
+    }
+    let mut roles = Vec::new();
+    let mut options = Vec::new();
+    for attrs in meta.attrs.iter() {
+      self.collect_named(attrs, &mut attributes);
+      roles.extend(attrs.roles.iter().map(|role| Value::from(&**role)));
+      options.extend(attrs.options.iter().map(|opt| Value::from(&**opt)));
+    }
+    if let Some(lang) = meta.attrs.source_language() {
+      attributes.set("language", lang);
+    }
+    if attributes.is_empty() && roles.is_empty() && options.is_empty() {
+      return;
+    }
+    let mut metadata = Map::new();
+    metadata.set_val("attributes", Value::Object(attributes));
+    metadata.set_val("options", Value::Array(options));
+    metadata.set_val("roles", Value::Array(roles));
+    map.set_val("metadata", Value::Object(metadata));
+  }
+
+  pub fn collect_named(&self, attrs: &AttrList, attributes: &mut Map<String, Value>) {
+    for (key, value) in attrs.named.iter() {
+      if &**key != "title" {
+        attributes.set(key, &value.plain_text().join(""));
+      }
+    }
+  }
+
+  pub fn loc_from_pos(&self, pos: u32) -> Loc {
+    Loc::from_pos(pos, self.src)
+  }
+
+  /// `None` for included content, whose offsets are not into `self.src`
+  pub fn locspan_from_pair(&self, first: SourceLocation, last: SourceLocation) -> Option<LocSpan> {
+    if first.include_depth != 0 || last.include_depth != 0 {
+      return None;
+    }
+    Some(LocSpan::from_src_pair(first, last, self.src))
+  }
+
+  pub fn push_multiloc(&self, multiloc: &MultiSourceLocation, map: &mut Map<String, Value>) {
+    if multiloc.start_depth == 0 && multiloc.end_depth == 0 {
+      self.push_locspan(Some(LocSpan::from_multi_loc(multiloc, self.src)), map);
+    }
+  }
+
+  pub fn push_locspan(&self, loc_span: Option<LocSpan>, map: &mut Map<String, Value>) {
+    if let Some(loc_span) = loc_span {
+      map.set_val("location", loc_span.into_value());
+    }
+  }
+
+  pub fn push_srcloc(&self, loc: SourceLocation, map: &mut Map<String, Value>) {
+    if loc.include_depth == 0 {
+      self.push_locspan(Some(LocSpan::from_src_loc(loc, self.src)), map);
+    }
+  }
+}
+
+pub trait MapExt {
+  fn set(&mut self, key: &str, value: &str);
+  fn set_val(&mut self, key: &str, value: Value);
+}
+
+impl MapExt for Map<String, Value> {
+  fn set(&mut self, key: &str, value: &str) {
+    self.insert(key.into(), value.into());
+  }
+  fn set_val(&mut self, key: &str, value: Value) {
+    self.insert(key.into(), value);
+  }
+}
@@ -0,0 +1,381 @@
+use serde_json::{Map, Value};
+
+use asciidork_ast::InlineNodes;
+use asciidork_ast::prelude::*;
+
+use crate::asg::{Asg, MapExt};
+
+impl Asg<'_> {
+  pub fn gen_blocks(&self, blocks: &[Block]) -> Vec<Value> {
+    blocks.iter().filter_map(|b| self.gen_block(b)).collect()
+  }
+
+  fn gen_block(&self, block: &Block) -> Option<Value> {
+    let mut map = Map::new();
+    let mut attributes = Map::new();
+    map.set("type", "block");
+    match &block.content {
+      BlockContent::Simple(nodes) => match block.context {
+        BlockContext::Paragraph => {
+          map.set("name", "paragraph");
+          map.set_val("inlines", Value::Array(self.gen_inlines(nodes)));
+        }
+        BlockContext::Listing
+        | BlockContext::Literal
+        | BlockContext::Passthrough
+        | BlockContext::Stem
+        | BlockContext::Verse => {
+          map.set("name", leaf_name(block.context));
+          self.push_form(block, &mut map);
+          map.set_val("inlines", Value::Array(self.gen_inlines(nodes)));
+        }
+        // eg: `NOTE: foo` or `[quote]` paragraphs, which the ASG
+        // models as parent blocks wrapping a single paragraph
+        _ => {
+          self.push_parent_name(block.context, &mut map);
+          self.push_form(block, &mut map);
+          let paragraph = self.gen_paragraph(nodes);
+          map.set_val("blocks", Value::Array(paragraph.into_iter().collect()));
+        }
+      },
+      BlockContent::Compound(blocks) => {
+        self.push_parent_name(block.context, &mut map);
+        self.push_form(block, &mut map);
+        map.set_val("blocks", Value::Array(self.gen_blocks(blocks)));
+      }
+      BlockContent::QuotedParagraph { quote, attr, cite } => {
+        map.set("name", "quote");
+        map.set("form", "paragraph");
+        let paragraph = self.gen_paragraph(quote);
+        map.set_val("blocks", Value::Array(paragraph.into_iter().collect()));
+        attributes.set("attribution", &attr.plain_text().join(""));
+        if let Some(cite) = cite {
+          attributes.set("citetitle", &cite.plain_text().join(""));
+        }
+      }
+      BlockContent::List {
+        variant: ListVariant::Description,
+        items,
+        ..
+      } => {
+        map.set("name", "dlist");
+        if let Some(item) = items.first() {
+          map.set("marker", &item.marker_src);
+        }
+        let values = items.iter().map(|i| self.gen_dlist_item(i)).collect();
+        map.set_val("items", Value::Array(values));
+        // the block location ends at the last term, so span the items instead
+        if let (Some(first), Some(last)) = (items.first(), items.last()) {
+          let span = self.locspan_from_pair(dlist_item_span(first).0, dlist_item_span(last).1);
+          self.push_chunk_meta(&block.meta, attributes, &mut map);
+          self.push_locspan(span, &mut map);
+          return Some(Value::Object(map));
+        }
+      }
+      BlockContent::List { variant, items, .. } => {
+        map.set("name", "list");
+        map.set("variant", list_variant(*variant));
+        if let Some(item) = items.first() {
+          map.set("marker", &item.marker_src);
+        }
+        let items = items.iter().map(|i| self.gen_list_item(i)).collect();
+        map.set_val("items", Value::Array(items));
+      }
+      BlockContent::Table(table) => {
+        map.set("name", "table");
+        if let Some(row) = &table.header_row {
+          map.set_val("header", self.gen_table_row(row));
+        }
+        let rows = table.rows.iter().map(|r| self.gen_table_row(r)).collect();
+        map.set_val("rows", Value::Array(rows));
+        if let Some(row) = &table.footer_row {
+          map.set_val("footer", self.gen_table_row(row));
+        }
+      }
+      BlockContent::Section(section) => return Some(self.gen_section(section)),
+      BlockContent::Empty(EmptyMetadata::Image { target, attrs, .. }) => {
+        map.set("name", "image");
+        map.set("form", "macro");
+        map.set("target", target);
+        if let Some(alt) = attrs.str_positional_at(0) {
+          attributes.set("alt", alt);
+        }
+        self.collect_named(attrs, &mut attributes);
+      }
+      BlockContent::Empty(EmptyMetadata::AudioVideo { target, attrs }) => {
+        let name = if block.context == BlockContext::Audio { "audio" } else { "video" };
+        map.set("name", name);
+        map.set("form", "macro");
+        map.set("target", target);
+        self.collect_named(attrs, &mut attributes);
+      }
+      BlockContent::Empty(EmptyMetadata::DiscreteHeading { level, content, id }) => {
+        map.set("name", "heading");
+        if let Some(id) = id {
+          map.set("id", id);
+        }
+        map.set_val("title", Value::Array(self.gen_inlines(content)));
+        map.set_val("level", Value::Number((*level).into()));
+      }
+      BlockContent::Empty(EmptyMetadata::None) => match block.context {
+        BlockContext::ThematicBreak => {
+          map.set("name", "break");
+          map.set("variant", "thematic");
+        }
+        BlockContext::PageBreak => {
+          map.set("name", "break");
+          map.set("variant", "page");
+        }
+        BlockContext::TableOfContents => {
+          map.set("name", "toc");
+          map.set("form", "macro");
+        }
+        _ => return None,
+      },
+      BlockContent::Empty(EmptyMetadata::Comment(_)) => return None,
+      BlockContent::DocumentAttribute(..) => return None,
+    };
+    if matches!(
+      block.context,
+      BlockContext::BlockQuote | BlockContext::Verse
+    ) {
+      if let Some(attribution) = block.meta.attrs.str_positional_at(1) {
+        attributes.set("attribution", attribution);
+      }
+      if let Some(cite) = block.meta.attrs.str_positional_at(2) {
+        attributes.set("citetitle", cite);
+      }
+    }
+    // the heading of a discrete heading is its title, not block metadata
+    if block.context != BlockContext::DiscreteHeading {
+      self.push_chunk_meta(&block.meta, attributes, &mut map);
+    }
+    self.push_multiloc(&self.block_loc(block), &mut map);
+    Some(Value::Object(map))
+  }
+
+  // the parsed location of a `NOTE: foo` admonition excludes its label
+  fn block_loc(&self, block: &Block) -> MultiSourceLocation {
+    let mut loc = block.loc.clone();
+    let admonition = matches!(
+      block.context,
+      BlockContext::AdmonitionCaution
+        | BlockContext::AdmonitionImportant
+        | BlockContext::AdmonitionNote
+        | BlockContext::AdmonitionTip
+        | BlockContext::AdmonitionWarning
+    );
+    if admonition && matches!(block.content, BlockContent::Simple(_)) {
+      let before = &self.src[..(loc.start_pos as usize).min(self.src.len())];
+      loc.start_pos = before
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |idx| idx as u32 + 1);
+    }
+    loc
+  }
+
+  fn gen_paragraph(&self, nodes: &InlineNodes) -> Option<Value> {
+    let mut map = Map::new();
+    map.set("name", "paragraph");
+    map.set("type", "block");
+    map.set_val("inlines", Value::Array(self.gen_inlines(nodes)));
+    let first = nodes.first()?.loc;
+    let last = nodes.last_loc()?;
+    self.push_locspan(self.locspan_from_pair(first, last), &mut map);
+    Some(Value::Object(map))
+  }
+
+  fn gen_list_item(&self, item: &ListItem) -> Value {
+    let mut map = Map::new();
+    map.set("name", "listItem");
+    map.set("type", "block");
+    map.set("marker", &item.marker_src);
+    map.set_val("principal", Value::Array(self.gen_inlines(&item.principle)));
+    if !item.blocks.is_empty() {
+      map.set_val("blocks", Value::Array(self.gen_blocks(&item.blocks)));
+    }
+    let last = item.last_loc().unwrap_or(item.marker_src.loc);
+    self.push_locspan(self.locspan_from_pair(item.marker_src.loc, last), &mut map);
+    Value::Object(map)
+  }
+
+  fn gen_dlist_item(&self, item: &ListItem) -> Value {
+    let mut map = Map::new();
+    map.set("name", "dlistItem");
+    map.set("type", "block");
+    map.set("marker", &item.marker_src);
+    let mut terms = vec![Value::Array(self.gen_inlines(&item.principle))];
+    let mut blocks = Vec::new();
+    if let ListItemTypeMeta::DescList { description, extra_terms } = &item.type_meta {
+      for (term, _) in extra_terms.iter() {
+        terms.push(Value::Array(self.gen_inlines(term)));
+      }
+      match description {
+        Some(Block {
+          context: BlockContext::Paragraph,
+          content: BlockContent::Simple(nodes),
+          ..
+        }) => map.set_val("principal", Value::Array(self.gen_inlines(nodes))),
+        Some(block) => blocks.extend(self.gen_block(block)),
+        None => {}
+      }
+    }
+    map.set_val("terms", Value::Array(terms));
+    blocks.extend(self.gen_blocks(&item.blocks));
+    if !blocks.is_empty() {
+      map.set_val("blocks", Value::Array(blocks));
+    }
+    let (first, last) = dlist_item_span(item);
+    self.push_locspan(self.locspan_from_pair(first, last), &mut map);
+    Value::Object(map)
+  }
+
+  fn gen_table_row(&self, row: &Row) -> Value {
+    Value::Array(row.cells.iter().map(|c| self.gen_table_cell(c)).collect())
+  }
+
+  fn gen_table_cell(&self, cell: &Cell) -> Value {
+    let mut map = Map::new();
+    map.set("name", "tableCell");
+    map.set("type", "block");
+    if cell.col_span > 1 {
+      map.set_val("colspan", Value::Number(cell.col_span.into()));
+    }
+    if cell.row_span > 1 {
+      map.set_val("rowspan", Value::Number(cell.row_span.into()));
+    }
+    let (style, blocks) = match &cell.content {
+      CellContent::AsciiDoc(document) => ("asciidoc", self.gen_doc_content(document)),
+      CellContent::Literal(nodes) => {
+        let mut literal = Map::new();
+        literal.set("name", "literal");
+        literal.set("type", "block");
+        literal.set_val("inlines", Value::Array(self.gen_inlines(nodes)));
+        ("literal", vec![Value::Object(literal)])
+      }
+      CellContent::Default(paras) => ("default", self.gen_cell_paragraphs(paras)),
+      CellContent::Emphasis(paras) => ("emphasis", self.gen_cell_paragraphs(paras)),
+      CellContent::Header(paras) => ("header", self.gen_cell_paragraphs(paras)),
+      CellContent::Monospace(paras) => ("monospace", self.gen_cell_paragraphs(paras)),
+      CellContent::Strong(paras) => ("strong", self.gen_cell_paragraphs(paras)),
+    };
+    map.set("style", style);
+    map.set_val("blocks", Value::Array(blocks));
+    Value::Object(map)
+  }
+
+  fn gen_cell_paragraphs(&self, paras: &[InlineNodes]) -> Vec<Value> {
+    paras.iter().filter_map(|p| self.gen_paragraph(p)).collect()
+  }
+
+  fn gen_doc_content(&self, document: &Document) -> Vec<Value> {
+    match &document.content {
+      DocContent::Blocks(blocks) => self.gen_blocks(blocks),
+      DocContent::Sections(Sectioned { preamble, sections }) => {
+        let mut blocks = preamble
+          .as_ref()
+          .map(|preamble| self.gen_blocks(preamble))
+          .unwrap_or_default();
+        blocks.extend(sections.iter().map(|s| self.gen_section(s)));
+        blocks
+      }
+      DocContent::Parts(_) => Vec::new(),
+    }
+  }
+
+  fn push_parent_name(&self, context: BlockContext, map: &mut Map<String, Value>) {
+    let (name, variant) = match context {
+      BlockContext::AdmonitionCaution => ("admonition", Some("caution")),
+      BlockContext::AdmonitionImportant => ("admonition", Some("important")),
+      BlockContext::AdmonitionNote => ("admonition", Some("note")),
+      BlockContext::AdmonitionTip => ("admonition", Some("tip")),
+      BlockContext::AdmonitionWarning => ("admonition", Some("warning")),
+      BlockContext::BlockQuote => ("quote", None),
+      BlockContext::Example => ("example", None),
+      BlockContext::Sidebar => ("sidebar", None),
+      _ => ("open", None),
+    };
+    map.set("name", name);
+    if let Some(variant) = variant {
+      map.set("variant", variant);
+    }
+  }
+
+  /// sets `form` (and `delimiter`) by examining the source of the block's
+  /// first line following its attribute and title lines
+  fn push_form(&self, block: &Block, map: &mut Map<String, Value>) {
+    let start = block.loc.start_pos as usize;
+    let end = (block.loc.end_pos as usize).clamp(start, self.src.len());
+    let src = String::from_utf8_lossy(&self.src[start.min(end)..end]);
+    let first_line = src.lines().find(|line| !is_meta_line(line)).unwrap_or("");
+    if is_delimiter(first_line) {
+      map.set("form", "delimited");
+      map.set("delimiter", first_line.trim_end());
+    } else if block.context == BlockContext::Literal && first_line.starts_with([' ', '\t']) {
+      map.set("form", "indented");
+    } else {
+      map.set("form", "paragraph");
+    }
+  }
+}
+
+fn dlist_item_span(item: &ListItem) -> (SourceLocation, SourceLocation) {
+  let first = item
+    .principle
+    .first()
+    .map_or(item.marker_src.loc, |node| node.loc);
+  let mut last = item.marker_src.loc;
+  if let ListItemTypeMeta::DescList { description, extra_terms } = &item.type_meta {
+    if let Some((_, marker)) = extra_terms.last() {
+      last = marker.loc;
+    }
+    if let Some(loc) = description.as_ref().and_then(|d| d.content.last_loc()) {
+      last = loc;
+    }
+  }
+  (
+    first,
+    item
+      .last_loc()
+      .filter(|_| !item.blocks.is_empty())
+      .unwrap_or(last),
+  )
+}
+
+const fn leaf_name(context: BlockContext) -> &'static str {
+  match context {
+    BlockContext::Literal => "literal",
+    BlockContext::Passthrough => "pass",
+    BlockContext::Stem => "stem",
+    BlockContext::Verse => "verse",
+    _ => "listing",
+  }
+}
+
+const fn list_variant(variant: ListVariant) -> &'static str {
+  match variant {
+    ListVariant::Ordered => "ordered",
+    ListVariant::Unordered => "unordered",
+    ListVariant::Callout => "callout",
+    ListVariant::Description => "description",
+  }
+}
+
+fn is_meta_line(line: &str) -> bool {
+  let line = line.trim_end();
+  let title = line.starts_with('.') && line.chars().nth(1).is_some_and(|c| c != '.' && c != ' ');
+  (line.starts_with('[') && line.ends_with(']')) || title
+}
+
+fn is_delimiter(line: &str) -> bool {
+  let line = line.trim_end();
+  if line == "--" || line.starts_with("```") {
+    return true;
+  }
+  let mut chars = line.chars();
+  match chars.next() {
+    Some(c) if "-.+_=*/".contains(c) => line.len() >= 4 && chars.all(|other| other == c),
+    _ => false,
+  }
+}
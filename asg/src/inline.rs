@@ -0,0 +1,323 @@
+use serde_json::{Map, Value};
+
+use asciidork_ast::prelude::*;
+use asciidork_ast::{AdjacentNewline, InlineNodes, MacroNode};
+
+use crate::asg::{Asg, MapExt};
+
+/// adjacent text-like nodes are merged into a single ASG text node
+struct PendingText {
+  value: String,
+  start: SourceLocation,
+  end: SourceLocation,
+}
+
+impl Asg<'_> {
+  pub fn gen_inlines(&self, nodes: &InlineNodes) -> Vec<Value> {
+    let mut values = Vec::new();
+    let mut pending = None;
+    for node in nodes.iter() {
+      self.push_inline(node, &mut values, &mut pending);
+    }
+    self.flush_text(&mut values, &mut pending);
+    values
+  }
+
+  fn push_inline(
+    &self,
+    node: &InlineNode,
+    values: &mut Vec<Value>,
+    pending: &mut Option<PendingText>,
+  ) {
+    let text = match &node.content {
+      Inline::Text(text) => Some(text.as_str()),
+      Inline::Newline => Some("\n"),
+      Inline::MultiCharWhitespace(whitespace) => Some(whitespace.as_str()),
+      Inline::CalloutTuck(comment) => Some(comment.as_str()),
+      Inline::SpecialChar(SpecialCharKind::Ampersand) => Some("&"),
+      Inline::SpecialChar(SpecialCharKind::LessThan) => Some("<"),
+      Inline::SpecialChar(SpecialCharKind::GreaterThan) => Some(">"),
+      _ => None,
+    };
+    if let Some(text) = text {
+      match pending {
+        Some(prev) => {
+          prev.value.push_str(text);
+          prev.end = node.loc;
+        }
+        None => {
+          *pending = Some(PendingText {
+            value: text.to_string(),
+            start: node.loc,
+            end: node.loc,
+          })
+        }
+      }
+      return;
+    }
+
+    match &node.content {
+      Inline::Quote(kind, nodes) => {
+        let (open, close) = match kind {
+          QuoteKind::Double => ("&#8220;", "&#8221;"),
+          QuoteKind::Single => ("&#8216;", "&#8217;"),
+        };
+        let (open_loc, close_loc) = marker_locs(node.loc, 2);
+        self.push_value(self.charref(open, open_loc), values, pending);
+        for child in nodes.iter() {
+          self.push_inline(child, values, pending);
+        }
+        self.push_value(self.charref(close, close_loc), values, pending);
+      }
+      Inline::IndexTerm(IndexTerm {
+        term_type: IndexTermType::Visible { term },
+        ..
+      }) => {
+        for child in term.iter() {
+          self.push_inline(child, values, pending);
+        }
+      }
+      Inline::IndexTerm(_) | Inline::Discarded | Inline::LineComment(_) => {}
+      _ => {
+        if let Some(value) = self.gen_inline_node(node) {
+          self.push_value(value, values, pending);
+        }
+      }
+    }
+  }
+
+  fn gen_inline_node(&self, node: &InlineNode) -> Option<Value> {
+    let mut map = Map::new();
+    match &node.content {
+      Inline::Span(kind, _, nodes) => {
+        map.set("name", "span");
+        map.set("type", "inline");
+        map.set("variant", span_variant(*kind));
+        map.set("form", self.span_form(node.loc));
+        map.set_val("inlines", Value::Array(self.gen_inlines(nodes)));
+      }
+      Inline::Symbol(kind) => return Some(self.charref(symbol_charref(*kind), node.loc)),
+      Inline::CurlyQuote(kind) => return Some(self.charref(curly_charref(*kind), node.loc)),
+      Inline::SpacedDashes(_, AdjacentNewline::None) => {
+        return Some(self.charref("&#8201;&#8212;&#8201;", node.loc));
+      }
+      Inline::SpacedDashes(..) => return Some(self.charref("&#8212;&#8203;", node.loc)),
+      Inline::InlinePassthru(nodes) => {
+        map.set("name", "raw");
+        map.set("type", "string");
+        map.set("value", &nodes.plain_text().join(""));
+      }
+      Inline::LineBreak => {
+        map.set("name", "break");
+        map.set("type", "inline");
+      }
+      Inline::CalloutNum(callout) => {
+        map.set("name", "callout");
+        map.set("type", "inline");
+        map.set_val("value", Value::Number(callout.number.into()));
+      }
+      Inline::InlineAnchor(id) => {
+        map.set("name", "anchor");
+        map.set("type", "inline");
+        map.set("id", id);
+      }
+      Inline::BiblioAnchor(id) => {
+        map.set("name", "anchor");
+        map.set("type", "inline");
+        map.set("variant", "bibliography");
+        map.set("id", id);
+      }
+      Inline::Macro(macro_node) => self.gen_macro(macro_node, node.loc, &mut map),
+      _ => return None,
+    }
+    self.push_srcloc(node.loc, &mut map);
+    Some(Value::Object(map))
+  }
+
+  fn gen_macro(&self, node: &MacroNode, loc: SourceLocation, map: &mut Map<String, Value>) {
+    match node {
+      MacroNode::Link { target, attrs, .. } => {
+        let text = attrs
+          .as_ref()
+          .and_then(|attrs| attrs.positional.first())
+          .and_then(|nodes| nodes.as_ref());
+        self.gen_link(target, text, loc, map);
+      }
+      MacroNode::Mailto { address, linktext, .. } => {
+        let target = format!("mailto:{}", &**address);
+        self.gen_link(&target, linktext.as_ref(), loc, map);
+      }
+      MacroNode::Xref { target, linktext, .. } => {
+        map.set("name", "ref");
+        map.set("type", "inline");
+        map.set("variant", "xref");
+        map.set("target", target);
+        let inlines = linktext.as_ref().map(|t| self.gen_inlines(t));
+        map.set_val("inlines", Value::Array(inlines.unwrap_or_default()));
+      }
+      MacroNode::Footnote { id, text } => {
+        map.set("name", "footnote");
+        map.set("type", "inline");
+        if let Some(id) = id {
+          map.set("id", id);
+        }
+        let inlines = text.as_ref().map(|t| self.gen_inlines(t));
+        map.set_val("inlines", Value::Array(inlines.unwrap_or_default()));
+      }
+      MacroNode::InlineImage { target, attrs, .. } => {
+        map.set("name", "image");
+        map.set("type", "inline");
+        map.set("target", target);
+        if let Some(alt) = attrs.str_positional_at(0) {
+          map.set("alt", alt);
+        }
+      }
+      MacroNode::Icon { target, .. } => {
+        map.set("name", "icon");
+        map.set("type", "inline");
+        map.set("target", target);
+      }
+      MacroNode::Keyboard { keys, .. } => {
+        map.set("name", "kbd");
+        map.set("type", "inline");
+        let keys = keys.iter().map(|key| Value::from(key.as_str())).collect();
+        map.set_val("keys", Value::Array(keys));
+      }
+      MacroNode::Button(text) => {
+        map.set("name", "button");
+        map.set("type", "inline");
+        map.set("value", text);
+      }
+      MacroNode::Menu(items) => {
+        map.set("name", "menu");
+        map.set("type", "inline");
+        let items = items.iter().map(|item| Value::from(&**item)).collect();
+        map.set_val("items", Value::Array(items));
+      }
+      MacroNode::Stem { notation, content } => {
+        map.set("name", "stem");
+        map.set("type", "inline");
+        map.set(
+          "variant",
+          match notation {
+            StemNotation::Asciimath => "asciimath",
+            StemNotation::Latexmath => "latexmath",
+          },
+        );
+        map.set("value", &content.plain_text().join(""));
+      }
+      MacroNode::Plugin(plugin) => {
+        map.set("name", "raw");
+        map.set("type", "string");
+        map.set("value", &plugin.source);
+      }
+    }
+  }
+
+  fn gen_link(
+    &self,
+    target: &str,
+    text: Option<&InlineNodes>,
+    loc: SourceLocation,
+    map: &mut Map<String, Value>,
+  ) {
+    map.set("name", "ref");
+    map.set("type", "inline");
+    map.set("variant", "link");
+    map.set("target", target);
+    let inlines = match text {
+      Some(text) => self.gen_inlines(text),
+      None => vec![self.text_value(target, loc, loc)],
+    };
+    map.set_val("inlines", Value::Array(inlines));
+  }
+
+  fn push_value(&self, value: Value, values: &mut Vec<Value>, pending: &mut Option<PendingText>) {
+    self.flush_text(values, pending);
+    values.push(value);
+  }
+
+  fn flush_text(&self, values: &mut Vec<Value>, pending: &mut Option<PendingText>) {
+    if let Some(text) = pending.take() {
+      values.push(self.text_value(&text.value, text.start, text.end));
+    }
+  }
+
+  fn text_value(&self, value: &str, start: SourceLocation, end: SourceLocation) -> Value {
+    let mut map = Map::new();
+    map.set("name", "text");
+    map.set("type", "string");
+    map.set("value", value);
+    self.push_locspan(self.locspan_from_pair(start, end), &mut map);
+    Value::Object(map)
+  }
+
+  fn charref(&self, value: &str, loc: SourceLocation) -> Value {
+    let mut map = Map::new();
+    map.set("name", "charref");
+    map.set("type", "string");
+    map.set("value", value);
+    self.push_srcloc(loc, &mut map);
+    Value::Object(map)
+  }
+
+  /// a span is unconstrained when its opening mark is doubled, eg: `**`
+  fn span_form(&self, loc: SourceLocation) -> &'static str {
+    let mut pos = loc.start as usize;
+    if self.src.get(pos) == Some(&b'[') {
+      while let Some(byte) = self.src.get(pos) {
+        pos += 1;
+        if *byte == b']' {
+          break;
+        }
+      }
+    }
+    match (self.src.get(pos), self.src.get(pos + 1)) {
+      (Some(a), Some(b)) if a == b => "unconstrained",
+      _ => "constrained",
+    }
+  }
+}
+
+fn marker_locs(loc: SourceLocation, len: u32) -> (SourceLocation, SourceLocation) {
+  let open_end = (loc.start + len).min(loc.end);
+  let close_start = loc.end.saturating_sub(len).max(loc.start);
+  (
+    SourceLocation::new(loc.start, open_end, loc.include_depth),
+    SourceLocation::new(close_start, loc.end, loc.include_depth),
+  )
+}
+
+const fn span_variant(kind: SpanKind) -> &'static str {
+  match kind {
+    SpanKind::Bold => "strong",
+    SpanKind::Italic => "emphasis",
+    SpanKind::Mono | SpanKind::LitMono => "code",
+    SpanKind::Highlight | SpanKind::Text => "mark",
+    SpanKind::Superscript => "superscript",
+    SpanKind::Subscript => "subscript",
+  }
+}
+
+const fn symbol_charref(kind: SymbolKind) -> &'static str {
+  match kind {
+    SymbolKind::Copyright => "&#169;",
+    SymbolKind::Registered => "&#174;",
+    SymbolKind::Trademark => "&#8482;",
+    SymbolKind::EmDash | SymbolKind::TripleDash => "&#8212;&#8203;",
+    SymbolKind::Ellipsis => "&#8230;&#8203;",
+    SymbolKind::SingleRightArrow => "&#8594;",
+    SymbolKind::DoubleRightArrow => "&#8658;",
+    SymbolKind::SingleLeftArrow => "&#8592;",
+    SymbolKind::DoubleLeftArrow => "&#8656;",
+  }
+}
+
+const fn curly_charref(kind: CurlyKind) -> &'static str {
+  match kind {
+    CurlyKind::LeftDouble => "&#8220;",
+    CurlyKind::RightDouble => "&#8221;",
+    CurlyKind::LeftSingle => "&#8216;",
+    CurlyKind::RightSingle | CurlyKind::LegacyImplicitApostrophe => "&#8217;",
+  }
+}
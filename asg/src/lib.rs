@@ -0,0 +1,23 @@
+use serde_json::Value;
+
+use asciidork_ast::prelude::*;
+
+mod asg;
+mod block;
+mod inline;
+mod loc;
+
+use crate::asg::Asg;
+
+/// generates the Abstract Semantic Graph for a parsed document,
+/// `src` must be the source the document was parsed from. Nodes
+/// sourced from included files are generated without a `location`,
+/// as their offsets are relative to sources `src` doesn't contain
+pub fn gen_document(document: &Document, src: &str) -> Value {
+  Asg::new(src).gen_document(document)
+}
+
+/// converts a parsed document to pretty-printed ASG json
+pub fn convert(document: &Document, src: &str) -> String {
+  serde_json::to_string_pretty(&gen_document(document, src)).unwrap()
+}
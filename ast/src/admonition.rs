@@ -1,6 +1,7 @@
 use crate::short::block::*;
 
 #[derive(Copy, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AdmonitionKind {
   Tip,
   Caution,
@@ -2,6 +2,7 @@ use crate::internal::*;
 
 // https://docs.asciidoctor.org/asciidoc/latest/attributes/positional-and-named-attributes/
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttrList<'arena> {
   pub positional: BumpVec<'arena, Option<InlineNodes<'arena>>>,
   pub named: Named<'arena>,
@@ -136,6 +137,7 @@ impl AttrData for AttrList<'_> {
       (Listing, Literal) => Some(Literal),
       (Literal, Listing) => Some(Listing),
       (BlockQuote, Verse) => Some(Verse),
+      (Passthrough, Stem) => Some(Stem),
       (
         Example,
         AdmonitionCaution | AdmonitionImportant | AdmonitionNote | AdmonitionTip
@@ -198,6 +200,7 @@ impl<'arena> AttrList<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Named<'arena>(BumpVec<'arena, (SourceString<'arena>, InlineNodes<'arena>)>);
 
 impl<'arena> Named<'arena> {
@@ -220,6 +223,16 @@ impl<'arena> Named<'arena> {
       .find_map(|(k, v)| if k == key { Some(v) } else { None })
   }
 
+  pub fn iter(&self) -> impl Iterator<Item = &(SourceString<'arena>, InlineNodes<'arena>)> {
+    self.0.iter()
+  }
+
+  pub fn iter_mut(
+    &mut self,
+  ) -> impl Iterator<Item = &mut (SourceString<'arena>, InlineNodes<'arena>)> {
+    self.0.iter_mut()
+  }
+
   pub fn get_with_src(&self, key: &str) -> Option<(SourceString<'arena>, &InlineNodes<'arena>)> {
     self
       .0
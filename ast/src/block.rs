@@ -1,6 +1,7 @@
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Block<'arena> {
   pub meta: ChunkMeta<'arena>,
   pub content: BlockContent<'arena>,
@@ -19,6 +20,7 @@ impl Block<'_> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BlockContent<'arena> {
   Compound(BumpVec<'arena, Block<'arena>>),
   Simple(InlineNodes<'arena>),
@@ -40,6 +42,7 @@ pub enum BlockContent<'arena> {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EmptyMetadata<'arena> {
   Image {
     target: SourceString<'arena>,
@@ -60,6 +63,7 @@ pub enum EmptyMetadata<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ImageKind<'arena> {
   Standard,
   InlineSvg(Option<BumpString<'arena>>),
@@ -67,6 +71,7 @@ pub enum ImageKind<'arena> {
 }
 
 #[derive(Copy, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BlockContext {
   AdmonitionCaution,
   AdmonitionImportant,
@@ -93,6 +98,7 @@ pub enum BlockContext {
   QuotedParagraph,
   Section,
   Sidebar,
+  Stem,
   Table,
   TableCell,
   ThematicBreak,
@@ -158,6 +164,7 @@ impl BlockContext {
       "listing" | "source" => Some(BlockContext::Listing),
       "literal" => Some(BlockContext::Literal),
       "pass" => Some(BlockContext::Passthrough),
+      "stem" | "latexmath" | "asciimath" => Some(BlockContext::Stem),
       "comment" => Some(BlockContext::Comment),
       "verse" => Some(BlockContext::Verse),
       "example" => Some(BlockContext::Example),
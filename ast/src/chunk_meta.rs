@@ -1,6 +1,7 @@
 use crate::internal::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ChunkMeta<'arena> {
   pub attrs: MultiAttrList<'arena>,
   pub dot_line_title: Option<InlineNodes<'arena>>,
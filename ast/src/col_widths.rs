@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ColWidths<'arena>(BumpVec<'arena, ColWidth>);
 
 impl Deref for ColWidths<'_> {
@@ -25,6 +26,7 @@ impl<'arena> From<BumpVec<'arena, ColWidth>> for ColWidths<'arena> {
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DistributedColWidth {
   Percentage(f32),
   Auto(f32),
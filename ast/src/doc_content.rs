@@ -1,6 +1,7 @@
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DocContent<'arena> {
   Parts(MultiPartBook<'arena>),
   Sections(Sectioned<'arena>),
@@ -8,12 +9,14 @@ pub enum DocContent<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Sectioned<'arena> {
   pub preamble: Option<BumpVec<'arena, Block<'arena>>>,
   pub sections: BumpVec<'arena, Section<'arena>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MultiPartBook<'arena> {
   pub preamble: Option<BumpVec<'arena, Block<'arena>>>,
   pub opening_special_sects: BumpVec<'arena, Section<'arena>>,
@@ -22,6 +25,7 @@ pub struct MultiPartBook<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Part<'arena> {
   pub title: PartTitle<'arena>,
   pub intro: Option<BumpVec<'arena, Block<'arena>>>,
@@ -29,6 +33,7 @@ pub struct Part<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PartTitle<'arena> {
   pub id: Option<BumpString<'arena>>,
   pub meta: ChunkMeta<'arena>,
@@ -36,6 +41,7 @@ pub struct PartTitle<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Section<'arena> {
   pub meta: ChunkMeta<'arena>,
   pub level: u8,
@@ -5,6 +5,7 @@ use crate::internal::*;
 
 // https://docs.asciidoctor.org/asciidoc/latest/key-concepts/#document
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Document<'arena> {
   pub meta: DocumentMeta,
   pub docinfo: DocInfo<'arena>,
@@ -49,12 +50,14 @@ impl<'arena> Document<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DocHeader<'arena> {
   pub title: Option<DocTitle<'arena>>,
   pub loc: SourceLocation,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DocInfo<'arena> {
   pub head: Option<DocInfoFragment<'arena>>,
   pub header: Option<DocInfoFragment<'arena>>,
@@ -64,6 +67,7 @@ pub struct DocInfo<'arena> {
 pub type DocInfoFragment<'arena> = InlineNodes<'arena>;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DocTitle<'arena> {
   pub attrs: MultiAttrList<'arena>,
   pub main: InlineNodes<'arena>,
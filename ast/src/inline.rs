@@ -1,6 +1,7 @@
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InlineNode<'arena> {
   pub content: Inline<'arena>,
   pub loc: SourceLocation,
@@ -14,6 +15,7 @@ impl<'arena> InlineNode<'arena> {
 
 // https://docs.asciidoctor.org/asciidoc/latest/key-concepts/#elements
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Inline<'arena> {
   CurlyQuote(CurlyKind),
   Discarded,
@@ -37,6 +39,7 @@ pub enum Inline<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SpanKind {
   Bold,
   Italic,
@@ -49,12 +52,14 @@ pub enum SpanKind {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum QuoteKind {
   Double,
   Single,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CurlyKind {
   LeftDouble,
   RightDouble,
@@ -64,6 +69,7 @@ pub enum CurlyKind {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SpecialCharKind {
   Ampersand,
   LessThan,
@@ -71,6 +77,7 @@ pub enum SpecialCharKind {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AdjacentNewline {
   None,
   Leading,
@@ -78,6 +85,7 @@ pub enum AdjacentNewline {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SymbolKind {
   Copyright,
   Registered,
@@ -92,12 +100,14 @@ pub enum SymbolKind {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndexTerm<'arena> {
   pub term_type: IndexTermType<'arena>,
   pub term_ref: IndexTermReference<'arena>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IndexTermType<'arena> {
   Visible {
     term: InlineNodes<'arena>,
@@ -110,6 +120,7 @@ pub enum IndexTermType<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IndexTermReference<'arena> {
   None,
   See(BumpString<'arena>),
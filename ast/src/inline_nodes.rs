@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InlineNodes<'arena>(BumpVec<'arena, InlineNode<'arena>>);
 
 impl<'arena> InlineNodes<'arena> {
@@ -13,10 +13,12 @@ mod multi_attr_list;
 mod multi_source_location;
 mod node;
 mod priority_attr_list;
+mod relocate;
 mod source_location;
 mod source_string;
 mod table;
 mod toc;
+mod walk;
 
 pub use internal::types::*;
 
@@ -34,15 +36,19 @@ mod internal {
     pub use crate::inline::{IndexTerm, IndexTermReference, IndexTermType};
     pub use crate::inline_nodes::InlineNodes;
     pub use crate::list::*;
-    pub use crate::r#macro::{Flow, MacroNode, PluginMacro, UrlScheme, XrefKind};
+    pub use crate::r#macro::{
+      Flow, MacroNode, PluginMacro, StemNotation, UrlScheme, XrefKind, XrefStyle,
+    };
     pub use crate::multi_attr_list::{MultiAttrList, NoAttrs};
     pub use crate::multi_source_location::MultiSourceLocation;
     pub use crate::node::{Anchor, Callout};
     pub use crate::priority_attr_list::PriorityAttrList;
+    pub use crate::relocate::Relocate;
     pub use crate::source_location::SourceLocation;
     pub use crate::source_string::SourceString;
     pub use crate::table::*;
     pub use crate::toc::*;
+    pub use crate::walk::VisitMut;
     pub use asciidork_core::{AttrValue, DocumentMeta, ReadAttr, SpecialSection};
     pub use smallvec::SmallVec;
   }
@@ -67,15 +73,17 @@ pub mod prelude {
   pub use crate::inline::{CurlyKind, Inline, InlineNode, QuoteKind, SpecialCharKind, SymbolKind};
   pub use crate::inline::{IndexTerm, IndexTermReference, IndexTermType, SpanKind};
   pub use crate::list::{ListItem, ListItemTypeMeta, ListMarker, ListVariant};
-  pub use crate::r#macro::{PluginMacro, UrlScheme, XrefKind};
+  pub use crate::r#macro::{PluginMacro, StemNotation, UrlScheme, XrefKind, XrefStyle};
   pub use crate::multi_attr_list::{MultiAttrList, NoAttrs};
   pub use crate::multi_source_location::MultiSourceLocation;
   pub use crate::node::{Anchor, Callout};
   pub use crate::priority_attr_list::PriorityAttrList;
+  pub use crate::relocate::Relocate;
   pub use crate::source_location::SourceLocation;
   pub use crate::source_string::SourceString;
   pub use crate::table::*;
   pub use crate::toc::*;
+  pub use crate::walk::VisitMut;
   pub use asciidork_core::DocumentMeta;
 }
 
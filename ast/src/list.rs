@@ -3,6 +3,7 @@ use std::fmt::{Debug, Formatter, Result};
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ListItem<'arena> {
   pub marker: ListMarker,
   pub marker_src: SourceString<'arena>,
@@ -13,6 +14,7 @@ pub struct ListItem<'arena> {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ListItemTypeMeta<'arena> {
   Checklist(bool, SourceString<'arena>),
   Callout(SmallVec<[Callout; 4]>),
@@ -50,6 +52,7 @@ impl ListItem<'_> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ListVariant {
   Ordered,
   Unordered,
@@ -69,6 +72,7 @@ impl ListVariant {
 }
 
 #[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ListMarker {
   // ordered
   Dot(u8),
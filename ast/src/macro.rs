@@ -1,6 +1,9 @@
+use std::str::FromStr;
+
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum MacroNode<'arena> {
   Footnote {
     id: Option<SourceString<'arena>>,
@@ -38,11 +41,17 @@ pub enum MacroNode<'arena> {
     target: SourceString<'arena>,
     linktext: Option<InlineNodes<'arena>>,
     kind: XrefKind,
+    xrefstyle: Option<XrefStyle>,
   },
   Plugin(Box<PluginMacro<'arena>>),
+  Stem {
+    notation: StemNotation,
+    content: InlineNodes<'arena>,
+  },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PluginMacro<'arena> {
   pub name: BumpString<'arena>,
   pub target: Option<SourceString<'arena>>,
@@ -52,12 +61,34 @@ pub struct PluginMacro<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum XrefKind {
   Shorthand,
   Macro,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum XrefStyle {
+  Full,
+  Short,
+  Basic,
+}
+
+impl FromStr for XrefStyle {
+  type Err = ();
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "full" => Ok(Self::Full),
+      "short" => Ok(Self::Short),
+      "basic" => Ok(Self::Basic),
+      _ => Err(()),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum UrlScheme {
   Https,
   Http,
@@ -68,7 +99,38 @@ pub enum UrlScheme {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Flow {
   Inline,
   Block,
 }
+
+/// https://docs.asciidoctor.org/asciidoc/latest/stem/
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StemNotation {
+  Asciimath,
+  Latexmath,
+}
+
+impl StemNotation {
+  /// resolves the notation for the generic `stem` style/macro name
+  /// from the value of the `stem` document attribute
+  pub fn from_stem_attr(value: Option<&str>) -> Self {
+    match value {
+      Some("latexmath" | "latex" | "tex") => StemNotation::Latexmath,
+      _ => StemNotation::Asciimath,
+    }
+  }
+
+  /// resolves the notation for a `stem`, `latexmath`, or `asciimath`
+  /// block style or macro name
+  pub fn from_name(name: &str, stem_attr: Option<&str>) -> Option<Self> {
+    match name {
+      "latexmath" => Some(StemNotation::Latexmath),
+      "asciimath" => Some(StemNotation::Asciimath),
+      "stem" => Some(Self::from_stem_attr(stem_attr)),
+      _ => None,
+    }
+  }
+}
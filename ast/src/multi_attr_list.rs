@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MultiAttrList<'arena>(BumpVec<'arena, AttrList<'arena>>);
 
 impl<'arena> MultiAttrList<'arena> {
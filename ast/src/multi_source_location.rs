@@ -4,6 +4,7 @@ use std::ops::Range;
 use crate::SourceLocation;
 
 #[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MultiSourceLocation {
   pub start_pos: u32,
   pub start_depth: u16,
@@ -3,6 +3,7 @@ use std::fmt::{Debug, Formatter, Result};
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Anchor<'arena> {
   pub reftext: Option<InlineNodes<'arena>>,
   pub title: InlineNodes<'arena>,
@@ -13,6 +14,7 @@ pub struct Anchor<'arena> {
 }
 
 #[derive(Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Callout {
   /// list index, e.g. `0` maps to `1` in dr id: `CO1-3`
   pub list_idx: u8,
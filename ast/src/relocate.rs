@@ -0,0 +1,284 @@
+use crate::internal::*;
+
+/// visits every source position in a node along with the index of the
+/// source it points into, used to keep reused subtrees in sync with
+/// edited source, see `Parser::reparse`
+///
+/// NB: the anchors of a document are not visited, as documents nested
+/// in table cells share them with the enclosing document
+pub trait Relocate {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16));
+}
+
+impl Relocate for SourceLocation {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    f(&mut self.start, self.include_depth);
+    f(&mut self.end, self.include_depth);
+  }
+}
+
+impl Relocate for MultiSourceLocation {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    f(&mut self.start_pos, self.start_depth);
+    f(&mut self.end_pos, self.end_depth);
+  }
+}
+
+impl Relocate for SourceString<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.loc.relocate(f);
+  }
+}
+
+impl<T: Relocate> Relocate for Option<T> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    if let Some(node) = self {
+      node.relocate(f);
+    }
+  }
+}
+
+impl<T: Relocate> Relocate for Box<T> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    (**self).relocate(f);
+  }
+}
+
+impl<T: Relocate> Relocate for [T] {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.iter_mut().for_each(|node| node.relocate(f));
+  }
+}
+
+impl<T: Relocate> Relocate for BumpVec<'_, T> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.as_mut_slice().relocate(f);
+  }
+}
+
+impl Relocate for InlineNodes<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.as_mut_slice().relocate(f);
+  }
+}
+
+impl Relocate for InlineNode<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.loc.relocate(f);
+    match &mut self.content {
+      Inline::InlinePassthru(nested) | Inline::Quote(_, nested) => nested.relocate(f),
+      Inline::Span(_, attrs, nested) => {
+        attrs.relocate(f);
+        nested.relocate(f);
+      }
+      Inline::Macro(node) => node.relocate(f),
+      Inline::IndexTerm(IndexTerm { term_type, .. }) => match term_type {
+        IndexTermType::Visible { term } => term.relocate(f),
+        IndexTermType::Concealed { primary, secondary, tertiary } => {
+          primary.relocate(f);
+          secondary.relocate(f);
+          tertiary.relocate(f);
+        }
+      },
+      _ => {}
+    }
+  }
+}
+
+impl Relocate for MacroNode<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    match self {
+      MacroNode::Footnote { id, text } => {
+        id.relocate(f);
+        text.relocate(f);
+      }
+      MacroNode::InlineImage { target, attrs, .. } | MacroNode::Icon { target, attrs } => {
+        target.relocate(f);
+        attrs.relocate(f);
+      }
+      MacroNode::Keyboard { keys_src, .. } => keys_src.relocate(f),
+      MacroNode::Link { target, attrs, .. } => {
+        target.relocate(f);
+        attrs.relocate(f);
+      }
+      MacroNode::Mailto {
+        address,
+        linktext,
+        subject,
+        body,
+        attrs,
+      } => {
+        address.relocate(f);
+        linktext.relocate(f);
+        subject.relocate(f);
+        body.relocate(f);
+        attrs.relocate(f);
+      }
+      MacroNode::Button(label) => label.relocate(f),
+      MacroNode::Menu(items) => items.relocate(f),
+      MacroNode::Xref { target, linktext, .. } => {
+        target.relocate(f);
+        linktext.relocate(f);
+      }
+      MacroNode::Plugin(plugin) => {
+        plugin.target.relocate(f);
+        plugin.attrs.relocate(f);
+        plugin.source.relocate(f);
+      }
+      MacroNode::Stem { content, .. } => content.relocate(f),
+    }
+  }
+}
+
+impl Relocate for AttrList<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.positional.relocate(f);
+    for (key, value) in self.named.iter_mut() {
+      key.relocate(f);
+      value.relocate(f);
+    }
+    self.id.relocate(f);
+    self.roles.relocate(f);
+    self.options.relocate(f);
+    self.loc.relocate(f);
+  }
+}
+
+impl Relocate for ChunkMeta<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.attrs.as_mut_slice().relocate(f);
+    self.dot_line_title.relocate(f);
+    self.start_loc.relocate(f);
+  }
+}
+
+impl Relocate for Block<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.meta.relocate(f);
+    self.loc.relocate(f);
+    match &mut self.content {
+      BlockContent::Compound(blocks) => blocks.relocate(f),
+      BlockContent::Simple(inlines) => inlines.relocate(f),
+      BlockContent::Section(section) => section.relocate(f),
+      BlockContent::Table(table) => table.relocate(f),
+      BlockContent::QuotedParagraph { quote, attr, cite } => {
+        quote.relocate(f);
+        attr.relocate(f);
+        cite.relocate(f);
+      }
+      BlockContent::List { items, .. } => items.relocate(f),
+      BlockContent::Empty(empty) => match empty {
+        EmptyMetadata::Image { target, attrs, .. }
+        | EmptyMetadata::AudioVideo { target, attrs } => {
+          target.relocate(f);
+          attrs.relocate(f);
+        }
+        EmptyMetadata::DiscreteHeading { content, .. } => content.relocate(f),
+        EmptyMetadata::Comment(src) => src.relocate(f),
+        EmptyMetadata::None => {}
+      },
+      BlockContent::DocumentAttribute(..) => {}
+    }
+  }
+}
+
+impl Relocate for ListItem<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.marker_src.relocate(f);
+    self.principle.relocate(f);
+    match &mut self.type_meta {
+      ListItemTypeMeta::Checklist(_, src) => src.relocate(f),
+      ListItemTypeMeta::DescList { description, extra_terms } => {
+        description.relocate(f);
+        for (term, src) in extra_terms.iter_mut() {
+          term.relocate(f);
+          src.relocate(f);
+        }
+      }
+      ListItemTypeMeta::Callout(_) | ListItemTypeMeta::None => {}
+    }
+    self.blocks.relocate(f);
+  }
+}
+
+impl Relocate for Table<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    let rows = self.header_row.iter_mut().chain(self.rows.iter_mut());
+    for cell in rows
+      .chain(self.footer_row.iter_mut())
+      .flat_map(|row| row.cells.iter_mut())
+    {
+      match &mut cell.content {
+        CellContent::AsciiDoc(document) => document.relocate(f),
+        CellContent::Literal(inlines) => inlines.relocate(f),
+        CellContent::Default(paras)
+        | CellContent::Emphasis(paras)
+        | CellContent::Header(paras)
+        | CellContent::Monospace(paras)
+        | CellContent::Strong(paras) => paras.relocate(f),
+      }
+    }
+  }
+}
+
+impl Relocate for Section<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.meta.relocate(f);
+    self.heading.relocate(f);
+    self.blocks.relocate(f);
+    self.loc.relocate(f);
+  }
+}
+
+impl Relocate for Part<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.title.meta.relocate(f);
+    self.title.text.relocate(f);
+    self.intro.relocate(f);
+    self.sections.relocate(f);
+  }
+}
+
+impl Relocate for TocNode<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.title.relocate(f);
+    self.children.relocate(f);
+  }
+}
+
+impl Relocate for Anchor<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    self.reftext.relocate(f);
+    self.title.relocate(f);
+    self.source_loc.relocate(f);
+  }
+}
+
+impl Relocate for Document<'_> {
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    if let Some(header) = self.header.as_mut() {
+      header.loc.relocate(f);
+      if let Some(title) = header.title.as_mut() {
+        title.attrs.as_mut_slice().relocate(f);
+        title.main.relocate(f);
+        title.subtitle.relocate(f);
+      }
+    }
+    match &mut self.content {
+      DocContent::Blocks(blocks) => blocks.relocate(f),
+      DocContent::Sections(Sectioned { preamble, sections }) => {
+        preamble.relocate(f);
+        sections.relocate(f);
+      }
+      DocContent::Parts(book) => {
+        book.preamble.relocate(f);
+        book.opening_special_sects.relocate(f);
+        book.parts.relocate(f);
+        book.closing_special_sects.relocate(f);
+      }
+    }
+    if let Some(toc) = self.toc.as_mut() {
+      toc.nodes.relocate(f);
+    }
+  }
+}
@@ -1,6 +1,7 @@
 use std::fmt::{Debug, Formatter, Result};
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SourceLocation {
   pub start: u32,
   pub end: u32,
@@ -3,6 +3,7 @@ use std::ops::Deref;
 use crate::internal::*;
 
 #[derive(PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SourceString<'arena> {
   pub src: BumpString<'arena>,
   pub loc: SourceLocation,
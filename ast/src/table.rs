@@ -3,6 +3,7 @@ use std::str::FromStr;
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum HorizontalAlignment {
   #[default]
   Left,
@@ -11,6 +12,7 @@ pub enum HorizontalAlignment {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum VerticalAlignment {
   #[default]
   Top,
@@ -20,6 +22,7 @@ pub enum VerticalAlignment {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CellContent<'arena> {
   AsciiDoc(Document<'arena>),
   Default(BumpVec<'arena, InlineNodes<'arena>>),
@@ -56,6 +59,7 @@ impl CellContent<'_> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CellContentStyle {
   AsciiDoc,
   #[default]
@@ -68,6 +72,7 @@ pub enum CellContentStyle {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ColWidth {
   Proportional(u8),
   Percentage(u8),
@@ -75,6 +80,7 @@ pub enum ColWidth {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ColSpec {
   pub width: ColWidth,
   pub h_align: HorizontalAlignment,
@@ -83,6 +89,7 @@ pub struct ColSpec {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Table<'arena> {
   pub col_widths: ColWidths<'arena>,
   pub header_row: Option<Row<'arena>>,
@@ -112,6 +119,7 @@ impl Default for ColSpec {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CellSpec {
   pub duplication: Option<u8>,
   pub col_span: Option<u8>,
@@ -122,6 +130,7 @@ pub struct CellSpec {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Cell<'arena> {
   pub content: CellContent<'arena>,
   pub col_span: u8,
@@ -153,6 +162,7 @@ impl<'arena> Cell<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Row<'arena> {
   pub cells: BumpVec<'arena, Cell<'arena>>,
 }
@@ -168,6 +178,7 @@ impl<'arena> Row<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TableSection {
   Header,
   Body,
@@ -1,6 +1,7 @@
 use crate::internal::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TableOfContents<'arena> {
   pub title: BumpString<'arena>,
   pub nodes: BumpVec<'arena, TocNode<'arena>>,
@@ -8,6 +9,7 @@ pub struct TableOfContents<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TocNode<'arena> {
   pub level: u8,
   pub title: InlineNodes<'arena>,
@@ -17,6 +19,7 @@ pub struct TocNode<'arena> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TocPosition {
   Left,
   Right,
@@ -0,0 +1,192 @@
+use crate::internal::*;
+
+/// a mutable, depth-first visitor over a document, used with
+/// `Document::walk_mut`, all methods default to doing nothing
+///
+/// each method is called before the node's children are walked,
+/// so children replaced by the visitor are walked too
+pub trait VisitMut<'arena> {
+  fn visit_section(&mut self, section: &mut Section<'arena>) {
+    _ = section;
+  }
+
+  fn visit_block(&mut self, block: &mut Block<'arena>) {
+    _ = block;
+  }
+
+  /// called with every run of inline nodes: headings, titles,
+  /// paragraph content, list principles, table cells, link text, etc.
+  fn visit_inlines(&mut self, inlines: &mut InlineNodes<'arena>) {
+    _ = inlines;
+  }
+}
+
+impl<'arena> Document<'arena> {
+  pub fn walk_mut(&mut self, visitor: &mut impl VisitMut<'arena>) {
+    if let Some(title) = self.header.as_mut().and_then(|h| h.title.as_mut()) {
+      walk_inlines(&mut title.main, visitor);
+      if let Some(subtitle) = title.subtitle.as_mut() {
+        walk_inlines(subtitle, visitor);
+      }
+    }
+    match &mut self.content {
+      DocContent::Blocks(blocks) => walk_blocks(blocks, visitor),
+      DocContent::Sections(Sectioned { preamble, sections }) => {
+        preamble.iter_mut().for_each(|b| walk_blocks(b, visitor));
+        sections.iter_mut().for_each(|s| walk_section(s, visitor));
+      }
+      DocContent::Parts(book) => {
+        book
+          .preamble
+          .iter_mut()
+          .for_each(|b| walk_blocks(b, visitor));
+        for section in book.opening_special_sects.iter_mut() {
+          walk_section(section, visitor);
+        }
+        for part in book.parts.iter_mut() {
+          walk_meta(&mut part.title.meta, visitor);
+          walk_inlines(&mut part.title.text, visitor);
+          part.intro.iter_mut().for_each(|b| walk_blocks(b, visitor));
+          part
+            .sections
+            .iter_mut()
+            .for_each(|s| walk_section(s, visitor));
+        }
+        for section in book.closing_special_sects.iter_mut() {
+          walk_section(section, visitor);
+        }
+      }
+    }
+  }
+
+  /// calls `f` with every block in the document, including nested blocks
+  pub fn for_each_block_mut(&mut self, f: impl FnMut(&mut Block<'arena>)) {
+    self.walk_mut(&mut BlockFn(f));
+  }
+
+  /// calls `f` with every run of inline nodes in the document
+  pub fn for_each_inlines_mut(&mut self, f: impl FnMut(&mut InlineNodes<'arena>)) {
+    self.walk_mut(&mut InlinesFn(f));
+  }
+}
+
+impl<'arena> Block<'arena> {
+  pub fn walk_mut(&mut self, visitor: &mut impl VisitMut<'arena>) {
+    walk_block(self, visitor);
+  }
+}
+
+impl<'arena> Section<'arena> {
+  pub fn walk_mut(&mut self, visitor: &mut impl VisitMut<'arena>) {
+    walk_section(self, visitor);
+  }
+}
+
+struct BlockFn<F>(F);
+
+impl<'arena, F: FnMut(&mut Block<'arena>)> VisitMut<'arena> for BlockFn<F> {
+  fn visit_block(&mut self, block: &mut Block<'arena>) {
+    (self.0)(block);
+  }
+}
+
+struct InlinesFn<F>(F);
+
+impl<'arena, F: FnMut(&mut InlineNodes<'arena>)> VisitMut<'arena> for InlinesFn<F> {
+  fn visit_inlines(&mut self, inlines: &mut InlineNodes<'arena>) {
+    (self.0)(inlines);
+  }
+}
+
+fn walk_section<'arena>(section: &mut Section<'arena>, visitor: &mut impl VisitMut<'arena>) {
+  visitor.visit_section(section);
+  walk_meta(&mut section.meta, visitor);
+  walk_inlines(&mut section.heading, visitor);
+  walk_blocks(&mut section.blocks, visitor);
+}
+
+fn walk_blocks<'arena>(blocks: &mut [Block<'arena>], visitor: &mut impl VisitMut<'arena>) {
+  blocks
+    .iter_mut()
+    .for_each(|block| walk_block(block, visitor));
+}
+
+fn walk_block<'arena>(block: &mut Block<'arena>, visitor: &mut impl VisitMut<'arena>) {
+  visitor.visit_block(block);
+  walk_meta(&mut block.meta, visitor);
+  match &mut block.content {
+    BlockContent::Compound(blocks) => walk_blocks(blocks, visitor),
+    BlockContent::Simple(inlines) => walk_inlines(inlines, visitor),
+    BlockContent::Section(section) => walk_section(section, visitor),
+    BlockContent::Empty(EmptyMetadata::DiscreteHeading { content, .. }) => {
+      walk_inlines(content, visitor)
+    }
+    BlockContent::QuotedParagraph { quote, attr, cite } => {
+      walk_inlines(quote, visitor);
+      walk_inlines(attr, visitor);
+      cite.iter_mut().for_each(|cite| walk_inlines(cite, visitor));
+    }
+    BlockContent::List { items, .. } => {
+      for item in items.iter_mut() {
+        walk_inlines(&mut item.principle, visitor);
+        if let ListItemTypeMeta::DescList { description, extra_terms } = &mut item.type_meta {
+          for (term, _) in extra_terms.iter_mut() {
+            walk_inlines(term, visitor);
+          }
+          description.iter_mut().for_each(|b| walk_block(b, visitor));
+        }
+        walk_blocks(&mut item.blocks, visitor);
+      }
+    }
+    BlockContent::Table(table) => {
+      let rows = table.header_row.iter_mut().chain(table.rows.iter_mut());
+      let rows = rows.chain(table.footer_row.iter_mut());
+      for cell in rows.flat_map(|row| row.cells.iter_mut()) {
+        match &mut cell.content {
+          CellContent::AsciiDoc(document) => document.walk_mut(visitor),
+          CellContent::Literal(inlines) => walk_inlines(inlines, visitor),
+          CellContent::Default(paras)
+          | CellContent::Emphasis(paras)
+          | CellContent::Header(paras)
+          | CellContent::Monospace(paras)
+          | CellContent::Strong(paras) => {
+            paras.iter_mut().for_each(|p| walk_inlines(p, visitor));
+          }
+        }
+      }
+    }
+    BlockContent::Empty(_) | BlockContent::DocumentAttribute(..) => {}
+  }
+}
+
+fn walk_meta<'arena>(meta: &mut ChunkMeta<'arena>, visitor: &mut impl VisitMut<'arena>) {
+  if let Some(title) = meta.dot_line_title.as_mut() {
+    walk_inlines(title, visitor);
+  }
+}
+
+fn walk_inlines<'arena>(inlines: &mut InlineNodes<'arena>, visitor: &mut impl VisitMut<'arena>) {
+  visitor.visit_inlines(inlines);
+  for node in inlines.iter_mut() {
+    match &mut node.content {
+      Inline::Span(_, _, nested) | Inline::Quote(_, nested) | Inline::InlinePassthru(nested) => {
+        walk_inlines(nested, visitor)
+      }
+      Inline::Macro(MacroNode::Xref { linktext: Some(nested), .. })
+      | Inline::Macro(MacroNode::Footnote { text: Some(nested), .. })
+      | Inline::Macro(MacroNode::Mailto { linktext: Some(nested), .. })
+      | Inline::Macro(MacroNode::Stem { content: nested, .. }) => walk_inlines(nested, visitor),
+      // link text is the first positional attribute
+      Inline::Macro(MacroNode::Link { attrs: Some(attrs), .. }) => {
+        if let Some(Some(text)) = attrs.positional.first_mut() {
+          walk_inlines(text, visitor)
+        }
+      }
+      Inline::IndexTerm(IndexTerm {
+        term_type: IndexTermType::Visible { term },
+        ..
+      }) => walk_inlines(term, visitor),
+      _ => {}
+    }
+  }
+}
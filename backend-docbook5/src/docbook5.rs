@@ -0,0 +1,1660 @@
+use crate::internal::*;
+use ast::AdjacentNewline;
+use backend::num_str;
+
+#[derive(Debug, Default)]
+pub struct Docbook5 {
+  doc_meta: DocumentMeta,
+  xml: String,
+  alt_xml: String,
+  hardbreaks: bool,
+  default_hardbreaks: bool,
+  in_asciidoc_table_cell: bool,
+  info_start: usize,
+  section_tags: Vec<&'static str>,
+  list_stack: Vec<ListKind>,
+  desc_list_depth: u8,
+  in_bibliography: bool,
+  quote_stack: Vec<usize>,
+  attribution_start: Option<usize>,
+  table: TableState,
+  xref_depth: u8,
+  footnote_ids: Vec<String>,
+  discard_from: Option<usize>,
+  capture_start: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListKind {
+  Itemized,
+  Ordered,
+  Bibliography,
+  Callout,
+  Variable,
+  Glossary,
+  Qanda,
+}
+
+#[derive(Debug, Default)]
+struct TableState {
+  col: usize,
+  row_spans: Vec<u8>,
+  body_start: Option<usize>,
+  footer_start: usize,
+}
+
+impl Backend for Docbook5 {
+  type Output = String;
+  type Error = std::convert::Infallible;
+  const OUTFILESUFFIX: &'static str = ".xml";
+
+  fn set_job_attrs(attrs: &mut asciidork_core::JobAttrs) {
+    attrs.insert_unchecked("backend", JobAttr::readonly("docbook5"));
+    attrs.insert_unchecked("backend-docbook5", JobAttr::readonly(true));
+    attrs.insert_unchecked("basebackend", JobAttr::readonly("docbook"));
+    attrs.insert_unchecked("basebackend-docbook", JobAttr::readonly(true));
+  }
+
+  fn enter_document(&mut self, document: &Document) {
+    self.doc_meta = document.meta.clone();
+    utils::set_backend_attrs::<Self>(&mut self.doc_meta);
+    if !self.standalone() {
+      return;
+    }
+    self.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    if self.doc_meta.get("toc").is_some() {
+      self.push_str("<?asciidoc-toc?>");
+    }
+    if self.doc_meta.is_true("sectnums") {
+      self.push_str("<?asciidoc-numbered?>");
+    }
+    self.push(["<", self.root_tag()]);
+    self.push_str(r#" xmlns="http://docbook.org/ns/docbook""#);
+    self.push_str(r#" xmlns:xl="http://www.w3.org/1999/xlink" version="5.0""#);
+    if !self.doc_meta.is_true("nolang") {
+      let lang = self.doc_meta.string_or("lang", "en");
+      self.push([r#" xml:lang=""#, &lang, "\""]);
+    }
+    self.push_ch('>');
+  }
+
+  fn exit_document(&mut self, _document: &Document) {
+    if self.standalone() {
+      self.push(["</", self.root_tag(), ">"]);
+    }
+  }
+
+  fn enter_header(&mut self) {
+    if self.standalone() {
+      self.push_str("<info>");
+      self.info_start = self.xml.len();
+    }
+  }
+
+  fn exit_header(&mut self) {
+    if !self.standalone() {
+      return;
+    }
+    self.render_info_details();
+    if self.xml.len() == self.info_start {
+      self.xml.truncate(self.info_start - "<info>".len());
+    } else {
+      self.push_str("</info>");
+    }
+  }
+
+  fn enter_content(&mut self) {}
+  fn exit_content(&mut self) {}
+  fn enter_footer(&mut self) {}
+  fn exit_footer(&mut self) {}
+
+  fn enter_document_title(&mut self) {
+    if self.standalone() {
+      self.push_str("<title>");
+    } else {
+      self.start_buffering();
+    }
+  }
+
+  fn exit_document_title(&mut self) {
+    if self.standalone() {
+      self.push_str("</title>");
+    } else {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  // docbook toolchains generate their own toc, so we drop
+  // the rendered toc nodes, signalling it via `<?asciidoc-toc?>`
+  fn enter_toc(&mut self, _toc: &TableOfContents, _macro_block: Option<&Block>) {
+    self.discard_from = Some(self.xml.len());
+  }
+
+  fn exit_toc(&mut self, _toc: &TableOfContents) {
+    if let Some(start) = self.discard_from.take() {
+      self.xml.truncate(start);
+    }
+  }
+
+  fn enter_book_part(&mut self, part: &Part) {
+    self.push_str("<part");
+    self.push_id_and_roles(part.title.id.as_deref(), &part.title.meta.attrs);
+    self.push_ch('>');
+  }
+
+  fn exit_book_part(&mut self, _part: &Part) {
+    self.push_str("</part>");
+  }
+
+  fn enter_book_part_title(&mut self, _title: &PartTitle) {
+    self.push_str("<title>");
+  }
+
+  fn exit_book_part_title(&mut self, _title: &PartTitle) {
+    self.push_str("</title>");
+  }
+
+  fn enter_book_part_intro(&mut self, part: &Part) {
+    self.push_str("<partintro>");
+    if part.title.meta.title().is_some() {
+      self.push_str("<title>");
+    }
+  }
+
+  fn exit_book_part_intro(&mut self, _part: &Part) {
+    self.push_str("</partintro>");
+  }
+
+  fn enter_book_part_intro_content(&mut self, part: &Part) {
+    if part.title.meta.title().is_some() {
+      self.push_str("</title>");
+    }
+  }
+
+  fn exit_book_part_intro_content(&mut self, _part: &Part) {}
+
+  fn enter_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {
+    if self.doc_meta.get_doctype() == DocType::Book {
+      self.push_str("<preface><title>");
+      if let Some(title) = self.doc_meta.string("preface-title") {
+        self.push_str(&title);
+      }
+      self.push_str("</title>");
+    }
+  }
+
+  fn exit_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {
+    if self.doc_meta.get_doctype() == DocType::Book {
+      self.push_str("</preface>");
+    }
+  }
+
+  fn enter_section(&mut self, section: &Section) {
+    let tag = self.section_tag(section);
+    self.section_tags.push(tag);
+    if section.meta.attrs.special_sect() == Some(SpecialSection::Bibliography) {
+      self.in_bibliography = true;
+    }
+    self.push(["<", tag]);
+    self.push_id_and_roles(section.id.as_deref(), &section.meta.attrs);
+    self.push_ch('>');
+  }
+
+  fn exit_section(&mut self, section: &Section) {
+    if section.meta.attrs.special_sect() == Some(SpecialSection::Bibliography) {
+      self.in_bibliography = false;
+    }
+    let tag = self.section_tags.pop().unwrap_or("section");
+    self.push(["</", tag, ">"]);
+  }
+
+  fn enter_section_heading(&mut self, _section: &Section) {
+    self.push_str("<title>");
+  }
+
+  fn exit_section_heading(&mut self, _section: &Section) {
+    self.push_str("</title>");
+  }
+
+  fn enter_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+  fn exit_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+
+  fn enter_simple_block_content(&mut self, block: &Block) {
+    if wraps_simple_content(block) {
+      self.push_str("<simpara>");
+    }
+    if block.meta.attrs.has_option("hardbreaks") {
+      self.hardbreaks = true;
+    }
+  }
+
+  fn exit_simple_block_content(&mut self, block: &Block) {
+    if wraps_simple_content(block) {
+      self.push_str("</simpara>");
+    } else if block.context == BlockContext::Verse {
+      self.push_str("</literallayout>");
+    }
+    self.hardbreaks = self.default_hardbreaks;
+  }
+
+  fn enter_paragraph_block(&mut self, block: &Block) {
+    if self.doc_meta.get_doctype() == DocType::Inline {
+      return;
+    }
+    if block.has_title() {
+      self.push_str("<formalpara");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+      self.push_buffered_title();
+      self.push_str("<para>");
+    } else {
+      self.push_str("<simpara");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+    }
+  }
+
+  fn exit_paragraph_block(&mut self, block: &Block) {
+    if self.doc_meta.get_doctype() == DocType::Inline {
+      return;
+    }
+    if block.has_title() {
+      self.push_str("</para></formalpara>");
+    } else {
+      self.push_str("</simpara>");
+    }
+  }
+
+  fn enter_sidebar_block(&mut self, block: &Block) {
+    self.push_str("<sidebar");
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+  }
+
+  fn exit_sidebar_block(&mut self, _block: &Block) {
+    self.push_str("</sidebar>");
+  }
+
+  fn enter_listing_block(&mut self, block: &Block) {
+    let tag = self.listing_tag(block);
+    if block.has_title() {
+      self.push_str("<formalpara");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+      self.push_buffered_title();
+      self.push(["<para><", tag]);
+    } else {
+      self.push(["<", tag]);
+      self.push_block_id_and_roles(block);
+    }
+    if tag == "programlisting" {
+      let doc_lang = self.doc_meta.string("source-language");
+      if let Some(lang) = block.meta.attrs.source_language().or(doc_lang.as_deref()) {
+        self.push_xml_attr("language", lang);
+      }
+      let numbered = block.meta.attrs.has_option("numbered")
+        || block.meta.attrs.has_option("linenums")
+        || block.meta.attrs.str_positional_at(2) == Some("linenums");
+      if numbered {
+        self.push_str(r#" linenumbering="numbered""#);
+        if let Some(start) = block.meta.attrs.named("start") {
+          self.push_xml_attr("startinglinenumber", start);
+        }
+      } else {
+        self.push_str(r#" linenumbering="unnumbered""#);
+      }
+    }
+    self.push_ch('>');
+  }
+
+  fn exit_listing_block(&mut self, block: &Block) {
+    self.push(["</", self.listing_tag(block), ">"]);
+    if block.has_title() {
+      self.push_str("</para></formalpara>");
+    }
+  }
+
+  fn enter_literal_block(&mut self, block: &Block) {
+    if block.has_title() {
+      self.push_str("<formalpara");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+      self.push_buffered_title();
+      self.push_str("<para><screen>");
+    } else {
+      self.push_str("<screen");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+    }
+  }
+
+  fn exit_literal_block(&mut self, block: &Block) {
+    self.push_str("</screen>");
+    if block.has_title() {
+      self.push_str("</para></formalpara>");
+    }
+  }
+
+  fn enter_stem_block(&mut self, block: &Block, _notation: StemNotation) {
+    let tag = if block.has_title() { "equation" } else { "informalequation" };
+    self.push(["<", tag]);
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+    self.capture_start = Some(self.xml.len());
+  }
+
+  fn exit_stem_block(&mut self, block: &Block, notation: StemNotation) {
+    self.finish_equation(notation, true);
+    let tag = if block.has_title() { "equation" } else { "informalequation" };
+    self.push(["</", tag, ">"]);
+  }
+
+  fn enter_quoted_paragraph(&mut self, block: &Block) {
+    self.open_blockquote(block);
+    self.push_str("<simpara>");
+  }
+
+  fn exit_quoted_paragraph(&mut self, _block: &Block) {
+    self.close_blockquote();
+  }
+
+  fn enter_quote_block(&mut self, block: &Block, _has_attribution: bool) {
+    self.open_blockquote(block);
+  }
+
+  fn exit_quote_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.close_blockquote();
+  }
+
+  fn enter_quote_attribution(&mut self, block: &Block, _has_cite: bool) {
+    if block.context == BlockContext::QuotedParagraph {
+      self.push_str("</simpara>");
+    }
+    self.attribution_start = Some(self.xml.len());
+    self.push_str("<attribution>");
+  }
+
+  fn exit_quote_attribution(&mut self, _block: &Block, has_cite: bool) {
+    if !has_cite {
+      self.push_str("</attribution>");
+    }
+  }
+
+  fn enter_quote_cite(&mut self, block: &Block, has_attribution: bool) {
+    if !has_attribution {
+      if block.context == BlockContext::QuotedParagraph {
+        self.push_str("</simpara>");
+      }
+      self.attribution_start = Some(self.xml.len());
+      self.push_str("<attribution>");
+    }
+    self.push_str("<citetitle>");
+  }
+
+  fn exit_quote_cite(&mut self, _block: &Block, _has_attribution: bool) {
+    self.push_str("</citetitle></attribution>");
+  }
+
+  fn enter_verse_block(&mut self, block: &Block, _has_attribution: bool) {
+    self.open_blockquote(block);
+    self.push_str("<literallayout>");
+  }
+
+  fn exit_verse_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.close_blockquote();
+  }
+
+  fn enter_example_block(&mut self, block: &Block) {
+    let tag = if block.has_title() { "example" } else { "informalexample" };
+    self.push(["<", tag]);
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+  }
+
+  fn exit_example_block(&mut self, block: &Block) {
+    let tag = if block.has_title() { "example" } else { "informalexample" };
+    self.push(["</", tag, ">"]);
+  }
+
+  fn enter_open_block(&mut self, block: &Block) {
+    if block.meta.attrs.special_sect() == Some(SpecialSection::Abstract) {
+      self.push_str("<abstract>");
+      self.push_buffered_title();
+    } else if block.has_title() {
+      self.push_str("<formalpara");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+      self.push_buffered_title();
+      self.push_str("<para>");
+    } else if block.meta.attrs.id().is_some() || block.meta.attrs.roles().next().is_some() {
+      self.push_str("<para");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+    }
+  }
+
+  fn exit_open_block(&mut self, block: &Block) {
+    if block.meta.attrs.special_sect() == Some(SpecialSection::Abstract) {
+      self.push_str("</abstract>");
+    } else if block.has_title() {
+      self.push_str("</para></formalpara>");
+    } else if block.meta.attrs.id().is_some() || block.meta.attrs.roles().next().is_some() {
+      self.push_str("</para>");
+    }
+  }
+
+  fn enter_discrete_heading(&mut self, level: u8, id: Option<&str>, block: &Block) {
+    self.push_str("<bridgehead");
+    self.push_id_and_roles(id, &block.meta.attrs);
+    self.push([r#" renderas="sect"#, &num_str!(level.clamp(1, 5)), "\">"]);
+  }
+
+  fn exit_discrete_heading(&mut self, _level: u8, _id: Option<&str>, _block: &Block) {
+    self.push_str("</bridgehead>");
+  }
+
+  fn enter_unordered_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    if self.in_bibliography || block.meta.attrs.has_str_positional("bibliography") {
+      self.list_stack.push(ListKind::Bibliography);
+      self.push_str("<bibliodiv");
+      self.push_block_id_and_roles(block);
+      self.push_ch('>');
+    } else {
+      self.list_stack.push(ListKind::Itemized);
+      self.push_str("<itemizedlist");
+      self.push_block_id_and_roles(block);
+      if let Some(mark) = block.meta.attrs.unordered_list_custom_marker_style() {
+        self.push_xml_attr("mark", mark);
+      }
+      self.push_ch('>');
+    }
+    self.push_buffered_title();
+  }
+
+  fn exit_unordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    match self.list_stack.pop() {
+      Some(ListKind::Bibliography) => self.push_str("</bibliodiv>"),
+      _ => self.push_str("</itemizedlist>"),
+    }
+  }
+
+  fn enter_ordered_list(&mut self, block: &Block, items: &[ListItem], depth: u8) {
+    self.list_stack.push(ListKind::Ordered);
+    let numeration = block
+      .meta
+      .attrs
+      .ordered_list_custom_number_style()
+      .unwrap_or_else(|| {
+        backend::html::list::class_from_depth(depth.saturating_sub(self.desc_list_depth))
+      });
+    self.push_str("<orderedlist");
+    self.push_block_id_and_roles(block);
+    self.push_xml_attr("numeration", numeration);
+    if let Some(start) = block.meta.attrs.named("start") {
+      self.push_xml_attr("startingnumber", start);
+    } else if let Some(ListMarker::Digits(n)) = items.first().map(|item| &item.marker)
+      && *n != 1
+    {
+      self.push_xml_attr("startingnumber", &n.to_string());
+    }
+    self.push_ch('>');
+    self.push_buffered_title();
+  }
+
+  fn exit_ordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+    self.push_str("</orderedlist>");
+  }
+
+  fn enter_callout_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.push(ListKind::Callout);
+    self.push_str("<calloutlist");
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+  }
+
+  fn exit_callout_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+    self.push_str("</calloutlist>");
+  }
+
+  fn enter_description_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.desc_list_depth += 1;
+    if block.meta.attrs.special_sect() == Some(SpecialSection::Glossary) {
+      // glossentries are emitted directly into the enclosing glossary
+      self.list_stack.push(ListKind::Glossary);
+      return;
+    }
+    let (kind, tag) = if block.meta.attrs.has_str_positional("qanda") {
+      (ListKind::Qanda, "qandaset")
+    } else {
+      (ListKind::Variable, "variablelist")
+    };
+    self.list_stack.push(kind);
+    self.push(["<", tag]);
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+  }
+
+  fn exit_description_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.desc_list_depth -= 1;
+    match self.list_stack.pop() {
+      Some(ListKind::Qanda) => self.push_str("</qandaset>"),
+      Some(ListKind::Glossary) => {}
+      _ => self.push_str("</variablelist>"),
+    }
+  }
+
+  fn enter_description_list_term(&mut self, _item: &ListItem, num: usize, _total: usize) {
+    match self.list_kind() {
+      ListKind::Glossary => {
+        self.push_str(iff!(num == 1, "<glossentry><glossterm>", "<glossterm>"));
+      }
+      ListKind::Qanda => {
+        self.push_str(iff!(
+          num == 1,
+          "<qandaentry><question><simpara>",
+          "<simpara>"
+        ));
+      }
+      _ => self.push_str(iff!(num == 1, "<varlistentry><term>", "<term>")),
+    }
+  }
+
+  fn exit_description_list_term(&mut self, item: &ListItem, num: usize, total: usize) {
+    let kind = self.list_kind();
+    match kind {
+      ListKind::Glossary => self.push_str("</glossterm>"),
+      ListKind::Qanda => self.push_str("</simpara>"),
+      _ => self.push_str("</term>"),
+    }
+    if num < total {
+      return;
+    }
+    if kind == ListKind::Qanda {
+      self.push_str("</question>");
+    }
+    let has_description = !item.blocks.is_empty()
+      || matches!(
+        item.type_meta,
+        ListItemTypeMeta::DescList { description: Some(_), .. }
+      );
+    if !has_description {
+      match kind {
+        ListKind::Glossary => self.push_str("</glossentry>"),
+        ListKind::Qanda => self.push_str("</qandaentry>"),
+        _ => self.push_str("<listitem><simpara></simpara></listitem></varlistentry>"),
+      }
+    }
+  }
+
+  fn enter_description_list_description(&mut self, _item: &ListItem) {
+    match self.list_kind() {
+      ListKind::Glossary => self.push_str("<glossdef>"),
+      ListKind::Qanda => self.push_str("<answer>"),
+      _ => self.push_str("<listitem>"),
+    }
+  }
+
+  fn exit_description_list_description(&mut self, _item: &ListItem) {
+    match self.list_kind() {
+      ListKind::Glossary => self.push_str("</glossdef></glossentry>"),
+      ListKind::Qanda => self.push_str("</answer></qandaentry>"),
+      _ => self.push_str("</listitem></varlistentry>"),
+    }
+  }
+
+  fn enter_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+  fn exit_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+
+  fn enter_list_item_principal(&mut self, item: &ListItem, variant: ListVariant) {
+    match (variant, self.list_kind()) {
+      (ListVariant::Callout, _) => {
+        self.push_str(r#"<callout arearefs=""#);
+        if let ListItemTypeMeta::Callout(callouts) = &item.type_meta {
+          for (idx, callout) in callouts.iter().enumerate() {
+            if idx > 0 {
+              self.push_ch(' ');
+            }
+            self.push_str(&callout_id(callout));
+          }
+        }
+        self.push_str(r#""><para>"#);
+      }
+      (_, ListKind::Bibliography) => self.push_str("<bibliomixed><bibliomisc>"),
+      _ => {
+        self.push_str("<listitem><simpara>");
+        if let ListItemTypeMeta::Checklist(checked, _) = &item.type_meta {
+          self.push_str(iff!(*checked, "&#10003;", "&#10063;"));
+        }
+      }
+    }
+  }
+
+  fn exit_list_item_principal(&mut self, _item: &ListItem, variant: ListVariant) {
+    match (variant, self.list_kind()) {
+      (ListVariant::Callout, _) => self.push_str("</para>"),
+      (_, ListKind::Bibliography) => self.push_str("</bibliomisc>"),
+      _ => self.push_str("</simpara>"),
+    }
+  }
+
+  fn enter_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, _variant: ListVariant) {
+  }
+
+  fn exit_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, variant: ListVariant) {
+    match (variant, self.list_kind()) {
+      (ListVariant::Callout, _) => self.push_str("</callout>"),
+      (_, ListKind::Bibliography) => self.push_str("</bibliomixed>"),
+      _ => self.push_str("</listitem>"),
+    }
+  }
+
+  fn asciidoc_table_cell_backend(&mut self) -> Self {
+    Self {
+      in_asciidoc_table_cell: true,
+      ..Self::default()
+    }
+  }
+
+  fn visit_asciidoc_table_cell_result(&mut self, cell_backend: Self) {
+    self.xml.push_str(&cell_backend.into_result().unwrap());
+  }
+
+  fn enter_table(&mut self, table: &Table, block: &Block) {
+    let tag = if block.has_title() { "table" } else { "informaltable" };
+    self.push(["<", tag]);
+    self.push_block_id_and_roles(block);
+    let frame = match self.resolved_table_attr("frame", block).as_str() {
+      "ends" => "topbot".to_string(),
+      frame => frame.to_string(),
+    };
+    let grid = self.resolved_table_attr("grid", block);
+    let (rowsep, colsep) = match grid.as_str() {
+      "none" => ("0", "0"),
+      "cols" => ("0", "1"),
+      "rows" => ("1", "0"),
+      _ => ("1", "1"),
+    };
+    self.push([r#" frame=""#, &frame, "\""]);
+    self.push([r#" rowsep=""#, rowsep, r#"" colsep=""#, colsep]);
+    self.push_ch('"');
+    if block.meta.attrs.has_option("rotate") {
+      self.push_str(r#" orient="land""#);
+    }
+    if block.meta.attrs.has_option("pgwide") {
+      self.push_str(r#" pgwide="1""#);
+    }
+    self.push_ch('>');
+    self.push_buffered_title();
+    let num_cols = table.col_widths.len();
+    self.push([r#"<tgroup cols=""#, &num_cols.to_string(), "\">"]);
+    for (idx, width) in table.col_widths.distribute().enumerate() {
+      let col = (idx + 1).to_string();
+      self.push([r#"<colspec colname="col_"#, &col, "\""]);
+      if let DistributedColWidth::Percentage(width) = width {
+        let width = format!("{width:.4}");
+        let width = width.trim_end_matches('0').trim_end_matches('.');
+        self.push([r#" colwidth=""#, width, "*\""]);
+      }
+      self.push_str("/>");
+    }
+    self.table = TableState {
+      row_spans: vec![0; num_cols],
+      ..TableState::default()
+    };
+  }
+
+  fn exit_table(&mut self, _table: &Table, block: &Block) {
+    self.push_str("</tgroup>");
+    self.push_str(iff!(block.has_title(), "</table>", "</informaltable>"));
+  }
+
+  fn enter_table_section(&mut self, section: TableSection) {
+    match section {
+      TableSection::Header => self.push_str("<thead>"),
+      TableSection::Body => {
+        self.table.body_start = Some(self.xml.len());
+        self.push_str("<tbody>");
+      }
+      TableSection::Footer => {
+        self.table.footer_start = self.xml.len();
+        self.push_str("<tfoot>");
+      }
+    }
+  }
+
+  fn exit_table_section(&mut self, section: TableSection) {
+    match section {
+      TableSection::Header => self.push_str("</thead>"),
+      TableSection::Body => self.push_str("</tbody>"),
+      TableSection::Footer => {
+        self.push_str("</tfoot>");
+        // cals tables require the footer to precede the body
+        if let Some(body_start) = self.table.body_start {
+          let tfoot = self.xml.split_off(self.table.footer_start);
+          self.xml.insert_str(body_start, &tfoot);
+        }
+      }
+    }
+  }
+
+  fn enter_table_row(&mut self, _row: &Row, _section: TableSection) {
+    self.table.col = 0;
+    self.push_str("<row>");
+  }
+
+  fn exit_table_row(&mut self, _row: &Row, _section: TableSection) {
+    self
+      .table
+      .row_spans
+      .iter_mut()
+      .for_each(|span| *span = span.saturating_sub(1));
+    self.push_str("</row>");
+  }
+
+  fn enter_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    let spans = &mut self.table.row_spans;
+    let mut col = self.table.col;
+    while spans.get(col).is_some_and(|span| *span > 0) {
+      col += 1;
+    }
+    let start = col + 1;
+    let end = col + cell.col_span as usize;
+    if cell.row_span > 1 {
+      spans
+        .iter_mut()
+        .skip(col)
+        .take(cell.col_span as usize)
+        .for_each(|span| *span = cell.row_span);
+    }
+    self.table.col = end;
+    self.push_str("<entry");
+    if cell.col_span > 1 {
+      self.push([r#" namest="col_"#, &start.to_string(), "\""]);
+      self.push([r#" nameend="col_"#, &end.to_string(), "\""]);
+    }
+    if cell.row_span > 1 {
+      self.push([r#" morerows=""#, &(cell.row_span - 1).to_string(), "\""]);
+    }
+    self.push_str(match cell.h_align {
+      HorizontalAlignment::Left => r#" align="left""#,
+      HorizontalAlignment::Center => r#" align="center""#,
+      HorizontalAlignment::Right => r#" align="right""#,
+    });
+    self.push_str(match cell.v_align {
+      VerticalAlignment::Top => r#" valign="top">"#,
+      VerticalAlignment::Middle => r#" valign="middle">"#,
+      VerticalAlignment::Bottom => r#" valign="bottom">"#,
+    });
+    if matches!(cell.content, CellContent::Literal(_)) {
+      self.push_str(r#"<literallayout class="monospaced">"#);
+    }
+  }
+
+  fn exit_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    if matches!(cell.content, CellContent::Literal(_)) {
+      self.push_str("</literallayout>");
+    }
+    self.push_str("</entry>");
+  }
+
+  fn enter_cell_paragraph(&mut self, cell: &Cell, section: TableSection) {
+    if section == TableSection::Header {
+      return;
+    }
+    self.push_str("<simpara>");
+    match &cell.content {
+      CellContent::Emphasis(_) => self.push_str("<emphasis>"),
+      CellContent::Monospace(_) => self.push_str("<literal>"),
+      CellContent::Strong(_) => self.push_str(r#"<emphasis role="strong">"#),
+      _ => {}
+    }
+  }
+
+  fn exit_cell_paragraph(&mut self, cell: &Cell, section: TableSection) {
+    if section == TableSection::Header {
+      return;
+    }
+    match &cell.content {
+      CellContent::Emphasis(_) | CellContent::Strong(_) => self.push_str("</emphasis>"),
+      CellContent::Monospace(_) => self.push_str("</literal>"),
+      _ => {}
+    }
+    self.push_str("</simpara>");
+  }
+
+  fn enter_inline_italic(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline("<emphasis>", attrs);
+  }
+
+  fn exit_inline_italic(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</emphasis>", attrs);
+  }
+
+  fn enter_inline_mono(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline("<literal>", attrs);
+  }
+
+  fn exit_inline_mono(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</literal>", attrs);
+  }
+
+  fn enter_inline_bold(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline(r#"<emphasis role="strong">"#, attrs);
+  }
+
+  fn exit_inline_bold(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</emphasis>", attrs);
+  }
+
+  fn enter_inline_lit_mono(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline("<literal>", attrs);
+  }
+
+  fn exit_inline_lit_mono(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</literal>", attrs);
+  }
+
+  fn enter_inline_stem(&mut self, _notation: StemNotation) {
+    self.push_str("<inlineequation>");
+    self.capture_start = Some(self.xml.len());
+  }
+
+  fn exit_inline_stem(&mut self, notation: StemNotation) {
+    self.finish_equation(notation, false);
+    self.push_str("</inlineequation>");
+  }
+
+  fn enter_inline_highlight(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline(r#"<emphasis role="marked">"#, attrs);
+  }
+
+  fn exit_inline_highlight(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</emphasis>", attrs);
+  }
+
+  fn enter_inline_subscript(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline("<subscript>", attrs);
+  }
+
+  fn exit_inline_subscript(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</subscript>", attrs);
+  }
+
+  fn enter_inline_superscript(&mut self, attrs: Option<&AttrList>) {
+    self.open_inline("<superscript>", attrs);
+  }
+
+  fn exit_inline_superscript(&mut self, attrs: Option<&AttrList>) {
+    self.close_inline("</superscript>", attrs);
+  }
+
+  fn visit_spaced_dashes(&mut self, len: u8, _adjacent_newline: AdjacentNewline) {
+    if len == 2 {
+      self.push_str("&#8201;&#8212;&#8201;");
+    } else {
+      self.push_str(" --- ");
+    }
+  }
+
+  fn enter_visible_index_term(&mut self) -> bool {
+    self.capture_start = Some(self.xml.len());
+    true
+  }
+
+  fn exit_visible_index_term(&mut self) {
+    if let Some(start) = self.capture_start.take() {
+      let term = self.xml[start..].to_string();
+      self.push(["<indexterm><primary>", &term, "</primary></indexterm>"]);
+    }
+  }
+
+  fn enter_concealed_index_term(&mut self, _num_terms: u8) -> bool {
+    self.push_str("<indexterm>");
+    true
+  }
+
+  fn exit_concealed_index_term(&mut self, _num_terms: u8) {
+    self.push_str("</indexterm>");
+  }
+
+  fn enter_concealed_index_term_primary(&mut self) {
+    self.push_str("<primary>");
+  }
+
+  fn exit_concealed_index_term_primary(&mut self) {
+    self.push_str("</primary>");
+  }
+
+  fn enter_concealed_index_term_secondary(&mut self) {
+    self.push_str("<secondary>");
+  }
+
+  fn exit_concealed_index_term_secondary(&mut self) {
+    self.push_str("</secondary>");
+  }
+
+  fn enter_concealed_index_term_tertiary(&mut self) {
+    self.push_str("<tertiary>");
+  }
+
+  fn exit_concealed_index_term_tertiary(&mut self) {
+    self.push_str("</tertiary>");
+  }
+
+  fn visit_inline_specialchar(&mut self, char: &SpecialCharKind) {
+    match char {
+      SpecialCharKind::Ampersand => self.push_str("&amp;"),
+      SpecialCharKind::LessThan => self.push_str("&lt;"),
+      SpecialCharKind::GreaterThan => self.push_str("&gt;"),
+    }
+  }
+
+  fn visit_symbol(&mut self, kind: SymbolKind) {
+    match kind {
+      SymbolKind::Copyright => self.push_str("&#169;"),
+      SymbolKind::Registered => self.push_str("&#174;"),
+      SymbolKind::Trademark => self.push_str("&#8482;"),
+      SymbolKind::EmDash => self.push_str("&#8212;&#8203;"),
+      SymbolKind::TripleDash => self.push_str("---"),
+      SymbolKind::Ellipsis => self.push_str("&#8230;&#8203;"),
+      SymbolKind::SingleRightArrow => self.push_str("&#8594;"),
+      SymbolKind::DoubleRightArrow => self.push_str("&#8658;"),
+      SymbolKind::SingleLeftArrow => self.push_str("&#8592;"),
+      SymbolKind::DoubleLeftArrow => self.push_str("&#8656;"),
+    }
+  }
+
+  fn enter_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_str("&#8220;"),
+      QuoteKind::Single => self.push_str("&#8216;"),
+    }
+  }
+
+  fn exit_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_str("&#8221;"),
+      QuoteKind::Single => self.push_str("&#8217;"),
+    }
+  }
+
+  fn visit_curly_quote(&mut self, kind: CurlyKind) {
+    match kind {
+      CurlyKind::LeftDouble => self.push_str("&#8221;"),
+      CurlyKind::RightDouble => self.push_str("&#8220;"),
+      CurlyKind::LeftSingle => self.push_str("&#8216;"),
+      CurlyKind::RightSingle => self.push_str("&#8217;"),
+      CurlyKind::LegacyImplicitApostrophe => self.push_str("&#8217;"),
+    }
+  }
+
+  fn visit_multichar_whitespace(&mut self, whitespace: &str) {
+    self.push_str(whitespace);
+  }
+
+  fn visit_thematic_break(&mut self, _block: &Block) {
+    self.push_str("<simpara><?asciidoc-hr?></simpara>");
+  }
+
+  fn visit_page_break(&mut self, _block: &Block) {
+    self.push_str("<simpara><?asciidoc-pagebreak?></simpara>");
+  }
+
+  fn visit_inline_text(&mut self, text: &str) {
+    self.push_str(text);
+  }
+
+  fn visit_joining_newline(&mut self) {
+    if self.hardbreaks {
+      self.push_str("<?asciidoc-br?>");
+    }
+    self.push_ch('\n');
+  }
+
+  fn enter_text_span(&mut self, attrs: Option<&AttrList>) {
+    self.push_str("<phrase");
+    if let Some(attrs) = attrs {
+      self.push_id_and_roles(attrs.id().map(|id| &**id), attrs);
+    }
+    self.push_ch('>');
+  }
+
+  fn exit_text_span(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("</phrase>");
+  }
+
+  fn enter_xref(&mut self, target: &SourceString, _has_reftext: bool, kind: XrefKind) {
+    self.xref_depth += 1;
+    if self.xref_depth > 1 {
+      return;
+    }
+    if utils::xref::is_interdoc(target, kind) {
+      let href = utils::xref::href(target, &self.doc_meta, kind, true);
+      self.push_str(r#"<link xl:href=""#);
+      self.push_str_attr_escaped(&href);
+    } else {
+      self.push_str(r#"<link linkend=""#);
+      self.push_str(utils::xref::remove_leading_hash(target));
+    }
+    self.push_str("\">");
+  }
+
+  fn exit_xref(&mut self, _target: &SourceString, _has_reftext: bool, _kind: XrefKind) {
+    self.xref_depth -= 1;
+    if self.xref_depth == 0 {
+      self.push_str("</link>");
+    }
+  }
+
+  fn enter_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_ch('[');
+    }
+  }
+
+  fn exit_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_ch(']');
+    }
+  }
+
+  fn visit_missing_xref(
+    &mut self,
+    target: &SourceString,
+    kind: XrefKind,
+    _doc_title: Option<&DocTitle>,
+  ) {
+    if utils::xref::is_interdoc(target, kind) {
+      let href = utils::xref::href(target, &self.doc_meta, kind, false);
+      self.push_str(utils::xref::remove_leading_hash(&href));
+    } else {
+      self.push(["[", utils::xref::remove_leading_hash(target), "]"]);
+    }
+  }
+
+  fn visit_inline_anchor(&mut self, id: &str) {
+    self.push([
+      r#"<anchor xml:id=""#,
+      id,
+      r#"" xreflabel="["#,
+      id,
+      r#"]"/>"#,
+    ]);
+  }
+
+  fn visit_biblio_anchor(&mut self, id: &str, reftext: Option<&str>) {
+    let label = reftext.unwrap_or(id);
+    self.push([
+      r#"<anchor xml:id=""#,
+      id,
+      r#"" xreflabel="["#,
+      label,
+      r#"]"/>"#,
+    ]);
+    self.push(["[", label, "]"]);
+  }
+
+  fn visit_callout(&mut self, callout: Callout) {
+    if !self.xml.ends_with(' ') {
+      self.push_ch(' ');
+    }
+    self.push([r#"<co xml:id=""#, &callout_id(&callout), r#""/>"#]);
+  }
+
+  fn visit_callout_tuck(&mut self, comment: &str) {
+    self.push_str(comment);
+  }
+
+  fn visit_linebreak(&mut self) {
+    self.push_str("<?asciidoc-br?>\n");
+  }
+
+  fn visit_button_macro(&mut self, text: &SourceString) {
+    self.push(["<guibutton>", text, "</guibutton>"]);
+  }
+
+  fn visit_menu_macro(&mut self, items: &[SourceString]) {
+    let Some((menu, rest)) = items.split_first() else {
+      return;
+    };
+    if rest.is_empty() {
+      self.push(["<guimenu>", menu, "</guimenu>"]);
+      return;
+    }
+    self.push(["<menuchoice><guimenu>", menu, "</guimenu>"]);
+    for (idx, item) in rest.iter().enumerate() {
+      if idx == rest.len() - 1 {
+        self.push([" <guimenuitem>", item, "</guimenuitem>"]);
+      } else {
+        self.push([" <guisubmenu>", item, "</guisubmenu>"]);
+      }
+    }
+    self.push_str("</menuchoice>");
+  }
+
+  fn visit_keyboard_macro(&mut self, keys: &[&str]) {
+    if keys.len() > 1 {
+      self.push_str("<keycombo>");
+    }
+    for key in keys {
+      self.push(["<keycap>", key, "</keycap>"]);
+    }
+    if keys.len() > 1 {
+      self.push_str("</keycombo>");
+    }
+  }
+
+  fn visit_image_macro(&mut self, target: &SourceString, attrs: &AttrList, _kind: &ImageKind) {
+    self.push_str("<inlinemediaobject");
+    self.push_id_and_roles(None, attrs);
+    self.push_ch('>');
+    self.push_image_object(target, attrs);
+    self.push_str("</inlinemediaobject>");
+  }
+
+  fn visit_icon_macro(&mut self, target: &SourceString, attrs: &AttrList) {
+    let iconsdir = self.doc_meta.string_or("iconsdir", "./images/icons");
+    let icontype = self.doc_meta.string_or("icontype", "png");
+    self.push_str("<inlinemediaobject");
+    self.push_id_and_roles(None, attrs);
+    self.push_str(r#"><imageobject><imagedata fileref=""#);
+    self.push_url_encoded(&format!("{iconsdir}/{}.{icontype}", &**target));
+    self.push_str(r#""/></imageobject><textobject><phrase>"#);
+    self.push_str_attr_escaped(attrs.named("alt").unwrap_or(target));
+    self.push_str("</phrase></textobject></inlinemediaobject>");
+  }
+
+  fn visit_audio_macro(&mut self, target: &SourceString, _attrs: &AttrList, block: &Block) {
+    let tag = self.open_figure(block, None);
+    self.push_str(r#"<mediaobject><audioobject><audiodata fileref=""#);
+    self.push_str_attr_escaped(target);
+    self.push_str(r#""/></audioobject></mediaobject>"#);
+    self.push(["</", tag, ">"]);
+  }
+
+  fn visit_video_macro(&mut self, target: &SourceString, attrs: &AttrList, block: &Block) {
+    let tag = self.open_figure(block, None);
+    self.push_str(r#"<mediaobject><videoobject><videodata fileref=""#);
+    self.push_str_attr_escaped(target);
+    self.push_ch('"');
+    self.push_dimension_attrs(attrs);
+    self.push_str("/></videoobject></mediaobject>");
+    self.push(["</", tag, ">"]);
+  }
+
+  fn enter_link_macro(
+    &mut self,
+    target: &SourceString,
+    _attrs: Option<&AttrList>,
+    scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    _has_link_text: bool,
+    _blank_window_shorthand: bool,
+  ) {
+    if resolving_xref {
+      return;
+    }
+    self.push_str(r#"<link xl:href=""#);
+    if matches!(scheme, Some(UrlScheme::Mailto)) {
+      self.push_str("mailto:");
+    }
+    self.push_str_attr_escaped(target);
+    self.push_str("\">");
+  }
+
+  fn exit_link_macro(
+    &mut self,
+    target: &SourceString,
+    _attrs: Option<&AttrList>,
+    _scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+  ) {
+    if resolving_xref {
+      return;
+    }
+    if !has_link_text {
+      if self.doc_meta.is_true("hide-uri-scheme") {
+        self.push_specialchar_escaped(file::remove_uri_scheme(target));
+      } else {
+        self.push_specialchar_escaped(target);
+      }
+    }
+    self.push_str("</link>");
+  }
+
+  fn enter_mailto_macro(
+    &mut self,
+    address: &SourceString,
+    subject: Option<&SourceString>,
+    body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    has_link_text: bool,
+  ) {
+    self.push([r#"<link xl:href="mailto:"#, address]);
+    if let Some(subject) = subject {
+      self.push_str("?subject=");
+      self.push_url_encoded(subject);
+    }
+    if let Some(body) = body {
+      self.push_str(iff!(subject.is_some(), "&amp;body=", "?body="));
+      self.push_url_encoded(body);
+    }
+    self.push_str("\">");
+    if !has_link_text {
+      self.push_str(address);
+    }
+  }
+
+  fn exit_mailto_macro(
+    &mut self,
+    _address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    _has_link_text: bool,
+  ) {
+    self.push_str("</link>");
+  }
+
+  fn enter_admonition_block(
+    &mut self,
+    kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    block: &Block,
+  ) {
+    self.push(["<", kind.lowercase_str()]);
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+    if !matches!(block.content, BlockContent::Compound(_)) {
+      self.push_str("<simpara>");
+    }
+  }
+
+  fn exit_admonition_block(
+    &mut self,
+    kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    block: &Block,
+  ) {
+    if !matches!(block.content, BlockContent::Compound(_)) {
+      self.push_str("</simpara>");
+    }
+    self.push(["</", kind.lowercase_str(), ">"]);
+  }
+
+  fn enter_image_block(
+    &mut self,
+    img_target: &SourceString,
+    img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    block: &Block,
+  ) {
+    self.open_figure(block, img_attrs.named("title"));
+    self.push_str("<mediaobject>");
+    self.push_image_object(img_target, img_attrs);
+    self.push_str("</mediaobject>");
+  }
+
+  fn exit_image_block(
+    &mut self,
+    _img_target: &SourceString,
+    img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    block: &Block,
+  ) {
+    if block.has_title() || img_attrs.named("title").is_some() {
+      self.push_str("</figure>");
+    } else {
+      self.push_str("</informalfigure>");
+    }
+  }
+
+  fn visit_document_attribute_decl(&mut self, name: &str, value: &AttrValue) {
+    if name == "hardbreaks-option" {
+      self.default_hardbreaks = value.is_true();
+      self.hardbreaks = value.is_true();
+    }
+    _ = self.doc_meta.insert_doc_attr(name, value.clone());
+  }
+
+  fn enter_footnote(&mut self, id: Option<&SourceString>) {
+    if let Some(id) = id
+      && self.footnote_ids.iter().any(|prev| prev == &**id)
+    {
+      self.push([r#"<footnoteref linkend="_footnote_"#, id, r#""/>"#]);
+      // discard duplicate content, common when "externalizing" footnotes by attr ref
+      self.discard_from = Some(self.xml.len());
+      return;
+    }
+    self.push_str("<footnote");
+    if let Some(id) = id {
+      self.push([r#" xml:id="_footnote_"#, id, "\""]);
+      self.footnote_ids.push(id.to_string());
+    }
+    self.push_str("><simpara>");
+  }
+
+  fn exit_footnote(&mut self, _id: Option<&SourceString>) {
+    if let Some(start) = self.discard_from.take() {
+      self.xml.truncate(start);
+    } else {
+      self.push_str("</simpara></footnote>");
+    }
+  }
+
+  fn enter_meta_title(&mut self, _block: &Block) {
+    self.start_buffering();
+  }
+
+  fn exit_meta_title(&mut self, block: &Block) {
+    if block.context != BlockContext::Passthrough {
+      self.stop_buffering();
+    } else {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  fn into_result(self) -> Result<Self::Output, Self::Error> {
+    Ok(self.xml)
+  }
+
+  fn result(&self) -> Result<&Self::Output, Self::Error> {
+    Ok(&self.xml)
+  }
+}
+
+impl Docbook5 {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn standalone(&self) -> bool {
+    self.doc_meta.get_doctype() != DocType::Inline
+      && !self.in_asciidoc_table_cell
+      && !self.doc_meta.embedded
+  }
+
+  const fn root_tag(&self) -> &'static str {
+    match self.doc_meta.get_doctype() {
+      DocType::Book => "book",
+      _ => "article",
+    }
+  }
+
+  fn section_tag(&self, section: &Section) -> &'static str {
+    let is_book = self.doc_meta.get_doctype() == DocType::Book;
+    if section.level <= 1 {
+      match section.meta.attrs.special_sect() {
+        Some(SpecialSection::Appendix) => return "appendix",
+        Some(SpecialSection::Glossary) => return "glossary",
+        Some(SpecialSection::Bibliography) => return "bibliography",
+        Some(SpecialSection::Index) => return "index",
+        Some(SpecialSection::Preface) if is_book => return "preface",
+        Some(SpecialSection::Colophon) if is_book => return "colophon",
+        Some(SpecialSection::Dedication) if is_book => return "dedication",
+        Some(SpecialSection::Acknowledgments) if is_book => return "acknowledgements",
+        _ => {}
+      }
+    }
+    if is_book && section.level == 1 { "chapter" } else { "section" }
+  }
+
+  fn listing_tag(&self, block: &Block) -> &'static str {
+    if block.meta.attrs.is_source() || self.doc_meta.str("source-language").is_some() {
+      "programlisting"
+    } else {
+      "screen"
+    }
+  }
+
+  fn list_kind(&self) -> ListKind {
+    self
+      .list_stack
+      .last()
+      .copied()
+      .unwrap_or(ListKind::Itemized)
+  }
+
+  fn resolved_table_attr(&self, name: &str, block: &Block) -> String {
+    block
+      .meta
+      .attrs
+      .named(name)
+      .or_else(|| self.doc_meta.str(&format!("table-{name}")))
+      .unwrap_or("all")
+      .to_string()
+  }
+
+  fn push_xml_attr(&mut self, name: &str, value: &str) {
+    self.push([" ", name, "=\""]);
+    self.push_str_attr_escaped(value);
+    self.push_ch('"');
+  }
+
+  fn push_id_and_roles<'a>(&mut self, id: Option<&str>, attrs: &'a impl AttrData) {
+    if let Some(id) = id {
+      self.push_xml_attr("xml:id", id);
+    }
+    let roles = attrs.roles().map(|r| &**r).collect::<Vec<&'a str>>();
+    if !roles.is_empty() {
+      self.push_xml_attr("role", &roles.join(" "));
+    }
+  }
+
+  fn push_block_id_and_roles(&mut self, block: &Block) {
+    let id = block.meta.attrs.id().map(|id| &**id);
+    self.push_id_and_roles(id, &block.meta.attrs);
+  }
+
+  fn push_buffered_title(&mut self) {
+    if !self.alt_xml.is_empty() {
+      let title = self.take_buffer();
+      self.push(["<title>", &title, "</title>"]);
+    }
+  }
+
+  fn open_inline(&mut self, open: &str, attrs: Option<&AttrList>) {
+    if let Some(id) = attrs.and_then(|attrs| attrs.id()) {
+      self.push([r#"<anchor xml:id=""#, id, "\"/>"]);
+    }
+    if let Some(attrs) = attrs.filter(|attrs| attrs.roles().next().is_some()) {
+      self.push_str("<phrase");
+      self.push_id_and_roles(None, attrs);
+      self.push_ch('>');
+    }
+    self.push_str(open);
+  }
+
+  fn close_inline(&mut self, close: &str, attrs: Option<&AttrList>) {
+    self.push_str(close);
+    if attrs.is_some_and(|attrs| attrs.roles().next().is_some()) {
+      self.push_str("</phrase>");
+    }
+  }
+
+  fn open_blockquote(&mut self, block: &Block) {
+    self.push_str("<blockquote");
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    self.push_buffered_title();
+    self.quote_stack.push(self.xml.len());
+  }
+
+  /// docbook requires the attribution to precede the quoted content,
+  /// so we move it into place after the fact
+  fn close_blockquote(&mut self) {
+    let insert_at = self.quote_stack.pop().unwrap_or(self.xml.len());
+    if let Some(start) = self.attribution_start.take() {
+      let attribution = self.xml.split_off(start);
+      self.xml.insert_str(insert_at, &attribution);
+    }
+    self.push_str("</blockquote>");
+  }
+
+  fn finish_equation(&mut self, notation: StemNotation, is_block: bool) {
+    let start = self.capture_start.take().unwrap_or(self.xml.len());
+    let equation = self.xml.split_off(start);
+    let (open, close) = if is_block {
+      stem::block_delimiters(notation)
+    } else {
+      stem::inline_delimiters(notation)
+    };
+    let equation = equation.trim();
+    let equation = equation
+      .strip_prefix(open)
+      .and_then(|eq| eq.strip_suffix(close))
+      .unwrap_or(equation)
+      .to_string();
+    if notation == StemNotation::Latexmath {
+      self.push([r#"<alt role="tex">"#, &equation, "</alt>"]);
+    }
+    self.push(["<mathphrase>", &equation, "</mathphrase>"]);
+  }
+
+  fn open_figure(&mut self, block: &Block, title_attr: Option<&str>) -> &'static str {
+    let tag = if block.has_title() || title_attr.is_some() {
+      "figure"
+    } else {
+      "informalfigure"
+    };
+    self.push(["<", tag]);
+    self.push_block_id_and_roles(block);
+    self.push_ch('>');
+    if let Some(title) = title_attr {
+      self.discard_alt_buffer();
+      self.push(["<title>", title, "</title>"]);
+    } else {
+      self.push_buffered_title();
+    }
+    tag
+  }
+
+  fn push_image_object(&mut self, target: &str, attrs: &AttrList) {
+    self.push_str(r#"<imageobject><imagedata fileref=""#);
+    if let Some(imagesdir) = self.doc_meta.str("imagesdir")
+      && !target.contains("://")
+      && !target.starts_with('/')
+    {
+      let mut path = Path::new_specifying_separator(imagesdir, '/');
+      path.push(target);
+      self.push_url_encoded(&path.to_string());
+    } else {
+      self.push_url_encoded(target);
+    }
+    self.push_ch('"');
+    self.push_dimension_attrs(attrs);
+    if let Some(scale) = attrs.named("scale") {
+      self.push_xml_attr("scale", scale);
+    }
+    if let Some(scaledwidth) = attrs.named("scaledwidth") {
+      self.push_xml_attr("width", scaledwidth);
+      self.push_str(r#" scalefit="1""#);
+    }
+    if let Some(align) = attrs.named("align") {
+      self.push_xml_attr("align", align);
+    }
+    self.push_str(r#"/></imageobject><textobject><phrase>"#);
+    if let Some(alt) = attrs.named("alt").or_else(|| attrs.str_positional_at(0)) {
+      self.push_specialchar_escaped(alt);
+    } else {
+      let alt = file::stem(target).replace(['-', '_'], " ");
+      self.push_specialchar_escaped(&alt);
+    }
+    self.push_str("</phrase></textobject>");
+  }
+
+  fn push_dimension_attrs(&mut self, attrs: &AttrList) {
+    if let Some(width) = attrs.named("width").or_else(|| attrs.str_positional_at(1)) {
+      self.push_xml_attr("contentwidth", width);
+    }
+    if let Some(height) = attrs.named("height").or_else(|| attrs.str_positional_at(2)) {
+      self.push_xml_attr("contentdepth", height);
+    }
+  }
+
+  fn render_info_details(&mut self) {
+    if let Some(date) = self.doc_meta.string("revdate") {
+      self.push(["<date>", &date, "</date>"]);
+    }
+    let authors = std::mem::take(&mut self.doc_meta.authors);
+    if authors.len() > 1 {
+      self.push_str("<authorgroup>");
+    }
+    for author in &authors {
+      self.push_str("<author><personname>");
+      self.push(["<firstname>", &author.first_name, "</firstname>"]);
+      if let Some(middle_name) = &author.middle_name {
+        self.push(["<othername>", middle_name, "</othername>"]);
+      }
+      self.push(["<surname>", &author.last_name, "</surname>"]);
+      self.push_str("</personname>");
+      if let Some(email) = &author.email {
+        self.push(["<email>", email, "</email>"]);
+      }
+      self.push_str("</author>");
+    }
+    if authors.len() > 1 {
+      self.push_str("</authorgroup>");
+    }
+    if let Some(author) = authors.first() {
+      self.push(["<authorinitials>", &author.initials(), "</authorinitials>"]);
+    }
+    if let Some(revnumber) = self.doc_meta.string("revnumber") {
+      self.push_str("<revhistory><revision>");
+      self.push(["<revnumber>", &revnumber, "</revnumber>"]);
+      if let Some(date) = self.doc_meta.string("revdate") {
+        self.push(["<date>", &date, "</date>"]);
+      }
+      if let Some(author) = authors.first() {
+        self.push(["<authorinitials>", &author.initials(), "</authorinitials>"]);
+      }
+      if let Some(revremark) = self.doc_meta.string("revremark") {
+        self.push(["<revremark>", &revremark, "</revremark>"]);
+      }
+      self.push_str("</revision></revhistory>");
+    }
+    self.doc_meta.authors = authors;
+  }
+}
+
+impl HtmlBuf for Docbook5 {
+  fn htmlbuf(&mut self) -> &mut String {
+    &mut self.xml
+  }
+
+  fn swapbuf(&mut self, s: &mut String) {
+    std::mem::swap(&mut self.xml, s);
+  }
+}
+
+impl AltHtmlBuf for Docbook5 {
+  fn alt_htmlbuf(&mut self) -> &mut String {
+    &mut self.alt_xml
+  }
+
+  fn buffers(&mut self) -> (&mut String, &mut String) {
+    (&mut self.xml, &mut self.alt_xml)
+  }
+}
+
+/// contexts whose simple (paragraph) content needs a docbook para wrapper
+const fn wraps_simple_content(block: &Block) -> bool {
+  matches!(
+    block.context,
+    BlockContext::Sidebar | BlockContext::Example | BlockContext::BlockQuote
+  )
+}
+
+/// matches asciidoctor's callout ids, e.g. `CO1-3`
+fn callout_id(callout: &Callout) -> String {
+  format!("CO{}-{}", callout.list_idx + 1, callout.callout_idx + 1)
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+
+extern crate asciidork_ast as ast;
+extern crate asciidork_backend as backend;
+extern crate asciidork_eval as eval;
+
+mod docbook5;
+
+pub use crate::docbook5::Docbook5;
+pub use backend::Postprocessor;
+
+pub fn convert(document: ast::Document) -> Result<String, Box<dyn Error>> {
+  convert_with_postprocessors(document, &mut [])
+}
+
+pub fn convert_with_postprocessors(
+  document: ast::Document,
+  postprocessors: &mut [Box<dyn Postprocessor>],
+) -> Result<String, Box<dyn Error>> {
+  let output = eval::eval(&document, Docbook5::new())?;
+  Ok(backend::postprocess(
+    output,
+    &document.meta,
+    postprocessors,
+  )?)
+}
+
+mod internal {
+  pub use asciidork_core::*;
+  pub use ast::prelude::*;
+  pub use backend::html::stem;
+  pub use backend::html::{AltHtmlBuf, HtmlBuf};
+  pub use backend::prelude::*;
+  pub use backend::utils;
+}
@@ -0,0 +1,275 @@
+use crate::helpers::*;
+use test_utils::*;
+
+assert_converted!(
+  basic_paragraphs,
+  docbook,
+  adoc! {r#"
+    Hello *world*, _this_ is `mono`.
+
+    .Titled
+    Second #para#.
+  "#},
+  xml! {r#"
+    <simpara>Hello <emphasis role="strong">world</emphasis>, <emphasis>this</emphasis> is <literal>mono</literal>.</simpara>
+    <formalpara><title>Titled</title><para>Second <emphasis role="marked">para</emphasis>.</para></formalpara>
+  "#}
+);
+
+assert_converted!(
+  sections_and_special_sections,
+  docbook,
+  adoc! {r#"
+    == Intro
+
+    === Nested
+
+    [appendix]
+    == Extra
+
+    [glossary]
+    == Glossary
+
+    [glossary]
+    term:: def
+  "#},
+  xml! {r#"
+    <section xml:id="_intro"><title>Intro</title>
+      <section xml:id="_nested"><title>Nested</title></section>
+    </section>
+    <appendix xml:id="_extra"><title>Extra</title></appendix>
+    <glossary xml:id="_glossary"><title>Glossary</title>
+      <glossentry><glossterm>term</glossterm><glossdef><simpara>def</simpara></glossdef></glossentry>
+    </glossary>
+  "#}
+);
+
+assert_converted!(
+  book_parts_and_chapters,
+  docbook_book,
+  adoc! {r#"
+    = Book
+
+    [preface]
+    == Preface
+
+    Before.
+
+    = Part One
+
+    Intro.
+
+    == Chapter
+
+    Text.
+  "#},
+  xml! {r#"
+    <preface xml:id="_preface"><title>Preface</title><simpara>Before.</simpara></preface>
+    <part xml:id="_part_one"><title>Part One</title>
+      <partintro><simpara>Intro.</simpara></partintro>
+      <chapter xml:id="_chapter"><title>Chapter</title><simpara>Text.</simpara></chapter>
+    </part>
+  "#}
+);
+
+assert_converted!(
+  admonitions,
+  docbook,
+  adoc! {r#"
+    TIP: Simple tip
+
+    .Careful
+    [WARNING]
+    ====
+    Compound warning
+    ====
+  "#},
+  xml! {r#"
+    <tip><simpara>Simple tip</simpara></tip>
+    <warning><title>Careful</title><simpara>Compound warning</simpara></warning>
+  "#}
+);
+
+assert_converted!(
+  source_block_w_callouts,
+  docbook,
+  adoc! {r#"
+    [source,ruby]
+    ----
+    require 'asciidork' # <1>
+    puts "hi" # <2>
+    ----
+    <1> Load it
+    <2> Print it
+  "#},
+  xml! {
+    r#"
+    <programlisting language="ruby" linenumbering="unnumbered">{}</programlisting>
+    <calloutlist>
+      <callout arearefs="CO1-1"><para>Load it</para></callout>
+      <callout arearefs="CO1-2"><para>Print it</para></callout>
+    </calloutlist>
+  "#,
+    r#"
+    require 'asciidork' # <co xml:id="CO1-1"/>
+    puts "hi" # <co xml:id="CO1-2"/>
+  "#
+  }
+);
+
+assert_converted!(
+  tables,
+  docbook,
+  adoc! {r#"
+    .Data
+    [%header%footer,cols="2,1",grid=rows]
+    |===
+    |Name |Qty
+    |foo |1
+    .2+|tall |2
+    |3
+    |Total |6
+    |===
+  "#},
+  xml! {r#"
+    <table frame="all" rowsep="1" colsep="0"><title>Data</title>
+      <tgroup cols="2">
+        <colspec colname="col_1" colwidth="66.6667*"/>
+        <colspec colname="col_2" colwidth="33.3333*"/>
+        <thead><row>
+          <entry align="left" valign="top">Name</entry>
+          <entry align="left" valign="top">Qty</entry>
+        </row></thead>
+        <tfoot><row>
+          <entry align="left" valign="top"><simpara>Total</simpara></entry>
+          <entry align="left" valign="top"><simpara>6</simpara></entry>
+        </row></tfoot>
+        <tbody>
+          <row>
+            <entry align="left" valign="top"><simpara>foo</simpara></entry>
+            <entry align="left" valign="top"><simpara>1</simpara></entry>
+          </row>
+          <row>
+            <entry morerows="1" align="left" valign="top"><simpara>tall</simpara></entry>
+            <entry align="left" valign="top"><simpara>2</simpara></entry>
+          </row>
+          <row>
+            <entry align="left" valign="top"><simpara>3</simpara></entry>
+          </row>
+        </tbody>
+      </tgroup>
+    </table>
+  "#}
+);
+
+assert_converted!(
+  index_terms,
+  docbook,
+  adoc! {r#"
+    The ((Arthur)) king (((Knights, Round Table, Lancelot))).
+  "#},
+  xml! {r#"
+    <simpara>The Arthur<indexterm><primary>Arthur</primary></indexterm> king <indexterm><primary>Knights</primary><secondary>Round Table</secondary><tertiary>Lancelot</tertiary></indexterm>.</simpara>
+  "#}
+);
+
+assert_converted!(
+  bibliography,
+  docbook,
+  adoc! {r#"
+    See <<prag>>.
+
+    [bibliography]
+    == References
+
+    * [[[prag]]] Andy Hunt. The Pragmatic Programmer.
+    * [[[gof,GoF]]] Design Patterns.
+  "#},
+  xml! {r#"
+    <simpara>See <link linkend="prag">[prag]</link>.</simpara>
+    <bibliography xml:id="_references"><title>References</title>
+      <bibliodiv>
+        <bibliomixed><bibliomisc><anchor xml:id="prag" xreflabel="[prag]"/>[prag] Andy Hunt. The Pragmatic Programmer.</bibliomisc></bibliomixed>
+        <bibliomixed><bibliomisc><anchor xml:id="gof" xreflabel="[GoF]"/>[GoF] Design Patterns.</bibliomisc></bibliomixed>
+      </bibliodiv>
+    </bibliography>
+  "#}
+);
+
+assert_converted!(
+  quote_w_attribution,
+  docbook,
+  adoc! {r#"
+    [quote,Abraham Lincoln,Gettysburg Address]
+    ____
+    Four score and seven years ago
+    ____
+  "#},
+  xml! {r#"
+    <blockquote>
+      <attribution>Abraham Lincoln<citetitle>Gettysburg Address</citetitle></attribution>
+      <simpara>Four score and seven years ago</simpara>
+    </blockquote>
+  "#}
+);
+
+assert_converted!(
+  lists,
+  docbook,
+  adoc! {r#"
+    * [x] done
+    * [ ] todo
+
+    [lowerroman,start=3]
+    . three
+    . four
+
+    //-
+
+    CPU:: The brain
+  "#},
+  xml! {r#"
+    <itemizedlist>
+      <listitem><simpara>&#10003; done</simpara></listitem>
+      <listitem><simpara>&#10063; todo</simpara></listitem>
+    </itemizedlist>
+    <orderedlist numeration="lowerroman" startingnumber="3">
+      <listitem><simpara>three</simpara></listitem>
+      <listitem><simpara>four</simpara></listitem>
+    </orderedlist>
+    <variablelist>
+      <varlistentry><term>CPU</term><listitem><simpara>The brain</simpara></listitem></varlistentry>
+    </variablelist>
+  "#}
+);
+
+assert_converted!(
+  footnotes_and_links,
+  docbook,
+  adoc! {r#"
+    Go to https://example.com[Example].footnote:disclaimer[Not real.]
+    Again.footnote:disclaimer[]
+  "#},
+  xml! {r#"
+    <simpara>Go to <link xl:href="https://example.com">Example</link>.<footnote xml:id="_footnote_disclaimer"><simpara>Not real.</simpara></footnote>
+    Again.<footnoteref linkend="_footnote_disclaimer"/></simpara>
+  "#}
+);
+
+test_non_embedded_contains!(
+  standalone_document_info,
+  adoc! {r#"
+    = Document Title
+    Kismet Chameleon <kismet@example.com>
+    v1.0, 2024-03-01
+
+    Body.
+  "#},
+  [
+    r#"<?xml version="1.0" encoding="UTF-8"?><article xmlns="http://docbook.org/ns/docbook" xmlns:xl="http://www.w3.org/1999/xlink" version="5.0" xml:lang="en">"#,
+    "<info><title>Document Title</title><date>2024-03-01</date>",
+    "<author><personname><firstname>Kismet</firstname><surname>Chameleon</surname></personname><email>kismet@example.com</email></author>",
+    "<revnumber>1.0</revnumber>",
+    "</info><simpara>Body.</simpara></article>",
+  ]
+);
@@ -0,0 +1,27 @@
+use asciidork_backend_docbook5::Docbook5;
+use asciidork_core::{DocType, JobSettings, Path, SafeMode};
+use asciidork_parser::prelude::*;
+
+pub fn test_backend_factory() -> Docbook5 {
+  Docbook5::new()
+}
+
+pub fn docbook(input: &str) -> String {
+  convert(input, |_| {})
+}
+
+pub fn docbook_book(input: &str) -> String {
+  convert(input, |settings| settings.doctype = Some(DocType::Book))
+}
+
+fn convert(input: &str, mod_settings: impl FnOnce(&mut JobSettings)) -> String {
+  let bump = &Bump::new();
+  let mut settings = JobSettings::embedded();
+  settings.safe_mode = SafeMode::Unsafe;
+  mod_settings(&mut settings);
+  let path = Path::new("test.adoc");
+  let mut parser = Parser::from_str(input, SourceFile::Path(path), bump);
+  parser.apply_job_settings(settings);
+  let document = parser.parse().unwrap().document;
+  asciidork_eval::eval(&document, test_backend_factory()).unwrap()
+}
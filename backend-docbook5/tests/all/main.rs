@@ -0,0 +1,5 @@
+#[macro_use]
+extern crate asciidork_backend;
+
+mod eval_docbook5;
+mod helpers;
@@ -39,6 +39,7 @@ impl Backend for Html5s {
     self.doc_meta = document.meta.clone();
     utils::set_backend_attrs::<Self>(&mut self.doc_meta);
     self.state.section_num_levels = document.meta.isize("sectnumlevels").unwrap_or(3);
+    self.enable_index(document);
 
     if !self.standalone() {
       return;
@@ -49,9 +50,16 @@ impl Backend for Html5s {
     self.render_meta_authors(document.meta.authors());
     self.render_title(document, &document.meta);
     self.render_embedded_stylesheet(crate::css::DEFAULT);
+    if let Some(highlighter) = Highlighter::from_doc_meta(&document.meta)
+      && !document.meta.is_true("linkcss")
+    {
+      self.push(["<style>", &highlighter.stylesheet(), "</style>"]);
+    }
   }
 
-  fn exit_document(&mut self, _document: &Document) {}
+  fn exit_document(&mut self, _document: &Document) {
+    self.render_index();
+  }
 
   fn enter_body(&mut self, document: &Document) {
     if self.standalone() {
@@ -122,6 +130,9 @@ impl Backend for Html5s {
     if !self.doc_meta.embedded && !self.doc_meta.is_true("nofooter") {
       self.push_str("</footer>");
     }
+    if self.standalone() && self.doc_meta.str("stem").is_some() {
+      self.push_str(&stem::mathjax_scripts(&self.doc_meta));
+    }
   }
 
   fn enter_document_title(&mut self) {
@@ -263,6 +274,7 @@ impl Backend for Html5s {
   }
 
   fn exit_section(&mut self, section: &Section) {
+    self.mark_index_section(section);
     self.push_str("</section>");
     self.exit_section_state(section);
     self.section_level_stack.pop();
@@ -341,9 +353,15 @@ impl Backend for Html5s {
     }
     html.push('>');
     self.swapbuf(&mut html);
+    if self.state.ephemeral.contains(&IsSourceBlock) {
+      self.enter_highlighted_source(block);
+    }
   }
 
   fn exit_listing_block(&mut self, block: &Block) {
+    if self.state.ephemeral.remove(&IsHighlightedSourceBlock) {
+      self.swap_discard_alt_buffer();
+    }
     if self.state.ephemeral.remove(&IsSourceBlock) {
       self.push_str("</code>");
     }
@@ -367,6 +385,24 @@ impl Backend for Html5s {
     self.newlines = self.default_newlines;
   }
 
+  fn enter_stem_block(&mut self, block: &Block, _notation: StemNotation) {
+    let el = if block.has_title() { "section" } else { "div" };
+    self.open_element(el, &["stem-block"], &block.meta.attrs);
+    self.render_buffered_block_title(block, true);
+    self.push_str(r#"<div class="math">"#);
+    self.newlines = Newlines::Preserve;
+    self.start_buffering();
+  }
+
+  fn exit_stem_block(&mut self, block: &Block, notation: StemNotation) {
+    let equation = self.swap_take_buffer();
+    self.push_str(&stem::block_equation(notation, &equation));
+    self.push_str("</div>");
+    let end = if block.has_title() { "</section>" } else { "</div>" };
+    self.push_str(end);
+    self.newlines = self.default_newlines;
+  }
+
   fn enter_quoted_paragraph(&mut self, block: &Block) {
     self.open_element("div", &["quote-block"], &block.meta.attrs);
     self.render_buffered_block_title(block, false);
@@ -705,6 +741,7 @@ impl Backend for Html5s {
   fn asciidoc_table_cell_backend(&mut self) -> Self {
     let mut backend = Self::default();
     backend.state.footnotes = Rc::clone(&self.state.footnotes);
+    backend.state.index = Rc::clone(&self.state.index);
     backend.state.in_asciidoc_table_cell = true;
     backend
   }
@@ -827,6 +864,14 @@ impl Backend for Html5s {
     self.push_str("</code>");
   }
 
+  fn enter_inline_stem(&mut self, notation: StemNotation) {
+    self.push_str(stem::inline_delimiters(notation).0);
+  }
+
+  fn exit_inline_stem(&mut self, notation: StemNotation) {
+    self.push_str(stem::inline_delimiters(notation).1);
+  }
+
   fn enter_inline_highlight(&mut self, attrs: Option<&AttrList>) {
     self.open_element_opt("mark", &[], attrs);
   }
@@ -860,11 +905,51 @@ impl Backend for Html5s {
   }
 
   fn enter_visible_index_term(&mut self) -> bool {
+    if self.start_index_term() {
+      self.start_index_term_text();
+    }
     true
   }
 
+  fn exit_visible_index_term(&mut self) {
+    self.end_index_term_text();
+    self.end_index_term(false);
+  }
+
   fn enter_concealed_index_term(&mut self, _num_terms: u8) -> bool {
-    false
+    self.start_index_term()
+  }
+
+  fn exit_concealed_index_term(&mut self, _num_terms: u8) {
+    self.end_index_term(true);
+  }
+
+  fn enter_concealed_index_term_primary(&mut self) {
+    self.start_index_term_text();
+  }
+
+  fn exit_concealed_index_term_primary(&mut self) {
+    self.end_index_term_text();
+  }
+
+  fn enter_concealed_index_term_secondary(&mut self) {
+    self.start_index_term_text();
+  }
+
+  fn exit_concealed_index_term_secondary(&mut self) {
+    self.end_index_term_text();
+  }
+
+  fn enter_concealed_index_term_tertiary(&mut self) {
+    self.start_index_term_text();
+  }
+
+  fn exit_concealed_index_term_tertiary(&mut self) {
+    self.end_index_term_text();
+  }
+
+  fn visit_index_term_reference(&mut self, term_ref: &IndexTermReference) {
+    self.set_index_term_reference(term_ref);
   }
 
   fn visit_inline_specialchar(&mut self, char: &SpecialCharKind) {
@@ -1476,6 +1561,23 @@ impl Html5s {
     Self::default()
   }
 
+  fn enter_highlighted_source(&mut self, block: &Block) {
+    let Some(highlighter) = Highlighter::from_doc_meta(&self.doc_meta) else {
+      return;
+    };
+    let BlockContent::Simple(nodes) = &block.content else {
+      return;
+    };
+    let Some(source) = HighlightSource::from_nodes(nodes, true) else {
+      return;
+    };
+    let doc_lang = self.doc_meta.string("source-language");
+    let lang = block.meta.attrs.source_language().or(doc_lang.as_deref());
+    self.render_highlighted_source(&source, lang, highlighter, block);
+    self.state.ephemeral.insert(IsHighlightedSourceBlock);
+    self.start_buffering();
+  }
+
   fn render_buffered_block_title(&mut self, block: &Block, wrap_in_h6: bool) {
     if block.has_title() {
       let buf = self.take_buffer();
@@ -8,9 +8,22 @@ mod css;
 mod html5s;
 
 pub use crate::html5s::Html5s;
+pub use backend::Postprocessor;
 
 pub fn convert(document: ast::Document) -> Result<String, Box<dyn Error>> {
-  Ok(eval::eval(&document, Html5s::new())?)
+  convert_with_postprocessors(document, &mut [])
+}
+
+pub fn convert_with_postprocessors(
+  document: ast::Document,
+  postprocessors: &mut [Box<dyn Postprocessor>],
+) -> Result<String, Box<dyn Error>> {
+  let output = eval::eval(&document, Html5s::new())?;
+  Ok(backend::postprocess(
+    output,
+    &document.meta,
+    postprocessors,
+  )?)
 }
 
 mod internal {
@@ -18,7 +31,9 @@ mod internal {
 
   pub use asciidork_core::*;
   pub use ast::prelude::*;
+  pub use backend::highlight::{HighlightSource, Highlighter};
   pub use backend::html::backend::*;
+  pub use backend::html::stem;
   pub use backend::html::{AltHtmlBuf, HtmlBuf, OpenTag};
   pub use backend::prelude::*;
   pub use backend::utils;
@@ -389,6 +389,46 @@ assert_html!(
     end</code></pre></div>"##}
 );
 
+assert_html!(
+  source_native_highlighter,
+  adoc! {r#"
+    :source-highlighter: rouge
+
+    [source, ruby]
+    ----
+    5.times do # <1>
+      print "Odelay!"
+    end
+    ----
+  "#},
+  raw_html! {r##"
+    <div class="listing-block"><pre class="highlight rouge"><code class="language-ruby" data-lang="ruby"><span class="m">5</span><span class="p">.</span>times <span class="k">do</span> <span class="c1">#</span> <b class="conum">1</b>
+      <span class="nb">print</span> <span class="s">"Odelay!"</span>
+    <span class="k">end</span></code></pre></div>"##}
+);
+
+assert_html!(
+  stem_block_and_inline,
+  adoc! {r#"
+    :stem: latexmath
+
+    .Sum
+    [stem]
+    ++++
+    a < b
+    ++++
+
+    Inline asciimath:[sqrt(4) = 2].
+  "#},
+  html! {r#"
+    <section class="stem-block">
+      <h6 class="block-title">Sum</h6>
+      <div class="math">\[a &lt; b\]</div>
+    </section>
+    <p>Inline \$sqrt(4) = 2\$.</p>
+  "#}
+);
+
 assert_html!(
   outline,
   adoc! {r#"
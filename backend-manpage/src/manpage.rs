@@ -0,0 +1,1200 @@
+use crate::internal::*;
+use crate::troff;
+use ast::AdjacentNewline;
+use backend::num_str;
+
+#[derive(Debug, Default)]
+pub struct Manpage {
+  doc_meta: DocumentMeta,
+  roff: String,
+  alt_roff: String,
+  hardbreaks: bool,
+  default_hardbreaks: bool,
+  uppercase: bool,
+  in_macro_arg: bool,
+  in_asciidoc_table_cell: bool,
+  list_stack: Vec<ListState>,
+  quote_stack: Vec<bool>,
+  table: TableState,
+  link_start: Option<usize>,
+  footnote_start: Option<usize>,
+  footnote_dupe: bool,
+  footnotes: Vec<(Option<String>, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListState {
+  Unordered,
+  Ordered(u32),
+  Callout,
+  Description,
+}
+
+#[derive(Debug, Default)]
+struct TableState {
+  col: usize,
+  row_spans: Vec<u8>,
+  occupied: Vec<bool>,
+  first_field: bool,
+  first_paragraph: bool,
+}
+
+impl Backend for Manpage {
+  type Output = String;
+  type Error = std::convert::Infallible;
+  const OUTFILESUFFIX: &'static str = ".man";
+
+  fn set_job_attrs(attrs: &mut asciidork_core::JobAttrs) {
+    attrs.insert_unchecked("backend", JobAttr::readonly("manpage"));
+    attrs.insert_unchecked("backend-manpage", JobAttr::readonly(true));
+    attrs.insert_unchecked("basebackend", JobAttr::readonly("manpage"));
+    attrs.insert_unchecked("basebackend-manpage", JobAttr::readonly(true));
+  }
+
+  fn enter_document(&mut self, document: &Document) {
+    self.doc_meta = document.meta.clone();
+    utils::set_backend_attrs::<Self>(&mut self.doc_meta);
+    let manvolnum = self.doc_meta.string_or("manvolnum", "1");
+    _ = self
+      .doc_meta
+      .insert_doc_attr("outfilesuffix", format!(".{manvolnum}"));
+    if self.standalone() {
+      self.push_preamble(&manvolnum);
+    }
+  }
+
+  fn exit_document(&mut self, _document: &Document) {
+    if self.in_asciidoc_table_cell {
+      return;
+    }
+    if !self.footnotes.is_empty() {
+      self.push_control(".SH \"NOTES\"");
+      for (idx, (_, text)) in std::mem::take(&mut self.footnotes).iter().enumerate() {
+        self.push_control(&format!(".IP \"[{}]\" 4", idx + 1));
+        self.push_str(text);
+      }
+    }
+    if self.standalone() && !self.doc_meta.authors.is_empty() {
+      let authors = std::mem::take(&mut self.doc_meta.authors);
+      let heading = iff!(authors.len() > 1, "AUTHORS", "AUTHOR");
+      self.push_control(&format!(".SH \"{heading}\""));
+      for author in &authors {
+        self.push_control(".sp");
+        self.push(["\\fB", &troff::escape(&author.fullname(), true), "\\fP"]);
+        self.push_control(".RS 4");
+        self.push_str("Author.");
+        self.push_control(".RE");
+      }
+      self.doc_meta.authors = authors;
+    }
+    self.ensure_newline();
+  }
+
+  fn enter_header(&mut self) {}
+  fn exit_header(&mut self) {}
+  fn enter_content(&mut self) {}
+  fn exit_content(&mut self) {}
+  fn enter_footer(&mut self) {}
+  fn exit_footer(&mut self) {}
+
+  // the title is rendered by the `.TH` macro
+  fn enter_document_title(&mut self) {
+    self.start_buffering();
+  }
+
+  fn exit_document_title(&mut self) {
+    self.swap_discard_alt_buffer();
+  }
+
+  // man pages have no table of contents
+  fn enter_toc(&mut self, _toc: &TableOfContents, _macro_block: Option<&Block>) {
+    self.start_buffering();
+  }
+
+  fn exit_toc(&mut self, _toc: &TableOfContents) {
+    self.swap_discard_alt_buffer();
+  }
+
+  fn enter_book_part(&mut self, _part: &Part) {}
+  fn exit_book_part(&mut self, _part: &Part) {}
+
+  fn enter_book_part_title(&mut self, _title: &PartTitle) {
+    self.start_control(".SH \"");
+    self.uppercase = true;
+    self.in_macro_arg = true;
+  }
+
+  fn exit_book_part_title(&mut self, _title: &PartTitle) {
+    self.uppercase = false;
+    self.in_macro_arg = false;
+    self.push_ch('"');
+  }
+
+  fn enter_book_part_intro(&mut self, _part: &Part) {}
+  fn exit_book_part_intro(&mut self, _part: &Part) {}
+  fn enter_book_part_intro_content(&mut self, _part: &Part) {}
+  fn exit_book_part_intro_content(&mut self, _part: &Part) {}
+
+  fn enter_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {}
+  fn exit_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {}
+
+  fn enter_section(&mut self, _section: &Section) {}
+  fn exit_section(&mut self, _section: &Section) {}
+
+  fn enter_section_heading(&mut self, section: &Section) {
+    match section.level {
+      0 | 1 => {
+        self.start_control(".SH \"");
+        self.uppercase = true;
+        self.in_macro_arg = true;
+      }
+      2 => {
+        self.start_control(".SS \"");
+        self.in_macro_arg = true;
+      }
+      _ => {
+        self.push_control(".sp");
+        self.push_str("\\fB");
+      }
+    }
+  }
+
+  fn exit_section_heading(&mut self, section: &Section) {
+    self.uppercase = false;
+    self.in_macro_arg = false;
+    if section.level <= 2 {
+      self.push_ch('"');
+    } else {
+      self.push_str("\\fP");
+      self.push_control(".br");
+    }
+  }
+
+  fn enter_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+  fn exit_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+
+  fn enter_simple_block_content(&mut self, block: &Block) {
+    if matches!(
+      block.context,
+      BlockContext::Sidebar | BlockContext::Example | BlockContext::BlockQuote
+    ) {
+      self.push_control(".sp");
+    }
+    if block.meta.attrs.has_option("hardbreaks") {
+      self.hardbreaks = true;
+    }
+  }
+
+  fn exit_simple_block_content(&mut self, _block: &Block) {
+    self.hardbreaks = self.default_hardbreaks;
+  }
+
+  fn enter_paragraph_block(&mut self, _block: &Block) {
+    if self.doc_meta.get_doctype() == DocType::Inline {
+      return;
+    }
+    self.push_block_title();
+    self.push_control(".sp");
+  }
+
+  fn exit_paragraph_block(&mut self, _block: &Block) {}
+
+  fn enter_sidebar_block(&mut self, _block: &Block) {
+    self.push_block_title();
+    self.push_control(".RS 4");
+  }
+
+  fn exit_sidebar_block(&mut self, _block: &Block) {
+    self.push_control(".RE");
+  }
+
+  fn enter_listing_block(&mut self, _block: &Block) {
+    self.enter_verbatim();
+  }
+
+  fn exit_listing_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_literal_block(&mut self, _block: &Block) {
+    self.enter_verbatim();
+  }
+
+  fn exit_literal_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_stem_block(&mut self, _block: &Block, _notation: StemNotation) {
+    self.enter_verbatim();
+  }
+
+  fn exit_stem_block(&mut self, _block: &Block, _notation: StemNotation) {
+    self.exit_verbatim();
+  }
+
+  fn enter_quoted_paragraph(&mut self, _block: &Block) {
+    self.enter_quote();
+    self.push_control(".sp");
+  }
+
+  fn exit_quoted_paragraph(&mut self, _block: &Block) {
+    self.exit_quote();
+  }
+
+  fn enter_quote_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.enter_quote();
+  }
+
+  fn exit_quote_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.exit_quote();
+  }
+
+  fn enter_quote_attribution(&mut self, block: &Block, _has_cite: bool) {
+    self.enter_attribution(block);
+  }
+
+  fn exit_quote_attribution(&mut self, _block: &Block, _has_cite: bool) {}
+
+  fn enter_quote_cite(&mut self, block: &Block, has_attribution: bool) {
+    if has_attribution {
+      self.push_str(", ");
+    } else {
+      self.enter_attribution(block);
+    }
+  }
+
+  fn exit_quote_cite(&mut self, _block: &Block, _has_attribution: bool) {}
+
+  fn enter_verse_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.enter_quote();
+    self.push_control(".sp");
+    self.push_control(".nf");
+  }
+
+  fn exit_verse_block(&mut self, _block: &Block, _has_attribution: bool) {
+    if self.quote_stack.last() == Some(&false) {
+      self.push_control(".fi");
+    }
+    self.exit_quote();
+  }
+
+  fn enter_example_block(&mut self, _block: &Block) {
+    self.push_block_title();
+    self.push_control(".RS 4");
+  }
+
+  fn exit_example_block(&mut self, _block: &Block) {
+    self.push_control(".RE");
+  }
+
+  fn enter_open_block(&mut self, _block: &Block) {
+    self.push_block_title();
+  }
+
+  fn exit_open_block(&mut self, _block: &Block) {}
+
+  fn enter_discrete_heading(&mut self, _level: u8, _id: Option<&str>, _block: &Block) {
+    self.push_control(".sp");
+    self.push_str("\\fB");
+  }
+
+  fn exit_discrete_heading(&mut self, _level: u8, _id: Option<&str>, _block: &Block) {
+    self.push_str("\\fP");
+    self.push_control(".br");
+  }
+
+  fn enter_unordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.push_block_title();
+    self.list_stack.push(ListState::Unordered);
+  }
+
+  fn exit_unordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_ordered_list(&mut self, block: &Block, items: &[ListItem], _depth: u8) {
+    self.push_block_title();
+    let start = block
+      .meta
+      .attrs
+      .named("start")
+      .and_then(|start| start.parse::<u32>().ok())
+      .or(match items.first().map(|item| &item.marker) {
+        Some(ListMarker::Digits(n)) => Some(*n as u32),
+        _ => None,
+      })
+      .unwrap_or(1);
+    self.list_stack.push(ListState::Ordered(start));
+  }
+
+  fn exit_ordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_callout_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.push_block_title();
+    self.list_stack.push(ListState::Callout);
+  }
+
+  fn exit_callout_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_description_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.push_block_title();
+    self.list_stack.push(ListState::Description);
+  }
+
+  fn exit_description_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_description_list_term(&mut self, _item: &ListItem, num: usize, _total: usize) {
+    self.push_control(iff!(num == 1, ".sp", ".br"));
+    self.push_str("\\fB");
+  }
+
+  fn exit_description_list_term(&mut self, _item: &ListItem, _num: usize, _total: usize) {
+    self.push_str("\\fP");
+  }
+
+  fn enter_description_list_description(&mut self, _item: &ListItem) {
+    self.push_control(".RS 4");
+  }
+
+  fn exit_description_list_description(&mut self, _item: &ListItem) {
+    self.push_control(".RE");
+  }
+
+  fn enter_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+  fn exit_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+
+  fn enter_list_item_principal(&mut self, item: &ListItem, variant: ListVariant) {
+    match (variant, self.list_stack.last_mut()) {
+      (ListVariant::Callout, _) => {
+        let number = match &item.type_meta {
+          ListItemTypeMeta::Callout(callouts) => callouts.first().map(|c| c.number),
+          _ => None,
+        };
+        let number = number.unwrap_or(1).to_string();
+        self.push_control(&format!(".IP \"\\fB({number})\\fP\" 4"));
+      }
+      (_, Some(ListState::Ordered(num))) => {
+        let marker = format!("{num:>2}.");
+        *num += 1;
+        self.push_list_marker(&format!("{marker}\\h'+01'"), &format!("\"{marker}\" 4.2"));
+      }
+      _ => {
+        self.push_list_marker("\\(bu\\h'+03'", "\\(bu 2.3");
+        if let ListItemTypeMeta::Checklist(checked, _) = &item.type_meta {
+          self.push_str(iff!(*checked, "[x] ", "[ ] "));
+        }
+      }
+    }
+  }
+
+  fn exit_list_item_principal(&mut self, _item: &ListItem, _variant: ListVariant) {}
+
+  fn enter_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, _variant: ListVariant) {
+  }
+
+  fn exit_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, variant: ListVariant) {
+    if variant != ListVariant::Callout {
+      self.push_control(".RE");
+    }
+  }
+
+  fn asciidoc_table_cell_backend(&mut self) -> Self {
+    Self {
+      in_asciidoc_table_cell: true,
+      ..Self::default()
+    }
+  }
+
+  fn visit_asciidoc_table_cell_result(&mut self, cell_backend: Self) {
+    let result = cell_backend.into_result().unwrap();
+    self.push_str(result.trim_start_matches(".sp\n").trim_end());
+  }
+
+  fn enter_table(&mut self, table: &Table, block: &Block) {
+    self.push_block_title();
+    self.push_control(".TS");
+    let grid = self.resolved_table_attr("grid", block);
+    let frame = self.resolved_table_attr("frame", block);
+    let options = match (grid.as_str(), frame.as_str()) {
+      ("all", _) => "allbox ",
+      (_, "all") => "box ",
+      _ => "",
+    };
+    self.push_control(&format!("{options}tab(:);"));
+    let formats = row_formats(table);
+    let last = formats.len().saturating_sub(1);
+    for (idx, format) in formats.iter().enumerate() {
+      self.push_control(&format!("{format}{}", iff!(idx == last, ".", "")));
+    }
+    let num_cols = table.col_widths.len();
+    self.table = TableState {
+      row_spans: vec![0; num_cols],
+      ..TableState::default()
+    };
+  }
+
+  fn exit_table(&mut self, _table: &Table, _block: &Block) {
+    self.push_control(".TE");
+    self.push_control(".sp");
+  }
+
+  fn enter_table_section(&mut self, _section: TableSection) {}
+  fn exit_table_section(&mut self, _section: TableSection) {}
+
+  fn enter_table_row(&mut self, _row: &Row, _section: TableSection) {
+    self.ensure_newline();
+    self.table.col = 0;
+    self.table.first_field = true;
+    self.table.occupied = self.table.row_spans.iter().map(|span| *span > 0).collect();
+  }
+
+  fn exit_table_row(&mut self, _row: &Row, _section: TableSection) {
+    self.push_span_placeholders();
+    for (span, occupied) in self.table.row_spans.iter_mut().zip(&self.table.occupied) {
+      if *occupied {
+        *span -= 1;
+      }
+    }
+    self.ensure_newline();
+  }
+
+  fn enter_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    self.push_span_placeholders();
+    let col = self.table.col;
+    if cell.row_span > 1 {
+      self
+        .table
+        .row_spans
+        .iter_mut()
+        .skip(col)
+        .take(cell.col_span as usize)
+        .for_each(|span| *span = cell.row_span - 1);
+    }
+    self.table.col += cell.col_span as usize;
+    self.push_field_separator();
+    self.push_str("T{\n");
+    self.table.first_paragraph = true;
+    if matches!(cell.content, CellContent::Literal(_)) {
+      self.push_control(".nf");
+    }
+  }
+
+  fn exit_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    if matches!(cell.content, CellContent::Literal(_)) {
+      self.push_control(".fi");
+    }
+    self.push_control("T}");
+    self.roff.pop();
+  }
+
+  fn enter_cell_paragraph(&mut self, cell: &Cell, _section: TableSection) {
+    if !self.table.first_paragraph {
+      self.push_control(".sp");
+    }
+    self.table.first_paragraph = false;
+    match &cell.content {
+      CellContent::Emphasis(_) => self.push_str("\\fI"),
+      CellContent::Monospace(_) => self.push_str("\\f(CR"),
+      CellContent::Strong(_) => self.push_str("\\fB"),
+      _ => {}
+    }
+  }
+
+  fn exit_cell_paragraph(&mut self, cell: &Cell, _section: TableSection) {
+    match &cell.content {
+      CellContent::Emphasis(_) | CellContent::Monospace(_) | CellContent::Strong(_) => {
+        self.push_str("\\fP")
+      }
+      _ => {}
+    }
+  }
+
+  fn enter_inline_italic(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\fI");
+  }
+
+  fn exit_inline_italic(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\fP");
+  }
+
+  fn enter_inline_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\f(CR");
+  }
+
+  fn exit_inline_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\fP");
+  }
+
+  fn enter_inline_bold(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\fB");
+  }
+
+  fn exit_inline_bold(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\fP");
+  }
+
+  fn enter_inline_lit_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\f(CR");
+  }
+
+  fn exit_inline_lit_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\fP");
+  }
+
+  fn enter_inline_stem(&mut self, _notation: StemNotation) {}
+  fn exit_inline_stem(&mut self, _notation: StemNotation) {}
+  fn enter_inline_highlight(&mut self, _attrs: Option<&AttrList>) {}
+  fn exit_inline_highlight(&mut self, _attrs: Option<&AttrList>) {}
+
+  fn enter_inline_subscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\d\\s-2");
+  }
+
+  fn exit_inline_subscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\s+2\\u");
+  }
+
+  fn enter_inline_superscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\u\\s-2");
+  }
+
+  fn exit_inline_superscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_str("\\s+2\\d");
+  }
+
+  fn visit_spaced_dashes(&mut self, _len: u8, adjacent_newline: AdjacentNewline) {
+    match adjacent_newline {
+      AdjacentNewline::None => self.push_str("\\ \\(em\\ "),
+      _ => self.push_str("\\(em"),
+    }
+  }
+
+  fn enter_visible_index_term(&mut self) -> bool {
+    true
+  }
+
+  fn enter_concealed_index_term(&mut self, _num_terms: u8) -> bool {
+    false
+  }
+
+  fn visit_inline_specialchar(&mut self, char: &SpecialCharKind) {
+    match char {
+      SpecialCharKind::Ampersand => self.push_ch('&'),
+      SpecialCharKind::LessThan => self.push_ch('<'),
+      SpecialCharKind::GreaterThan => self.push_ch('>'),
+    }
+  }
+
+  fn visit_symbol(&mut self, kind: SymbolKind) {
+    match kind {
+      SymbolKind::Copyright => self.push_str("\\(co"),
+      SymbolKind::Registered => self.push_str("\\(rg"),
+      SymbolKind::Trademark => self.push_str("\\(tm"),
+      SymbolKind::EmDash => self.push_str("\\(em"),
+      SymbolKind::TripleDash => self.push_str("\\-\\-\\-"),
+      SymbolKind::Ellipsis => self.push_str("..."),
+      SymbolKind::SingleRightArrow => self.push_str("\\(->"),
+      SymbolKind::DoubleRightArrow => self.push_str("\\(rA"),
+      SymbolKind::SingleLeftArrow => self.push_str("\\(<-"),
+      SymbolKind::DoubleLeftArrow => self.push_str("\\(lA"),
+    }
+  }
+
+  fn enter_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_str("\\(lq"),
+      QuoteKind::Single => self.push_str("\\(oq"),
+    }
+  }
+
+  fn exit_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_str("\\(rq"),
+      QuoteKind::Single => self.push_str("\\(cq"),
+    }
+  }
+
+  fn visit_curly_quote(&mut self, kind: CurlyKind) {
+    match kind {
+      CurlyKind::LeftDouble => self.push_str("\\(rq"),
+      CurlyKind::RightDouble => self.push_str("\\(lq"),
+      CurlyKind::LeftSingle => self.push_str("\\(oq"),
+      CurlyKind::RightSingle => self.push_str("\\(cq"),
+      CurlyKind::LegacyImplicitApostrophe => self.push_str("\\(cq"),
+    }
+  }
+
+  fn visit_multichar_whitespace(&mut self, whitespace: &str) {
+    self.push_str(whitespace);
+  }
+
+  fn visit_thematic_break(&mut self, _block: &Block) {
+    self.push_control(".sp");
+    self.push_control(".ce");
+    self.push_control("\\l'\\n(.lu*25u/100u\\(ap'");
+  }
+
+  fn visit_page_break(&mut self, _block: &Block) {
+    self.push_control(".bp");
+  }
+
+  fn visit_inline_text(&mut self, text: &str) {
+    let at_line_start = self.roff.is_empty() || self.roff.ends_with('\n');
+    if self.in_macro_arg {
+      let text = iff!(self.uppercase, text.to_uppercase(), text.to_string());
+      self.push_str(&troff::quoted_arg(&text));
+    } else {
+      let escaped = troff::escape(text, at_line_start);
+      self.push_str(&escaped);
+    }
+  }
+
+  fn visit_joining_newline(&mut self) {
+    if self.hardbreaks {
+      self.push_control(".br");
+    } else {
+      self.push_ch('\n');
+    }
+  }
+
+  fn enter_text_span(&mut self, _attrs: Option<&AttrList>) {}
+  fn exit_text_span(&mut self, _attrs: Option<&AttrList>) {}
+
+  fn enter_xref(&mut self, _target: &SourceString, _has_reftext: bool, _kind: XrefKind) {}
+  fn exit_xref(&mut self, _target: &SourceString, _has_reftext: bool, _kind: XrefKind) {}
+
+  fn enter_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_ch('[');
+    }
+  }
+
+  fn exit_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_ch(']');
+    }
+  }
+
+  fn visit_missing_xref(
+    &mut self,
+    target: &SourceString,
+    _kind: XrefKind,
+    _doc_title: Option<&DocTitle>,
+  ) {
+    let target = utils::xref::remove_leading_hash(target);
+    self.push(["[", &troff::escape(target, false), "]"]);
+  }
+
+  fn visit_inline_anchor(&mut self, _id: &str) {}
+
+  fn visit_biblio_anchor(&mut self, id: &str, reftext: Option<&str>) {
+    let label = reftext.unwrap_or(id);
+    self.push(["[", &troff::escape(label, false), "]"]);
+  }
+
+  fn visit_callout(&mut self, callout: Callout) {
+    self.push(["\\fB(", &num_str!(callout.number), ")\\fP"]);
+  }
+
+  fn visit_callout_tuck(&mut self, comment: &str) {
+    self.push_str(&troff::escape(comment, false));
+  }
+
+  fn visit_linebreak(&mut self) {
+    self.push_control(".br");
+  }
+
+  fn visit_button_macro(&mut self, text: &SourceString) {
+    self.push(["\\fB[\\0", &troff::escape(text, false), "\\0]\\fP"]);
+  }
+
+  fn visit_menu_macro(&mut self, items: &[SourceString]) {
+    self.push_str("\\fI");
+    for (idx, item) in items.iter().enumerate() {
+      if idx > 0 {
+        self.push_str("\\fP\\ \\(->\\ \\fI");
+      }
+      self.push_str(&troff::escape(item, false));
+    }
+    self.push_str("\\fP");
+  }
+
+  fn visit_keyboard_macro(&mut self, keys: &[&str]) {
+    self.push_str("\\f(CR");
+    for (idx, key) in keys.iter().enumerate() {
+      if idx > 0 {
+        self.push_str("\\0+\\0");
+      }
+      self.push_str(&troff::escape(key, false));
+    }
+    self.push_str("\\fP");
+  }
+
+  fn visit_image_macro(&mut self, target: &SourceString, attrs: &AttrList, _kind: &ImageKind) {
+    self.push_image_alt(target, attrs);
+  }
+
+  fn visit_icon_macro(&mut self, target: &SourceString, attrs: &AttrList) {
+    let alt = attrs.named("alt").unwrap_or(target);
+    self.push(["[", &troff::escape(alt, false), "]"]);
+  }
+
+  fn visit_audio_macro(&mut self, target: &SourceString, _attrs: &AttrList, _block: &Block) {
+    self.push_block_title();
+    self.push_control(".sp");
+    self.push(["[audio: \\%", &troff::escape(target, false), "]"]);
+  }
+
+  fn visit_video_macro(&mut self, target: &SourceString, _attrs: &AttrList, _block: &Block) {
+    self.push_block_title();
+    self.push_control(".sp");
+    self.push(["[video: \\%", &troff::escape(target, false), "]"]);
+  }
+
+  fn enter_link_macro(
+    &mut self,
+    _target: &SourceString,
+    _attrs: Option<&AttrList>,
+    _scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+    _blank_window_shorthand: bool,
+  ) {
+    if !resolving_xref && has_link_text {
+      self.link_start = Some(self.roff.len());
+    }
+  }
+
+  fn exit_link_macro(
+    &mut self,
+    target: &SourceString,
+    _attrs: Option<&AttrList>,
+    scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+  ) {
+    if resolving_xref {
+      return;
+    }
+    let mut url = troff::escape(target, false);
+    if matches!(scheme, Some(UrlScheme::Mailto)) {
+      url = url.trim_start_matches("mailto:").to_string();
+    } else if self.doc_meta.is_true("hide-uri-scheme") && !has_link_text {
+      url = troff::escape(file::remove_uri_scheme(target), false);
+    }
+    let link_text = self
+      .link_start
+      .take()
+      .map(|start| self.roff.split_off(start))
+      .filter(|text| !text.is_empty() && *text != url);
+    if let Some(text) = link_text {
+      self.push([&text, " \\(la\\%", &url, "\\(ra"]);
+    } else {
+      self.push(["\\%", &url]);
+    }
+  }
+
+  fn enter_mailto_macro(
+    &mut self,
+    _address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    has_link_text: bool,
+  ) {
+    if has_link_text {
+      self.link_start = Some(self.roff.len());
+    }
+  }
+
+  fn exit_mailto_macro(
+    &mut self,
+    address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    _has_link_text: bool,
+  ) {
+    let address = troff::escape(address, false);
+    match self
+      .link_start
+      .take()
+      .map(|start| self.roff.split_off(start))
+    {
+      Some(text) => self.push([&text, " \\(la\\%", &address, "\\(ra"]),
+      None => self.push(["\\%", &address]),
+    }
+  }
+
+  fn enter_admonition_block(
+    &mut self,
+    kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    block: &Block,
+  ) {
+    let title = self.take_title();
+    self.push_control(".if n .sp");
+    self.push_control(".RS 4");
+    self.push_control(".it 1 an-trap");
+    self.push_control(".nr an-no-space-flag 1");
+    self.push_control(".nr an-break-flag 1");
+    self.push_control(".br");
+    self.push_control(".ps +1");
+    let caption = self.doc_meta.string_or(kind.caption_name(), kind.str());
+    self.push(["\\fB", &troff::escape(&caption, true), "\\fP"]);
+    self.push_control(".ps -1");
+    self.push_control(".br");
+    if let Some(title) = title {
+      self.push(["\\fB", &title, "\\fP"]);
+      self.push_control(".br");
+    }
+    if !matches!(block.content, BlockContent::Compound(_)) {
+      self.ensure_newline();
+    }
+  }
+
+  fn exit_admonition_block(
+    &mut self,
+    _kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    _block: &Block,
+  ) {
+    self.push_control(".sp .5v");
+    self.push_control(".RE");
+  }
+
+  fn enter_image_block(
+    &mut self,
+    img_target: &SourceString,
+    img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    _block: &Block,
+  ) {
+    self.push_block_title();
+    self.push_control(".sp");
+    self.push_image_alt(img_target, img_attrs);
+  }
+
+  fn exit_image_block(
+    &mut self,
+    _img_target: &SourceString,
+    _img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    _block: &Block,
+  ) {
+  }
+
+  fn visit_document_attribute_decl(&mut self, name: &str, value: &AttrValue) {
+    if name == "hardbreaks-option" {
+      self.default_hardbreaks = value.is_true();
+      self.hardbreaks = value.is_true();
+    }
+    _ = self.doc_meta.insert_doc_attr(name, value.clone());
+  }
+
+  fn enter_footnote(&mut self, id: Option<&SourceString>) {
+    let prev = id.and_then(|id| {
+      self
+        .footnotes
+        .iter()
+        .position(|(prev, _)| prev.as_deref() == Some(id))
+    });
+    self.footnote_dupe = prev.is_some();
+    let num = prev.unwrap_or(self.footnotes.len()) + 1;
+    self.push(["\\u[", &num.to_string(), "]\\d"]);
+    self.footnote_start = Some(self.roff.len());
+  }
+
+  fn exit_footnote(&mut self, id: Option<&SourceString>) {
+    let Some(start) = self.footnote_start.take() else {
+      return;
+    };
+    let text = self.roff.split_off(start);
+    if !self.footnote_dupe {
+      self.footnotes.push((id.map(|id| id.to_string()), text));
+    }
+  }
+
+  fn enter_meta_title(&mut self, _block: &Block) {
+    self.start_buffering();
+  }
+
+  fn exit_meta_title(&mut self, block: &Block) {
+    if block.context != BlockContext::Passthrough {
+      self.stop_buffering();
+    } else {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  fn into_result(self) -> Result<Self::Output, Self::Error> {
+    Ok(self.roff)
+  }
+
+  fn result(&self) -> Result<&Self::Output, Self::Error> {
+    Ok(&self.roff)
+  }
+}
+
+impl Manpage {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn standalone(&self) -> bool {
+    self.doc_meta.get_doctype() != DocType::Inline
+      && !self.in_asciidoc_table_cell
+      && !self.doc_meta.embedded
+  }
+
+  fn push_preamble(&mut self, manvolnum: &str) {
+    let mantitle = self
+      .doc_meta
+      .string("mantitle")
+      .or_else(|| self.doc_meta.string("doctitle"))
+      .unwrap_or_else(|| "UNTITLED".to_string());
+    let date = self
+      .doc_meta
+      .string("revdate")
+      .or_else(|| self.doc_meta.string("docdate"))
+      .unwrap_or_default();
+    let source = self.doc_meta.string_or("mansource", "");
+    let manual = self.doc_meta.string_or("manmanual", "");
+    let author = match self.doc_meta.authors.first() {
+      Some(author) => author.fullname(),
+      None => "[see the \"AUTHOR(S)\" section]".to_string(),
+    };
+    self.push_str("'\\\" t\n");
+    self.push([".\\\"     Title: ", &mantitle, "\n"]);
+    self.push([".\\\"    Author: ", &author, "\n"]);
+    self.push_str(".\\\" Generator: Asciidork\n");
+    self.push([".\\\"      Date: ", &date, "\n"]);
+    self.push([
+      ".\\\"    Manual: ",
+      iff!(manual.is_empty(), "\\ \\&", &manual),
+      "\n",
+    ]);
+    self.push([
+      ".\\\"    Source: ",
+      iff!(source.is_empty(), "\\ \\&", &source),
+      "\n",
+    ]);
+    self.push_str(".\\\"  Language: English\n.\\\"\n");
+    self.push([
+      ".TH \"",
+      &troff::quoted_arg(&mantitle.to_uppercase()),
+      "\" \"",
+      &troff::quoted_arg(manvolnum),
+      "\" \"",
+      &troff::quoted_arg(&date),
+      "\" \"",
+      &troff::quoted_arg(&source),
+      "\" \"",
+      &troff::quoted_arg(&manual),
+      "\"\n",
+    ]);
+    self.push_str(".ie \\n(.g .ds Aq \\(aq\n");
+    self.push_str(".el       .ds Aq '\n");
+    self.push_str(".ss \\n[.ss] 0\n");
+    self.push_str(".nh\n");
+    self.push_str(".ad l\n");
+  }
+
+  fn ensure_newline(&mut self) {
+    if !self.roff.is_empty() && !self.roff.ends_with('\n') {
+      self.push_ch('\n');
+    }
+  }
+
+  /// control lines must begin a line, and nothing may follow them
+  fn push_control(&mut self, line: &str) {
+    self.start_control(line);
+    self.push_ch('\n');
+  }
+
+  /// begins a control line whose (quoted) argument is evaluated inline
+  fn start_control(&mut self, macro_start: &str) {
+    self.ensure_newline();
+    self.push_str(macro_start);
+  }
+
+  fn take_title(&mut self) -> Option<String> {
+    if self.alt_roff.is_empty() { None } else { Some(self.take_buffer()) }
+  }
+
+  fn push_block_title(&mut self) {
+    if let Some(title) = self.take_title() {
+      self.push_control(".sp");
+      self.push(["\\fB", &title, "\\fP"]);
+      self.push_control(".br");
+    }
+  }
+
+  fn enter_verbatim(&mut self) {
+    self.push_block_title();
+    self.push_control(".sp");
+    self.push_control(".if n .RS 4");
+    self.push_control(".nf");
+    self.push_control(".fam C");
+  }
+
+  fn exit_verbatim(&mut self) {
+    self.push_control(".fam");
+    self.push_control(".fi");
+    self.push_control(".if n .RE");
+  }
+
+  fn enter_quote(&mut self) {
+    self.push_block_title();
+    self.quote_stack.push(false);
+    self.push_control(".RS 3");
+    self.push_control(".ll -.6i");
+  }
+
+  fn enter_attribution(&mut self, block: &Block) {
+    if block.context == BlockContext::Verse {
+      self.push_control(".fi");
+    }
+    if let Some(closed) = self.quote_stack.last_mut() {
+      *closed = true;
+    }
+    self.push_control(".br");
+    self.push_control(".RE");
+    self.push_control(".ll");
+    self.push_control(".RS 5");
+    self.push_control(".ll -.10i");
+    self.push_str("\\(em ");
+  }
+
+  fn exit_quote(&mut self) {
+    if self.quote_stack.pop() == Some(false) {
+      self.push_control(".br");
+    }
+    self.push_control(".RE");
+    self.push_control(".ll");
+  }
+
+  fn push_list_marker(&mut self, nroff_marker: &str, ip_args: &str) {
+    self.push_control(".sp");
+    self.push_control(".RS 4");
+    self.push_control(".ie n \\{\\");
+    self.push_control(&format!("\\h'-04'{nroff_marker}\\c"));
+    self.push_control(".\\}");
+    self.push_control(".el \\{\\");
+    self.push_control(".  sp -1");
+    self.push_control(&format!(".  IP {ip_args}"));
+    self.push_control(".\\}");
+  }
+
+  fn push_image_alt(&mut self, target: &str, attrs: &AttrList) {
+    let alt = match attrs.named("alt").or_else(|| attrs.str_positional_at(0)) {
+      Some(alt) => alt.to_string(),
+      None => file::stem(target).replace(['-', '_'], " "),
+    };
+    self.push(["[", &troff::escape(&alt, false), "]"]);
+  }
+
+  fn resolved_table_attr(&self, name: &str, block: &Block) -> String {
+    block
+      .meta
+      .attrs
+      .named(name)
+      .or_else(|| self.doc_meta.str(&format!("table-{name}")))
+      .unwrap_or("all")
+      .to_string()
+  }
+
+  fn push_field_separator(&mut self) {
+    if !self.table.first_field {
+      self.push_ch(':');
+    }
+    self.table.first_field = false;
+  }
+
+  /// vertically spanned cells still require an (ignored) data field
+  fn push_span_placeholders(&mut self) {
+    while self.table.occupied.get(self.table.col) == Some(&true) {
+      self.table.col += 1;
+      self.push_field_separator();
+      self.push_str("\\^");
+    }
+  }
+}
+
+impl HtmlBuf for Manpage {
+  fn htmlbuf(&mut self) -> &mut String {
+    &mut self.roff
+  }
+
+  fn swapbuf(&mut self, s: &mut String) {
+    std::mem::swap(&mut self.roff, s);
+  }
+}
+
+impl AltHtmlBuf for Manpage {
+  fn alt_htmlbuf(&mut self) -> &mut String {
+    &mut self.alt_roff
+  }
+
+  fn buffers(&mut self) -> (&mut String, &mut String) {
+    (&mut self.roff, &mut self.alt_roff)
+  }
+}
+
+/// builds the tbl(1) format line for each row, in evaluation order
+fn row_formats(table: &Table) -> Vec<String> {
+  let num_cols = table.col_widths.len();
+  let mut row_spans = vec![0u8; num_cols];
+  let header = table.header_row.iter().map(|row| (row, true));
+  let body = table.rows.iter().map(|row| (row, false));
+  let footer = table.footer_row.iter().map(|row| (row, false));
+  let mut formats = Vec::new();
+  for (row, is_header) in header.chain(body).chain(footer) {
+    let occupied = row_spans.iter().map(|span| *span > 0).collect::<Vec<_>>();
+    let mut specs = Vec::with_capacity(num_cols);
+    let mut col = 0;
+    for cell in row.cells.iter() {
+      while occupied.get(col) == Some(&true) {
+        specs.push("^".to_string());
+        col += 1;
+      }
+      let align = match cell.h_align {
+        HorizontalAlignment::Left => "l",
+        HorizontalAlignment::Center => "c",
+        HorizontalAlignment::Right => "r",
+      };
+      let weight = if is_header || matches!(cell.content, CellContent::Header(_)) {
+        "B"
+      } else {
+        ""
+      };
+      specs.push(format!("{align}t{weight}"));
+      for _ in 1..cell.col_span {
+        specs.push("s".to_string());
+      }
+      if cell.row_span > 1 {
+        row_spans
+          .iter_mut()
+          .skip(col)
+          .take(cell.col_span as usize)
+          .for_each(|span| *span = cell.row_span);
+      }
+      col += cell.col_span as usize;
+    }
+    while occupied.get(col) == Some(&true) {
+      specs.push("^".to_string());
+      col += 1;
+    }
+    row_spans
+      .iter_mut()
+      .for_each(|span| *span = span.saturating_sub(1));
+    formats.push(specs.join(" "));
+  }
+  formats
+}
@@ -0,0 +1,49 @@
+/// escapes text for roff, `at_line_start` guards against the text
+/// being interpreted as a control line (leading `.` or `'`)
+pub fn escape(text: &str, at_line_start: bool) -> String {
+  let mut escaped = String::with_capacity(text.len() + 8);
+  let mut line_start = at_line_start;
+  for c in text.chars() {
+    if line_start && (c == '.' || c == '\'') {
+      escaped.push_str("\\&");
+    }
+    line_start = c == '\n';
+    match c {
+      '\\' => escaped.push_str("\\(rs"),
+      '-' => escaped.push_str("\\-"),
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// escapes a value destined for a double-quoted macro argument
+pub fn quoted_arg(text: &str) -> String {
+  escape(text, false).replace('"', "\\(dq")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_escape() {
+    let cases = vec![
+      ("foo", false, "foo"),
+      ("git-foo", false, "git\\-foo"),
+      ("a\\b", false, "a\\(rsb"),
+      (".hidden", true, "\\&.hidden"),
+      (".hidden", false, ".hidden"),
+      ("'quoted", true, "\\&'quoted"),
+      ("a\n.b", false, "a\n\\&.b"),
+    ];
+    for (input, at_line_start, expected) in cases {
+      assert_eq!(escape(input, at_line_start), expected, "input: {input:?}");
+    }
+  }
+
+  #[test]
+  fn test_quoted_arg() {
+    assert_eq!(quoted_arg(r#"say "hi""#), r#"say \(dqhi\(dq"#);
+  }
+}
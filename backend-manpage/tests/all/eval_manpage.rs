@@ -0,0 +1,220 @@
+use crate::helpers::*;
+use test_utils::*;
+
+assert_converted!(
+  sections_and_paragraphs,
+  roff,
+  adoc! {r#"
+    == Name
+
+    git-foo - do *foo* things
+
+    === Sub section
+
+    a path C:\Users and
+    {empty}.leading dot
+  "#},
+  adoc! {r#"
+    .SH "NAME"
+    .sp
+    git\-foo \- do \fBfoo\fP things
+    .SS "Sub section"
+    .sp
+    a path C:\(rsUsers and
+    \&.leading dot
+  "#}
+);
+
+assert_converted!(
+  listing_block_w_title,
+  roff,
+  adoc! {r#"
+    .Example
+    ----
+    $ foo --bar
+    ----
+  "#},
+  adoc! {r#"
+    .sp
+    \fBExample\fP
+    .br
+    .sp
+    .if n .RS 4
+    .nf
+    .fam C
+    $ foo \-\-bar
+    .fam
+    .fi
+    .if n .RE
+  "#}
+);
+
+assert_converted!(
+  lists,
+  roff,
+  adoc! {r#"
+    * one
+
+    //-
+
+    . first
+
+    //-
+
+    -v::
+      Be verbose.
+  "#},
+  adoc! {r#"
+    .sp
+    .RS 4
+    .ie n \{\
+    \h'-04'\(bu\h'+03'\c
+    .\}
+    .el \{\
+    .  sp -1
+    .  IP \(bu 2.3
+    .\}
+    one
+    .RE
+    .sp
+    .RS 4
+    .ie n \{\
+    \h'-04' 1.\h'+01'\c
+    .\}
+    .el \{\
+    .  sp -1
+    .  IP " 1." 4.2
+    .\}
+    first
+    .RE
+    .sp
+    \fB\-v\fP
+    .RS 4
+    .sp
+    Be verbose.
+    .RE
+  "#}
+);
+
+assert_converted!(
+  admonition,
+  roff,
+  adoc! {r#"
+    WARNING: Watch out.
+  "#},
+  adoc! {r#"
+    .if n .sp
+    .RS 4
+    .it 1 an-trap
+    .nr an-no-space-flag 1
+    .nr an-break-flag 1
+    .br
+    .ps +1
+    \fBWarning\fP
+    .ps -1
+    .br
+    Watch out.
+    .sp .5v
+    .RE
+  "#}
+);
+
+assert_converted!(
+  table_w_spans,
+  roff,
+  adoc! {r#"
+    [%header,grid=none,frame=none]
+    |===
+    |A |B
+    .2+|tall |1
+    |2
+    2+^|wide
+    |===
+  "#},
+  adoc! {r#"
+    .TS
+    tab(:);
+    ltB ltB
+    lt lt
+    ^ lt
+    ct s.
+    T{
+    A
+    T}:T{
+    B
+    T}
+    T{
+    tall
+    T}:T{
+    1
+    T}
+    \^:T{
+    2
+    T}
+    T{
+    wide
+    T}
+    .TE
+    .sp
+  "#}
+);
+
+assert_converted!(
+  links_and_footnotes,
+  roff,
+  adoc! {r#"
+    Visit https://example.com or
+    see https://example.com[the docs].footnote:[Really.]
+  "#},
+  adoc! {r#"
+    .sp
+    Visit \%https://example.com or
+    see the docs \(la\%https://example.com\(ra.\u[1]\d
+    .SH "NOTES"
+    .IP "[1]" 4
+    Really.
+  "#}
+);
+
+assert_converted!(
+  standalone_manpage,
+  roff_standalone,
+  adoc! {r#"
+    = git-foo(8)
+    Jane Doe
+    :doctype: manpage
+    :manmanual: Git Manual
+    :mansource: Git
+    :revdate: 2024-05-01
+
+    == NAME
+
+    git-foo - do foo things
+  "#},
+  adoc! {r#"
+    '\" t
+    .\"     Title: git-foo
+    .\"    Author: Jane Doe
+    .\" Generator: Asciidork
+    .\"      Date: 2024-05-01
+    .\"    Manual: Git Manual
+    .\"    Source: Git
+    .\"  Language: English
+    .\"
+    .TH "GIT\-FOO" "8" "2024\-05\-01" "Git" "Git Manual"
+    .ie \n(.g .ds Aq \(aq
+    .el       .ds Aq '
+    .ss \n[.ss] 0
+    .nh
+    .ad l
+    .SH "NAME"
+    .sp
+    git\-foo \- do foo things
+    .SH "AUTHOR"
+    .sp
+    \fBJane Doe\fP
+    .RS 4
+    Author.
+    .RE
+  "#}
+);
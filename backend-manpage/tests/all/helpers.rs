@@ -0,0 +1,13 @@
+use asciidork_core::JobSettings;
+
+pub fn test_backend_factory() -> asciidork_backend_manpage::Manpage {
+  asciidork_backend_manpage::Manpage::new()
+}
+
+pub fn roff(input: &str) -> String {
+  _html!(input, |_| {}, None)
+}
+
+pub fn roff_standalone(input: &str) -> String {
+  _html!(input, |s: &mut JobSettings| s.embedded = false, None)
+}
@@ -0,0 +1,126 @@
+use crate::internal::*;
+
+/// escapes characters that would otherwise be interpreted as markdown,
+/// `at_line_start` additionally guards against block-level constructs
+pub fn escape(text: &str, at_line_start: bool) -> String {
+  let mut escaped = String::with_capacity(text.len() + 8);
+  let chars = text.chars().collect::<Vec<_>>();
+  let mut line_start = at_line_start;
+  let mut marker_at = None;
+  for (idx, &c) in chars.iter().enumerate() {
+    if line_start {
+      marker_at = block_marker(&chars[idx..]).map(|offset| idx + offset);
+    }
+    if marker_at == Some(idx) {
+      escaped.push('\\');
+    }
+    line_start = c == '\n';
+    match c {
+      '\\' | '`' | '*' | '[' | ']' | '<' => {
+        escaped.push('\\');
+        escaped.push(c);
+      }
+      '_' => {
+        let prev = idx.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(idx + 1).copied();
+        let intraword =
+          prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric);
+        if !intraword {
+          escaped.push('\\');
+        }
+        escaped.push(c);
+      }
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// offset of the char to escape so a line doesn't start a markdown block,
+/// only ascii punctuation is escapable, so for an ordered list marker
+/// like `10.` that's the delimiter, not the number
+fn block_marker(chars: &[char]) -> Option<usize> {
+  match chars {
+    ['#' | '>' | '=' | '~', ..] => Some(0),
+    ['-' | '+', next, ..] if next.is_whitespace() || *next == '-' => Some(0),
+    ['-' | '+'] => Some(0),
+    _ => {
+      let digits = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+      let ordered = digits > 0
+        && matches!(chars.get(digits), Some('.' | ')'))
+        && chars.get(digits + 1).is_none_or(|c| c.is_whitespace());
+      ordered.then_some(digits)
+    }
+  }
+}
+
+/// wraps text in a code span, using a backtick fence long enough
+/// to contain any backticks in the text itself
+pub fn code_span(code: &str) -> String {
+  let fence = "`".repeat(longest_run(code, '`') + 1);
+  let pad = iff!(code.starts_with('`') || code.ends_with('`'), " ", "");
+  format!("{fence}{pad}{code}{pad}{fence}")
+}
+
+/// a code fence that cannot be closed by the content it wraps
+pub fn code_fence(code: &str) -> String {
+  "`".repeat(longest_run(code, '`').max(2) + 1)
+}
+
+/// escapes a value destined for a link destination
+pub fn link_destination(url: &str) -> String {
+  if url.contains([' ', '(', ')', '<', '>']) {
+    format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+  } else {
+    url.to_string()
+  }
+}
+
+fn longest_run(text: &str, needle: char) -> usize {
+  let (mut longest, mut current) = (0, 0);
+  for c in text.chars() {
+    current = if c == needle { current + 1 } else { 0 };
+    longest = longest.max(current);
+  }
+  longest
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_escape() {
+    let cases = vec![
+      ("foo", false, "foo"),
+      ("snake_case", false, "snake_case"),
+      ("_leading", false, "\\_leading"),
+      ("a *b* [c]", false, "a \\*b\\* \\[c\\]"),
+      ("# not a heading", true, "\\# not a heading"),
+      ("# mid-line", false, "# mid-line"),
+      ("- not a list", true, "\\- not a list"),
+      ("-v flag", true, "-v flag"),
+      ("1. not a list", true, "1\\. not a list"),
+      ("10. foo", true, "10\\. foo"),
+      ("2) not a list", true, "2\\) not a list"),
+      ("1.5 is a number", true, "1.5 is a number"),
+      ("a\n> b", false, "a\n\\> b"),
+    ];
+    for (input, at_line_start, expected) in cases {
+      assert_eq!(escape(input, at_line_start), expected, "input: {input:?}");
+    }
+  }
+
+  #[test]
+  fn test_code_span() {
+    assert_eq!(code_span("foo"), "`foo`");
+    assert_eq!(code_span("a`b"), "``a`b``");
+    assert_eq!(code_span("`a"), "`` `a ``");
+  }
+
+  #[test]
+  fn test_code_fence() {
+    assert_eq!(code_fence("foo"), "```");
+    assert_eq!(code_fence("```\nfoo\n```"), "````");
+  }
+}
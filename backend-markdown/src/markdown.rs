@@ -0,0 +1,1319 @@
+use crate::gfm;
+use crate::internal::*;
+use ast::AdjacentNewline;
+use backend::num_str;
+
+#[derive(Debug, Default)]
+pub struct Markdown {
+  doc_meta: DocumentMeta,
+  md: String,
+  alt_md: String,
+  hardbreaks: bool,
+  default_hardbreaks: bool,
+  verbatim: bool,
+  passthrough: bool,
+  in_asciidoc_table_cell: bool,
+  containers: Vec<Container>,
+  list_stack: Vec<ListState>,
+  table: Option<TableState>,
+  verbatim_start: Option<usize>,
+  code_span_start: Option<usize>,
+  stem_start: Option<usize>,
+  link_start: Option<usize>,
+  xref_start: Option<usize>,
+  xref_depth: u8,
+  footnote_start: Option<usize>,
+  footnote_dupe: bool,
+  footnotes: Vec<(Option<String>, String)>,
+}
+
+/// a region of output whose lines get prefixed when the container closes,
+/// `floor` marks where its first child block may begin without a blank line
+#[derive(Debug, Clone, Copy)]
+struct Container {
+  start: usize,
+  floor: usize,
+  kind: ContainerKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContainerKind {
+  Quote,
+  ListItem(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ListState {
+  next_num: u32,
+  num_items: usize,
+  principal_end: Option<usize>,
+  item_open: bool,
+}
+
+#[derive(Debug, Default)]
+struct TableState {
+  rows: Vec<Vec<String>>,
+  row: Vec<String>,
+  aligns: Vec<HorizontalAlignment>,
+  has_header: bool,
+  col: usize,
+  row_spans: Vec<u8>,
+  occupied: Vec<bool>,
+  cell_start: usize,
+  first_paragraph: bool,
+}
+
+impl Backend for Markdown {
+  type Output = String;
+  type Error = std::convert::Infallible;
+  const OUTFILESUFFIX: &'static str = ".md";
+
+  fn set_job_attrs(attrs: &mut asciidork_core::JobAttrs) {
+    attrs.insert_unchecked("backend", JobAttr::readonly("markdown"));
+    attrs.insert_unchecked("backend-markdown", JobAttr::readonly(true));
+    attrs.insert_unchecked("basebackend", JobAttr::readonly("markdown"));
+    attrs.insert_unchecked("basebackend-markdown", JobAttr::readonly(true));
+  }
+
+  fn enter_document(&mut self, document: &Document) {
+    self.doc_meta = document.meta.clone();
+    utils::set_backend_attrs::<Self>(&mut self.doc_meta);
+  }
+
+  fn exit_document(&mut self, _document: &Document) {
+    if self.in_asciidoc_table_cell {
+      return;
+    }
+    for (idx, (_, text)) in std::mem::take(&mut self.footnotes).iter().enumerate() {
+      self.start_block_if(idx == 0);
+      self.ensure_newline();
+      self.push(["[^", &num_str!(idx + 1), "]: ", &text.replace('\n', " ")]);
+    }
+    while self.md.ends_with('\n') {
+      self.md.pop();
+    }
+    if !self.md.is_empty() {
+      self.push_ch('\n');
+    }
+  }
+
+  fn enter_header(&mut self) {}
+  fn exit_header(&mut self) {}
+  fn enter_content(&mut self) {}
+  fn exit_content(&mut self) {}
+  fn enter_footer(&mut self) {}
+  fn exit_footer(&mut self) {}
+
+  fn enter_document_title(&mut self) {
+    if self.standalone() {
+      self.push_str("# ");
+    } else {
+      self.start_buffering();
+    }
+  }
+
+  fn exit_document_title(&mut self) {
+    if !self.standalone() {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  // github and most markdown renderers generate their own outline
+  fn enter_toc(&mut self, _toc: &TableOfContents, _macro_block: Option<&Block>) {
+    self.start_buffering();
+  }
+
+  fn exit_toc(&mut self, _toc: &TableOfContents) {
+    self.swap_discard_alt_buffer();
+  }
+
+  fn enter_book_part(&mut self, _part: &Part) {}
+  fn exit_book_part(&mut self, _part: &Part) {}
+
+  fn enter_book_part_title(&mut self, title: &PartTitle) {
+    self.start_block();
+    self.push_str("# ");
+    if let Some(id) = &title.id {
+      self.push_anchor(id);
+    }
+  }
+
+  fn exit_book_part_title(&mut self, _title: &PartTitle) {}
+  fn enter_book_part_intro(&mut self, _part: &Part) {}
+  fn exit_book_part_intro(&mut self, _part: &Part) {}
+  fn enter_book_part_intro_content(&mut self, _part: &Part) {}
+  fn exit_book_part_intro_content(&mut self, _part: &Part) {}
+  fn enter_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {}
+  fn exit_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {}
+  fn enter_section(&mut self, _section: &Section) {}
+  fn exit_section(&mut self, _section: &Section) {}
+
+  fn enter_section_heading(&mut self, section: &Section) {
+    self.push_heading_marker(section.level, section.id.as_deref());
+  }
+
+  fn exit_section_heading(&mut self, _section: &Section) {}
+
+  fn enter_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+  fn exit_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+
+  fn enter_simple_block_content(&mut self, block: &Block) {
+    if block.meta.attrs.has_option("hardbreaks") {
+      self.hardbreaks = true;
+    }
+  }
+
+  fn exit_simple_block_content(&mut self, _block: &Block) {
+    self.hardbreaks = self.default_hardbreaks;
+  }
+
+  fn enter_paragraph_block(&mut self, block: &Block) {
+    self.open_block(block);
+  }
+
+  fn exit_paragraph_block(&mut self, _block: &Block) {}
+
+  // sidebars have no markdown equivalent, so we set them off as a quote
+  fn enter_sidebar_block(&mut self, block: &Block) {
+    self.open_block(block);
+    self.open_container(ContainerKind::Quote);
+  }
+
+  fn exit_sidebar_block(&mut self, _block: &Block) {
+    self.close_container();
+  }
+
+  fn enter_listing_block(&mut self, block: &Block) {
+    self.open_block(block);
+    let lang = block
+      .meta
+      .attrs
+      .source_language()
+      .map(str::to_string)
+      .or_else(|| {
+        self
+          .doc_meta
+          .string("source-language")
+          .filter(|_| block.meta.attrs.is_source())
+      });
+    self.enter_verbatim(lang);
+  }
+
+  fn exit_listing_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_literal_block(&mut self, block: &Block) {
+    self.open_block(block);
+    self.enter_verbatim(None);
+  }
+
+  fn exit_literal_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_passthrough_block(&mut self, block: &Block) {
+    self.open_block(block);
+    self.passthrough = true;
+  }
+
+  fn exit_passthrough_block(&mut self, _block: &Block) {
+    self.passthrough = false;
+  }
+
+  // github renders `math` fences as latex, asciimath is left as-is
+  fn enter_stem_block(&mut self, block: &Block, notation: StemNotation) {
+    self.open_block(block);
+    let lang = match notation {
+      StemNotation::Latexmath => "math",
+      StemNotation::Asciimath => "asciimath",
+    };
+    self.enter_verbatim(Some(lang.to_string()));
+  }
+
+  fn exit_stem_block(&mut self, _block: &Block, _notation: StemNotation) {
+    self.exit_verbatim();
+  }
+
+  fn enter_quoted_paragraph(&mut self, block: &Block) {
+    self.open_block(block);
+    self.open_container(ContainerKind::Quote);
+  }
+
+  fn exit_quoted_paragraph(&mut self, _block: &Block) {
+    self.close_container();
+  }
+
+  fn enter_quote_block(&mut self, block: &Block, _has_attribution: bool) {
+    self.open_block(block);
+    self.open_container(ContainerKind::Quote);
+  }
+
+  fn exit_quote_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.close_container();
+  }
+
+  fn enter_quote_attribution(&mut self, _block: &Block, _has_cite: bool) {
+    self.hardbreaks = self.default_hardbreaks;
+    self.start_block();
+    self.push_str("— ");
+  }
+
+  fn exit_quote_attribution(&mut self, _block: &Block, _has_cite: bool) {}
+
+  fn enter_quote_cite(&mut self, _block: &Block, has_attribution: bool) {
+    if has_attribution {
+      self.push_str(", ");
+    } else {
+      self.start_block();
+      self.push_str("— ");
+    }
+  }
+
+  fn exit_quote_cite(&mut self, _block: &Block, _has_attribution: bool) {}
+
+  fn enter_verse_block(&mut self, block: &Block, _has_attribution: bool) {
+    self.open_block(block);
+    self.open_container(ContainerKind::Quote);
+    self.hardbreaks = true;
+  }
+
+  fn exit_verse_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.hardbreaks = self.default_hardbreaks;
+    self.close_container();
+  }
+
+  fn enter_example_block(&mut self, block: &Block) {
+    self.open_block(block);
+  }
+
+  fn exit_example_block(&mut self, _block: &Block) {}
+
+  fn enter_open_block(&mut self, block: &Block) {
+    self.open_block(block);
+  }
+
+  fn exit_open_block(&mut self, _block: &Block) {}
+
+  fn enter_discrete_heading(&mut self, level: u8, id: Option<&str>, _block: &Block) {
+    self.push_heading_marker(level, id);
+  }
+
+  fn exit_discrete_heading(&mut self, _level: u8, _id: Option<&str>, _block: &Block) {}
+
+  fn enter_unordered_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.open_list(block, 1);
+  }
+
+  fn exit_unordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_ordered_list(&mut self, block: &Block, items: &[ListItem], _depth: u8) {
+    let start = block
+      .meta
+      .attrs
+      .named("start")
+      .and_then(|start| start.parse::<u32>().ok())
+      .or(match items.first().map(|item| &item.marker) {
+        Some(ListMarker::Digits(n)) => Some(*n as u32),
+        _ => None,
+      })
+      .unwrap_or(1);
+    self.open_list(block, start);
+  }
+
+  fn exit_ordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_callout_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.open_list(block, 1);
+  }
+
+  fn exit_callout_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  // description lists degrade to an unordered list of bold terms
+  fn enter_description_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.open_list(block, 1);
+  }
+
+  fn exit_description_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.close_list_item();
+    self.list_stack.pop();
+  }
+
+  fn enter_description_list_term(&mut self, _item: &ListItem, num: usize, _total: usize) {
+    if num == 1 {
+      self.close_list_item();
+      self.open_list_item("- ");
+      self.push_str("**");
+    } else {
+      self.push_str(", **");
+    }
+  }
+
+  fn exit_description_list_term(&mut self, _item: &ListItem, _num: usize, _total: usize) {
+    self.push_str("**");
+  }
+
+  fn enter_description_list_description(&mut self, _item: &ListItem) {}
+
+  fn exit_description_list_description(&mut self, _item: &ListItem) {
+    self.close_list_item();
+  }
+
+  fn enter_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {
+    // a trailing backslash is a hard line break, keeping the
+    // description in the same paragraph as the term
+    self.push_str("\\\n");
+    if let Some(container) = self.containers.last_mut() {
+      container.floor = self.md.len();
+    }
+  }
+
+  fn exit_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+
+  fn enter_list_item_principal(&mut self, item: &ListItem, variant: ListVariant) {
+    let Some(list) = self.list_stack.last_mut() else {
+      return;
+    };
+    let marker = match (variant, &item.type_meta) {
+      (ListVariant::Callout, ListItemTypeMeta::Callout(callouts)) => {
+        let number = callouts.first().map_or(1, |callout| callout.number);
+        format!("{number}. ")
+      }
+      (ListVariant::Ordered, _) => {
+        list.next_num += 1;
+        format!("{}. ", list.next_num - 1)
+      }
+      // the principal text of a checklist item retains its leading space
+      (_, ListItemTypeMeta::Checklist(checked, _)) => iff!(*checked, "- [x]", "- [ ]").to_string(),
+      _ => "- ".to_string(),
+    };
+    self.open_list_item(&marker);
+    if marker.starts_with("- [") {
+      // task list items continue at the bullet's indentation
+      if let Some(ContainerKind::ListItem(indent)) = self
+        .containers
+        .last_mut()
+        .map(|container| &mut container.kind)
+      {
+        *indent = 2;
+      }
+    }
+  }
+
+  fn exit_list_item_principal(&mut self, _item: &ListItem, _variant: ListVariant) {
+    let len = self.md.len();
+    if let Some(list) = self.list_stack.last_mut() {
+      list.principal_end = Some(len);
+    }
+  }
+
+  fn enter_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, _variant: ListVariant) {
+  }
+
+  fn exit_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, _variant: ListVariant) {
+    self.close_list_item();
+  }
+
+  fn asciidoc_table_cell_backend(&mut self) -> Self {
+    Self {
+      in_asciidoc_table_cell: true,
+      ..Self::default()
+    }
+  }
+
+  fn visit_asciidoc_table_cell_result(&mut self, cell_backend: Self) {
+    let result = cell_backend.into_result().unwrap();
+    self.push_str(&result.trim().replace('\n', "<br>"));
+  }
+
+  fn enter_table(&mut self, table: &Table, block: &Block) {
+    self.open_block(block);
+    let num_cols = table.col_widths.len();
+    let first_row = table.header_row.as_ref().or(table.rows.first());
+    let mut aligns = first_row
+      .iter()
+      .flat_map(|row| row.cells.iter())
+      .flat_map(|cell| std::iter::repeat_n(cell.h_align, cell.col_span as usize))
+      .collect::<Vec<_>>();
+    aligns.resize(num_cols, HorizontalAlignment::Left);
+    self.table = Some(TableState {
+      aligns,
+      has_header: table.header_row.is_some(),
+      row_spans: vec![0; num_cols],
+      ..TableState::default()
+    });
+  }
+
+  fn exit_table(&mut self, _table: &Table, _block: &Block) {
+    let Some(table) = self.table.take() else {
+      return;
+    };
+    let mut rows = table.rows.into_iter();
+    // gfm tables require a header row, so we leave it blank if necessary
+    let header = if table.has_header {
+      rows.next().unwrap_or_default()
+    } else {
+      vec![String::new(); table.aligns.len()]
+    };
+    self.push_table_row(&header);
+    let delimiters = table
+      .aligns
+      .iter()
+      .map(|align| match align {
+        HorizontalAlignment::Left => "---".to_string(),
+        HorizontalAlignment::Center => ":---:".to_string(),
+        HorizontalAlignment::Right => "---:".to_string(),
+      })
+      .collect::<Vec<_>>();
+    self.push_table_row(&delimiters);
+    for row in rows {
+      self.push_table_row(&row);
+    }
+  }
+
+  fn enter_table_section(&mut self, _section: TableSection) {}
+  fn exit_table_section(&mut self, _section: TableSection) {}
+
+  fn enter_table_row(&mut self, _row: &Row, _section: TableSection) {
+    if let Some(table) = &mut self.table {
+      table.col = 0;
+      table.occupied = table.row_spans.iter().map(|span| *span > 0).collect();
+    }
+  }
+
+  fn exit_table_row(&mut self, _row: &Row, _section: TableSection) {
+    let Some(table) = &mut self.table else {
+      return;
+    };
+    table.push_span_placeholders();
+    for (span, occupied) in table.row_spans.iter_mut().zip(&table.occupied) {
+      if *occupied {
+        *span -= 1;
+      }
+    }
+    let row = std::mem::take(&mut table.row);
+    table.rows.push(row);
+  }
+
+  fn enter_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    let len = self.md.len();
+    let Some(table) = &mut self.table else {
+      return;
+    };
+    table.push_span_placeholders();
+    if cell.row_span > 1 {
+      let col = table.col;
+      table
+        .row_spans
+        .iter_mut()
+        .skip(col)
+        .take(cell.col_span as usize)
+        .for_each(|span| *span = cell.row_span - 1);
+    }
+    table.cell_start = len;
+    table.first_paragraph = true;
+  }
+
+  fn exit_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    let Some(table) = &mut self.table else {
+      return;
+    };
+    let content = self.md.split_off(table.cell_start);
+    table.row.push(content.trim().replace('|', "\\|"));
+    // spanned columns have no markdown equivalent, so they are left empty
+    for _ in 1..cell.col_span {
+      table.row.push(String::new());
+    }
+    table.col += cell.col_span as usize;
+  }
+
+  fn enter_cell_paragraph(&mut self, cell: &Cell, _section: TableSection) {
+    if let Some(table) = &mut self.table {
+      if !table.first_paragraph {
+        self.md.push_str("<br><br>");
+      }
+      table.first_paragraph = false;
+    }
+    match &cell.content {
+      CellContent::Emphasis(_) => self.push_ch('*'),
+      CellContent::Strong(_) | CellContent::Header(_) => self.push_str("**"),
+      CellContent::Monospace(_) => self.code_span_start = Some(self.md.len()),
+      _ => {}
+    }
+  }
+
+  fn exit_cell_paragraph(&mut self, cell: &Cell, _section: TableSection) {
+    match &cell.content {
+      CellContent::Emphasis(_) => self.push_ch('*'),
+      CellContent::Strong(_) | CellContent::Header(_) => self.push_str("**"),
+      CellContent::Monospace(_) => self.exit_code_span(),
+      _ => {}
+    }
+  }
+
+  fn enter_inline_italic(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("*");
+  }
+
+  fn exit_inline_italic(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("*");
+  }
+
+  fn enter_inline_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.enter_code_span();
+  }
+
+  fn exit_inline_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.exit_code_span();
+  }
+
+  fn enter_inline_bold(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("**");
+  }
+
+  fn exit_inline_bold(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("**");
+  }
+
+  fn enter_inline_lit_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.enter_code_span();
+  }
+
+  fn exit_inline_lit_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.exit_code_span();
+  }
+
+  fn enter_inline_passthrough(&mut self) {
+    self.passthrough = true;
+  }
+
+  fn exit_inline_passthrough(&mut self) {
+    self.passthrough = false;
+  }
+
+  fn enter_inline_stem(&mut self, _notation: StemNotation) {
+    self.stem_start = Some(self.md.len());
+  }
+
+  fn exit_inline_stem(&mut self, notation: StemNotation) {
+    let Some(start) = self.stem_start.take() else {
+      return;
+    };
+    let content = self.md.split_off(start);
+    match notation {
+      StemNotation::Latexmath => self.push(["$`", &content, "`$"]),
+      StemNotation::Asciimath => self.push_str(&gfm::code_span(&content)),
+    }
+  }
+
+  fn enter_inline_highlight(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("<mark>");
+  }
+
+  fn exit_inline_highlight(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("</mark>");
+  }
+
+  fn enter_inline_subscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("<sub>");
+  }
+
+  fn exit_inline_subscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("</sub>");
+  }
+
+  fn enter_inline_superscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("<sup>");
+  }
+
+  fn exit_inline_superscript(&mut self, _attrs: Option<&AttrList>) {
+    self.push_inline_delimiter("</sup>");
+  }
+
+  fn visit_spaced_dashes(&mut self, len: u8, adjacent_newline: AdjacentNewline) {
+    match (len, adjacent_newline) {
+      (2, AdjacentNewline::None) => self.push_str(" — "),
+      (2, _) => self.push_str("—"),
+      _ => self.push_str(" \\-\\-\\- "),
+    }
+  }
+
+  fn enter_visible_index_term(&mut self) -> bool {
+    true
+  }
+
+  fn enter_concealed_index_term(&mut self, _num_terms: u8) -> bool {
+    false
+  }
+
+  fn visit_inline_specialchar(&mut self, char: &SpecialCharKind) {
+    match (char, self.raw()) {
+      (SpecialCharKind::Ampersand, _) => self.push_ch('&'),
+      (SpecialCharKind::LessThan, true) => self.push_ch('<'),
+      (SpecialCharKind::LessThan, false) => self.push_str("&lt;"),
+      (SpecialCharKind::GreaterThan, true) => self.push_ch('>'),
+      (SpecialCharKind::GreaterThan, false) => self.push_str("&gt;"),
+    }
+  }
+
+  fn visit_symbol(&mut self, kind: SymbolKind) {
+    match kind {
+      SymbolKind::Copyright => self.push_ch('©'),
+      SymbolKind::Registered => self.push_ch('®'),
+      SymbolKind::Trademark => self.push_ch('™'),
+      SymbolKind::EmDash => self.push_ch('—'),
+      SymbolKind::TripleDash => self.push_str("\\-\\-\\-"),
+      SymbolKind::Ellipsis => self.push_ch('…'),
+      SymbolKind::SingleRightArrow => self.push_ch('→'),
+      SymbolKind::DoubleRightArrow => self.push_ch('⇒'),
+      SymbolKind::SingleLeftArrow => self.push_ch('←'),
+      SymbolKind::DoubleLeftArrow => self.push_ch('⇐'),
+    }
+  }
+
+  fn enter_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_ch('“'),
+      QuoteKind::Single => self.push_ch('‘'),
+    }
+  }
+
+  fn exit_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_ch('”'),
+      QuoteKind::Single => self.push_ch('’'),
+    }
+  }
+
+  fn visit_curly_quote(&mut self, kind: CurlyKind) {
+    match kind {
+      CurlyKind::LeftDouble => self.push_ch('”'),
+      CurlyKind::RightDouble => self.push_ch('“'),
+      CurlyKind::LeftSingle => self.push_ch('‘'),
+      CurlyKind::RightSingle => self.push_ch('’'),
+      CurlyKind::LegacyImplicitApostrophe => self.push_ch('’'),
+    }
+  }
+
+  fn visit_multichar_whitespace(&mut self, whitespace: &str) {
+    if self.raw() {
+      self.push_str(whitespace);
+    } else {
+      self.push_ch(' ');
+    }
+  }
+
+  fn visit_thematic_break(&mut self, block: &Block) {
+    self.open_block(block);
+    self.push_str("---");
+  }
+
+  fn visit_page_break(&mut self, block: &Block) {
+    self.open_block(block);
+    self.push_str(r#"<div style="page-break-after: always;"></div>"#);
+  }
+
+  fn visit_inline_text(&mut self, text: &str) {
+    if self.raw() {
+      self.push_str(text);
+    } else {
+      let at_line_start = self.md.is_empty() || self.md.ends_with('\n');
+      self.push_str(&gfm::escape(text, at_line_start));
+    }
+  }
+
+  fn visit_joining_newline(&mut self) {
+    if self.table.is_some() && !self.verbatim {
+      self.push_ch(' ');
+    } else if self.hardbreaks && !self.raw() {
+      self.push_str("\\\n");
+    } else {
+      self.push_ch('\n');
+    }
+  }
+
+  fn enter_text_span(&mut self, attrs: Option<&AttrList>) {
+    if attrs.is_some_and(|attrs| attrs.has_role("line-through")) {
+      self.push_inline_delimiter("~~");
+    } else if attrs.is_some_and(|attrs| attrs.has_role("underline")) {
+      self.push_inline_delimiter("<ins>");
+    }
+  }
+
+  fn exit_text_span(&mut self, attrs: Option<&AttrList>) {
+    if attrs.is_some_and(|attrs| attrs.has_role("line-through")) {
+      self.push_inline_delimiter("~~");
+    } else if attrs.is_some_and(|attrs| attrs.has_role("underline")) {
+      self.push_inline_delimiter("</ins>");
+    }
+  }
+
+  fn enter_xref(&mut self, _target: &SourceString, _has_reftext: bool, _kind: XrefKind) {
+    self.xref_depth += 1;
+    if self.xref_depth == 1 {
+      self.xref_start = Some(self.md.len());
+    }
+  }
+
+  fn exit_xref(&mut self, target: &SourceString, _has_reftext: bool, kind: XrefKind) {
+    self.xref_depth -= 1;
+    if self.xref_depth > 0 {
+      return;
+    }
+    let Some(start) = self.xref_start.take() else {
+      return;
+    };
+    let text = self.md.split_off(start);
+    let href = utils::xref::href(target, &self.doc_meta, kind, true);
+    self.push(["[", &text, "](", &gfm::link_destination(&href), ")"]);
+  }
+
+  fn enter_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_str("\\[");
+    }
+  }
+
+  fn exit_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_str("\\]");
+    }
+  }
+
+  fn visit_missing_xref(
+    &mut self,
+    target: &SourceString,
+    kind: XrefKind,
+    _doc_title: Option<&DocTitle>,
+  ) {
+    if utils::xref::is_interdoc(target, kind) {
+      let href = utils::xref::href(target, &self.doc_meta, kind, false);
+      self.push_str(&gfm::escape(utils::xref::remove_leading_hash(&href), false));
+    } else {
+      let target = utils::xref::remove_leading_hash(target);
+      self.push(["\\[", &gfm::escape(target, false), "\\]"]);
+    }
+  }
+
+  fn visit_inline_anchor(&mut self, id: &str) {
+    self.push_anchor(id);
+  }
+
+  fn visit_biblio_anchor(&mut self, id: &str, reftext: Option<&str>) {
+    self.push_anchor(id);
+    self.push(["\\[", &gfm::escape(reftext.unwrap_or(id), false), "\\]"]);
+  }
+
+  fn visit_callout(&mut self, callout: Callout) {
+    if !self.md.is_empty() && !self.md.ends_with([' ', '\n']) {
+      self.push_ch(' ');
+    }
+    if self.raw() {
+      self.push(["(", &num_str!(callout.number), ")"]);
+    } else {
+      self.push(["**(", &num_str!(callout.number), ")**"]);
+    }
+  }
+
+  fn visit_callout_tuck(&mut self, comment: &str) {
+    self.push_str(comment);
+  }
+
+  fn visit_linebreak(&mut self) {
+    if self.table.is_some() {
+      self.push_str("<br>");
+    } else {
+      self.push_ch('\\');
+    }
+  }
+
+  fn visit_button_macro(&mut self, text: &SourceString) {
+    self.push(["**\\[", &gfm::escape(text, false), "\\]**"]);
+  }
+
+  fn visit_menu_macro(&mut self, items: &[SourceString]) {
+    for (idx, item) in items.iter().enumerate() {
+      if idx > 0 {
+        self.push_str(" ▸ ");
+      }
+      self.push(["**", &gfm::escape(item, false), "**"]);
+    }
+  }
+
+  fn visit_keyboard_macro(&mut self, keys: &[&str]) {
+    for (idx, key) in keys.iter().enumerate() {
+      if idx > 0 {
+        self.push_ch('+');
+      }
+      self.push(["<kbd>", &gfm::escape(key, false), "</kbd>"]);
+    }
+  }
+
+  fn visit_image_macro(&mut self, target: &SourceString, attrs: &AttrList, _kind: &ImageKind) {
+    self.push_image(target, attrs);
+  }
+
+  fn visit_icon_macro(&mut self, target: &SourceString, attrs: &AttrList) {
+    let alt = attrs.named("alt").unwrap_or(target);
+    self.push(["\\[", &gfm::escape(alt, false), "\\]"]);
+  }
+
+  fn visit_audio_macro(&mut self, target: &SourceString, _attrs: &AttrList, block: &Block) {
+    self.open_block(block);
+    self.push_media_link("audio", target);
+  }
+
+  fn visit_video_macro(&mut self, target: &SourceString, _attrs: &AttrList, block: &Block) {
+    self.open_block(block);
+    self.push_media_link("video", target);
+  }
+
+  fn enter_link_macro(
+    &mut self,
+    _target: &SourceString,
+    _attrs: Option<&AttrList>,
+    _scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+    _blank_window_shorthand: bool,
+  ) {
+    if !resolving_xref && has_link_text {
+      self.link_start = Some(self.md.len());
+    }
+  }
+
+  fn exit_link_macro(
+    &mut self,
+    target: &SourceString,
+    _attrs: Option<&AttrList>,
+    _scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+  ) {
+    if resolving_xref {
+      return;
+    }
+    let link_text = self
+      .link_start
+      .take()
+      .map(|start| self.md.split_off(start))
+      .filter(|text| !text.is_empty());
+    let text = match link_text {
+      Some(text) => text,
+      None if self.doc_meta.is_true("hide-uri-scheme") && !has_link_text => {
+        gfm::escape(file::remove_uri_scheme(target), false)
+      }
+      None => {
+        self.push(["<", target, ">"]);
+        return;
+      }
+    };
+    self.push(["[", &text, "](", &gfm::link_destination(target), ")"]);
+  }
+
+  fn enter_mailto_macro(
+    &mut self,
+    _address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    has_link_text: bool,
+  ) {
+    if has_link_text {
+      self.link_start = Some(self.md.len());
+    }
+  }
+
+  fn exit_mailto_macro(
+    &mut self,
+    address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    _has_link_text: bool,
+  ) {
+    match self.link_start.take().map(|start| self.md.split_off(start)) {
+      Some(text) => self.push(["[", &text, "](mailto:", address, ")"]),
+      None => self.push(["<", address, ">"]),
+    }
+  }
+
+  fn enter_admonition_block(
+    &mut self,
+    kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    block: &Block,
+  ) {
+    let title = self.take_title();
+    self.start_block();
+    if let Some(id) = block.meta.attrs.id() {
+      self.push_anchor(id);
+      self.start_block();
+    }
+    self.open_container(ContainerKind::Quote);
+    self.push(["[!", &kind.str().to_uppercase(), "]\n"]);
+    if let Some(title) = title {
+      self.push(["**", &title, "**\\\n"]);
+    }
+    if let Some(container) = self.containers.last_mut() {
+      container.floor = self.md.len();
+    }
+  }
+
+  fn exit_admonition_block(
+    &mut self,
+    _kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    _block: &Block,
+  ) {
+    self.close_container();
+  }
+
+  fn enter_image_block(
+    &mut self,
+    img_target: &SourceString,
+    img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    block: &Block,
+  ) {
+    self.open_block(block);
+    self.push_image(img_target, img_attrs);
+  }
+
+  fn exit_image_block(
+    &mut self,
+    _img_target: &SourceString,
+    _img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    _block: &Block,
+  ) {
+  }
+
+  fn visit_document_attribute_decl(&mut self, name: &str, value: &AttrValue) {
+    if name == "hardbreaks-option" {
+      self.default_hardbreaks = value.is_true();
+      self.hardbreaks = value.is_true();
+    }
+    _ = self.doc_meta.insert_doc_attr(name, value.clone());
+  }
+
+  fn enter_footnote(&mut self, id: Option<&SourceString>) {
+    let prev = id.and_then(|id| {
+      self
+        .footnotes
+        .iter()
+        .position(|(prev, _)| prev.as_deref() == Some(id))
+    });
+    self.footnote_dupe = prev.is_some();
+    let num = prev.unwrap_or(self.footnotes.len()) + 1;
+    self.push(["[^", &num_str!(num), "]"]);
+    self.footnote_start = Some(self.md.len());
+  }
+
+  fn exit_footnote(&mut self, id: Option<&SourceString>) {
+    let Some(start) = self.footnote_start.take() else {
+      return;
+    };
+    let text = self.md.split_off(start);
+    if !self.footnote_dupe {
+      self.footnotes.push((id.map(|id| id.to_string()), text));
+    }
+  }
+
+  fn enter_meta_title(&mut self, _block: &Block) {
+    self.start_buffering();
+  }
+
+  fn exit_meta_title(&mut self, block: &Block) {
+    if block.context != BlockContext::Passthrough {
+      self.stop_buffering();
+    } else {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  fn into_result(self) -> Result<Self::Output, Self::Error> {
+    Ok(self.md)
+  }
+
+  fn result(&self) -> Result<&Self::Output, Self::Error> {
+    Ok(&self.md)
+  }
+}
+
+impl Markdown {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn standalone(&self) -> bool {
+    self.doc_meta.get_doctype() != DocType::Inline
+      && !self.in_asciidoc_table_cell
+      && !self.doc_meta.embedded
+  }
+
+  /// true when text should be emitted without markdown escaping
+  const fn raw(&self) -> bool {
+    self.verbatim || self.passthrough || self.code_span_start.is_some() || self.stem_start.is_some()
+  }
+
+  fn floor(&self) -> usize {
+    self
+      .containers
+      .last()
+      .map_or(0, |container| container.floor)
+  }
+
+  fn ensure_newline(&mut self) {
+    if !self.md.is_empty() && !self.md.ends_with('\n') {
+      self.push_ch('\n');
+    }
+  }
+
+  /// blocks are separated by a blank line, unless they are the
+  /// first child of a container
+  fn start_block(&mut self) {
+    self.start_block_if(true);
+  }
+
+  fn start_block_if(&mut self, condition: bool) {
+    if !condition || self.md.len() <= self.floor() {
+      return;
+    }
+    self.ensure_newline();
+    if !self.md.ends_with("\n\n") {
+      self.push_ch('\n');
+    }
+  }
+
+  /// starts a block, emitting its anchor and title (if any) first
+  fn open_block(&mut self, block: &Block) {
+    let title = self.take_title();
+    self.start_block();
+    let id = block.meta.attrs.id();
+    if title.is_none() && block.context == BlockContext::Paragraph {
+      if let Some(id) = id {
+        self.push_anchor(id);
+      }
+      return;
+    }
+    if let Some(id) = id {
+      self.push_anchor(id);
+    }
+    if let Some(title) = &title {
+      self.push(["**", title, "**"]);
+    }
+    if id.is_some() || title.is_some() {
+      self.start_block();
+    }
+  }
+
+  fn open_list(&mut self, block: &Block, start: u32) {
+    let follows_principal = self
+      .list_stack
+      .last()
+      .is_some_and(|list| list.principal_end == Some(self.md.len()));
+    if follows_principal && !block.has_title() && block.meta.attrs.id().is_none() {
+      self.ensure_newline();
+    } else {
+      self.open_block(block);
+    }
+    self.list_stack.push(ListState {
+      next_num: start,
+      num_items: 0,
+      principal_end: None,
+      item_open: false,
+    });
+  }
+
+  fn open_list_item(&mut self, marker: &str) {
+    let Some(list) = self.list_stack.last_mut() else {
+      return;
+    };
+    list.num_items += 1;
+    list.item_open = true;
+    if list.num_items > 1 {
+      self.ensure_newline();
+    }
+    self.open_container(ContainerKind::ListItem(marker.len()));
+    self.push_str(marker);
+    if let Some(container) = self.containers.last_mut() {
+      container.floor = self.md.len();
+    }
+  }
+
+  fn close_list_item(&mut self) {
+    if let Some(list) = self.list_stack.last_mut()
+      && list.item_open
+    {
+      list.item_open = false;
+      list.principal_end = None;
+      self.close_container();
+    }
+  }
+
+  fn open_container(&mut self, kind: ContainerKind) {
+    let start = self.md.len();
+    self
+      .containers
+      .push(Container { start, floor: start, kind });
+  }
+
+  fn close_container(&mut self) {
+    let Some(container) = self.containers.pop() else {
+      return;
+    };
+    let content = self.md.split_off(container.start);
+    let lines = content.trim_end_matches('\n').split('\n');
+    for (idx, line) in lines.enumerate() {
+      if idx > 0 {
+        self.push_ch('\n');
+      }
+      match container.kind {
+        ContainerKind::Quote if line.is_empty() => self.push_ch('>'),
+        ContainerKind::Quote => self.push(["> ", line]),
+        ContainerKind::ListItem(indent) if idx > 0 && !line.is_empty() => {
+          self.push([&" ".repeat(indent), line]);
+        }
+        ContainerKind::ListItem(_) => self.push_str(line),
+      }
+    }
+    self.push_ch('\n');
+  }
+
+  fn push_heading_marker(&mut self, level: u8, id: Option<&str>) {
+    self.start_block();
+    self.push(["#".repeat((level as usize + 1).min(6)).as_str(), " "]);
+    if let Some(id) = id {
+      self.push_anchor(id);
+    }
+  }
+
+  fn push_anchor(&mut self, id: &str) {
+    self.push(["<a id=\"", id, "\"></a>"]);
+  }
+
+  fn take_title(&mut self) -> Option<String> {
+    if self.alt_md.is_empty() { None } else { Some(self.take_buffer()) }
+  }
+
+  /// formatting marks are meaningless inside code spans, so we omit them
+  fn push_inline_delimiter(&mut self, delimiter: &str) {
+    if !self.raw() {
+      self.push_str(delimiter);
+    }
+  }
+
+  const fn enter_code_span(&mut self) {
+    if !self.raw() {
+      self.code_span_start = Some(self.md.len());
+    }
+  }
+
+  fn exit_code_span(&mut self) {
+    if let Some(start) = self.code_span_start.take() {
+      let code = self.md.split_off(start);
+      self.push_str(&gfm::code_span(&code));
+    }
+  }
+
+  fn enter_verbatim(&mut self, lang: Option<String>) {
+    self.verbatim = true;
+    self.verbatim_start = Some(self.md.len());
+    // stash the info string until we know how long the fence must be
+    self.push_str(lang.as_deref().unwrap_or(""));
+    self.push_ch('\n');
+  }
+
+  fn exit_verbatim(&mut self) {
+    self.verbatim = false;
+    let Some(start) = self.verbatim_start.take() else {
+      return;
+    };
+    let content = self.md.split_off(start);
+    let fence = gfm::code_fence(&content);
+    self.push([&fence, &content]);
+    self.ensure_newline();
+    self.push_str(&fence);
+  }
+
+  fn push_image(&mut self, target: &str, attrs: &AttrList) {
+    let alt = match attrs.named("alt").or_else(|| attrs.str_positional_at(0)) {
+      Some(alt) => alt.to_string(),
+      None => file::stem(target).replace(['-', '_'], " "),
+    };
+    let src = match self.doc_meta.str("imagesdir") {
+      Some(imagesdir) if !target.contains("://") && !target.starts_with('/') => {
+        let mut path = Path::new_specifying_separator(imagesdir, '/');
+        path.push(target);
+        path.to_string()
+      }
+      _ => target.to_string(),
+    };
+    let image = format!(
+      "![{}]({})",
+      gfm::escape(&alt, false),
+      gfm::link_destination(&src)
+    );
+    match attrs.named("link") {
+      Some(link) => self.push(["[", &image, "](", &gfm::link_destination(link), ")"]),
+      None => self.push_str(&image),
+    }
+  }
+
+  fn push_media_link(&mut self, kind: &str, target: &str) {
+    self.push([
+      "[",
+      kind,
+      ": ",
+      &gfm::escape(target, false),
+      "](",
+      &gfm::link_destination(target),
+      ")",
+    ]);
+  }
+
+  fn push_table_row(&mut self, cells: &[String]) {
+    self.ensure_newline();
+    self.push_ch('|');
+    for cell in cells {
+      self.push([" ", cell, " |"]);
+    }
+  }
+}
+
+impl TableState {
+  /// vertically spanned cells have no markdown equivalent, we leave them empty
+  fn push_span_placeholders(&mut self) {
+    while self.occupied.get(self.col) == Some(&true) {
+      self.col += 1;
+      self.row.push(String::new());
+    }
+  }
+}
+
+impl HtmlBuf for Markdown {
+  fn htmlbuf(&mut self) -> &mut String {
+    &mut self.md
+  }
+
+  fn swapbuf(&mut self, s: &mut String) {
+    std::mem::swap(&mut self.md, s);
+  }
+}
+
+impl AltHtmlBuf for Markdown {
+  fn alt_htmlbuf(&mut self) -> &mut String {
+    &mut self.alt_md
+  }
+
+  fn buffers(&mut self) -> (&mut String, &mut String) {
+    (&mut self.md, &mut self.alt_md)
+  }
+}
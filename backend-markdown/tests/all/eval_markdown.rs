@@ -0,0 +1,271 @@
+use crate::helpers::*;
+use test_utils::*;
+
+assert_converted!(
+  sections_and_inline_formatting,
+  markdown,
+  adoc! {r#"
+    == Install
+
+    Use *bold*, _italic_, `mono` and #marked# text.
+
+    === Sub section
+
+    Escape snake_case but not *literal* \*stars*.
+  "#},
+  adoc! {r#"
+    ## <a id="_install"></a>Install
+
+    Use **bold**, *italic*, `mono` and <mark>marked</mark> text.
+
+    ### <a id="_sub_section"></a>Sub section
+
+    Escape snake_case but not **literal** \*stars\*.
+  "#}
+);
+
+assert_converted!(
+  lists,
+  markdown,
+  adoc! {r#"
+    * one
+    ** nested
+    * [x] done
+
+    //-
+
+    . first
+    . second
+    +
+    continued
+  "#},
+  adoc! {r#"
+    - one
+      - nested
+    - [x] done
+
+    1. first
+    2. second
+
+       continued
+  "#}
+);
+
+assert_converted!(
+  description_list,
+  markdown,
+  adoc! {r#"
+    -v:: Be verbose.
+    --out, -o::
+      Output file.
+  "#},
+  adoc! {r#"
+    - **-v**\
+      Be verbose.
+    - **--out, -o**\
+      Output file.
+  "#}
+);
+
+assert_converted!(
+  admonitions_as_alerts,
+  markdown,
+  adoc! {r#"
+    NOTE: Be careful.
+
+    [WARNING]
+    .Danger
+    ====
+    First.
+
+    Second.
+    ====
+  "#},
+  adoc! {r#"
+    > [!NOTE]
+    > Be careful.
+
+    > [!WARNING]
+    > **Danger**\
+    > First.
+    >
+    > Second.
+  "#}
+);
+
+assert_converted!(
+  source_block_w_callouts,
+  markdown,
+  adoc! {r#"
+    .main.rs
+    [source,rust]
+    ----
+    fn main() {} // <1>
+    ----
+    <1> The entry point.
+  "#},
+  adoc! {r#"
+    **main.rs**
+
+    ```rust
+    fn main() {} // (1)
+    ```
+
+    1. The entry point.
+  "#}
+);
+
+assert_converted!(
+  listing_w_bare_callouts,
+  markdown,
+  adoc! {r#"
+    ----
+    let x = 1; <1>
+    let y = 2; <2>
+    ----
+    <1> One.
+    <2> Two.
+  "#},
+  adoc! {r#"
+    ```
+    let x = 1; (1)
+    let y = 2; (2)
+    ```
+
+    1. One.
+    2. Two.
+  "#}
+);
+
+assert_converted!(
+  fence_longer_than_content_fence,
+  markdown,
+  adoc! {r#"
+    ....
+    ```
+    code
+    ```
+    ....
+  "#},
+  adoc! {r#"
+    ````
+    ```
+    code
+    ```
+    ````
+  "#}
+);
+
+assert_converted!(
+  pipe_table,
+  markdown,
+  adoc! {r#"
+    [cols="1,^1,>1",options="header"]
+    |===
+    |A |B |C
+    |x \| y |`2` |3
+    2+|span |z
+    |===
+  "#},
+  adoc! {r#"
+    | A | B | C |
+    | --- | :---: | ---: |
+    | x \| y | `2` | 3 |
+    | span |  | z |
+  "#}
+);
+
+assert_converted!(
+  table_wo_header,
+  markdown,
+  adoc! {r#"
+    |===
+    |a |b
+    |===
+  "#},
+  adoc! {r#"
+    |  |  |
+    | --- | --- |
+    | a | b |
+  "#}
+);
+
+assert_converted!(
+  xrefs_links_and_footnotes,
+  markdown,
+  adoc! {r#"
+    See <<_usage>>, <<_usage,that one>> and https://example.com[the docs].footnote:[Really.]
+
+    == Usage
+
+    [#target]
+    Target paragraph at https://example.com.
+  "#},
+  adoc! {r#"
+    See [Usage](#_usage), [that one](#_usage) and [the docs](https://example.com).[^1]
+
+    ## <a id="_usage"></a>Usage
+
+    <a id="target"></a>Target paragraph at <https://example.com>.
+
+    [^1]: Really.
+  "#}
+);
+
+assert_converted!(
+  quotes_and_verses,
+  markdown,
+  adoc! {r#"
+    [quote, Jane Doe, Book]
+    ____
+    Quoted text.
+    ____
+
+    [verse]
+    ____
+    line one
+    line two
+    ____
+  "#},
+  adoc! {r#"
+    > Quoted text.
+    >
+    > — Jane Doe, Book
+
+    > line one\
+    > line two
+  "#}
+);
+
+assert_converted!(
+  images_and_inline_macros,
+  markdown,
+  adoc! {r#"
+    :imagesdir: img
+
+    image::sunset.png[Sunset,link=https://example.com]
+
+    Press kbd:[Ctrl+T] then btn:[Save] via menu:File[Save].
+  "#},
+  adoc! {r#"
+    [![Sunset](img/sunset.png)](https://example.com)
+
+    Press <kbd>Ctrl</kbd>+<kbd>T</kbd> then **\[Save\]** via **File** ▸ **Save**.
+  "#}
+);
+
+assert_converted!(
+  standalone_doc_title,
+  markdown_standalone,
+  adoc! {r#"
+    = Document Title
+    :toc:
+
+    Preamble.
+  "#},
+  adoc! {r#"
+    # Document Title
+
+    Preamble.
+  "#}
+);
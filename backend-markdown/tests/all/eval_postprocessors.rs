@@ -0,0 +1,36 @@
+use asciidork_backend_markdown::{Postprocessor, convert_with_postprocessors};
+use asciidork_core::{DocumentMeta, JobSettings, ReadAttr};
+use asciidork_parser::prelude::*;
+use test_utils::*;
+
+/// prepends yaml front matter for static site generators
+struct FrontMatter;
+
+impl Postprocessor for FrontMatter {
+  fn process(&mut self, output: String, doc_meta: &DocumentMeta) -> Result<String, String> {
+    let title = doc_meta.str("doctitle").ok_or("missing doctitle")?;
+    Ok(format!("---\ntitle: {title}\n---\n\n{output}"))
+  }
+}
+
+fn convert(input: &str) -> Result<String, String> {
+  let bump = &Bump::new();
+  let mut parser = Parser::from_str(input, SourceFile::Tmp, bump);
+  parser.apply_job_settings(JobSettings::embedded());
+  let document = parser.parse().unwrap().document;
+  let mut postprocessors: Vec<Box<dyn Postprocessor>> = vec![Box::new(FrontMatter)];
+  convert_with_postprocessors(document, &mut postprocessors).map_err(|err| err.to_string())
+}
+
+#[test]
+fn postprocessors_rewrite_markdown() {
+  expect_eq!(
+    convert("= Guide\n\nhello _world_").unwrap(),
+    "---\ntitle: Guide\n---\n\nhello *world*\n"
+  );
+}
+
+#[test]
+fn postprocessor_error() {
+  expect_eq!(convert("hello").unwrap_err(), "missing doctitle");
+}
@@ -0,0 +1,13 @@
+use asciidork_core::JobSettings;
+
+pub fn test_backend_factory() -> asciidork_backend_markdown::Markdown {
+  asciidork_backend_markdown::Markdown::new()
+}
+
+pub fn markdown(input: &str) -> String {
+  _html!(input, |_| {}, None)
+}
+
+pub fn markdown_standalone(input: &str) -> String {
+  _html!(input, |s: &mut JobSettings| s.embedded = false, None)
+}
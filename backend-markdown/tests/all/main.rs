@@ -0,0 +1,6 @@
+#[macro_use]
+extern crate asciidork_backend;
+
+mod eval_markdown;
+mod eval_postprocessors;
+mod helpers;
@@ -0,0 +1,21 @@
+// each style is closed by its specific reset (not `\x1b[0m`)
+// so that an outer style survives the end of a nested span
+pub const BOLD: (&str, &str) = ("\x1b[1m", "\x1b[22m");
+pub const ITALIC: (&str, &str) = ("\x1b[3m", "\x1b[23m");
+pub const UNDERLINE: (&str, &str) = ("\x1b[4m", "\x1b[24m");
+pub const STRIKE: (&str, &str) = ("\x1b[9m", "\x1b[29m");
+pub const REVERSE: (&str, &str) = ("\x1b[7m", "\x1b[27m");
+pub const MONO: (&str, &str) = ("\x1b[36m", "\x1b[39m");
+
+pub const fn admonition_color(kind: asciidork_ast::AdmonitionKind) -> &'static str {
+  use asciidork_ast::AdmonitionKind;
+  match kind {
+    AdmonitionKind::Note => "\x1b[34m",
+    AdmonitionKind::Tip => "\x1b[32m",
+    AdmonitionKind::Important => "\x1b[35m",
+    AdmonitionKind::Warning => "\x1b[33m",
+    AdmonitionKind::Caution => "\x1b[31m",
+  }
+}
+
+pub const RESET_COLOR: &str = "\x1b[39m";
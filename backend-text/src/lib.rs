@@ -0,0 +1,38 @@
+use std::error::Error;
+
+extern crate asciidork_ast as ast;
+extern crate asciidork_backend as backend;
+extern crate asciidork_eval as eval;
+
+mod ansi;
+mod table;
+mod text;
+mod wrap;
+
+pub use crate::text::{Text, TextOptions};
+pub use backend::Postprocessor;
+
+pub fn convert(document: ast::Document, options: TextOptions) -> Result<String, Box<dyn Error>> {
+  convert_with_postprocessors(document, options, &mut [])
+}
+
+pub fn convert_with_postprocessors(
+  document: ast::Document,
+  options: TextOptions,
+  postprocessors: &mut [Box<dyn Postprocessor>],
+) -> Result<String, Box<dyn Error>> {
+  let output = eval::eval(&document, Text::new(options))?;
+  Ok(backend::postprocess(
+    output,
+    &document.meta,
+    postprocessors,
+  )?)
+}
+
+mod internal {
+  pub use asciidork_core::*;
+  pub use ast::prelude::*;
+  pub use backend::html::{AltHtmlBuf, HtmlBuf};
+  pub use backend::prelude::*;
+  pub use backend::utils;
+}
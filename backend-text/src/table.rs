@@ -0,0 +1,168 @@
+use crate::wrap::{self, Align};
+
+#[derive(Debug, Clone)]
+pub struct TextCell {
+  pub text: String,
+  pub preformatted: bool,
+  pub col_span: usize,
+  pub align: Align,
+}
+
+impl TextCell {
+  pub const fn empty() -> Self {
+    Self {
+      text: String::new(),
+      preformatted: false,
+      col_span: 1,
+      align: Align::Left,
+    }
+  }
+
+  fn lines(&self, width: usize) -> Vec<String> {
+    if self.preformatted {
+      self.text.lines().map(str::to_string).collect()
+    } else {
+      wrap::wrap(&self.text, width)
+    }
+  }
+
+  fn natural_width(&self) -> usize {
+    self
+      .text
+      .lines()
+      .map(wrap::visible_width)
+      .max()
+      .unwrap_or(0)
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct TextTable {
+  pub num_cols: usize,
+  pub rows: Vec<Vec<TextCell>>,
+  pub has_header: bool,
+  pub has_footer: bool,
+}
+
+impl TextTable {
+  /// renders the table with box-drawing borders, shrinking the widest
+  /// columns (and wrapping their contents) to fit within `max_width`
+  pub fn render(&self, max_width: usize) -> Vec<String> {
+    let widths = self.col_widths(max_width);
+    let mut lines = vec![border(&widths, '┌', '┬', '┐')];
+    let last = self.rows.len().saturating_sub(1);
+    for (idx, row) in self.rows.iter().enumerate() {
+      if idx > 0 && idx == last && self.has_footer {
+        lines.push(border(&widths, '├', '┼', '┤'));
+      }
+      lines.extend(render_row(row, &widths));
+      if idx == 0 && self.has_header && idx != last {
+        lines.push(border(&widths, '╞', '╪', '╡').replace('─', "═"));
+      }
+    }
+    lines.push(border(&widths, '└', '┴', '┘'));
+    lines
+  }
+
+  fn col_widths(&self, max_width: usize) -> Vec<usize> {
+    let mut widths = vec![1; self.num_cols];
+    for row in &self.rows {
+      let mut col = 0;
+      for cell in row {
+        if cell.col_span == 1 && col < widths.len() {
+          widths[col] = widths[col].max(cell.natural_width());
+        }
+        col += cell.col_span;
+      }
+    }
+    let available = max_width.saturating_sub(self.num_cols * 3 + 1);
+    while widths.iter().sum::<usize>() > available {
+      let Some(widest) = widths.iter_mut().max() else {
+        break;
+      };
+      if *widest <= 3 {
+        break;
+      }
+      *widest -= 1;
+    }
+    widths
+  }
+}
+
+fn border(widths: &[usize], left: char, mid: char, right: char) -> String {
+  let segments = widths
+    .iter()
+    .map(|width| "─".repeat(width + 2))
+    .collect::<Vec<_>>();
+  format!("{left}{}{right}", segments.join(&mid.to_string()))
+}
+
+fn render_row(row: &[TextCell], widths: &[usize]) -> Vec<String> {
+  let mut col = 0;
+  let mut cells = Vec::with_capacity(row.len());
+  for cell in row {
+    let spanned = &widths[col.min(widths.len())..(col + cell.col_span).min(widths.len())];
+    let width = spanned.iter().sum::<usize>() + 3 * spanned.len().saturating_sub(1);
+    cells.push((cell.lines(width), width, cell.align));
+    col += cell.col_span;
+  }
+  let height = cells
+    .iter()
+    .map(|(lines, ..)| lines.len())
+    .max()
+    .unwrap_or(1);
+  (0..height)
+    .map(|line_idx| {
+      let mut line = String::from("│");
+      for (lines, width, align) in &cells {
+        let text = lines.get(line_idx).map_or("", String::as_str);
+        line.push(' ');
+        line.push_str(&wrap::pad(text, *width, *align));
+        line.push_str(" │");
+      }
+      line
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cell(text: &str) -> TextCell {
+    TextCell {
+      text: text.to_string(),
+      ..TextCell::empty()
+    }
+  }
+
+  #[test]
+  fn test_render_table() {
+    let table = TextTable {
+      num_cols: 2,
+      rows: vec![
+        vec![cell("Name"), cell("Value")],
+        vec![cell("a"), cell("one two three")],
+      ],
+      has_header: true,
+      has_footer: false,
+    };
+    let expected = vec![
+      "┌──────┬───────────────┐",
+      "│ Name │ Value         │",
+      "╞══════╪═══════════════╡",
+      "│ a    │ one two three │",
+      "└──────┴───────────────┘",
+    ];
+    assert_eq!(table.render(80), expected);
+    let expected = vec![
+      "┌──────┬─────────┐",
+      "│ Name │ Value   │",
+      "╞══════╪═════════╡",
+      "│ a    │ one two │",
+      "│      │ three   │",
+      "└──────┴─────────┘",
+    ];
+    assert_eq!(table.render(18), expected);
+  }
+}
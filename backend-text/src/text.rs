@@ -0,0 +1,1212 @@
+use crate::ansi;
+use crate::internal::*;
+use crate::table::{TextCell, TextTable};
+use crate::wrap::{self, Align};
+use ast::AdjacentNewline;
+use backend::num_str;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextOptions {
+  /// the column at which text is wrapped
+  pub width: usize,
+  /// style output with ansi escape sequences
+  pub ansi: bool,
+}
+
+impl Default for TextOptions {
+  fn default() -> Self {
+    Self { width: 80, ansi: false }
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct Text {
+  options: TextOptions,
+  doc_meta: DocumentMeta,
+  out: String,
+  buf: String,
+  alt_buf: String,
+  indents: Vec<Indent>,
+  pending_blank: bool,
+  after_principal: bool,
+  verbatim: bool,
+  hardbreaks: bool,
+  default_hardbreaks: bool,
+  in_asciidoc_table_cell: bool,
+  list_stack: Vec<ListState>,
+  table: Option<TableState>,
+  link_start: Option<usize>,
+  footnote_start: Option<usize>,
+  footnote_dupe: bool,
+  footnotes: Vec<(Option<String>, String)>,
+}
+
+/// a line prefix applied to every line emitted within a container,
+/// `first` is used once (e.g. for a list marker), then `rest`
+#[derive(Debug)]
+struct Indent {
+  first: String,
+  rest: String,
+  used: bool,
+}
+
+impl Indent {
+  fn uniform(prefix: &str) -> Self {
+    Self {
+      first: prefix.to_string(),
+      rest: prefix.to_string(),
+      used: false,
+    }
+  }
+
+  fn hanging(first: String) -> Self {
+    let rest = " ".repeat(wrap::visible_width(&first));
+    Self { first, rest, used: false }
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ListState {
+  next_num: u32,
+  num_width: usize,
+  num_items: usize,
+  loose: bool,
+}
+
+#[derive(Debug, Default)]
+struct TableState {
+  table: TextTable,
+  row: Vec<TextCell>,
+  col: usize,
+  row_spans: Vec<u8>,
+  occupied: Vec<bool>,
+  first_paragraph: bool,
+  preformatted: bool,
+}
+
+impl Backend for Text {
+  type Output = String;
+  type Error = std::convert::Infallible;
+  const OUTFILESUFFIX: &'static str = ".txt";
+
+  fn set_job_attrs(attrs: &mut asciidork_core::JobAttrs) {
+    attrs.insert_unchecked("backend", JobAttr::readonly("text"));
+    attrs.insert_unchecked("backend-text", JobAttr::readonly(true));
+    attrs.insert_unchecked("basebackend", JobAttr::readonly("text"));
+    attrs.insert_unchecked("basebackend-text", JobAttr::readonly(true));
+  }
+
+  fn enter_document(&mut self, document: &Document) {
+    self.doc_meta = document.meta.clone();
+    utils::set_backend_attrs::<Self>(&mut self.doc_meta);
+  }
+
+  fn exit_document(&mut self, _document: &Document) {
+    self.flush();
+    if self.in_asciidoc_table_cell || self.footnotes.is_empty() {
+      return;
+    }
+    self.start_block();
+    self.emit_line(&"─".repeat(self.available_width().min(20)));
+    for (idx, (_, text)) in std::mem::take(&mut self.footnotes).iter().enumerate() {
+      self
+        .indents
+        .push(Indent::hanging(format!("[{}] ", idx + 1)));
+      self.push_str(text);
+      self.flush();
+      self.indents.pop();
+    }
+  }
+
+  fn enter_header(&mut self) {}
+  fn exit_header(&mut self) {}
+  fn enter_content(&mut self) {}
+  fn exit_content(&mut self) {}
+  fn enter_footer(&mut self) {}
+  fn exit_footer(&mut self) {}
+
+  fn enter_document_title(&mut self) {
+    if self.standalone() {
+      self.enter_heading();
+    } else {
+      self.start_buffering();
+    }
+  }
+
+  fn exit_document_title(&mut self) {
+    if self.standalone() {
+      self.exit_heading(0);
+    } else {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  fn enter_toc(&mut self, _toc: &TableOfContents, _macro_block: Option<&Block>) {
+    self.start_buffering();
+  }
+
+  fn exit_toc(&mut self, _toc: &TableOfContents) {
+    self.swap_discard_alt_buffer();
+  }
+
+  fn enter_book_part(&mut self, _part: &Part) {}
+  fn exit_book_part(&mut self, _part: &Part) {}
+
+  fn enter_book_part_title(&mut self, _title: &PartTitle) {
+    self.enter_heading();
+  }
+
+  fn exit_book_part_title(&mut self, _title: &PartTitle) {
+    self.exit_heading(0);
+  }
+
+  fn enter_book_part_intro(&mut self, _part: &Part) {}
+  fn exit_book_part_intro(&mut self, _part: &Part) {}
+  fn enter_book_part_intro_content(&mut self, _part: &Part) {}
+  fn exit_book_part_intro_content(&mut self, _part: &Part) {}
+  fn enter_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {}
+  fn exit_preamble(&mut self, _doc_has_title: bool, _blocks: &[Block]) {}
+  fn enter_section(&mut self, _section: &Section) {}
+  fn exit_section(&mut self, _section: &Section) {}
+
+  fn enter_section_heading(&mut self, _section: &Section) {
+    self.enter_heading();
+  }
+
+  fn exit_section_heading(&mut self, section: &Section) {
+    self.exit_heading(section.level);
+  }
+
+  fn enter_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+  fn exit_compound_block_content(&mut self, _children: &[Block], _block: &Block) {}
+
+  fn enter_simple_block_content(&mut self, block: &Block) {
+    if block.meta.attrs.has_option("hardbreaks") {
+      self.hardbreaks = true;
+    }
+  }
+
+  fn exit_simple_block_content(&mut self, _block: &Block) {
+    self.hardbreaks = self.default_hardbreaks;
+  }
+
+  fn enter_paragraph_block(&mut self, block: &Block) {
+    self.open_block(block);
+  }
+
+  fn exit_paragraph_block(&mut self, _block: &Block) {
+    self.flush();
+  }
+
+  fn enter_sidebar_block(&mut self, block: &Block) {
+    self.open_block(block);
+    self.indents.push(Indent::uniform("┃ "));
+  }
+
+  fn exit_sidebar_block(&mut self, _block: &Block) {
+    self.close_indent();
+  }
+
+  fn enter_listing_block(&mut self, block: &Block) {
+    self.enter_verbatim(block, "    ");
+  }
+
+  fn exit_listing_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_literal_block(&mut self, block: &Block) {
+    self.enter_verbatim(block, "    ");
+  }
+
+  fn exit_literal_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_passthrough_block(&mut self, block: &Block) {
+    self.enter_verbatim(block, "");
+  }
+
+  fn exit_passthrough_block(&mut self, _block: &Block) {
+    self.exit_verbatim();
+  }
+
+  fn enter_stem_block(&mut self, block: &Block, _notation: StemNotation) {
+    self.enter_verbatim(block, "    ");
+  }
+
+  fn exit_stem_block(&mut self, _block: &Block, _notation: StemNotation) {
+    self.exit_verbatim();
+  }
+
+  fn enter_quoted_paragraph(&mut self, block: &Block) {
+    self.open_block(block);
+    self.indents.push(Indent::uniform("│ "));
+  }
+
+  fn exit_quoted_paragraph(&mut self, _block: &Block) {
+    self.close_indent();
+  }
+
+  fn enter_quote_block(&mut self, block: &Block, _has_attribution: bool) {
+    self.open_block(block);
+    self.indents.push(Indent::uniform("│ "));
+  }
+
+  fn exit_quote_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.close_indent();
+  }
+
+  fn enter_quote_attribution(&mut self, _block: &Block, _has_cite: bool) {
+    self.hardbreaks = self.default_hardbreaks;
+    self.start_block();
+    self.push_str("— ");
+  }
+
+  fn exit_quote_attribution(&mut self, _block: &Block, _has_cite: bool) {}
+
+  fn enter_quote_cite(&mut self, _block: &Block, has_attribution: bool) {
+    if has_attribution {
+      self.push_str(", ");
+    } else {
+      self.start_block();
+      self.push_str("— ");
+    }
+    self.push_style(ansi::ITALIC.0);
+  }
+
+  fn exit_quote_cite(&mut self, _block: &Block, _has_attribution: bool) {
+    self.push_style(ansi::ITALIC.1);
+  }
+
+  fn enter_verse_block(&mut self, block: &Block, _has_attribution: bool) {
+    self.open_block(block);
+    self.indents.push(Indent::uniform("│ "));
+    self.hardbreaks = true;
+  }
+
+  fn exit_verse_block(&mut self, _block: &Block, _has_attribution: bool) {
+    self.hardbreaks = self.default_hardbreaks;
+    self.close_indent();
+  }
+
+  fn enter_example_block(&mut self, block: &Block) {
+    self.open_block(block);
+    self.indents.push(Indent::uniform("  "));
+  }
+
+  fn exit_example_block(&mut self, _block: &Block) {
+    self.close_indent();
+  }
+
+  fn enter_open_block(&mut self, block: &Block) {
+    self.open_block(block);
+  }
+
+  fn exit_open_block(&mut self, _block: &Block) {
+    self.flush();
+  }
+
+  fn enter_discrete_heading(&mut self, _level: u8, _id: Option<&str>, _block: &Block) {
+    self.enter_heading();
+  }
+
+  fn exit_discrete_heading(&mut self, level: u8, _id: Option<&str>, _block: &Block) {
+    self.exit_heading(level);
+  }
+
+  fn enter_unordered_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.open_list(block, 1, 0);
+  }
+
+  fn exit_unordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_ordered_list(&mut self, block: &Block, items: &[ListItem], _depth: u8) {
+    let start = block
+      .meta
+      .attrs
+      .named("start")
+      .and_then(|start| start.parse::<u32>().ok())
+      .or(match items.first().map(|item| &item.marker) {
+        Some(ListMarker::Digits(n)) => Some(*n as u32),
+        _ => None,
+      })
+      .unwrap_or(1);
+    let last = start as usize + items.len().saturating_sub(1);
+    self.open_list(block, start, last.to_string().len());
+  }
+
+  fn exit_ordered_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_callout_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.open_list(block, 1, 0);
+  }
+
+  fn exit_callout_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_description_list(&mut self, block: &Block, _items: &[ListItem], _depth: u8) {
+    self.open_list(block, 1, 0);
+  }
+
+  fn exit_description_list(&mut self, _block: &Block, _items: &[ListItem], _depth: u8) {
+    self.list_stack.pop();
+  }
+
+  fn enter_description_list_term(&mut self, _item: &ListItem, num: usize, _total: usize) {
+    if num == 1 {
+      self.start_block();
+    } else {
+      self.flush();
+    }
+    self.push_style(ansi::BOLD.0);
+  }
+
+  fn exit_description_list_term(&mut self, _item: &ListItem, _num: usize, _total: usize) {
+    self.push_style(ansi::BOLD.1);
+    self.flush();
+  }
+
+  fn enter_description_list_description(&mut self, _item: &ListItem) {
+    self.pending_blank = false;
+    self.indents.push(Indent::uniform("    "));
+  }
+
+  fn exit_description_list_description(&mut self, _item: &ListItem) {
+    self.close_indent();
+  }
+
+  fn enter_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+  fn exit_description_list_description_text(&mut self, _text: &Block, _item: &ListItem) {}
+
+  fn enter_list_item_principal(&mut self, item: &ListItem, variant: ListVariant) {
+    self.flush();
+    let depth = self.list_stack.len();
+    let Some(list) = self.list_stack.last_mut() else {
+      return;
+    };
+    if list.num_items > 0 && !list.loose {
+      self.pending_blank = false;
+    }
+    list.num_items += 1;
+    let marker = match (variant, &item.type_meta) {
+      (ListVariant::Callout, ListItemTypeMeta::Callout(callouts)) => {
+        let number = callouts.first().map_or(1, |callout| callout.number);
+        format!("({number}) ")
+      }
+      (ListVariant::Ordered, _) => {
+        list.next_num += 1;
+        format!("{:>width$}. ", list.next_num - 1, width = list.num_width)
+      }
+      (_, ListItemTypeMeta::Checklist(checked, _)) => iff!(*checked, "[x] ", "[ ] ").to_string(),
+      _ => ["• ", "◦ ", "▪ "][(depth - 1) % 3].to_string(),
+    };
+    self.start_block();
+    self.indents.push(Indent::hanging(marker));
+  }
+
+  fn exit_list_item_principal(&mut self, _item: &ListItem, _variant: ListVariant) {
+    self.flush();
+    self.after_principal = true;
+  }
+
+  fn enter_list_item_blocks(&mut self, _blocks: &[Block], _item: &ListItem, _variant: ListVariant) {
+  }
+
+  fn exit_list_item_blocks(&mut self, blocks: &[Block], _item: &ListItem, _variant: ListVariant) {
+    self.close_indent();
+    self.after_principal = false;
+    // a nested list alone doesn't loosen the spacing of its parent
+    let has_blocks = blocks.iter().any(|block| {
+      !matches!(
+        block.context,
+        BlockContext::UnorderedList | BlockContext::OrderedList | BlockContext::DescriptionList
+      )
+    });
+    if has_blocks && let Some(list) = self.list_stack.last_mut() {
+      list.loose = true;
+    }
+  }
+
+  fn asciidoc_table_cell_backend(&mut self) -> Self {
+    Self::new(TextOptions {
+      width: self.available_width(),
+      ..self.options
+    })
+    .in_table_cell()
+  }
+
+  fn visit_asciidoc_table_cell_result(&mut self, cell_backend: Self) {
+    let result = cell_backend.into_result().unwrap();
+    self.push_str(result.trim_end());
+    if let Some(table) = &mut self.table {
+      table.preformatted = true;
+    }
+  }
+
+  fn enter_table(&mut self, table: &Table, block: &Block) {
+    self.open_block(block);
+    let num_cols = table.col_widths.len();
+    self.table = Some(TableState {
+      table: TextTable {
+        num_cols,
+        has_header: table.header_row.is_some(),
+        has_footer: table.footer_row.is_some(),
+        ..TextTable::default()
+      },
+      row_spans: vec![0; num_cols],
+      ..TableState::default()
+    });
+  }
+
+  fn exit_table(&mut self, _table: &Table, _block: &Block) {
+    let Some(state) = self.table.take() else {
+      return;
+    };
+    for line in state.table.render(self.available_width()) {
+      self.emit_line(&line);
+    }
+  }
+
+  fn enter_table_section(&mut self, _section: TableSection) {}
+  fn exit_table_section(&mut self, _section: TableSection) {}
+
+  fn enter_table_row(&mut self, _row: &Row, _section: TableSection) {
+    if let Some(table) = &mut self.table {
+      table.col = 0;
+      table.occupied = table.row_spans.iter().map(|span| *span > 0).collect();
+    }
+  }
+
+  fn exit_table_row(&mut self, _row: &Row, _section: TableSection) {
+    let Some(table) = &mut self.table else {
+      return;
+    };
+    table.push_span_placeholders();
+    table.pad_row();
+    for (span, occupied) in table.row_spans.iter_mut().zip(&table.occupied) {
+      if *occupied {
+        *span -= 1;
+      }
+    }
+    let row = std::mem::take(&mut table.row);
+    table.table.rows.push(row);
+  }
+
+  fn enter_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    self.buf.clear();
+    let literal = matches!(cell.content, CellContent::Literal(_));
+    self.verbatim = literal;
+    let Some(table) = &mut self.table else {
+      return;
+    };
+    table.push_span_placeholders();
+    if cell.row_span > 1 {
+      let col = table.col;
+      table
+        .row_spans
+        .iter_mut()
+        .skip(col)
+        .take(cell.col_span as usize)
+        .for_each(|span| *span = cell.row_span - 1);
+    }
+    table.first_paragraph = true;
+    table.preformatted = literal;
+  }
+
+  fn exit_table_cell(&mut self, cell: &Cell, _section: TableSection) {
+    self.verbatim = false;
+    let text = std::mem::take(&mut self.buf);
+    let Some(table) = &mut self.table else {
+      return;
+    };
+    table.row.push(TextCell {
+      text: text.trim_end().to_string(),
+      preformatted: table.preformatted,
+      col_span: cell.col_span as usize,
+      align: match cell.h_align {
+        HorizontalAlignment::Left => Align::Left,
+        HorizontalAlignment::Center => Align::Center,
+        HorizontalAlignment::Right => Align::Right,
+      },
+    });
+    table.col += cell.col_span as usize;
+  }
+
+  fn enter_cell_paragraph(&mut self, cell: &Cell, _section: TableSection) {
+    if let Some(table) = &mut self.table {
+      if !table.first_paragraph {
+        self.buf.push_str("\n\n");
+      }
+      table.first_paragraph = false;
+    }
+    if let Some((open, _)) = cell_style(cell) {
+      self.push_style(open);
+    }
+  }
+
+  fn exit_cell_paragraph(&mut self, cell: &Cell, _section: TableSection) {
+    if let Some((_, close)) = cell_style(cell) {
+      self.push_style(close);
+    }
+  }
+
+  fn enter_inline_italic(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::ITALIC.0);
+  }
+
+  fn exit_inline_italic(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::ITALIC.1);
+  }
+
+  fn enter_inline_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::MONO.0);
+  }
+
+  fn exit_inline_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::MONO.1);
+  }
+
+  fn enter_inline_bold(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::BOLD.0);
+  }
+
+  fn exit_inline_bold(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::BOLD.1);
+  }
+
+  fn enter_inline_lit_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::MONO.0);
+  }
+
+  fn exit_inline_lit_mono(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::MONO.1);
+  }
+
+  fn enter_inline_stem(&mut self, _notation: StemNotation) {}
+  fn exit_inline_stem(&mut self, _notation: StemNotation) {}
+
+  fn enter_inline_highlight(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::REVERSE.0);
+  }
+
+  fn exit_inline_highlight(&mut self, _attrs: Option<&AttrList>) {
+    self.push_style(ansi::REVERSE.1);
+  }
+
+  fn enter_inline_subscript(&mut self, _attrs: Option<&AttrList>) {}
+  fn exit_inline_subscript(&mut self, _attrs: Option<&AttrList>) {}
+  fn enter_inline_superscript(&mut self, _attrs: Option<&AttrList>) {}
+  fn exit_inline_superscript(&mut self, _attrs: Option<&AttrList>) {}
+
+  fn visit_spaced_dashes(&mut self, _len: u8, adjacent_newline: AdjacentNewline) {
+    match adjacent_newline {
+      AdjacentNewline::None => self.push_str(" — "),
+      _ => self.push_ch('—'),
+    }
+  }
+
+  fn enter_visible_index_term(&mut self) -> bool {
+    true
+  }
+
+  fn enter_concealed_index_term(&mut self, _num_terms: u8) -> bool {
+    false
+  }
+
+  fn visit_inline_specialchar(&mut self, char: &SpecialCharKind) {
+    match char {
+      SpecialCharKind::Ampersand => self.push_ch('&'),
+      SpecialCharKind::LessThan => self.push_ch('<'),
+      SpecialCharKind::GreaterThan => self.push_ch('>'),
+    }
+  }
+
+  fn visit_symbol(&mut self, kind: SymbolKind) {
+    match kind {
+      SymbolKind::Copyright => self.push_ch('©'),
+      SymbolKind::Registered => self.push_ch('®'),
+      SymbolKind::Trademark => self.push_ch('™'),
+      SymbolKind::EmDash => self.push_ch('—'),
+      SymbolKind::TripleDash => self.push_str("---"),
+      SymbolKind::Ellipsis => self.push_ch('…'),
+      SymbolKind::SingleRightArrow => self.push_ch('→'),
+      SymbolKind::DoubleRightArrow => self.push_ch('⇒'),
+      SymbolKind::SingleLeftArrow => self.push_ch('←'),
+      SymbolKind::DoubleLeftArrow => self.push_ch('⇐'),
+    }
+  }
+
+  fn enter_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_ch('“'),
+      QuoteKind::Single => self.push_ch('‘'),
+    }
+  }
+
+  fn exit_inline_quote(&mut self, kind: QuoteKind) {
+    match kind {
+      QuoteKind::Double => self.push_ch('”'),
+      QuoteKind::Single => self.push_ch('’'),
+    }
+  }
+
+  fn visit_curly_quote(&mut self, kind: CurlyKind) {
+    match kind {
+      CurlyKind::LeftDouble => self.push_ch('”'),
+      CurlyKind::RightDouble => self.push_ch('“'),
+      CurlyKind::LeftSingle => self.push_ch('‘'),
+      CurlyKind::RightSingle => self.push_ch('’'),
+      CurlyKind::LegacyImplicitApostrophe => self.push_ch('’'),
+    }
+  }
+
+  fn visit_multichar_whitespace(&mut self, whitespace: &str) {
+    if self.verbatim {
+      self.push_str(whitespace);
+    } else {
+      self.push_ch(' ');
+    }
+  }
+
+  fn visit_thematic_break(&mut self, block: &Block) {
+    self.open_block(block);
+    self.emit_line(&"─".repeat(self.available_width()));
+  }
+
+  // a form feed is the plain text page break
+  fn visit_page_break(&mut self, block: &Block) {
+    self.open_block(block);
+    self.emit_line("\x0c");
+  }
+
+  fn visit_inline_text(&mut self, text: &str) {
+    self.push_str(text);
+  }
+
+  fn visit_joining_newline(&mut self) {
+    if self.verbatim || self.hardbreaks {
+      self.push_ch('\n');
+    } else {
+      self.push_ch(' ');
+    }
+  }
+
+  fn enter_text_span(&mut self, attrs: Option<&AttrList>) {
+    if let Some((open, _)) = span_style(attrs) {
+      self.push_style(open);
+    }
+  }
+
+  fn exit_text_span(&mut self, attrs: Option<&AttrList>) {
+    if let Some((_, close)) = span_style(attrs) {
+      self.push_style(close);
+    }
+  }
+
+  fn enter_xref(&mut self, _target: &SourceString, _has_reftext: bool, _kind: XrefKind) {
+    self.push_style(ansi::UNDERLINE.0);
+  }
+
+  fn exit_xref(&mut self, _target: &SourceString, _has_reftext: bool, _kind: XrefKind) {
+    self.push_style(ansi::UNDERLINE.1);
+  }
+
+  fn enter_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_ch('[');
+    }
+  }
+
+  fn exit_xref_text(&mut self, is_biblio: bool) {
+    if is_biblio {
+      self.push_ch(']');
+    }
+  }
+
+  fn visit_missing_xref(
+    &mut self,
+    target: &SourceString,
+    _kind: XrefKind,
+    _doc_title: Option<&DocTitle>,
+  ) {
+    self.push(["[", utils::xref::remove_leading_hash(target), "]"]);
+  }
+
+  fn visit_inline_anchor(&mut self, _id: &str) {}
+
+  fn visit_biblio_anchor(&mut self, id: &str, reftext: Option<&str>) {
+    self.push(["[", reftext.unwrap_or(id), "]"]);
+  }
+
+  fn visit_callout(&mut self, callout: Callout) {
+    self.push_style(ansi::BOLD.0);
+    self.push(["(", &num_str!(callout.number), ")"]);
+    self.push_style(ansi::BOLD.1);
+  }
+
+  fn visit_callout_tuck(&mut self, comment: &str) {
+    self.push_str(comment);
+  }
+
+  fn visit_linebreak(&mut self) {
+    self.push_ch('\n');
+  }
+
+  fn visit_button_macro(&mut self, text: &SourceString) {
+    self.push_style(ansi::BOLD.0);
+    self.push(["[", text, "]"]);
+    self.push_style(ansi::BOLD.1);
+  }
+
+  fn visit_menu_macro(&mut self, items: &[SourceString]) {
+    self.push_style(ansi::BOLD.0);
+    for (idx, item) in items.iter().enumerate() {
+      if idx > 0 {
+        self.push_str(" ▸ ");
+      }
+      self.push_str(item);
+    }
+    self.push_style(ansi::BOLD.1);
+  }
+
+  fn visit_keyboard_macro(&mut self, keys: &[&str]) {
+    for (idx, key) in keys.iter().enumerate() {
+      if idx > 0 {
+        self.push_ch('+');
+      }
+      self.push_style(ansi::REVERSE.0);
+      self.push_str(key);
+      self.push_style(ansi::REVERSE.1);
+    }
+  }
+
+  fn visit_image_macro(&mut self, target: &SourceString, attrs: &AttrList, _kind: &ImageKind) {
+    self.push(["[", &image_alt(target, attrs), "]"]);
+  }
+
+  fn visit_icon_macro(&mut self, target: &SourceString, attrs: &AttrList) {
+    let alt = attrs.named("alt").unwrap_or(target);
+    self.push(["[", alt, "]"]);
+  }
+
+  fn visit_audio_macro(&mut self, target: &SourceString, _attrs: &AttrList, block: &Block) {
+    self.open_block(block);
+    self.push(["[Audio: ", target, "]"]);
+    self.flush();
+  }
+
+  fn visit_video_macro(&mut self, target: &SourceString, _attrs: &AttrList, block: &Block) {
+    self.open_block(block);
+    self.push(["[Video: ", target, "]"]);
+    self.flush();
+  }
+
+  fn enter_link_macro(
+    &mut self,
+    _target: &SourceString,
+    _attrs: Option<&AttrList>,
+    _scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+    _blank_window_shorthand: bool,
+  ) {
+    if !resolving_xref && has_link_text {
+      self.link_start = Some(self.buf.len());
+    }
+  }
+
+  fn exit_link_macro(
+    &mut self,
+    target: &SourceString,
+    _attrs: Option<&AttrList>,
+    scheme: Option<UrlScheme>,
+    resolving_xref: bool,
+    has_link_text: bool,
+  ) {
+    if resolving_xref {
+      return;
+    }
+    let mut url = target.to_string();
+    if matches!(scheme, Some(UrlScheme::Mailto)) {
+      url = url.trim_start_matches("mailto:").to_string();
+    } else if self.doc_meta.is_true("hide-uri-scheme") && !has_link_text {
+      url = file::remove_uri_scheme(target).to_string();
+    }
+    let has_text = self
+      .link_start
+      .take()
+      .is_some_and(|start| self.buf.len() > start && self.buf[start..] != url);
+    if has_text {
+      self.push_str(" <");
+    }
+    self.push_style(ansi::UNDERLINE.0);
+    self.push_str(&url);
+    self.push_style(ansi::UNDERLINE.1);
+    if has_text {
+      self.push_ch('>');
+    }
+  }
+
+  fn enter_mailto_macro(
+    &mut self,
+    _address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    _has_link_text: bool,
+  ) {
+  }
+
+  fn exit_mailto_macro(
+    &mut self,
+    address: &SourceString,
+    _subject: Option<&SourceString>,
+    _body: Option<&SourceString>,
+    _attrs: Option<&AttrList>,
+    has_link_text: bool,
+  ) {
+    if has_link_text {
+      self.push_str(" <");
+    }
+    self.push_style(ansi::UNDERLINE.0);
+    self.push_str(address);
+    self.push_style(ansi::UNDERLINE.1);
+    if has_link_text {
+      self.push_ch('>');
+    }
+  }
+
+  fn enter_admonition_block(
+    &mut self,
+    kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    _block: &Block,
+  ) {
+    let title = self.take_title();
+    self.start_block();
+    let caption = self.doc_meta.string_or(kind.caption_name(), kind.str());
+    let label = caption.to_uppercase();
+    let first = if self.options.ansi {
+      let color = ansi::admonition_color(kind);
+      format!(
+        "{color}{}{label}{}{}: ",
+        ansi::BOLD.0,
+        ansi::BOLD.1,
+        ansi::RESET_COLOR
+      )
+    } else {
+      format!("{label}: ")
+    };
+    self.indents.push(Indent::hanging(first));
+    if let Some(title) = title {
+      let title = self.styled(&title, ansi::BOLD);
+      self.emit_line(&title);
+      self.pending_blank = false;
+    }
+  }
+
+  fn exit_admonition_block(
+    &mut self,
+    _kind: AdmonitionKind,
+    _icon_uri: Option<&str>,
+    _block: &Block,
+  ) {
+    self.close_indent();
+  }
+
+  fn enter_image_block(
+    &mut self,
+    img_target: &SourceString,
+    img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    block: &Block,
+  ) {
+    self.open_block(block);
+    self.push(["[Image: ", &image_alt(img_target, img_attrs), "]"]);
+    self.flush();
+  }
+
+  fn exit_image_block(
+    &mut self,
+    _img_target: &SourceString,
+    _img_attrs: &AttrList,
+    _img_kind: &ImageKind,
+    _block: &Block,
+  ) {
+  }
+
+  fn visit_document_attribute_decl(&mut self, name: &str, value: &AttrValue) {
+    if name == "hardbreaks-option" {
+      self.default_hardbreaks = value.is_true();
+      self.hardbreaks = value.is_true();
+    }
+    _ = self.doc_meta.insert_doc_attr(name, value.clone());
+  }
+
+  fn enter_footnote(&mut self, id: Option<&SourceString>) {
+    let prev = id.and_then(|id| {
+      self
+        .footnotes
+        .iter()
+        .position(|(prev, _)| prev.as_deref() == Some(id))
+    });
+    self.footnote_dupe = prev.is_some();
+    let num = prev.unwrap_or(self.footnotes.len()) + 1;
+    self.push(["[", &num_str!(num), "]"]);
+    self.footnote_start = Some(self.buf.len());
+  }
+
+  fn exit_footnote(&mut self, id: Option<&SourceString>) {
+    let Some(start) = self.footnote_start.take() else {
+      return;
+    };
+    let text = self.buf.split_off(start);
+    if !self.footnote_dupe {
+      self.footnotes.push((id.map(|id| id.to_string()), text));
+    }
+  }
+
+  fn enter_meta_title(&mut self, _block: &Block) {
+    self.start_buffering();
+  }
+
+  fn exit_meta_title(&mut self, block: &Block) {
+    if block.context != BlockContext::Passthrough {
+      self.stop_buffering();
+    } else {
+      self.swap_discard_alt_buffer();
+    }
+  }
+
+  fn into_result(mut self) -> Result<Self::Output, Self::Error> {
+    self.flush();
+    Ok(self.out)
+  }
+
+  fn result(&self) -> Result<&Self::Output, Self::Error> {
+    Ok(&self.out)
+  }
+}
+
+impl Text {
+  pub fn new(options: TextOptions) -> Self {
+    Self { options, ..Self::default() }
+  }
+
+  const fn in_table_cell(mut self) -> Self {
+    self.in_asciidoc_table_cell = true;
+    self
+  }
+
+  fn standalone(&self) -> bool {
+    self.doc_meta.get_doctype() != DocType::Inline
+      && !self.in_asciidoc_table_cell
+      && !self.doc_meta.embedded
+  }
+
+  fn available_width(&self) -> usize {
+    let indent = self
+      .indents
+      .iter()
+      .map(|indent| wrap::visible_width(&indent.rest))
+      .sum::<usize>();
+    // deeply nested content still gets a readable measure
+    self.options.width.saturating_sub(indent).max(20)
+  }
+
+  fn push_style(&mut self, code: &str) {
+    if self.options.ansi {
+      self.push_str(code);
+    }
+  }
+
+  fn styled(&self, text: &str, (open, close): (&str, &str)) -> String {
+    if self.options.ansi {
+      format!("{open}{text}{close}")
+    } else {
+      text.to_string()
+    }
+  }
+
+  /// writes a single line of output, prefixed by the current indentation
+  fn emit_line(&mut self, line: &str) {
+    let mut prefixed = String::new();
+    for indent in &mut self.indents {
+      if indent.used {
+        prefixed.push_str(&indent.rest);
+      } else {
+        prefixed.push_str(&indent.first);
+        indent.used = true;
+      }
+    }
+    prefixed.push_str(line);
+    self.out.push_str(prefixed.trim_end_matches(' '));
+    self.out.push('\n');
+    self.pending_blank = true;
+    self.after_principal = false;
+  }
+
+  /// wraps and emits any pending inline content
+  fn flush(&mut self) {
+    if self.buf.trim().is_empty() {
+      self.buf.clear();
+      return;
+    }
+    let text = std::mem::take(&mut self.buf);
+    let text = text.trim_end_matches([' ', '\n']);
+    for line in wrap::wrap(text, self.available_width()) {
+      self.emit_line(&line);
+    }
+  }
+
+  /// blocks are separated by a blank line, except at the start of a container
+  fn start_block(&mut self) {
+    self.flush();
+    let container_started = self.indents.iter().all(|indent| indent.used);
+    if self.pending_blank && !self.out.is_empty() && container_started {
+      self.emit_line("");
+    }
+    self.pending_blank = false;
+  }
+
+  fn open_block(&mut self, block: &Block) {
+    let title = self.take_title();
+    self.start_block();
+    if let Some(title) = title {
+      let style = iff!(
+        block.context == BlockContext::Example,
+        ansi::BOLD,
+        ansi::ITALIC
+      );
+      let title = self.styled(&title, style);
+      self.emit_line(&title);
+      self.pending_blank = false;
+    }
+  }
+
+  fn close_indent(&mut self) {
+    self.flush();
+    self.indents.pop();
+  }
+
+  fn take_title(&mut self) -> Option<String> {
+    if self.alt_buf.is_empty() { None } else { Some(self.take_buffer()) }
+  }
+
+  fn enter_heading(&mut self) {
+    self.start_block();
+    self.push_style(ansi::BOLD.0);
+  }
+
+  fn exit_heading(&mut self, level: u8) {
+    self.push_style(ansi::BOLD.1);
+    let heading = std::mem::take(&mut self.buf);
+    let heading = heading.trim();
+    self.emit_line(heading);
+    let rule = match level {
+      0 => '═',
+      1 => '─',
+      _ => return,
+    };
+    let width = wrap::visible_width(heading).min(self.available_width());
+    self.emit_line(&rule.to_string().repeat(width));
+  }
+
+  fn open_list(&mut self, block: &Block, start: u32, num_width: usize) {
+    if self.after_principal {
+      self.pending_blank = false;
+    }
+    self.open_block(block);
+    self.list_stack.push(ListState {
+      next_num: start,
+      num_width,
+      num_items: 0,
+      loose: false,
+    });
+  }
+
+  fn enter_verbatim(&mut self, block: &Block, indent: &str) {
+    self.open_block(block);
+    self.indents.push(Indent::uniform(indent));
+    self.verbatim = true;
+  }
+
+  fn exit_verbatim(&mut self) {
+    self.verbatim = false;
+    let text = std::mem::take(&mut self.buf);
+    for line in text.trim_end_matches('\n').lines() {
+      self.emit_line(line);
+    }
+    self.indents.pop();
+  }
+}
+
+impl TableState {
+  fn push_span_placeholders(&mut self) {
+    while self.occupied.get(self.col) == Some(&true) {
+      self.col += 1;
+      self.row.push(TextCell::empty());
+    }
+  }
+
+  /// fills out a ragged (last) row, so its borders line up with the others
+  fn pad_row(&mut self) {
+    while self.col < self.table.num_cols {
+      self.col += 1;
+      self.row.push(TextCell::empty());
+    }
+  }
+}
+
+const fn cell_style(cell: &Cell) -> Option<(&'static str, &'static str)> {
+  match &cell.content {
+    CellContent::Emphasis(_) => Some(ansi::ITALIC),
+    CellContent::Strong(_) | CellContent::Header(_) => Some(ansi::BOLD),
+    CellContent::Monospace(_) => Some(ansi::MONO),
+    _ => None,
+  }
+}
+
+fn span_style(attrs: Option<&AttrList>) -> Option<(&'static str, &'static str)> {
+  let attrs = attrs?;
+  if attrs.has_role("line-through") {
+    Some(ansi::STRIKE)
+  } else if attrs.has_role("underline") {
+    Some(ansi::UNDERLINE)
+  } else {
+    None
+  }
+}
+
+fn image_alt(target: &str, attrs: &AttrList) -> String {
+  match attrs.named("alt").or_else(|| attrs.str_positional_at(0)) {
+    Some(alt) => alt.to_string(),
+    None => file::stem(target).replace(['-', '_'], " "),
+  }
+}
+
+impl HtmlBuf for Text {
+  fn htmlbuf(&mut self) -> &mut String {
+    &mut self.buf
+  }
+
+  fn swapbuf(&mut self, s: &mut String) {
+    std::mem::swap(&mut self.buf, s);
+  }
+}
+
+impl AltHtmlBuf for Text {
+  fn alt_htmlbuf(&mut self) -> &mut String {
+    &mut self.alt_buf
+  }
+
+  fn buffers(&mut self) -> (&mut String, &mut String) {
+    (&mut self.buf, &mut self.alt_buf)
+  }
+}
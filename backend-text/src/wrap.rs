@@ -0,0 +1,99 @@
+/// the number of terminal columns `text` occupies, ignoring ansi escapes
+/// NB: counts chars, so wide (e.g. CJK) glyphs are under-measured
+pub fn visible_width(text: &str) -> usize {
+  let mut width = 0;
+  let mut chars = text.chars();
+  while let Some(c) = chars.next() {
+    if c == '\x1b' {
+      for c in chars.by_ref() {
+        if c.is_ascii_alphabetic() {
+          break;
+        }
+      }
+    } else {
+      width += 1;
+    }
+  }
+  width
+}
+
+/// greedily fills lines up to `width` columns, honoring hard line breaks,
+/// words longer than `width` are left to overflow on their own line
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+  let mut lines = Vec::new();
+  for segment in text.split('\n') {
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in segment.split(' ').filter(|word| !word.is_empty()) {
+      let word_width = visible_width(word);
+      if line_width > 0 && line_width + 1 + word_width > width {
+        lines.push(std::mem::take(&mut line));
+        line_width = 0;
+      }
+      if line_width > 0 {
+        line.push(' ');
+        line_width += 1;
+      }
+      line.push_str(word);
+      line_width += word_width;
+    }
+    lines.push(line);
+  }
+  lines
+}
+
+/// pads (or aligns) `text` to exactly `width` visible columns
+pub fn pad(text: &str, width: usize, align: Align) -> String {
+  let fill = width.saturating_sub(visible_width(text));
+  let (left, right) = match align {
+    Align::Left => (0, fill),
+    Align::Center => (fill / 2, fill - fill / 2),
+    Align::Right => (fill, 0),
+  };
+  format!("{}{text}{}", " ".repeat(left), " ".repeat(right))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+  Left,
+  Center,
+  Right,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_visible_width() {
+    assert_eq!(visible_width("foo"), 3);
+    assert_eq!(visible_width("\x1b[1mfoo\x1b[22m"), 3);
+    assert_eq!(visible_width("café"), 4);
+  }
+
+  #[test]
+  fn test_wrap() {
+    let cases: Vec<(&str, usize, Vec<&str>)> = vec![
+      ("foo bar baz", 20, vec!["foo bar baz"]),
+      ("foo bar baz", 7, vec!["foo bar", "baz"]),
+      ("foo  bar", 20, vec!["foo bar"]),
+      ("a\nb c", 20, vec!["a", "b c"]),
+      (
+        "supercalifragilistic is long",
+        5,
+        vec!["supercalifragilistic", "is", "long"],
+      ),
+      ("\x1b[1mfoo\x1b[22m bar", 7, vec!["\x1b[1mfoo\x1b[22m bar"]),
+    ];
+    for (input, width, expected) in cases {
+      assert_eq!(wrap(input, width), expected, "input: {input:?}");
+    }
+  }
+
+  #[test]
+  fn test_pad() {
+    assert_eq!(pad("ab", 6, Align::Left), "ab    ");
+    assert_eq!(pad("ab", 6, Align::Center), "  ab  ");
+    assert_eq!(pad("ab", 6, Align::Right), "    ab");
+  }
+}
@@ -0,0 +1,218 @@
+use crate::helpers::*;
+use asciidork_backend_text::TextOptions;
+use test_utils::*;
+
+assert_converted!(
+  sections_and_paragraphs,
+  text,
+  adoc! {r#"
+    == Install
+
+    Use *bold*, _italic_ and `mono` text.
+
+    === Sub section
+
+    Another paragraph.
+  "#},
+  adoc! {r#"
+    Install
+    ───────
+
+    Use bold, italic and mono text.
+
+    Sub section
+
+    Another paragraph.
+  "#}
+);
+
+assert_converted!(
+  wraps_to_width,
+  |input| convert(input, TextOptions { width: 24, ansi: false }),
+  adoc! {r#"
+    The quick brown fox jumps over the lazy dog, twice.
+
+    * a list item that needs to wrap onto a second line
+  "#},
+  adoc! {r#"
+    The quick brown fox
+    jumps over the lazy dog,
+    twice.
+
+    • a list item that needs
+      to wrap onto a second
+      line
+  "#}
+);
+
+assert_converted!(
+  lists,
+  text,
+  adoc! {r#"
+    * one
+    ** nested
+    * [x] done
+
+    //-
+
+    . first
+    . second
+    +
+    continued
+  "#},
+  adoc! {r#"
+    • one
+      ◦ nested
+    [x] done
+
+    1. first
+    2. second
+
+       continued
+  "#}
+);
+
+assert_converted!(
+  description_lists,
+  text,
+  adoc! {r#"
+    CPU:: The brain
+    RAM:: The memory
+  "#},
+  adoc! {r#"
+    CPU
+        The brain
+
+    RAM
+        The memory
+  "#}
+);
+
+assert_converted!(
+  admonitions,
+  text,
+  adoc! {r#"
+    NOTE: Mind the gap
+    between the train and the platform.
+
+    [WARNING]
+    ====
+    First.
+
+    Second.
+    ====
+  "#},
+  adoc! {r#"
+    NOTE: Mind the gap between the train and the platform.
+
+    WARNING: First.
+
+             Second.
+  "#}
+);
+
+assert_converted!(
+  listings_and_quotes,
+  text,
+  adoc! {r#"
+    .Example
+    ----
+    fn main() {
+      println!("hi");
+    }
+    ----
+
+    [quote, Abraham Lincoln, Gettysburg]
+    ____
+    Four score and seven years ago.
+    ____
+  "#},
+  adoc! {r#"
+    Example
+        fn main() {
+          println!("hi");
+        }
+
+    │ Four score and seven years ago.
+    │
+    │ — Abraham Lincoln, Gettysburg
+  "#}
+);
+
+assert_converted!(
+  tables,
+  text,
+  adoc! {r#"
+    [cols="1,1"]
+    |===
+    |Name |Value
+
+    |a
+    |one two three
+    |===
+  "#},
+  adoc! {r#"
+    ┌──────┬───────────────┐
+    │ Name │ Value         │
+    ╞══════╪═══════════════╡
+    │ a    │ one two three │
+    └──────┴───────────────┘
+  "#}
+);
+
+assert_converted!(
+  table_w_ragged_last_row,
+  text,
+  adoc! {r#"
+    [cols="1,1"]
+    |===
+    |a |b
+    |c
+    |===
+  "#},
+  adoc! {r#"
+    ┌───┬───┐
+    │ a │ b │
+    │ c │   │
+    └───┴───┘
+  "#}
+);
+
+assert_converted!(
+  links_and_footnotes,
+  text,
+  adoc! {r#"
+    Visit https://example.com or
+    see https://example.com[the docs].footnote:[Really.]
+  "#},
+  adoc! {r#"
+    Visit https://example.com or see the docs <https://example.com>.[1]
+
+    ────────────────────
+    [1] Really.
+  "#}
+);
+
+assert_converted!(
+  inline_macros,
+  text,
+  adoc! {r#"
+    :experimental:
+
+    Press kbd:[Ctrl+T], then btn:[Save] via menu:File[Save].
+  "#},
+  adoc! {r#"
+    Press Ctrl+T, then [Save] via File ▸ Save.
+  "#}
+);
+
+assert_converted!(
+  ansi_styles,
+  |input| convert(input, TextOptions { width: 80, ansi: true }),
+  adoc! {r#"
+    Use *bold* and `mono`.
+
+    TIP: Try it.
+  "#},
+  "Use \x1b[1mbold\x1b[22m and \x1b[36mmono\x1b[39m.\n\n\x1b[32m\x1b[1mTIP\x1b[22m\x1b[39m: Try it.\n"
+);
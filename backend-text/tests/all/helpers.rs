@@ -0,0 +1,21 @@
+use asciidork_backend_text::{Text, TextOptions};
+
+// like `_html!`, but evaluates with a backend configured by `options`
+pub fn convert(input: &str, options: TextOptions) -> String {
+  let bump = &::asciidork_parser::prelude::Bump::new();
+  let mut settings = ::asciidork_core::JobSettings::embedded();
+  settings.safe_mode = ::asciidork_core::SafeMode::Unsafe;
+  let path = ::asciidork_core::Path::new("test.adoc");
+  let mut parser = ::asciidork_parser::Parser::from_str(
+    input,
+    ::asciidork_parser::prelude::SourceFile::Path(path),
+    bump,
+  );
+  parser.apply_job_settings(settings);
+  let document = parser.parse().unwrap().document;
+  ::asciidork_eval::eval(&document, Text::new(options)).unwrap()
+}
+
+pub fn text(input: &str) -> String {
+  convert(input, TextOptions::default())
+}
@@ -0,0 +1,2 @@
+mod eval_text;
+mod helpers;
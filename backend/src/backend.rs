@@ -80,6 +80,11 @@ pub trait Backend {
   fn exit_literal_block(&mut self, block: &Block);
   fn enter_passthrough_block(&mut self, _block: &Block) {}
   fn exit_passthrough_block(&mut self, _block: &Block) {}
+  fn enter_stem_block(&mut self, block: &Block, notation: StemNotation) {
+    _ = (block, notation);
+    warn_unimplemented!(enter_stem_block);
+  }
+  fn exit_stem_block(&mut self, _block: &Block, _notation: StemNotation) {}
   fn enter_image_block(
     &mut self,
     img_target: &SourceString,
@@ -177,6 +182,8 @@ pub trait Backend {
   fn exit_concealed_index_term_secondary(&mut self) {}
   fn enter_concealed_index_term_tertiary(&mut self) {}
   fn exit_concealed_index_term_tertiary(&mut self) {}
+  /// called with the `see` or `see-also` terms of a visited index term
+  fn visit_index_term_reference(&mut self, _term_ref: &IndexTermReference) {}
 
   fn visit_plugin_macro(&mut self, plugin_macro: &PluginMacro) {
     _ = plugin_macro;
@@ -251,6 +258,11 @@ pub trait Backend {
   fn visit_inline_specialchar(&mut self, char: &SpecialCharKind);
   fn enter_inline_passthrough(&mut self) {}
   fn exit_inline_passthrough(&mut self) {}
+  fn enter_inline_stem(&mut self, notation: StemNotation) {
+    _ = notation;
+    warn_unimplemented!(enter_inline_stem);
+  }
+  fn exit_inline_stem(&mut self, _notation: StemNotation) {}
   fn enter_inline_highlight(&mut self, attrs: Option<&AttrList>);
   fn exit_inline_highlight(&mut self, attrs: Option<&AttrList>);
   fn enter_inline_subscript(&mut self, attrs: Option<&AttrList>);
@@ -0,0 +1,661 @@
+/// a declarative description of a language, interpreted by `scan::scan`
+#[derive(Debug)]
+pub struct Syntax {
+  pub line_comments: &'static [&'static str],
+  pub block_comments: &'static [(&'static str, &'static str)],
+  /// string delimiters, longest first, e.g. `"""` before `"`
+  pub strings: &'static [&'static str],
+  pub keywords: &'static [&'static str],
+  pub constants: &'static [&'static str],
+  pub types: &'static [&'static str],
+  pub builtins: &'static [&'static str],
+  /// keywords after which the next identifier names a function
+  pub fn_keywords: &'static [&'static str],
+  /// keywords after which the next identifier names a type
+  pub type_keywords: &'static [&'static str],
+  /// chars which introduce a variable, e.g. `$` in shell
+  pub var_prefixes: &'static [char],
+  /// char which introduces a decorator/annotation, e.g. `@` in python
+  pub decorator: Option<char>,
+  /// char which introduces a preprocessor line, e.g. `#` in c
+  pub preproc: Option<char>,
+  /// extra non-alphanumeric chars allowed inside identifiers
+  pub ident_chars: &'static str,
+  /// identifiers followed by `:` (or `=`) are keys/attributes
+  pub key_suffix: Option<char>,
+  pub case_insensitive: bool,
+  /// rust-style `'a` lifetimes vs `'a'` char literals
+  pub lifetimes: bool,
+  /// rust-style `println!` macros
+  pub bang_macros: bool,
+  /// ruby-style `:symbol` literals
+  pub symbols: bool,
+  /// identifiers starting w/ an uppercase letter are types
+  pub capitalized_types: bool,
+  /// use the dedicated markup (xml/html) scanner
+  pub markup: bool,
+  /// use the dedicated line-based diff scanner
+  pub diff: bool,
+}
+
+impl Syntax {
+  pub fn find(lang: &str) -> Option<&'static Syntax> {
+    let syntax = match lang.to_ascii_lowercase().as_str() {
+      "rust" | "rs" => &RUST,
+      "ruby" | "rb" => &RUBY,
+      "python" | "py" | "python3" => &PYTHON,
+      "javascript" | "js" | "jsx" | "mjs" | "typescript" | "ts" | "tsx" => &JAVASCRIPT,
+      "java" | "kotlin" | "kt" | "scala" | "groovy" => &JAVA,
+      "c" | "h" | "cpp" | "c++" | "cxx" | "hpp" | "objc" => &C,
+      "csharp" | "cs" | "c#" => &CSHARP,
+      "go" | "golang" => &GO,
+      "shell" | "sh" | "bash" | "zsh" | "console" | "shell-session" => &SHELL,
+      "json" | "jsonc" | "json5" => &JSON,
+      "yaml" | "yml" => &YAML,
+      "toml" | "ini" => &TOML,
+      "xml" | "html" | "xhtml" | "svg" | "vue" => &MARKUP,
+      "css" | "scss" | "less" => &CSS,
+      "sql" => &SQL,
+      "diff" | "patch" => &DIFF,
+      _ => return None,
+    };
+    Some(syntax)
+  }
+}
+
+const C_COMMENTS: &[(&str, &str)] = &[("/*", "*/")];
+
+static RUST: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\""],
+  keywords: &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while",
+  ],
+  constants: &["true", "false", "None", "Some", "Ok", "Err"],
+  types: &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64", "String", "Vec", "Option", "Result", "Box",
+  ],
+  fn_keywords: &["fn"],
+  type_keywords: &["struct", "enum", "trait", "type", "impl"],
+  decorator: Some('#'),
+  lifetimes: true,
+  bang_macros: true,
+  ..Syntax::EMPTY
+};
+
+static RUBY: Syntax = Syntax {
+  line_comments: &["#"],
+  strings: &["\"", "'", "`"],
+  keywords: &[
+    "alias", "and", "begin", "break", "case", "class", "def", "defined?", "do", "else", "elsif",
+    "end", "ensure", "for", "if", "in", "module", "next", "not", "or", "redo", "rescue", "retry",
+    "return", "self", "super", "then", "undef", "unless", "until", "when", "while", "yield",
+  ],
+  constants: &["true", "false", "nil"],
+  builtins: &[
+    "puts",
+    "print",
+    "p",
+    "require",
+    "require_relative",
+    "include",
+    "extend",
+    "attr_reader",
+    "attr_writer",
+    "attr_accessor",
+    "raise",
+    "lambda",
+    "proc",
+    "private",
+    "protected",
+    "public",
+  ],
+  fn_keywords: &["def"],
+  type_keywords: &["class", "module"],
+  var_prefixes: &['@', '$'],
+  ident_chars: "?!",
+  symbols: true,
+  capitalized_types: true,
+  ..Syntax::EMPTY
+};
+
+static PYTHON: Syntax = Syntax {
+  line_comments: &["#"],
+  strings: &["\"\"\"", "'''", "\"", "'"],
+  keywords: &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+  ],
+  constants: &["True", "False", "None"],
+  builtins: &[
+    "print",
+    "len",
+    "range",
+    "open",
+    "str",
+    "int",
+    "float",
+    "list",
+    "dict",
+    "set",
+    "tuple",
+    "bool",
+    "isinstance",
+    "super",
+    "self",
+    "enumerate",
+    "zip",
+    "map",
+    "filter",
+    "sorted",
+  ],
+  fn_keywords: &["def"],
+  type_keywords: &["class"],
+  decorator: Some('@'),
+  ..Syntax::EMPTY
+};
+
+static JAVASCRIPT: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\"", "'", "`"],
+  keywords: &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "of",
+    "return",
+    "static",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "type",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+  ],
+  constants: &["true", "false", "null", "undefined", "NaN", "Infinity"],
+  types: &[
+    "string", "number", "boolean", "any", "unknown", "never", "object",
+  ],
+  builtins: &[
+    "console", "window", "document", "require", "module", "exports", "Promise", "Array", "Object",
+    "JSON", "Math",
+  ],
+  fn_keywords: &["function"],
+  type_keywords: &["class", "interface", "type", "extends", "implements", "new"],
+  decorator: Some('@'),
+  ident_chars: "$",
+  ..Syntax::EMPTY
+};
+
+static JAVA: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\"\"\"", "\"", "'"],
+  keywords: &[
+    "abstract",
+    "assert",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "do",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "for",
+    "fun",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "interface",
+    "new",
+    "object",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "try",
+    "val",
+    "var",
+    "void",
+    "when",
+    "while",
+  ],
+  constants: &["true", "false", "null"],
+  types: &[
+    "boolean", "byte", "char", "double", "float", "int", "long", "short", "String",
+  ],
+  fn_keywords: &["fun"],
+  type_keywords: &[
+    "class",
+    "interface",
+    "enum",
+    "extends",
+    "implements",
+    "new",
+    "object",
+  ],
+  decorator: Some('@'),
+  capitalized_types: true,
+  ..Syntax::EMPTY
+};
+
+static C: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\"", "'"],
+  keywords: &[
+    "auto",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "constexpr",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "extern",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "namespace",
+    "new",
+    "operator",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "return",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "try",
+    "typedef",
+    "typename",
+    "union",
+    "using",
+    "virtual",
+    "volatile",
+    "while",
+  ],
+  constants: &["true", "false", "NULL", "nullptr"],
+  types: &[
+    "bool", "char", "double", "float", "int", "long", "short", "signed", "unsigned", "void",
+    "size_t", "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t", "uint32_t",
+    "uint64_t",
+  ],
+  type_keywords: &["class", "struct", "enum", "union", "namespace"],
+  preproc: Some('#'),
+  ..Syntax::EMPTY
+};
+
+static CSHARP: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\"", "'"],
+  keywords: &[
+    "abstract",
+    "as",
+    "async",
+    "await",
+    "base",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delegate",
+    "do",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "finally",
+    "for",
+    "foreach",
+    "get",
+    "if",
+    "implicit",
+    "in",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "namespace",
+    "new",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "record",
+    "ref",
+    "return",
+    "sealed",
+    "set",
+    "static",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "using",
+    "var",
+    "virtual",
+    "while",
+  ],
+  constants: &["true", "false", "null"],
+  types: &[
+    "bool", "byte", "char", "decimal", "double", "float", "int", "long", "object", "sbyte",
+    "short", "string", "uint", "ulong", "ushort", "void",
+  ],
+  type_keywords: &["class", "struct", "enum", "interface", "record", "new"],
+  preproc: Some('#'),
+  capitalized_types: true,
+  ..Syntax::EMPTY
+};
+
+static GO: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\"", "'", "`"],
+  keywords: &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+  ],
+  constants: &["true", "false", "nil", "iota"],
+  types: &[
+    "bool",
+    "byte",
+    "complex64",
+    "complex128",
+    "error",
+    "float32",
+    "float64",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "rune",
+    "string",
+    "uint",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uintptr",
+  ],
+  builtins: &[
+    "append", "cap", "close", "copy", "delete", "len", "make", "new", "panic", "print", "println",
+    "recover",
+  ],
+  fn_keywords: &["func"],
+  type_keywords: &["type"],
+  ..Syntax::EMPTY
+};
+
+static SHELL: Syntax = Syntax {
+  line_comments: &["#"],
+  strings: &["\"", "'"],
+  keywords: &[
+    "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in",
+    "local", "return", "select", "then", "until", "while",
+  ],
+  builtins: &[
+    "alias", "cd", "echo", "eval", "exec", "exit", "printf", "read", "set", "shift", "source",
+    "sudo", "test", "unset",
+  ],
+  fn_keywords: &["function"],
+  var_prefixes: &['$'],
+  ident_chars: "-./",
+  ..Syntax::EMPTY
+};
+
+static JSON: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\""],
+  constants: &["true", "false", "null"],
+  key_suffix: Some(':'),
+  ..Syntax::EMPTY
+};
+
+static YAML: Syntax = Syntax {
+  line_comments: &["#"],
+  strings: &["\"", "'"],
+  constants: &["true", "false", "null", "yes", "no", "on", "off", "~"],
+  ident_chars: "-./",
+  key_suffix: Some(':'),
+  ..Syntax::EMPTY
+};
+
+static TOML: Syntax = Syntax {
+  line_comments: &["#", ";"],
+  strings: &["\"\"\"", "'''", "\"", "'"],
+  constants: &["true", "false"],
+  ident_chars: "-.",
+  key_suffix: Some('='),
+  ..Syntax::EMPTY
+};
+
+static MARKUP: Syntax = Syntax { markup: true, ..Syntax::EMPTY };
+
+static CSS: Syntax = Syntax {
+  line_comments: &["//"],
+  block_comments: C_COMMENTS,
+  strings: &["\"", "'"],
+  constants: &["!important"],
+  decorator: Some('@'),
+  ident_chars: "-",
+  key_suffix: Some(':'),
+  ..Syntax::EMPTY
+};
+
+static SQL: Syntax = Syntax {
+  line_comments: &["--"],
+  block_comments: C_COMMENTS,
+  strings: &["'", "\""],
+  keywords: &[
+    "add",
+    "all",
+    "alter",
+    "and",
+    "as",
+    "asc",
+    "between",
+    "by",
+    "case",
+    "check",
+    "column",
+    "constraint",
+    "create",
+    "database",
+    "default",
+    "delete",
+    "desc",
+    "distinct",
+    "drop",
+    "else",
+    "end",
+    "exists",
+    "foreign",
+    "from",
+    "group",
+    "having",
+    "if",
+    "in",
+    "index",
+    "inner",
+    "insert",
+    "into",
+    "is",
+    "join",
+    "key",
+    "left",
+    "like",
+    "limit",
+    "not",
+    "on",
+    "or",
+    "order",
+    "outer",
+    "primary",
+    "references",
+    "right",
+    "select",
+    "set",
+    "table",
+    "then",
+    "union",
+    "unique",
+    "update",
+    "values",
+    "view",
+    "when",
+    "where",
+    "with",
+  ],
+  constants: &["true", "false", "null"],
+  types: &[
+    "bigint",
+    "blob",
+    "boolean",
+    "char",
+    "date",
+    "datetime",
+    "decimal",
+    "float",
+    "int",
+    "integer",
+    "numeric",
+    "real",
+    "serial",
+    "smallint",
+    "text",
+    "timestamp",
+    "uuid",
+    "varchar",
+  ],
+  builtins: &["avg", "coalesce", "count", "max", "min", "now", "sum"],
+  case_insensitive: true,
+  ..Syntax::EMPTY
+};
+
+static DIFF: Syntax = Syntax { diff: true, ..Syntax::EMPTY };
+
+impl Syntax {
+  const EMPTY: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[],
+    strings: &[],
+    keywords: &[],
+    constants: &[],
+    types: &[],
+    builtins: &[],
+    fn_keywords: &[],
+    type_keywords: &[],
+    var_prefixes: &[],
+    decorator: None,
+    preproc: None,
+    ident_chars: "",
+    key_suffix: None,
+    case_insensitive: false,
+    lifetimes: false,
+    bang_macros: false,
+    symbols: false,
+    capitalized_types: false,
+    markup: false,
+    diff: false,
+  };
+}
@@ -0,0 +1,229 @@
+mod lang;
+mod scan;
+mod source;
+
+use ast::{DocumentMeta, ReadAttr};
+
+use lang::Syntax;
+
+pub use source::{HighlightSource, highlight_lines};
+
+/// server-side highlighters which asciidork can emulate natively,
+/// selected via the `source-highlighter` document attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlighter {
+  Asciidork,
+  Rouge,
+  Pygments,
+}
+
+impl Highlighter {
+  pub fn from_doc_meta(meta: &DocumentMeta) -> Option<Self> {
+    match meta.str("source-highlighter")? {
+      "asciidork" => Some(Self::Asciidork),
+      "rouge" => Some(Self::Rouge),
+      "pygments" => Some(Self::Pygments),
+      _ => None,
+    }
+  }
+
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::Asciidork => "asciidork",
+      Self::Rouge => "rouge",
+      Self::Pygments => "pygments",
+    }
+  }
+
+  /// asciidoctor's pygments adapter prefixes token classes w/ `tok-`
+  pub const fn class_prefix(self) -> &'static str {
+    match self {
+      Self::Pygments => "tok-",
+      _ => "",
+    }
+  }
+
+  pub fn stylesheet(self) -> String {
+    let pre = self.name();
+    let tok = self.class_prefix();
+    let mut css = format!(
+      "pre.{pre} table td{{padding:5px}}pre.{pre} table pre{{margin:0}}\
+      pre.{pre} .linenos{{color:#999;border-right:1px solid #ddd;padding-right:.5em}}\
+      pre.{pre} .hll{{background-color:#ffffcc;display:block}}"
+    );
+    for (classes, style) in TOKEN_STYLES {
+      for (idx, class) in classes.iter().enumerate() {
+        if idx > 0 {
+          css.push(',');
+        }
+        css.push_str(&format!("pre.{pre} .{tok}{class}"));
+      }
+      css.push('{');
+      css.push_str(style);
+      css.push('}');
+    }
+    css
+  }
+}
+
+/// pygments short token names, shared by rouge and pygments stylesheets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+  Text,
+  Keyword,
+  KeywordConstant,
+  KeywordType,
+  Comment,
+  CommentMultiline,
+  CommentPreproc,
+  String,
+  StringChar,
+  StringSymbol,
+  Number,
+  Operator,
+  Punctuation,
+  Builtin,
+  NameFunction,
+  NameClass,
+  NameVariable,
+  NameAttribute,
+  NameTag,
+  NameDecorator,
+  NameLabel,
+  NameEntity,
+  Inserted,
+  Deleted,
+  Heading,
+  Prompt,
+}
+
+impl TokenKind {
+  pub const fn class(self) -> Option<&'static str> {
+    match self {
+      TokenKind::Text => None,
+      TokenKind::Keyword => Some("k"),
+      TokenKind::KeywordConstant => Some("kc"),
+      TokenKind::KeywordType => Some("kt"),
+      TokenKind::Comment => Some("c1"),
+      TokenKind::CommentMultiline => Some("cm"),
+      TokenKind::CommentPreproc => Some("cp"),
+      TokenKind::String => Some("s"),
+      TokenKind::StringChar => Some("sc"),
+      TokenKind::StringSymbol => Some("ss"),
+      TokenKind::Number => Some("m"),
+      TokenKind::Operator => Some("o"),
+      TokenKind::Punctuation => Some("p"),
+      TokenKind::Builtin => Some("nb"),
+      TokenKind::NameFunction => Some("nf"),
+      TokenKind::NameClass => Some("nc"),
+      TokenKind::NameVariable => Some("nv"),
+      TokenKind::NameAttribute => Some("na"),
+      TokenKind::NameTag => Some("nt"),
+      TokenKind::NameDecorator => Some("nd"),
+      TokenKind::NameLabel => Some("nl"),
+      TokenKind::NameEntity => Some("ni"),
+      TokenKind::Inserted => Some("gi"),
+      TokenKind::Deleted => Some("gd"),
+      TokenKind::Heading => Some("gu"),
+      TokenKind::Prompt => Some("gp"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+  pub kind: TokenKind,
+  pub text: &'a str,
+}
+
+/// tokenize `src` according to `lang`, unknown languages
+/// produce a single `TokenKind::Text` token
+pub fn tokenize<'a>(lang: Option<&str>, src: &'a str) -> Vec<Token<'a>> {
+  match lang.and_then(Syntax::find) {
+    Some(syntax) => scan::scan(syntax, src),
+    None => vec![Token { kind: TokenKind::Text, text: src }],
+  }
+}
+
+/// parses a `highlight` attr value like `1,3..5;7-8` into a sorted
+/// list of 1-based line numbers, relative to the `start` line number
+pub fn parse_line_ranges(spec: &str, start: usize) -> Vec<usize> {
+  let mut lines = Vec::new();
+  for part in spec.split([',', ';', ' ']).filter(|p| !p.is_empty()) {
+    let range = part.split_once("..").or_else(|| part.split_once('-'));
+    if let Some((from, to)) = range {
+      let (Ok(from), Ok(to)) = (from.trim().parse::<usize>(), to.trim().parse::<usize>()) else {
+        continue;
+      };
+      lines.extend(from..=to);
+    } else if let Ok(line) = part.trim().parse::<usize>() {
+      lines.push(line);
+    }
+  }
+  let mut lines = lines
+    .into_iter()
+    .filter(|line| *line >= start)
+    .map(|line| line - start + 1)
+    .collect::<Vec<_>>();
+  lines.sort_unstable();
+  lines.dedup();
+  lines
+}
+
+const TOKEN_STYLES: &[(&[&str], &str)] = &[
+  (&["c1", "cm"], "color:#998;font-style:italic"),
+  (&["cp"], "color:#999;font-weight:bold"),
+  (&["k", "kt"], "color:#000;font-weight:bold"),
+  (&["kc"], "color:#008080"),
+  (&["s", "sc"], "color:#d14"),
+  (&["ss"], "color:#990073"),
+  (&["m"], "color:#099"),
+  (&["o"], "color:#000;font-weight:bold"),
+  (&["nb"], "color:#0086b3"),
+  (&["nf"], "color:#900;font-weight:bold"),
+  (&["nc"], "color:#458;font-weight:bold"),
+  (&["nv", "nl"], "color:#008080"),
+  (&["na"], "color:#008080"),
+  (&["nt"], "color:#000080"),
+  (&["nd"], "color:#3c5d5d;font-weight:bold"),
+  (&["ni"], "color:#800080"),
+  (&["gi"], "color:#000;background-color:#dfd"),
+  (&["gd"], "color:#000;background-color:#fdd"),
+  (&["gu"], "color:#aaa"),
+  (&["gp"], "color:#555"),
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_line_ranges() {
+    let cases = vec![
+      ("1", 1, vec![1]),
+      ("1,3", 1, vec![1, 3]),
+      ("2..4", 1, vec![2, 3, 4]),
+      ("2-4", 1, vec![2, 3, 4]),
+      ("5;1..2", 1, vec![1, 2, 5]),
+      ("1, 3", 1, vec![1, 3]),
+      ("11,12", 10, vec![2, 3]),
+      ("3,3,1", 1, vec![1, 3]),
+      ("foo,2", 1, vec![2]),
+    ];
+    for (spec, start, expected) in cases {
+      assert_eq!(parse_line_ranges(spec, start), expected, "spec: {spec}");
+    }
+  }
+
+  #[test]
+  fn test_unknown_lang_is_plain_text() {
+    let tokens = tokenize(Some("not-a-lang"), "foo bar");
+    assert_eq!(
+      tokens,
+      vec![Token {
+        kind: TokenKind::Text,
+        text: "foo bar"
+      }]
+    );
+  }
+}
@@ -0,0 +1,587 @@
+use super::lang::Syntax;
+use super::{Token, TokenKind};
+
+pub fn scan<'a>(syntax: &Syntax, src: &'a str) -> Vec<Token<'a>> {
+  if syntax.markup {
+    return scan_markup(src);
+  } else if syntax.diff {
+    return scan_diff(src);
+  }
+  let mut scanner = Scanner::new(src);
+  // set when a keyword like `fn` or `class` names the next identifier
+  let mut pending: Option<TokenKind> = None;
+  while let Some(c) = scanner.peek() {
+    let rest = scanner.rest();
+    if c.is_whitespace() {
+      let len = scanner.len_while(char::is_whitespace);
+      scanner.push(TokenKind::Text, len);
+    } else if syntax.preproc == Some(c) && scanner.at_line_start() {
+      let len = scanner.len_until_eol();
+      scanner.push(TokenKind::CommentPreproc, len);
+    } else if let Some(prefix) = line_comment(syntax, &scanner) {
+      let len = prefix.len().max(scanner.len_until_eol());
+      scanner.push(TokenKind::Comment, len);
+    } else if let Some((open, close)) = syntax
+      .block_comments
+      .iter()
+      .find(|(open, _)| rest.starts_with(open))
+    {
+      let len = rest[open.len()..]
+        .find(close)
+        .map_or(rest.len(), |idx| open.len() + idx + close.len());
+      scanner.push(TokenKind::CommentMultiline, len);
+    } else if c == '\'' && syntax.lifetimes {
+      match rust_char_literal_len(rest) {
+        Some(len) => scanner.push(TokenKind::StringChar, len),
+        None => {
+          let len = 1 + ident_len(&rest[1..], "");
+          scanner.push(TokenKind::NameLabel, len);
+        }
+      }
+    } else if let Some(delim) = syntax.strings.iter().find(|d| rest.starts_with(*d)) {
+      let len = string_len(rest, delim);
+      let kind = if syntax.key_suffix.is_some() && scanner.followed_by(len, ':') {
+        TokenKind::NameTag
+      } else {
+        TokenKind::String
+      };
+      scanner.push(kind, len);
+    } else if c.is_ascii_digit() {
+      scanner.push(TokenKind::Number, number_len(rest));
+    } else if syntax.var_prefixes.contains(&c) {
+      scanner.push(TokenKind::NameVariable, var_len(rest, syntax.var_prefixes));
+    } else if syntax.decorator == Some(c) && is_decorator_start(&rest[1..]) {
+      scanner.push(TokenKind::NameDecorator, decorator_len(rest));
+    } else if c == ':' && syntax.symbols && scanner.symbol_allowed() {
+      let len = ident_len(&rest[1..], syntax.ident_chars);
+      scanner.push(TokenKind::StringSymbol, len + 1);
+    } else if is_ident_start(c) {
+      let mut len = ident_len(rest, syntax.ident_chars);
+      let word = &rest[..len];
+      let kind = if let Some(kind) = pending.take() {
+        kind
+      } else if contains(syntax.fn_keywords, word, syntax) {
+        pending = Some(TokenKind::NameFunction);
+        TokenKind::Keyword
+      } else if contains(syntax.type_keywords, word, syntax) {
+        pending = Some(TokenKind::NameClass);
+        TokenKind::Keyword
+      } else if contains(syntax.keywords, word, syntax) {
+        TokenKind::Keyword
+      } else if contains(syntax.constants, word, syntax) {
+        TokenKind::KeywordConstant
+      } else if contains(syntax.types, word, syntax) {
+        TokenKind::KeywordType
+      } else if contains(syntax.builtins, word, syntax) {
+        TokenKind::Builtin
+      } else if syntax.bang_macros && rest[len..].starts_with('!') && !rest[len..].starts_with("!=")
+      {
+        len += 1;
+        TokenKind::NameFunction
+      } else if syntax
+        .key_suffix
+        .is_some_and(|suffix| scanner.key_follows(len, suffix))
+      {
+        TokenKind::NameAttribute
+      } else if syntax.capitalized_types && word.starts_with(|c: char| c.is_ascii_uppercase()) {
+        TokenKind::NameClass
+      } else {
+        TokenKind::Text
+      };
+      scanner.push(kind, len);
+      continue;
+    } else if OPERATORS.contains(c) {
+      let len = scanner.len_while(|c| OPERATORS.contains(c));
+      scanner.push(TokenKind::Operator, len);
+    } else if PUNCTUATION.contains(c) {
+      scanner.push(TokenKind::Punctuation, 1);
+    } else {
+      scanner.push(TokenKind::Text, c.len_utf8());
+    }
+    // only whitespace may separate a keyword from the name it introduces
+    if !c.is_whitespace() {
+      pending = None;
+    }
+  }
+  scanner.tokens
+}
+
+fn scan_markup(src: &str) -> Vec<Token<'_>> {
+  let mut scanner = Scanner::new(src);
+  while let Some(c) = scanner.peek() {
+    let rest = scanner.rest();
+    if rest.starts_with("<!--") {
+      let len = rest.find("-->").map_or(rest.len(), |idx| idx + 3);
+      scanner.push(TokenKind::CommentMultiline, len);
+    } else if rest.starts_with("<!") || rest.starts_with("<?") {
+      let len = rest.find('>').map_or(rest.len(), |idx| idx + 1);
+      scanner.push(TokenKind::CommentPreproc, len);
+    } else if c == '<' && rest[1..].starts_with(|c: char| c == '/' || is_ident_start(c)) {
+      let name_start = if rest[1..].starts_with('/') { 2 } else { 1 };
+      let len = name_start + ident_len(&rest[name_start..], "-:.");
+      scanner.push(TokenKind::NameTag, len);
+      scan_markup_attrs(&mut scanner);
+    } else if c == '&' {
+      let len = rest
+        .find(';')
+        .filter(|idx| *idx < 10 && !rest[1..*idx].contains(char::is_whitespace))
+        .map_or(1, |idx| idx + 1);
+      let kind = if len > 1 { TokenKind::NameEntity } else { TokenKind::Text };
+      scanner.push(kind, len);
+    } else {
+      let len = rest.find(['<', '&']).unwrap_or(rest.len());
+      scanner.push(TokenKind::Text, len.max(c.len_utf8()));
+    }
+  }
+  scanner.tokens
+}
+
+fn scan_markup_attrs(scanner: &mut Scanner) {
+  while let Some(c) = scanner.peek() {
+    let rest = scanner.rest();
+    if c == '>' {
+      scanner.push(TokenKind::NameTag, 1);
+      return;
+    } else if rest.starts_with("/>") || rest.starts_with("?>") {
+      scanner.push(TokenKind::NameTag, 2);
+      return;
+    } else if c.is_whitespace() {
+      let len = scanner.len_while(char::is_whitespace);
+      scanner.push(TokenKind::Text, len);
+    } else if c == '=' {
+      scanner.push(TokenKind::Operator, 1);
+    } else if c == '"' || c == '\'' {
+      let len = rest[1..].find(c).map_or(rest.len(), |idx| idx + 2);
+      scanner.push(TokenKind::String, len);
+    } else if c == '<' {
+      return;
+    } else {
+      let len = scanner.len_while(|c| !c.is_whitespace() && !matches!(c, '=' | '>' | '/' | '<'));
+      scanner.push(TokenKind::NameAttribute, len.max(c.len_utf8()));
+    }
+  }
+}
+
+fn scan_diff(src: &str) -> Vec<Token<'_>> {
+  let mut scanner = Scanner::new(src);
+  while scanner.peek().is_some() {
+    let rest = scanner.rest();
+    let line_len = rest.find('\n').map_or(rest.len(), |idx| idx + 1);
+    let line = &rest[..line_len];
+    let kind = if line.starts_with("+++") || line.starts_with("---") || line.starts_with("@@") {
+      TokenKind::Heading
+    } else if line.starts_with("diff ") || line.starts_with("index ") {
+      TokenKind::Heading
+    } else if line.starts_with('+') {
+      TokenKind::Inserted
+    } else if line.starts_with('-') {
+      TokenKind::Deleted
+    } else {
+      TokenKind::Text
+    };
+    scanner.push(kind, line_len);
+  }
+  scanner.tokens
+}
+
+struct Scanner<'a> {
+  src: &'a str,
+  pos: usize,
+  tokens: Vec<Token<'a>>,
+}
+
+impl<'a> Scanner<'a> {
+  const fn new(src: &'a str) -> Self {
+    Self { src, pos: 0, tokens: Vec::new() }
+  }
+
+  fn rest(&self) -> &'a str {
+    &self.src[self.pos..]
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.rest().chars().next()
+  }
+
+  fn prev(&self) -> Option<char> {
+    self.src[..self.pos].chars().next_back()
+  }
+
+  fn push(&mut self, kind: TokenKind, len: usize) {
+    let len = len.min(self.src.len() - self.pos);
+    let text = &self.src[self.pos..self.pos + len];
+    self.pos += len;
+    match self.tokens.last_mut() {
+      // merge adjacent tokens of the same kind to minimize markup
+      Some(last) if last.kind == kind && kind == TokenKind::Text => {
+        let start = self.pos - len - last.text.len();
+        last.text = &self.src[start..self.pos];
+      }
+      _ => self.tokens.push(Token { kind, text }),
+    }
+  }
+
+  fn len_while(&self, predicate: impl Fn(char) -> bool) -> usize {
+    let rest = self.rest();
+    rest.find(|c| !predicate(c)).unwrap_or(rest.len())
+  }
+
+  fn len_until_eol(&self) -> usize {
+    self.len_while(|c| c != '\n')
+  }
+
+  fn at_line_start(&self) -> bool {
+    let line_start = self.src[..self.pos].rfind('\n').map_or(0, |idx| idx + 1);
+    self.src[line_start..self.pos].trim().is_empty()
+  }
+
+  /// is the token of `len` followed (after optional spaces) by `c`
+  fn followed_by(&self, len: usize, c: char) -> bool {
+    self.rest()[len..]
+      .trim_start_matches([' ', '\t'])
+      .starts_with(c)
+  }
+
+  /// like `followed_by`, but excluding things like urls, i.e. `http://`
+  fn key_follows(&self, len: usize, suffix: char) -> bool {
+    let after = self.rest()[len..].trim_start_matches([' ', '\t']);
+    let mut chars = after.chars();
+    chars.next() == Some(suffix)
+      && chars
+        .next()
+        .is_none_or(|c| c.is_whitespace() || suffix != ':')
+  }
+
+  fn symbol_allowed(&self) -> bool {
+    let next_is_ident = self.rest()[1..].starts_with(is_ident_start);
+    let prev_ok = self
+      .prev()
+      .is_none_or(|c| !c.is_alphanumeric() && c != ':' && c != '_');
+    next_is_ident && prev_ok
+  }
+}
+
+const OPERATORS: &str = "+-*/%=<>!&|^~?";
+const PUNCTUATION: &str = "()[]{},;.:";
+
+fn line_comment<'s>(syntax: &'s Syntax, scanner: &Scanner) -> Option<&'s str> {
+  let rest = scanner.rest();
+  let prefix = syntax
+    .line_comments
+    .iter()
+    .find(|prefix| rest.starts_with(*prefix))?;
+  // `#` and `;` comments must not start mid-word, e.g. `foo#bar`
+  if matches!(*prefix, "#" | ";") && scanner.prev().is_some_and(|c| !c.is_whitespace()) {
+    return None;
+  }
+  Some(prefix)
+}
+
+fn contains(words: &[&str], word: &str, syntax: &Syntax) -> bool {
+  if syntax.case_insensitive {
+    words.iter().any(|w| w.eq_ignore_ascii_case(word))
+  } else {
+    words.contains(&word)
+  }
+}
+
+fn is_ident_start(c: char) -> bool {
+  c.is_alphabetic() || c == '_'
+}
+
+fn ident_len(src: &str, extra: &str) -> usize {
+  src
+    .find(|c: char| !c.is_alphanumeric() && c != '_' && !extra.contains(c))
+    .unwrap_or(src.len())
+}
+
+fn string_len(src: &str, delim: &str) -> usize {
+  let mut chars = src[delim.len()..].char_indices();
+  while let Some((idx, c)) = chars.next() {
+    if c == '\\' {
+      chars.next();
+    } else if src[delim.len() + idx..].starts_with(delim) {
+      return delim.len() * 2 + idx;
+    }
+  }
+  src.len()
+}
+
+fn number_len(src: &str) -> usize {
+  let mut len = 0;
+  let bytes = src.as_bytes();
+  while len < bytes.len() {
+    let b = bytes[len];
+    if b.is_ascii_alphanumeric() || b == b'_' {
+      len += 1;
+    } else if b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit) {
+      len += 2;
+    } else {
+      break;
+    }
+  }
+  len
+}
+
+fn var_len(src: &str, prefixes: &[char]) -> usize {
+  let prefix_len = src.find(|c| !prefixes.contains(&c)).unwrap_or(src.len());
+  let rest = &src[prefix_len..];
+  if rest.starts_with('{') {
+    prefix_len + rest.find('}').map_or(rest.len(), |idx| idx + 1)
+  } else if rest.starts_with(|c: char| is_ident_start(c)) {
+    prefix_len + ident_len(rest, "")
+  } else if rest.starts_with(|c: char| c.is_ascii_digit() || "?!@#*-$".contains(c)) {
+    prefix_len + 1
+  } else {
+    prefix_len
+  }
+}
+
+fn is_decorator_start(src: &str) -> bool {
+  src.starts_with(is_ident_start) || src.starts_with('[') || src.starts_with("![")
+}
+
+fn decorator_len(src: &str) -> usize {
+  let rest = &src[1..];
+  if rest.starts_with('[') || rest.starts_with("![") {
+    let mut depth = 0;
+    for (idx, c) in rest.char_indices() {
+      match c {
+        '[' => depth += 1,
+        ']' if depth == 1 => return idx + 2,
+        ']' => depth -= 1,
+        '\n' => return idx + 1,
+        _ => {}
+      }
+    }
+    src.len()
+  } else {
+    1 + ident_len(rest, ".")
+  }
+}
+
+/// distinguishes `'a'` and `'\n'` from lifetimes like `'a`
+fn rust_char_literal_len(src: &str) -> Option<usize> {
+  let mut chars = src.char_indices().skip(1);
+  let (_, c) = chars.next()?;
+  if c == '\\' {
+    let close = src[2..].find('\'')?;
+    return Some(close + 3);
+  }
+  let (idx, close) = chars.next()?;
+  (close == '\'').then_some(idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use TokenKind::*;
+
+  fn tokens(lang: &str, src: &str) -> Vec<(TokenKind, std::string::String)> {
+    scan(Syntax::find(lang).unwrap(), src)
+      .into_iter()
+      .map(|t| (t.kind, t.text.to_string()))
+      .collect()
+  }
+
+  fn expect(lang: &str, src: &str, expected: &[(TokenKind, &str)]) {
+    let expected = expected
+      .iter()
+      .map(|(k, s)| (*k, s.to_string()))
+      .collect::<Vec<_>>();
+    assert_eq!(tokens(lang, src), expected, "lang: {lang}, src: {src}");
+  }
+
+  #[test]
+  fn test_scan_ruby() {
+    expect(
+      "ruby",
+      "puts \"hi\" # greet",
+      &[
+        (Builtin, "puts"),
+        (Text, " "),
+        (String, "\"hi\""),
+        (Text, " "),
+        (Comment, "# greet"),
+      ],
+    );
+    expect(
+      "ruby",
+      "def foo(:bar, @baz)",
+      &[
+        (Keyword, "def"),
+        (Text, " "),
+        (NameFunction, "foo"),
+        (Punctuation, "("),
+        (StringSymbol, ":bar"),
+        (Punctuation, ","),
+        (Text, " "),
+        (NameVariable, "@baz"),
+        (Punctuation, ")"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_rust() {
+    expect(
+      "rust",
+      "fn main<'a>() { println!(\"{}\", 'x'); } // done",
+      &[
+        (Keyword, "fn"),
+        (Text, " "),
+        (NameFunction, "main"),
+        (Operator, "<"),
+        (NameLabel, "'a"),
+        (Operator, ">"),
+        (Punctuation, "("),
+        (Punctuation, ")"),
+        (Text, " "),
+        (Punctuation, "{"),
+        (Text, " "),
+        (NameFunction, "println!"),
+        (Punctuation, "("),
+        (String, "\"{}\""),
+        (Punctuation, ","),
+        (Text, " "),
+        (StringChar, "'x'"),
+        (Punctuation, ")"),
+        (Punctuation, ";"),
+        (Text, " "),
+        (Punctuation, "}"),
+        (Text, " "),
+        (Comment, "// done"),
+      ],
+    );
+    expect(
+      "rust",
+      "#[derive(Debug)]\nlet x = 1..5;",
+      &[
+        (NameDecorator, "#[derive(Debug)]"),
+        (Text, "\n"),
+        (Keyword, "let"),
+        (Text, " x "),
+        (Operator, "="),
+        (Text, " "),
+        (Number, "1"),
+        (Punctuation, "."),
+        (Punctuation, "."),
+        (Number, "5"),
+        (Punctuation, ";"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_multiline_comment_and_preproc() {
+    expect(
+      "c",
+      "#include <stdio.h>\n/* a\nb */int x = 0x1F;",
+      &[
+        (CommentPreproc, "#include <stdio.h>"),
+        (Text, "\n"),
+        (CommentMultiline, "/* a\nb */"),
+        (KeywordType, "int"),
+        (Text, " x "),
+        (Operator, "="),
+        (Text, " "),
+        (Number, "0x1F"),
+        (Punctuation, ";"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_keys() {
+    expect(
+      "json",
+      r#"{"a": true}"#,
+      &[
+        (Punctuation, "{"),
+        (NameTag, r#""a""#),
+        (Punctuation, ":"),
+        (Text, " "),
+        (KeywordConstant, "true"),
+        (Punctuation, "}"),
+      ],
+    );
+    expect(
+      "yaml",
+      "url: http://x.com",
+      &[
+        (NameAttribute, "url"),
+        (Punctuation, ":"),
+        (Text, " http"),
+        (Punctuation, ":"),
+        (Operator, "//"),
+        (Text, "x.com"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_shell() {
+    expect(
+      "bash",
+      "echo ${HOME} foo#bar # note",
+      &[
+        (Builtin, "echo"),
+        (Text, " "),
+        (NameVariable, "${HOME}"),
+        (Text, " foo#bar "),
+        (Comment, "# note"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_sql_case_insensitive() {
+    expect(
+      "sql",
+      "SELECT * from t",
+      &[
+        (Keyword, "SELECT"),
+        (Text, " "),
+        (Operator, "*"),
+        (Text, " "),
+        (Keyword, "from"),
+        (Text, " t"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_markup() {
+    expect(
+      "xml",
+      "<a href=\"x\">&amp; b</a><!-- c -->",
+      &[
+        (NameTag, "<a"),
+        (Text, " "),
+        (NameAttribute, "href"),
+        (Operator, "="),
+        (String, "\"x\""),
+        (NameTag, ">"),
+        (NameEntity, "&amp;"),
+        (Text, " b"),
+        (NameTag, "</a"),
+        (NameTag, ">"),
+        (CommentMultiline, "<!-- c -->"),
+      ],
+    );
+  }
+
+  #[test]
+  fn test_scan_diff() {
+    expect(
+      "diff",
+      "@@ -1 +1 @@\n-a\n+b\n c",
+      &[
+        (Heading, "@@ -1 +1 @@\n"),
+        (Deleted, "-a\n"),
+        (Inserted, "+b\n"),
+        (Text, " c"),
+      ],
+    );
+  }
+}
@@ -0,0 +1,118 @@
+use ast::{Callout, Inline, InlineNodes, SpecialCharKind};
+
+use super::tokenize;
+
+/// the raw code of a source block, with callouts lifted out so that
+/// the code can be tokenized, to be re-inserted at the end of their lines
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct HighlightSource {
+  pub code: String,
+  /// (0-based line index, callout)
+  pub callouts: Vec<(usize, Callout)>,
+}
+
+impl HighlightSource {
+  /// returns `None` if the nodes contain anything other than verbatim
+  /// text and callouts (e.g. if `subs` enabled inline formatting), in which
+  /// case the block should be rendered without highlighting
+  pub fn from_nodes(nodes: &InlineNodes, include_tucks: bool) -> Option<Self> {
+    let mut source = Self::default();
+    let mut line = 0;
+    let mut line_has_callout = false;
+    for node in nodes.iter() {
+      match &node.content {
+        Inline::Text(text) if line_has_callout && text.trim().is_empty() => {}
+        Inline::Text(text) => source.code.push_str(text),
+        Inline::MultiCharWhitespace(ws) => source.code.push_str(ws),
+        Inline::SpecialChar(SpecialCharKind::Ampersand) => source.code.push('&'),
+        Inline::SpecialChar(SpecialCharKind::LessThan) => source.code.push('<'),
+        Inline::SpecialChar(SpecialCharKind::GreaterThan) => source.code.push('>'),
+        Inline::Newline => {
+          source.code.push('\n');
+          line += 1;
+          line_has_callout = false;
+        }
+        // the callout itself restores the space trimmed from the tuck
+        Inline::CalloutTuck(comment) if include_tucks => source.code.push_str(comment.trim_end()),
+        Inline::CalloutTuck(_) => {}
+        Inline::CalloutNum(callout) => {
+          source.callouts.push((line, *callout));
+          line_has_callout = true;
+        }
+        Inline::Discarded | Inline::LineComment(_) => {}
+        _ => return None,
+      }
+    }
+    Some(source)
+  }
+
+  pub fn line_callouts(&self, line_idx: usize) -> impl Iterator<Item = Callout> + '_ {
+    self
+      .callouts
+      .iter()
+      .filter(move |(idx, _)| *idx == line_idx)
+      .map(|(_, callout)| *callout)
+  }
+}
+
+/// tokenizes and renders `code` as html, one string per line, so
+/// that line numbers, highlighted lines and callouts can be interleaved
+pub fn highlight_lines(lang: Option<&str>, code: &str, class_prefix: &str) -> Vec<String> {
+  let mut lines = vec![String::new()];
+  for token in tokenize(lang, code) {
+    for (idx, segment) in token.text.split('\n').enumerate() {
+      if idx > 0 {
+        lines.push(String::new());
+      }
+      if segment.is_empty() {
+        continue;
+      }
+      let line = lines.last_mut().unwrap();
+      if let Some(class) = token.kind.class() {
+        line.push_str("<span class=\"");
+        line.push_str(class_prefix);
+        line.push_str(class);
+        line.push_str("\">");
+        push_escaped(line, segment);
+        line.push_str("</span>");
+      } else {
+        push_escaped(line, segment);
+      }
+    }
+  }
+  lines
+}
+
+fn push_escaped(buf: &mut String, s: &str) {
+  for c in s.chars() {
+    match c {
+      '&' => buf.push_str("&amp;"),
+      '<' => buf.push_str("&lt;"),
+      '>' => buf.push_str("&gt;"),
+      _ => buf.push(c),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_highlight_lines_splits_multiline_tokens() {
+    let lines = highlight_lines(Some("c"), "/* a\nb */ x < 1", "");
+    assert_eq!(
+      lines,
+      vec![
+        r#"<span class="cm">/* a</span>"#,
+        r#"<span class="cm">b */</span> x <span class="o">&lt;</span> <span class="m">1</span>"#,
+      ]
+    );
+  }
+
+  #[test]
+  fn test_highlight_lines_class_prefix() {
+    let lines = highlight_lines(Some("python"), "None", "tok-");
+    assert_eq!(lines, vec![r#"<span class="tok-kc">None</span>"#]);
+  }
+}
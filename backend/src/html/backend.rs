@@ -7,7 +7,12 @@ use asciidork_core::{DocType, JobAttr, Path, SafeMode, file, iff, regx};
 use ast::{AttrValue, ReadAttr, SpecialSection, prelude::*};
 
 use crate::{
-  html::{HtmlBuf, OpenTag},
+  Backend,
+  highlight::{self, HighlightSource, Highlighter},
+  html::{
+    HtmlBuf, OpenTag,
+    index::{IndexCatalog, IndexTermCapture},
+  },
   utils,
 };
 
@@ -24,6 +29,9 @@ pub struct BackendState {
   pub xref_depth: u8,
   #[allow(clippy::type_complexity)]
   pub footnotes: Rc<RefCell<Vec<(Option<String>, String)>>>,
+  pub index: Rc<RefCell<IndexCatalog>>,
+  pub index_term: Option<IndexTermCapture>,
+  pub index_offset: Option<usize>,
 }
 
 pub trait HtmlBackend: HtmlBuf {
@@ -760,6 +768,86 @@ pub trait HtmlBackend: HtmlBuf {
     self.push(["</h", &level_str, ">"]);
   }
 
+  /// starts cataloging an index term, false if the document has no index
+  fn start_index_term(&mut self) -> bool {
+    let state = self.state();
+    if !state.index.borrow().enabled
+      || state.xref_depth > 0
+      || state.ephemeral.contains(&EphemeralState::InTableOfContents)
+    {
+      return false;
+    }
+    let anchor = state.index.borrow_mut().next_anchor();
+    self.push([r#"<a id=""#, &anchor, r#""></a>"#]);
+    let anchor_end = self.htmlbuf().len();
+    self.state_mut().index_term = Some(IndexTermCapture {
+      anchor,
+      anchor_end,
+      text_start: anchor_end,
+      terms: Vec::new(),
+      see: None,
+      see_also: Vec::new(),
+    });
+    true
+  }
+
+  fn start_index_term_text(&mut self) {
+    let len = self.htmlbuf().len();
+    if let Some(capture) = self.state_mut().index_term.as_mut() {
+      capture.text_start = len;
+    }
+  }
+
+  fn end_index_term_text(&mut self) {
+    let Some(start) = self.state().index_term.as_ref().map(|c| c.text_start) else {
+      return;
+    };
+    let text = self.htmlbuf()[start..].to_string();
+    if let Some(capture) = self.state_mut().index_term.as_mut() {
+      capture.terms.push(text);
+    }
+  }
+
+  fn set_index_term_reference(&mut self, term_ref: &IndexTermReference) {
+    if let Some(capture) = self.state_mut().index_term.as_mut() {
+      capture.set_reference(term_ref);
+    }
+  }
+
+  /// concealed terms are only rendered to capture their text
+  fn end_index_term(&mut self, concealed: bool) {
+    let Some(capture) = self.state_mut().index_term.take() else {
+      return;
+    };
+    if concealed {
+      self.htmlbuf().truncate(capture.anchor_end);
+    }
+    self.state().index.borrow_mut().insert(capture);
+  }
+
+  fn enable_index(&mut self, document: &Document) {
+    if !self.state().in_asciidoc_table_cell {
+      self.state().index.borrow_mut().enabled = IndexCatalog::should_enable(document);
+    }
+  }
+
+  /// remembers where the index goes, it's rendered once all terms are seen
+  fn mark_index_section(&mut self, section: &Section) {
+    if section.meta.attrs.special_sect() == Some(SpecialSection::Index)
+      && self.state().index.borrow().enabled
+      && !self.state().in_asciidoc_table_cell
+    {
+      self.state_mut().index_offset = Some(self.htmlbuf().len());
+    }
+  }
+
+  fn render_index(&mut self) {
+    if let Some(offset) = self.state_mut().index_offset.take() {
+      let html = self.state().index.borrow().render();
+      self.htmlbuf().insert_str(offset, &html);
+    }
+  }
+
   fn standalone(&self) -> bool {
     self.doc_meta().get_doctype() != DocType::Inline
       && !self.state().in_asciidoc_table_cell
@@ -817,6 +905,60 @@ pub trait HtmlBackend: HtmlBuf {
     }
   }
 
+  fn render_highlighted_source(
+    &mut self,
+    source: &HighlightSource,
+    lang: Option<&str>,
+    highlighter: Highlighter,
+    block: &Block,
+  ) where
+    Self: Backend + Sized,
+  {
+    let attrs = &block.meta.attrs;
+    let start = attrs
+      .named("start")
+      .and_then(|start| start.parse::<usize>().ok())
+      .unwrap_or(1);
+    let highlighted = attrs
+      .named("highlight")
+      .map(|spec| highlight::parse_line_ranges(spec, start))
+      .unwrap_or_default();
+    let linenums = attrs.has_option("linenums")
+      || attrs.str_positional_at(2) == Some("linenums")
+      || self.doc_meta().is_true("source-linenums-option");
+    let lines = highlight::highlight_lines(lang, &source.code, highlighter.class_prefix());
+    if linenums {
+      self.push_str(r#"<table class="linenotable"><tbody><tr>"#);
+      self.push_str(r#"<td class="linenos gl"><pre class="lineno">"#);
+      for idx in 0..lines.len() {
+        if idx > 0 {
+          self.push_ch('\n');
+        }
+        self.push_str(&(start + idx).to_string());
+      }
+      self.push_str(r#"</pre></td><td class="code"><pre>"#);
+    }
+    for (idx, line) in lines.iter().enumerate() {
+      let is_highlighted = highlighted.binary_search(&(idx + 1)).is_ok();
+      if is_highlighted {
+        self.push_str(r#"<span class="hll">"#);
+      }
+      self.push_str(line);
+      for callout in source.line_callouts(idx) {
+        self.visit_callout(callout);
+      }
+      if idx + 1 < lines.len() {
+        self.push_ch('\n');
+      }
+      if is_highlighted {
+        self.push_str("</span>");
+      }
+    }
+    if linenums {
+      self.push_str("</pre></td></tr></tbody></table>");
+    }
+  }
+
   fn render_doc_title(&self) -> bool {
     !self.doc_meta().is_true("noheader") && self.doc_meta().show_doc_title()
   }
@@ -880,6 +1022,7 @@ pub enum EphemeralState {
   InQandaDescList,
   InDescListDesc,
   IsSourceBlock,
+  IsHighlightedSourceBlock,
   InBibliography,
   InGlossaryList,
   InAppendix,
@@ -147,7 +147,7 @@ pub trait AltHtmlBuf: HtmlBuf {
   }
 }
 
-fn push_escaped(src: &str, dest: &mut String, escape_quotes: bool) {
+pub(crate) fn push_escaped(src: &str, dest: &mut String, escape_quotes: bool) {
   let mut it = src.chars().peekable();
   let mut pos = 0;
   while let Some(c) = it.next() {
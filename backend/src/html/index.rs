@@ -0,0 +1,230 @@
+use asciidork_core::DocType;
+use ast::{IndexTermReference, SpecialSection, prelude::*};
+
+use super::htmlbuf::push_escaped;
+
+/// index terms collected while converting, rendered into the
+/// `[index]` section of a book once the whole document is seen
+#[derive(Debug, Default)]
+pub struct IndexCatalog {
+  pub enabled: bool,
+  num_anchors: usize,
+  entries: Vec<IndexEntry>,
+}
+
+#[derive(Debug)]
+struct IndexEntry {
+  term: String,
+  key: String,
+  anchors: Vec<String>,
+  see: Option<String>,
+  see_also: Vec<String>,
+  children: Vec<IndexEntry>,
+}
+
+/// an index term being converted, see `HtmlBackend::start_index_term`
+#[derive(Debug)]
+pub struct IndexTermCapture {
+  pub anchor: String,
+  pub anchor_end: usize,
+  pub text_start: usize,
+  pub terms: Vec<String>,
+  pub see: Option<String>,
+  pub see_also: Vec<String>,
+}
+
+impl IndexCatalog {
+  pub fn should_enable(document: &Document) -> bool {
+    if document.meta.get_doctype() != DocType::Book {
+      return false;
+    }
+    let is_index = |s: &Section| s.meta.attrs.special_sect() == Some(SpecialSection::Index);
+    match &document.content {
+      DocContent::Sections(sectioned) => sectioned.sections.iter().any(is_index),
+      DocContent::Parts(book) => {
+        book.opening_special_sects.iter().any(is_index)
+          || book.closing_special_sects.iter().any(is_index)
+          || book
+            .parts
+            .iter()
+            .any(|part| part.sections.iter().any(is_index))
+      }
+      DocContent::Blocks(_) => false,
+    }
+  }
+
+  pub fn next_anchor(&mut self) -> String {
+    self.num_anchors += 1;
+    format!("_indexterm_{}", self.num_anchors)
+  }
+
+  pub fn insert(&mut self, capture: IndexTermCapture) {
+    let mut entries = &mut self.entries;
+    let num_terms = capture.terms.len();
+    for (idx, term) in capture.terms.into_iter().enumerate() {
+      let key = sort_key(&term);
+      if key.is_empty() {
+        return;
+      }
+      let pos = match entries.iter().position(|entry| entry.key == key) {
+        Some(pos) => pos,
+        None => {
+          entries.push(IndexEntry {
+            term,
+            key,
+            anchors: Vec::new(),
+            see: None,
+            see_also: Vec::new(),
+            children: Vec::new(),
+          });
+          entries.len() - 1
+        }
+      };
+      if idx + 1 < num_terms {
+        entries = &mut entries[pos].children;
+        continue;
+      }
+      let entry = &mut entries[pos];
+      match capture.see {
+        // a `see` entry only points elsewhere, it has no locations
+        Some(see) => entry.see = Some(see),
+        None => entry.anchors.push(capture.anchor),
+      }
+      for see_also in capture.see_also {
+        if !entry.see_also.contains(&see_also) {
+          entry.see_also.push(see_also);
+        }
+      }
+      return;
+    }
+  }
+
+  pub fn render(&self) -> String {
+    let mut entries = self.entries.iter().collect::<Vec<_>>();
+    sort(&mut entries);
+    let ids = entries
+      .iter()
+      .enumerate()
+      .map(|(idx, entry)| (entry.key.as_str(), format!("_indexentry_{}", idx + 1)))
+      .collect::<Vec<_>>();
+    let mut html = String::from(r#"<div class="index">"#);
+    let mut category = None;
+    for (entry, (_, id)) in entries.iter().zip(&ids) {
+      let entry_category = category_of(&entry.key);
+      if category != Some(entry_category) {
+        if category.is_some() {
+          html.push_str("</ul></div>");
+        }
+        html.push_str(r#"<div class="index-category"><h3>"#);
+        html.push(entry_category);
+        html.push_str(r#"</h3><ul class="index-terms">"#);
+        category = Some(entry_category);
+      }
+      render_entry(entry, Some(id), &ids, &mut html);
+    }
+    if category.is_some() {
+      html.push_str("</ul></div>");
+    }
+    html.push_str("</div>");
+    html
+  }
+}
+
+impl IndexTermCapture {
+  pub fn set_reference(&mut self, term_ref: &IndexTermReference) {
+    match term_ref {
+      IndexTermReference::See(see) => self.see = Some(see.to_string()),
+      IndexTermReference::SeeAlso(see_also) => {
+        self.see_also = see_also.iter().map(|s| s.to_string()).collect();
+      }
+      IndexTermReference::None => {}
+    }
+  }
+}
+
+fn render_entry(entry: &IndexEntry, id: Option<&str>, ids: &[(&str, String)], html: &mut String) {
+  html.push_str("<li");
+  if let Some(id) = id {
+    html.push_str(r#" id=""#);
+    html.push_str(id);
+    html.push('"');
+  }
+  html.push_str(r#"><span class="index-term">"#);
+  html.push_str(&entry.term);
+  html.push_str("</span>");
+  for (idx, anchor) in entry.anchors.iter().enumerate() {
+    html.push_str(r##", <a href="#"##);
+    html.push_str(anchor);
+    html.push_str(r#"">"#);
+    html.push_str(&(idx + 1).to_string());
+    html.push_str("</a>");
+  }
+  if let Some(see) = &entry.see {
+    html.push_str(", <em>see</em> ");
+    push_reference(see, ids, html);
+  }
+  if !entry.see_also.is_empty() {
+    html.push_str(", <em>see also</em> ");
+    for (idx, see_also) in entry.see_also.iter().enumerate() {
+      if idx > 0 {
+        html.push_str(", ");
+      }
+      push_reference(see_also, ids, html);
+    }
+  }
+  if !entry.children.is_empty() {
+    let mut children = entry.children.iter().collect::<Vec<_>>();
+    sort(&mut children);
+    html.push_str("<ul>");
+    for child in children {
+      render_entry(child, None, ids, html);
+    }
+    html.push_str("</ul>");
+  }
+  html.push_str("</li>");
+}
+
+fn push_reference(term: &str, ids: &[(&str, String)], html: &mut String) {
+  let key = term.trim().to_lowercase();
+  match ids.iter().find(|(entry_key, _)| *entry_key == key) {
+    Some((_, id)) => {
+      html.push_str(r##"<a href="#"##);
+      html.push_str(id);
+      html.push_str(r#"">"#);
+      push_escaped(term, html, false);
+      html.push_str("</a>");
+    }
+    None => push_escaped(term, html, false),
+  }
+}
+
+fn sort(entries: &mut [&IndexEntry]) {
+  entries.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.term.cmp(&b.term)));
+}
+
+fn category_of(key: &str) -> char {
+  match key.chars().next() {
+    Some(c) if c.is_alphabetic() => c.to_uppercase().next().unwrap_or(c),
+    _ => '#',
+  }
+}
+
+/// the lowercased text of a rendered term, without markup
+fn sort_key(html: &str) -> String {
+  let mut text = String::with_capacity(html.len());
+  let mut in_tag = false;
+  for c in html.chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' if in_tag => in_tag = false,
+      _ if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+  text
+    .replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&amp;", "&")
+    .trim()
+    .to_lowercase()
+}
@@ -1,7 +1,9 @@
 pub mod backend;
 pub mod htmlbuf;
+pub mod index;
 pub mod list;
 pub mod open_tag;
+pub mod stem;
 pub mod table;
 pub mod util;
 pub mod video;
@@ -0,0 +1,116 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use ast::{DocumentMeta, ReadAttr, StemNotation};
+
+pub const fn inline_delimiters(notation: StemNotation) -> (&'static str, &'static str) {
+  match notation {
+    StemNotation::Asciimath => (r"\$", r"\$"),
+    StemNotation::Latexmath => (r"\(", r"\)"),
+  }
+}
+
+pub const fn block_delimiters(notation: StemNotation) -> (&'static str, &'static str) {
+  match notation {
+    StemNotation::Asciimath => (r"\$", r"\$"),
+    StemNotation::Latexmath => (r"\[", r"\]"),
+  }
+}
+
+/// wraps the rendered content of a stem block in display delimiters,
+/// unless the author already supplied them. multi-line asciimath is split
+/// into separate equations, per asciidoctor
+pub fn block_equation(notation: StemNotation, equation: &str) -> String {
+  let equation = equation.trim_end_matches('\n');
+  if equation.is_empty() {
+    return String::new();
+  }
+  let (open, close) = block_delimiters(notation);
+  let mut equation = if notation == StemNotation::Asciimath && equation.contains('\n') {
+    STEM_BREAK
+      .replace_all(equation, |caps: &regex::Captures| {
+        let breaks = caps[0].matches('\n').count();
+        format!("{close}{}\n{open}", "\n<br>".repeat(breaks))
+      })
+      .into_owned()
+  } else {
+    equation.to_string()
+  };
+  if !equation.starts_with(open) || !equation.ends_with(close) {
+    equation = format!("{open}{equation}{close}");
+  }
+  equation
+}
+
+/// the mathjax config and loader, appended to standalone documents
+/// that set the `stem` attribute
+pub fn mathjax_scripts(meta: &DocumentMeta) -> String {
+  let eqnums = match meta.str("eqnums") {
+    Some("") => "AMS",
+    Some(value) => value,
+    None => "none",
+  };
+  format!(
+    r#"<script type="text/x-mathjax-config">
+MathJax.Hub.Config({{
+  messageStyle: "none",
+  tex2jax: {{
+    inlineMath: [["\\(", "\\)"]],
+    displayMath: [["\\[", "\\]"]],
+    ignoreClass: "nostem|nolatexmath"
+  }},
+  asciimath2jax: {{
+    delimiters: [["\\$", "\\$"]],
+    ignoreClass: "nostem|noasciimath"
+  }},
+  TeX: {{ equationNumbers: {{ autoNumber: "{eqnums}" }} }}
+}})
+MathJax.Hub.Register.StartupHook("AsciiMath Jax Ready", function () {{
+  MathJax.InputJax.AsciiMath.postfilterHooks.Add(function (data, node) {{
+    if ((node = data.script.parentNode) && (node = node.parentNode) && node.classList.contains("stemblock")) {{
+      data.math.root.display = "block"
+    }}
+    return data
+  }})
+}})
+</script>
+<script src="https://cdnjs.cloudflare.com/ajax/libs/mathjax/2.7.9/MathJax.js?config=TeX-MML-AM_HTMLorMML"></script>"#
+  )
+}
+
+lazy_static! {
+  static ref STEM_BREAK: Regex = Regex::new(r" *\\\n(?:\\?\n)*|\n\n+").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_block_equation() {
+    let cases = vec![
+      (StemNotation::Asciimath, "sqrt(4) = 2", r"\$sqrt(4) = 2\$"),
+      (StemNotation::Latexmath, r"C = \alpha", r"\[C = \alpha\]"),
+      (
+        StemNotation::Latexmath,
+        r"\[C = \alpha\]",
+        r"\[C = \alpha\]",
+      ),
+      (StemNotation::Latexmath, "a\nb", "\\[a\nb\\]"),
+      (
+        StemNotation::Asciimath,
+        "a\n\nb",
+        "\\$a\\$\n<br>\n<br>\n\\$b\\$",
+      ),
+      (StemNotation::Asciimath, "a \\\nb", "\\$a\\$\n<br>\n\\$b\\$"),
+      (StemNotation::Asciimath, "", ""),
+    ];
+    for (notation, input, expected) in cases {
+      assert_eq!(
+        block_equation(notation, input),
+        expected,
+        "input: {input:?}"
+      );
+    }
+  }
+}
@@ -2,11 +2,14 @@ extern crate asciidork_ast as ast;
 extern crate asciidork_core as core;
 
 mod backend;
+pub mod highlight;
 pub mod html;
+mod postprocessor;
 pub mod time;
 pub mod utils;
 
 pub use backend::Backend;
+pub use postprocessor::{Postprocessor, postprocess};
 
 pub mod prelude {
   pub use super::Backend;
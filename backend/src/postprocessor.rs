@@ -0,0 +1,19 @@
+use core::DocumentMeta;
+
+/// a callback that rewrites a backend's converted output, e.g. to
+/// inject analytics or rewrite asset urls without re-parsing html
+pub trait Postprocessor {
+  fn process(&mut self, output: String, doc_meta: &DocumentMeta) -> Result<String, String>;
+}
+
+/// runs postprocessors in order, each seeing the output of the last
+pub fn postprocess(
+  mut output: String,
+  doc_meta: &DocumentMeta,
+  postprocessors: &mut [Box<dyn Postprocessor>],
+) -> Result<String, String> {
+  for postprocessor in postprocessors.iter_mut() {
+    output = postprocessor.process(output, doc_meta)?;
+  }
+  Ok(output)
+}
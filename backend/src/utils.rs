@@ -53,7 +53,7 @@ pub mod xref {
     href
   }
 
-  fn xref_path_was_included(path: &str, doc_meta: &DocumentMeta) -> bool {
+  pub fn xref_path_was_included(path: &str, doc_meta: &DocumentMeta) -> bool {
     if file::has_adoc_ext(path) {
       if Some(path) == doc_meta.str("asciidork-docfilename") {
         true
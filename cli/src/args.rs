@@ -1,16 +1,28 @@
-use asciidork_core::{DocType, JobAttr, JobAttrs, JobSettings, SafeMode};
+use asciidork_core::{DocType, JobAttr, JobAttrs, JobSettings, SafeMode, Severity};
 use clap::Parser;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::str::FromStr;
 
+use crate::remote::UriOptions;
+
 #[derive(Parser, Debug, Clone)]
 #[command(version, about = "🤓 Asciidork CLI")]
 #[command(name = "asciidork", bin_name = "asciidork")]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Args {
+  #[command(subcommand)]
+  pub command: Option<Subcommand>,
+
   #[clap(short, long, help = "The file path to parse - omit to read from stdin")]
   pub input: Option<std::path::PathBuf>,
 
+  #[clap(long, conflicts_with = "input", value_name = "NAME")]
+  #[clap(
+    help = "Virtual filename of a document read from stdin, for diagnostics and docname/docfile"
+  )]
+  pub input_name: Option<String>,
+
   #[clap(short, long, default_value = "dr-html")]
   #[clap(help = "Select output format")]
   pub format: Output,
@@ -39,11 +51,84 @@ pub struct Args {
   pub embedded: bool,
 
   #[clap(long, default_value = "false")]
+  #[clap(help = "Stop at the first diagnostic at or above the failure level")]
   pub strict: bool,
 
+  #[arg(value_parser = Severity::from_str)]
+  #[clap(long, default_value = "warn")]
+  #[clap(help = "Report diagnostics at or above this level: info, warn, error or fatal")]
+  pub log_level: Severity,
+
+  #[arg(value_parser = Severity::from_str)]
+  #[clap(long)]
+  #[clap(
+    help = "Fail if a diagnostic at or above this level is reported (default with --strict: warn)"
+  )]
+  pub failure_level: Option<Severity>,
+
+  #[arg(value_parser = parse_block_processor)]
+  #[clap(long = "block-processor", value_name = "NAME=COMMAND")]
+  #[clap(
+    help = "Pipe the lines of blocks styled [NAME] through a shell command, passing its output through - may be set more than once"
+  )]
+  pub block_processors: Vec<(String, String)>,
+
+  #[clap(long = "postprocessor", value_name = "COMMAND")]
+  #[clap(
+    help = "Pipe the converted (and --self-contained embedded) output through a shell command before writing it - may be set more than once"
+  )]
+  pub postprocessors: Vec<String>,
+
+  #[clap(long, default_value = "false")]
+  #[clap(
+    help = "Inline every image, icon, stylesheet and font into a single html file (requires --safe-mode safe or lower)"
+  )]
+  pub self_contained: bool,
+
+  #[clap(long, default_value = "10485760", value_name = "BYTES")]
+  #[clap(help = "Largest single asset --self-contained will inline")]
+  pub max_embed_size: usize,
+
+  #[clap(long, value_name = "DIR")]
+  #[clap(
+    help = "Directory for cached remote uris, used with `cache-uri` (default: ~/.cache/asciidork/uri)"
+  )]
+  pub uri_cache_dir: Option<std::path::PathBuf>,
+
+  #[clap(long, default_value = "30", value_name = "SECONDS")]
+  #[clap(help = "Timeout for reading remote uris")]
+  pub uri_timeout: u64,
+
+  #[clap(long, default_value = "10485760", value_name = "BYTES")]
+  #[clap(help = "Largest remote uri response to accept")]
+  pub uri_max_bytes: usize,
+
+  #[clap(long = "allow-host", value_name = "HOST")]
+  #[clap(
+    help = "Only read remote uris from this host or its subdomains, without following redirects - may be set more than once"
+  )]
+  pub allow_hosts: Vec<String>,
+
+  #[clap(long = "deny-host", value_name = "HOST")]
+  #[clap(
+    help = "Never read remote uris from this host or its subdomains, and don't follow redirects - may be set more than once"
+  )]
+  pub deny_hosts: Vec<String>,
+
+  #[clap(long, default_value = "false")]
+  #[clap(help = "Serve remote uris only from the cache, never the network")]
+  pub offline: bool,
+
+  #[clap(long, help = "Wrap column for text formats (default: $COLUMNS or 80)")]
+  pub width: Option<usize>,
+
   #[clap(long, default_value = "false")]
   pub json_errors: bool,
 
+  #[clap(long, default_value = "false", conflicts_with = "json_errors")]
+  #[clap(help = "Print diagnostics to stderr as a SARIF 2.1 log")]
+  pub sarif: bool,
+
   #[clap(
     short = 'B',
     long,
@@ -51,23 +136,125 @@ pub struct Args {
   )]
   pub base_dir: Option<std::path::PathBuf>,
 
+  #[clap(long, default_value = "false", requires = "input")]
+  #[clap(help = "Convert again whenever the input, its includes, docinfo or stylesheet change")]
+  pub watch: bool,
+
+  #[clap(long, default_value = "false", requires = "watch")]
+  #[clap(help = "Serve the converted html locally, reloading the page on change")]
+  pub serve: bool,
+
+  #[clap(long, default_value = "8000", help = "Port for --serve")]
+  pub port: u16,
+
   #[clap(short = 't', long, default_value = "false")]
   #[clap(help = "Print timing/perf info\n")]
   pub print_timings: bool,
 }
 
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum Subcommand {
+  #[command(about = "Format asciidoc source files in place")]
+  Fmt(FmtArgs),
+  #[command(about = "Convert a directory of asciidoc files to html, checking cross references")]
+  Build(BuildArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct FmtArgs {
+  #[clap(help = "The file paths to format - omit to format stdin to stdout")]
+  pub files: Vec<std::path::PathBuf>,
+
+  #[clap(long, default_value = "false")]
+  #[clap(help = "Check that files are formatted, without writing them")]
+  pub check: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct BuildArgs {
+  #[clap(help = "Source directory - files and directories starting with `_` or `.` are skipped")]
+  pub src_dir: std::path::PathBuf,
+
+  #[clap(help = "Output directory, mirroring the structure of the source directory")]
+  pub out_dir: std::path::PathBuf,
+
+  #[arg(value_parser = parse_attr)]
+  #[clap(short, long = "attribute")]
+  #[clap(help = "Set a document attribute for every document - may be set more than once")]
+  pub attributes: Vec<(String, JobAttr)>,
+
+  #[arg(value_parser = SafeMode::from_str)]
+  #[clap(short, long, default_value = "safe")]
+  #[clap(help = "Set safe mode explicitly")]
+  pub safe_mode: SafeMode,
+
+  #[clap(long, default_value = "false")]
+  #[clap(
+    help = "Stop converting a document at its first diagnostic at or above the failure level"
+  )]
+  pub strict: bool,
+
+  #[arg(value_parser = Severity::from_str)]
+  #[clap(long, default_value = "warn")]
+  #[clap(help = "Report diagnostics at or above this level: info, warn, error or fatal")]
+  pub log_level: Severity,
+
+  #[arg(value_parser = Severity::from_str)]
+  #[clap(long)]
+  #[clap(
+    help = "Fail if a diagnostic at or above this level is reported (default with --strict: warn)"
+  )]
+  pub failure_level: Option<Severity>,
+
+  #[clap(long, default_value = "false")]
+  #[clap(help = "Print diagnostics to stderr as a SARIF 2.1 log")]
+  pub sarif: bool,
+
+  #[clap(
+    short,
+    long,
+    help = "Number of documents to convert at once (default: number of cpus)"
+  )]
+  pub jobs: Option<usize>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum Output {
   DrHtml,
   DrHtmlPrettier,
   Html5,
   Html5Prettier,
+  Docbook5,
+  Manpage,
+  Markdown,
+  Text,
+  Terminal,
+  AsgJson,
+  AstJson,
+}
+
+impl Output {
+  pub const fn is_html(self) -> bool {
+    matches!(
+      self,
+      Output::DrHtml | Output::DrHtmlPrettier | Output::Html5 | Output::Html5Prettier
+    )
+  }
 }
 
 lazy_static! {
   pub static ref ATTR_RE: Regex = Regex::new(r"(!)?(@)?(\w(?:[\w-]*))(!)?(@)?(?:=(.+))?").unwrap();
 }
 
+fn parse_block_processor(input: &str) -> Result<(String, String), &'static str> {
+  match input.split_once('=') {
+    Some((name, command)) if !name.trim().is_empty() && !command.trim().is_empty() => {
+      Ok((name.trim().to_string(), command.to_string()))
+    }
+    _ => Err("Expected NAME=COMMAND"),
+  }
+}
+
 fn parse_attr(input: &str) -> Result<(String, JobAttr), &'static str> {
   let captures = ATTR_RE.captures(input).ok_or("Invalid attribute")?;
   let key = captures.get(3).unwrap().as_str().to_lowercase().to_string();
@@ -112,6 +299,20 @@ fn test_parse_job_attr() {
   }
 }
 
+impl From<&Args> for UriOptions {
+  fn from(args: &Args) -> Self {
+    let defaults = UriOptions::default();
+    UriOptions {
+      cache_dir: args.uri_cache_dir.clone().unwrap_or(defaults.cache_dir),
+      timeout: args.uri_timeout,
+      max_bytes: args.uri_max_bytes,
+      allow_hosts: args.allow_hosts.clone(),
+      deny_hosts: args.deny_hosts.clone(),
+      offline: args.offline,
+    }
+  }
+}
+
 impl TryFrom<Args> for JobSettings {
   type Error = String;
   fn try_from(args: Args) -> Result<Self, Self::Error> {
@@ -120,6 +321,8 @@ impl TryFrom<Args> for JobSettings {
       doctype: args.doctype,
       embedded: args.embedded,
       strict: args.strict,
+      log_level: args.log_level,
+      failure_level: args.failure_level.unwrap_or(Severity::Warn),
       job_attrs: JobAttrs::empty(),
     };
     for (key, attr) in args.attributes {
@@ -0,0 +1,33 @@
+use asciidork_parser::{BlockProcessor, BlockProcessorInput, BlockProcessorOutput};
+
+use crate::shell::pipe_through;
+
+/// a block processor that pipes the block's lines through a shell
+/// command, passing its stdout through to the output as-is
+#[derive(Debug, Clone)]
+pub struct CommandProcessor {
+  command: String,
+}
+
+impl CommandProcessor {
+  pub const fn new(command: String) -> Self {
+    Self { command }
+  }
+}
+
+impl BlockProcessor for CommandProcessor {
+  fn process<'arena>(
+    &mut self,
+    input: BlockProcessorInput<'_, 'arena>,
+  ) -> Result<BlockProcessorOutput<'arena>, String> {
+    let mut lines = input.lines.join("\n");
+    lines.push('\n');
+    let env = [("ASCIIDORK_BLOCK_NAME", input.name)];
+    let stdout = pipe_through(&self.command, &env, lines)?;
+    Ok(BlockProcessorOutput::Raw(stdout.trim_end().to_string()))
+  }
+
+  fn clone_box(&self) -> Box<dyn BlockProcessor> {
+    Box::new(self.clone())
+  }
+}
@@ -0,0 +1,339 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path as StdPath, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bumpalo::Bump;
+
+use asciidork_ast::{BlockContent, EmptyMetadata, Inline, MacroNode};
+use asciidork_backend::Backend;
+use asciidork_backend::utils::xref;
+use asciidork_core::{JobAttrs, JobSettings, ReadAttr, Severity, file};
+use asciidork_dr_html_backend::AsciidoctorHtml;
+use asciidork_parser::prelude::*;
+
+use crate::args::BuildArgs;
+use crate::resolver::CliResolver;
+
+pub fn run(args: &BuildArgs, mut stderr: impl Write) -> Result<(), Box<dyn Error>> {
+  let src_dir = dunce::canonicalize(&args.src_dir)?;
+  fs::create_dir_all(&args.out_dir)?;
+  let out_dir = dunce::canonicalize(&args.out_dir)?;
+  let mut sources = Vec::new();
+  collect_sources(&src_dir, &out_dir, &mut sources)?;
+  sources.sort();
+
+  let mut job_attrs = JobAttrs::empty();
+  for (key, attr) in &args.attributes {
+    job_attrs.insert(key.clone(), attr.clone())?;
+  }
+  AsciidoctorHtml::set_job_attrs(&mut job_attrs);
+  let settings = JobSettings {
+    safe_mode: args.safe_mode,
+    doctype: None,
+    embedded: false,
+    strict: args.strict,
+    log_level: args.log_level,
+    failure_level: args.failure_level.unwrap_or(Severity::Warn),
+    job_attrs,
+  };
+
+  let num_jobs = args
+    .jobs
+    .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+    .unwrap_or(1)
+    .clamp(1, sources.len().max(1));
+  let next = AtomicUsize::new(0);
+  let reports = Mutex::new(Vec::with_capacity(sources.len()));
+  thread::scope(|scope| {
+    for _ in 0..num_jobs {
+      scope.spawn(|| {
+        loop {
+          let idx = next.fetch_add(1, Ordering::Relaxed);
+          let Some(path) = sources.get(idx) else {
+            break;
+          };
+          let report = build_doc(path, &src_dir, &out_dir, &settings);
+          reports.lock().unwrap().push(report);
+        }
+      });
+    }
+  });
+  let mut reports = reports.into_inner().unwrap();
+  reports.sort_by(|a, b| a.path.cmp(&b.path));
+
+  let docs: HashMap<&PathBuf, &DocReport> = reports.iter().map(|r| (&r.path, r)).collect();
+  let mut failed = 0;
+  let mut broken = 0;
+  let mut at_failure_level = 0;
+  let mut copied = HashSet::new();
+  let mut sarif_diagnostics = Vec::new();
+  for report in &reports {
+    if !report.errors.is_empty() {
+      failed += 1;
+    }
+    for error in &report.errors {
+      writeln!(stderr, "ERROR: {}: {error}", report.path.display())?;
+    }
+    if let Some(level) = args.failure_level {
+      let logged = report.diagnostics.iter().filter(|d| d.severity >= level);
+      // diagnostics below the log level still count, once per document
+      let unlogged = report
+        .highest_severity
+        .is_some_and(|severity| severity >= level);
+      at_failure_level += logged.count().max(usize::from(unlogged));
+    }
+    let mut diagnostics = report.diagnostics.clone();
+    for link in &report.links {
+      let message = match (docs.get(&link.path), &link.id) {
+        (None, _) => "no document found",
+        // a document that failed to build may be missing anchors
+        (Some(doc), Some(id)) if doc.errors.is_empty() && !doc.anchors.contains(id) => {
+          "no anchor found"
+        }
+        _ => continue,
+      };
+      let mut diagnostic = link.diagnostic.clone();
+      diagnostic.message = format!("Invalid cross reference, {message} for `{}`", link.target);
+      diagnostics.push(diagnostic);
+    }
+    broken += diagnostics.len() - report.diagnostics.len();
+    if args.sarif {
+      sarif_diagnostics.extend(diagnostics);
+    } else {
+      crate::print_human_diagnostics(&mut stderr, diagnostics);
+    }
+    for image in &report.images {
+      if !copied.insert(image) || !image.is_file() {
+        continue;
+      }
+      let Ok(rel) = image.strip_prefix(&src_dir) else {
+        continue;
+      };
+      let dest = out_dir.join(rel);
+      if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+      }
+      fs::copy(image, dest)?;
+    }
+  }
+
+  if args.sarif {
+    writeln!(stderr, "{:#}", crate::sarif::log(&sarif_diagnostics))?;
+  }
+  if failed > 0 {
+    return Err(format!("{failed} document(s) failed to build").into());
+  }
+  if broken > 0 {
+    return Err(format!("{broken} broken cross reference(s)").into());
+  }
+  if let Some(level) = args.failure_level.filter(|_| at_failure_level > 0) {
+    return Err(
+      format!(
+        "{at_failure_level} diagnostic(s) at or above failure level `{}`",
+        level.to_str()
+      )
+      .into(),
+    );
+  }
+  Ok(())
+}
+
+#[derive(Debug)]
+struct DocReport {
+  path: PathBuf,
+  anchors: HashSet<String>,
+  links: Vec<InterdocLink>,
+  images: Vec<PathBuf>,
+  diagnostics: Vec<Diagnostic>,
+  highest_severity: Option<Severity>,
+  errors: Vec<String>,
+}
+
+/// an xref to another document in the tree, checked once every
+/// document has been parsed
+#[derive(Debug)]
+struct InterdocLink {
+  target: String,
+  path: PathBuf,
+  id: Option<String>,
+  diagnostic: Diagnostic,
+}
+
+fn build_doc(
+  path: &StdPath,
+  src_dir: &StdPath,
+  out_dir: &StdPath,
+  settings: &JobSettings,
+) -> DocReport {
+  let mut report = DocReport {
+    path: path.to_path_buf(),
+    anchors: HashSet::new(),
+    links: Vec::new(),
+    images: Vec::new(),
+    diagnostics: Vec::new(),
+    highest_severity: None,
+    errors: Vec::new(),
+  };
+  if let Err(err) = convert_doc(&mut report, src_dir, out_dir, settings) {
+    report.errors.push(err.to_string());
+  }
+  report
+}
+
+fn convert_doc(
+  report: &mut DocReport,
+  src_dir: &StdPath,
+  out_dir: &StdPath,
+  settings: &JobSettings,
+) -> Result<(), Box<dyn Error>> {
+  let src = fs::read_to_string(&report.path)?;
+  let doc_dir = report.path.parent().unwrap_or(src_dir).to_path_buf();
+  let input_mtime = fs::metadata(&report.path)
+    .and_then(|metadata| metadata.modified())
+    .ok()
+    .map(|mtime| mtime.duration_since(UNIX_EPOCH).unwrap().as_secs());
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs();
+
+  let bump = &Bump::with_capacity(src.len() * 2);
+  let src_file = SourceFile::Path(report.path.clone().into());
+  let mut parser = Parser::from_str(&src, src_file, bump);
+  parser.apply_job_settings(settings.clone());
+  parser.set_resolver(Box::new(CliResolver::new(
+    Some(doc_dir.clone()),
+    settings.strict,
+  )));
+  parser.set_check_interdoc_xrefs(false);
+  parser.provide_timestamps(now, input_mtime, None);
+
+  let mut result = match parser.parse() {
+    Ok(result) => result,
+    Err(diagnostics) => {
+      report.highest_severity = diagnostics.iter().map(|d| d.severity).max();
+      report.diagnostics = diagnostics;
+      return Err("Parse error".into());
+    }
+  };
+  report.diagnostics = std::mem::take(&mut result.warnings);
+  report.highest_severity = result.highest_severity;
+  report.anchors = result
+    .document
+    .anchors
+    .borrow()
+    .keys()
+    .map(|id| id.to_string())
+    .collect();
+
+  crate::css::resolve(&mut result.document)?;
+  let meta = result.document.meta.clone();
+  let mut xrefs = Vec::new();
+  let mut images = Vec::new();
+  result.document.for_each_inlines_mut(|inlines| {
+    for node in inlines.iter() {
+      match &node.content {
+        Inline::Macro(MacroNode::Xref { target, kind, .. }) if xref::is_interdoc(target, *kind) => {
+          xrefs.push((target.src.to_string(), target.loc));
+        }
+        Inline::Macro(MacroNode::InlineImage { target, .. }) => images.push(target.to_string()),
+        _ => {}
+      }
+    }
+  });
+  result.document.for_each_block_mut(|block| {
+    if let BlockContent::Empty(EmptyMetadata::Image { target, .. }) = &block.content {
+      images.push(target.to_string());
+    }
+  });
+
+  for (target, loc) in xrefs {
+    let (target_path, id) = match target.split_once('#') {
+      Some((target_path, id)) => (target_path, Some(id).filter(|id| !id.is_empty())),
+      None => (target.as_str(), None),
+    };
+    // only links to asciidoc sources are checked, other files are
+    // not part of the build, and self/included links are diagnosed
+    // by the parser
+    if file::ext(target_path).is_some_and(|_| !file::has_adoc_ext(target_path))
+      || xref::xref_path_was_included(target_path, &meta)
+    {
+      continue;
+    }
+    let mut target_path = doc_dir.join(target_path);
+    if target_path.extension().is_none() {
+      target_path.set_extension("adoc");
+    }
+    report.links.push(InterdocLink {
+      diagnostic: result.diagnostic_at(DiagnosticCode::XrefInvalid, "", loc),
+      path: normalize(&target_path),
+      id: id.map(str::to_string),
+      target,
+    });
+  }
+
+  let imagesdir = meta.str("imagesdir").unwrap_or("");
+  for target in images {
+    if target.contains("://") || target.starts_with("data:") || target.starts_with('/') {
+      continue;
+    }
+    report
+      .images
+      .push(normalize(&doc_dir.join(imagesdir).join(target)));
+  }
+
+  let html = asciidork_dr_html_backend::convert(result.document)?;
+  let rel = report.path.strip_prefix(src_dir)?;
+  // matches the suffix of interdocument xref hrefs
+  let suffix = meta.str_or("outfilesuffix", ".html");
+  let dest = out_dir
+    .join(rel)
+    .with_extension(suffix.trim_start_matches('.'));
+  if let Some(parent) = dest.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  fs::write(dest, html)?;
+  Ok(())
+}
+
+fn collect_sources(
+  dir: &StdPath,
+  out_dir: &StdPath,
+  acc: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if name.starts_with('_') || name.starts_with('.') || path == out_dir {
+      continue;
+    }
+    if path.is_dir() {
+      collect_sources(&path, out_dir, acc)?;
+    } else if file::has_adoc_ext(&name) {
+      acc.push(path);
+    }
+  }
+  Ok(())
+}
+
+/// resolves `.` and `..` without touching the filesystem,
+/// so links to missing documents can still be compared
+fn normalize(path: &StdPath) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        normalized.pop();
+      }
+      _ => normalized.push(component),
+    }
+  }
+  normalized
+}
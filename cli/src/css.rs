@@ -1,5 +1,5 @@
 use asciidork_ast::Document;
-use asciidork_core::{Path, ReadAttr, SafeMode};
+use asciidork_core::{DocumentMeta, Path, ReadAttr, SafeMode};
 
 pub fn resolve(document: &mut Document) -> Result<(), String> {
   let attrs = document.meta.header_attrs();
@@ -50,3 +50,15 @@ pub fn resolve(document: &mut Document) -> Result<(), String> {
     .unwrap();
   Ok(())
 }
+
+/// the custom stylesheet `resolve` would read, if any
+pub fn stylesheet_path(meta: &DocumentMeta) -> Option<std::path::PathBuf> {
+  let attrs = meta.header_attrs();
+  let custom_filename = attrs.str("stylesheet").filter(|name| !name.is_empty())?;
+  let cwd = std::env::current_dir().ok()?;
+  Some(
+    cwd
+      .join(attrs.str_or("stylesdir", "."))
+      .join(custom_filename),
+  )
+}
@@ -10,6 +10,8 @@ pub struct DiagnosticError {
   pub column_num_start: u32,
   pub column_num_end: u32,
   pub source_file: String,
+  pub code: String,
+  pub severity: String,
 }
 
 impl From<Diagnostic> for DiagnosticError {
@@ -21,6 +23,8 @@ impl From<Diagnostic> for DiagnosticError {
       column_num_start: diagnostic.underline_start,
       column_num_end: diagnostic.underline_start + diagnostic.underline_width,
       source_file: diagnostic.source_file.file_name().to_string(),
+      code: diagnostic.code.to_str().to_string(),
+      severity: diagnostic.severity.to_str().to_string(),
     }
   }
 }
@@ -0,0 +1,63 @@
+use std::error::Error;
+use std::fs;
+use std::io::{Read, Write};
+
+use asciidork_parser::prelude::*;
+
+use crate::args::FmtArgs;
+
+pub fn run(
+  args: &FmtArgs,
+  mut stdin: impl Read,
+  mut stdout: impl Write,
+  mut stderr: impl Write,
+) -> Result<(), Box<dyn Error>> {
+  if args.files.is_empty() {
+    let mut src = String::new();
+    stdin.read_to_string(&mut src)?;
+    let cwd = Path::new(std::env::current_dir()?.to_string_lossy());
+    let Some(formatted) = format(&src, SourceFile::Stdin { cwd, name: None }, &mut stderr) else {
+      return Err("Parse error".into());
+    };
+    if args.check {
+      if formatted != src {
+        return Err("stdin is not formatted".into());
+      }
+    } else {
+      write!(stdout, "{formatted}")?;
+    }
+    return Ok(());
+  }
+
+  let mut unformatted = 0;
+  for path in &args.files {
+    let src = fs::read_to_string(path)?;
+    let abspath = dunce::canonicalize(path)?;
+    let Some(formatted) = format(&src, SourceFile::Path(abspath.into()), &mut stderr) else {
+      return Err("Parse error".into());
+    };
+    if formatted == src {
+      continue;
+    }
+    if args.check {
+      writeln!(stderr, "Not formatted: {}", path.display())?;
+      unformatted += 1;
+    } else {
+      fs::write(path, formatted)?;
+    }
+  }
+  if unformatted > 0 {
+    return Err(format!("{unformatted} file(s) not formatted").into());
+  }
+  Ok(())
+}
+
+fn format(src: &str, file: SourceFile, stderr: &mut impl Write) -> Option<String> {
+  match asciidork_fmt::format(src, file) {
+    Ok(formatted) => Some(formatted),
+    Err(diagnostics) => {
+      crate::print_human_diagnostics(stderr, diagnostics);
+      None
+    }
+  }
+}
@@ -1,5 +1,6 @@
 use std::env;
 use std::io::{Read, Write};
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::result::Result;
 use std::time::{Duration, Instant, SystemTime};
@@ -9,18 +10,35 @@ use bumpalo::Bump;
 use clap::Parser as ClapParser;
 use colored::*;
 
-use asciidork_core::{JobSettings, Path};
+use asciidork_backend::Postprocessor;
+use asciidork_backend_docbook5::Docbook5;
+use asciidork_backend_manpage::Manpage;
+use asciidork_backend_markdown::Markdown;
+use asciidork_backend_text::{Text, TextOptions};
+use asciidork_core::{DocType, DocumentMeta, JobSettings, Path, Severity};
 use asciidork_dr_html_backend::*;
 use asciidork_parser::prelude::*;
 
 mod args;
+mod block_processor;
+mod build;
 mod css;
 mod error;
+mod fmt;
+mod postprocessor;
+mod remote;
 mod resolver;
+mod sarif;
+mod self_contained;
+mod shell;
+mod watch;
 
-use args::{Args, Output};
+use args::{Args, Output, Subcommand};
+use block_processor::CommandProcessor;
 use error::DiagnosticError;
+use postprocessor::CommandPostprocessor;
 use resolver::CliResolver;
+use self_contained::Embedder;
 
 fn main() -> Result<(), Box<dyn Error>> {
   let args = Args::parse();
@@ -33,74 +51,29 @@ fn run(
   mut stdout: impl Write,
   mut stderr: impl Write,
 ) -> Result<(), Box<dyn Error>> {
-  let (src, src_file, base_dir, input_mtime) = {
-    if let Some(pathbuf) = &args.input {
-      let abspath = dunce::canonicalize(pathbuf)?;
-      let mut file = fs::File::open(pathbuf.clone())?;
-      let mut input_mtime = None;
-      let mut src = file
-        .metadata()
-        .ok()
-        .map(|metadata| {
-          if let Ok(mtime) = metadata.modified() {
-            input_mtime = Some(mtime.duration_since(UNIX_EPOCH).unwrap().as_secs());
-          }
-          String::with_capacity(metadata.len() as usize)
-        })
-        .unwrap_or_else(String::new);
-      // TODO: for perf, better to read the file straight into a BumpVec<u8>
-      // have an initializer on Parser that takes ownership of it
-      file.read_to_string(&mut src)?;
-      let base_dir = args
-        .base_dir
-        .as_ref()
-        .cloned()
-        .or_else(|| abspath.parent().map(|p| p.to_path_buf()));
-      (src, SourceFile::Path(abspath.into()), base_dir, input_mtime)
-    } else {
-      let mut src = String::new();
-      stdin.read_to_string(&mut src)?;
-      let cwd_buf = env::current_dir()?;
-      let cwd = Path::new(cwd_buf.to_str().unwrap_or(""));
-      (src, SourceFile::Stdin { cwd }, Some(cwd_buf), None)
-    }
-  };
+  match &args.command {
+    Some(Subcommand::Fmt(fmt_args)) => return fmt::run(fmt_args, stdin, stdout, stderr),
+    Some(Subcommand::Build(build_args)) => return build::run(build_args, stderr),
+    None => {}
+  }
+  if args.watch {
+    return watch::run(&args, stdout, stderr);
+  }
+  let Input { src, src_file, base_dir, mtime } = read_input(&args, &mut stdin)?;
 
   let parse_start = Instant::now();
   let bump = &Bump::with_capacity(src.len() * 2);
-  let strict = args.strict;
-  let mut parser = Parser::from_str(&src, src_file, bump);
-  let mut job_settings: JobSettings = args.clone().try_into()?;
-  AsciidoctorHtml::set_job_attrs(&mut job_settings.job_attrs);
-  parser.apply_job_settings(job_settings);
-  parser.set_resolver(Box::new(CliResolver::new(base_dir, strict)));
-
-  let now = SystemTime::now()
-    .duration_since(UNIX_EPOCH)
-    .unwrap()
-    .as_secs();
-  parser.provide_timestamps(now, input_mtime, None);
-
+  let resolver = resolver(&args, base_dir.clone());
+  let parser = parser(&args, &src, src_file, resolver, mtime, bump)?;
   let result = parser.parse();
   let parse_time = parse_start.elapsed();
 
   match result {
     Ok(mut parse_result) => {
+      let warnings = std::mem::take(&mut parse_result.warnings);
+      let highest_severity = parse_result.highest_severity;
       let convert_start = Instant::now();
-      if let Err(css_err) = css::resolve(&mut parse_result.document) {
-        writeln!(stderr, "ERROR: {css_err}")?;
-        if args.strict {
-          std::process::exit(1);
-        }
-      }
-      let mut html = match &args.format {
-        Output::DrHtml | Output::DrHtmlPrettier => {
-          asciidork_dr_html_backend::convert(parse_result.document)?
-        }
-        Output::Html5 | Output::Html5Prettier => {
-          asciidork_backend_html5s::convert(parse_result.document)?
-        }
-      };
+      let mut html = convert(&args, parse_result, &src, base_dir, &mut stderr)?;
       let convert_time = convert_start.elapsed();
       let prettify = args.format == Output::Html5Prettier || args.format == Output::DrHtmlPrettier;
       if prettify {
@@ -114,6 +87,7 @@ fn run(
         }
         writeln!(stdout, "{html}")?;
       }
+      report_warnings(&args, &mut stderr, warnings, highest_severity)?;
       if args.print_timings {
         if !prettify {
           writeln!(stderr)?;
@@ -125,6 +99,9 @@ fn run(
       if args.json_errors {
         print_json_diagnostics(&mut stderr, diagnostics);
         std::process::exit(1);
+      } else if args.sarif {
+        print_sarif_diagnostics(&mut stderr, &diagnostics);
+        return Err("Parse error".into());
       } else {
         print_human_diagnostics(&mut stderr, diagnostics);
         return Err("Parse error".into());
@@ -134,6 +111,176 @@ fn run(
   Ok(())
 }
 
+struct Input {
+  src: String,
+  src_file: SourceFile,
+  base_dir: Option<PathBuf>,
+  mtime: Option<u64>,
+}
+
+fn read_input(args: &Args, stdin: &mut impl Read) -> Result<Input, Box<dyn Error>> {
+  if let Some(pathbuf) = &args.input {
+    let abspath = dunce::canonicalize(pathbuf)?;
+    let mut file = fs::File::open(pathbuf.clone())?;
+    let mut mtime = None;
+    let mut src = file
+      .metadata()
+      .ok()
+      .map(|metadata| {
+        if let Ok(modified) = metadata.modified() {
+          mtime = Some(modified.duration_since(UNIX_EPOCH).unwrap().as_secs());
+        }
+        String::with_capacity(metadata.len() as usize)
+      })
+      .unwrap_or_else(String::new);
+    // TODO: for perf, better to read the file straight into a BumpVec<u8>
+    // have an initializer on Parser that takes ownership of it
+    file.read_to_string(&mut src)?;
+    let base_dir = args
+      .base_dir
+      .as_ref()
+      .cloned()
+      .or_else(|| abspath.parent().map(|p| p.to_path_buf()));
+    Ok(Input {
+      src,
+      src_file: SourceFile::Path(abspath.into()),
+      base_dir,
+      mtime,
+    })
+  } else {
+    let mut src = String::new();
+    stdin.read_to_string(&mut src)?;
+    let cwd_buf = env::current_dir()?;
+    let cwd = Path::new(cwd_buf.to_str().unwrap_or(""));
+    Ok(Input {
+      src,
+      src_file: SourceFile::Stdin { cwd, name: args.input_name.clone() },
+      base_dir: args.base_dir.clone().or(Some(cwd_buf)),
+      mtime: None,
+    })
+  }
+}
+
+fn parser<'arena>(
+  args: &Args,
+  src: &str,
+  src_file: SourceFile,
+  resolver: CliResolver,
+  input_mtime: Option<u64>,
+  bump: &'arena Bump,
+) -> Result<Parser<'arena>, Box<dyn Error>> {
+  let mut parser = Parser::from_str(src, src_file, bump);
+  let mut job_settings: JobSettings = args.clone().try_into()?;
+  match args.format {
+    Output::Docbook5 => Docbook5::set_job_attrs(&mut job_settings.job_attrs),
+    Output::Manpage => {
+      Manpage::set_job_attrs(&mut job_settings.job_attrs);
+      job_settings.doctype.get_or_insert(DocType::Manpage);
+    }
+    Output::Markdown => Markdown::set_job_attrs(&mut job_settings.job_attrs),
+    Output::Text | Output::Terminal => Text::set_job_attrs(&mut job_settings.job_attrs),
+    _ => AsciidoctorHtml::set_job_attrs(&mut job_settings.job_attrs),
+  }
+  parser.apply_job_settings(job_settings);
+  parser.set_resolver(Box::new(resolver));
+  for (name, command) in &args.block_processors {
+    parser.register_block_processor(name, Box::new(CommandProcessor::new(command.clone())));
+  }
+
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs();
+  parser.provide_timestamps(now, input_mtime, None);
+  Ok(parser)
+}
+
+fn resolver(args: &Args, base_dir: Option<PathBuf>) -> CliResolver {
+  CliResolver::new(base_dir, args.strict).with_uri_options(args.into())
+}
+
+fn convert(
+  args: &Args,
+  mut parse_result: ParseResult,
+  src: &str,
+  base_dir: Option<PathBuf>,
+  stderr: &mut impl Write,
+) -> Result<String, Box<dyn Error>> {
+  if args.self_contained && !args.format.is_html() {
+    return Err("--self-contained requires an html output format".into());
+  }
+  // stylesheets only apply to html output
+  if args.format.is_html()
+    && let Err(css_err) = css::resolve(&mut parse_result.document)
+  {
+    writeln!(stderr, "ERROR: {css_err}")?;
+    if args.strict {
+      std::process::exit(1);
+    }
+  }
+  let mut postprocessors = args
+    .postprocessors
+    .iter()
+    .map(|command| Box::new(CommandPostprocessor::new(command.clone())) as Box<dyn Postprocessor>)
+    .collect::<Vec<_>>();
+  let doc_meta = parse_result.document.meta.clone();
+  let output = match &args.format {
+    Output::DrHtml | Output::DrHtmlPrettier => {
+      asciidork_dr_html_backend::convert(parse_result.document)?
+    }
+    Output::Html5 | Output::Html5Prettier => {
+      asciidork_backend_html5s::convert(parse_result.document)?
+    }
+    Output::Docbook5 => asciidork_backend_docbook5::convert(parse_result.document)?,
+    Output::Manpage => asciidork_backend_manpage::convert(parse_result.document)?,
+    Output::Markdown => asciidork_backend_markdown::convert(parse_result.document)?,
+    Output::Text | Output::Terminal => {
+      let options = TextOptions {
+        width: args.width.unwrap_or_else(terminal_width),
+        ansi: args.format == Output::Terminal,
+      };
+      asciidork_backend_text::convert(parse_result.document, options)?
+    }
+    Output::AsgJson => asciidork_asg::convert(&parse_result.document, src),
+    Output::AstJson => serde_json::to_string_pretty(&parse_result.document)?,
+  };
+  let output = if args.self_contained {
+    embed(args, &doc_meta, base_dir, &output, stderr)?
+  } else {
+    output
+  };
+  // postprocess last, so nothing rewrites what the postprocessors produce
+  Ok(asciidork_backend::postprocess(
+    output,
+    &doc_meta,
+    &mut postprocessors,
+  )?)
+}
+
+fn embed(
+  args: &Args,
+  doc_meta: &DocumentMeta,
+  base_dir: Option<PathBuf>,
+  output: &str,
+  stderr: &mut impl Write,
+) -> Result<String, Box<dyn Error>> {
+  let mut embedder = Embedder::new(
+    doc_meta,
+    base_dir,
+    args.strict,
+    args.max_embed_size,
+    args.into(),
+  )?;
+  let output = embedder.embed(output);
+  for problem in &embedder.problems {
+    writeln!(stderr, "WARNING: {problem}")?;
+  }
+  if args.strict && !embedder.problems.is_empty() {
+    return Err("Unable to embed all assets".into());
+  }
+  Ok(output)
+}
+
 fn print_timings(
   dest: &mut impl Write,
   len: usize,
@@ -184,6 +331,13 @@ fn print_timings(
   }
 }
 
+fn terminal_width() -> usize {
+  std::env::var("COLUMNS")
+    .ok()
+    .and_then(|columns| columns.parse().ok())
+    .unwrap_or(80)
+}
+
 fn format_html(html: String) -> String {
   let mut child = Command::new("prettier")
     .arg("--parser")
@@ -200,6 +354,50 @@ fn format_html(html: String) -> String {
   String::from_utf8_lossy(&output.stdout).to_string()
 }
 
+/// prints the diagnostics of a successful parse, failing if any
+/// diagnostic, logged or not, reached the `--failure-level`
+fn report_warnings(
+  args: &Args,
+  stderr: &mut impl Write,
+  warnings: Vec<Diagnostic>,
+  highest_severity: Option<Severity>,
+) -> Result<(), Box<dyn Error>> {
+  let failure = args
+    .failure_level
+    .filter(|level| highest_severity.is_some_and(|severity| severity >= *level))
+    .map(|level| failure_message(&warnings, level, args.log_level));
+  // an empty sarif log still tells CI there were no problems
+  if !warnings.is_empty() || args.sarif {
+    if args.json_errors {
+      print_json_diagnostics(stderr, warnings);
+    } else if args.sarif {
+      print_sarif_diagnostics(stderr, &warnings);
+    } else {
+      print_human_diagnostics(stderr, warnings);
+    }
+  }
+  match failure {
+    Some(message) => Err(message.into()),
+    None => Ok(()),
+  }
+}
+
+fn failure_message(warnings: &[Diagnostic], level: Severity, log_level: Severity) -> String {
+  let num_failed = warnings.iter().filter(|w| w.severity >= level).count();
+  if num_failed > 0 {
+    format!(
+      "{num_failed} diagnostic(s) at or above failure level `{}`",
+      level.to_str()
+    )
+  } else {
+    format!(
+      "diagnostic(s) below log level `{}` reached failure level `{}`",
+      log_level.to_str(),
+      level.to_str()
+    )
+  }
+}
+
 fn print_human_diagnostics(dest: &mut impl Write, diagnostics: Vec<Diagnostic>) {
   for diagnostic in diagnostics {
     writeln!(dest, "\n{}", diagnostic.plain_text_with(Colorizer)).unwrap();
@@ -212,6 +410,10 @@ fn print_json_diagnostics(dest: &mut impl Write, diagnostics: Vec<Diagnostic>) {
   writeln!(dest, "{json}").unwrap();
 }
 
+fn print_sarif_diagnostics(dest: &mut impl Write, diagnostics: &[Diagnostic]) {
+  writeln!(dest, "{:#}", sarif::log(diagnostics)).unwrap();
+}
+
 struct Colorizer;
 
 impl DiagnosticColor for Colorizer {
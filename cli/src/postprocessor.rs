@@ -0,0 +1,29 @@
+use asciidork_backend::Postprocessor;
+use asciidork_core::{DocumentMeta, ReadAttr};
+
+use crate::shell::pipe_through;
+
+/// a postprocessor that pipes the converted output through a shell command
+#[derive(Debug, Clone)]
+pub struct CommandPostprocessor {
+  command: String,
+}
+
+impl CommandPostprocessor {
+  pub const fn new(command: String) -> Self {
+    Self { command }
+  }
+}
+
+impl Postprocessor for CommandPostprocessor {
+  fn process(&mut self, output: String, doc_meta: &DocumentMeta) -> Result<String, String> {
+    let mut env = vec![("ASCIIDORK_DOCTYPE", doc_meta.get_doctype().to_str())];
+    if let Some(doctitle) = doc_meta.str("doctitle") {
+      env.push(("ASCIIDORK_DOCTITLE", doctitle));
+    }
+    if let Some(docname) = doc_meta.str("docname") {
+      env.push(("ASCIIDORK_DOCNAME", docname));
+    }
+    pipe_through(&self.command, &env, output)
+  }
+}
@@ -0,0 +1,170 @@
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+use asciidork_parser::includes::ResolveError;
+
+/// limits and caching for the remote uris read by `CliResolver`
+#[derive(Debug, Clone)]
+pub struct UriOptions {
+  pub cache_dir: PathBuf,
+  /// seconds to wait on a remote host before giving up
+  pub timeout: u64,
+  pub max_bytes: usize,
+  /// when not empty, the only hosts (and their subdomains) that may be read,
+  /// redirects are never followed while either host list is set
+  pub allow_hosts: Vec<String>,
+  pub deny_hosts: Vec<String>,
+  /// never touch the network, only serve uris already in the cache
+  pub offline: bool,
+}
+
+impl Default for UriOptions {
+  fn default() -> Self {
+    Self {
+      cache_dir: default_cache_dir(),
+      timeout: 30,
+      max_bytes: 10 * 1024 * 1024,
+      allow_hosts: Vec::new(),
+      deny_hosts: Vec::new(),
+      offline: false,
+    }
+  }
+}
+
+impl UriOptions {
+  /// reads the uri from the cache when `use_cache` (or offline),
+  /// otherwise over the network, caching the response when `use_cache`
+  pub fn read(&self, uri: &str, use_cache: bool) -> Result<Vec<u8>, ResolveError> {
+    self.check_host(uri)?;
+    let cache_path = self.cache_dir.join(cache_key(uri));
+    if use_cache || self.offline {
+      match fs::read(&cache_path) {
+        Ok(bytes) => return Ok(bytes),
+        Err(_) if self.offline => {
+          return Err(ResolveError::UriRead(format!(
+            "`{uri}` is not cached and --offline is set"
+          )));
+        }
+        Err(_) => {}
+      }
+    }
+
+    let bytes = self.fetch(uri)?;
+    if use_cache {
+      // a failure to cache shouldn't fail the include itself
+      _ = fs::create_dir_all(&self.cache_dir).and_then(|_| fs::write(&cache_path, &bytes));
+    }
+    Ok(bytes)
+  }
+
+  fn fetch(&self, uri: &str) -> Result<Vec<u8>, ResolveError> {
+    let mut request = minreq::get(uri).with_timeout(self.timeout);
+    // minreq can't check a redirect's host before following it,
+    // so redirects are refused entirely when hosts are restricted
+    let restricted = !self.allow_hosts.is_empty() || !self.deny_hosts.is_empty();
+    if restricted {
+      request = request.with_max_redirects(0);
+    }
+    let response = request.send_lazy().map_err(|err| match err {
+      minreq::Error::TooManyRedirections if restricted => ResolveError::UriRead(
+        "redirects are not followed when --allow-host or --deny-host is set".into(),
+      ),
+      err => ResolveError::UriRead(err.to_string()),
+    })?;
+    if !(200..300).contains(&response.status_code) {
+      return Err(ResolveError::UriRead(format!(
+        "HTTP {} {}",
+        response.status_code, response.reason_phrase
+      )));
+    }
+    let mut bytes = Vec::new();
+    Read::take(response, self.max_bytes as u64 + 1)
+      .read_to_end(&mut bytes)
+      .map_err(|err| ResolveError::UriRead(err.to_string()))?;
+    if bytes.len() > self.max_bytes {
+      return Err(ResolveError::UriRead(format!(
+        "response exceeds --uri-max-bytes of {}",
+        self.max_bytes
+      )));
+    }
+    Ok(bytes)
+  }
+
+  fn check_host(&self, uri: &str) -> Result<(), ResolveError> {
+    let host = host(uri);
+    if self
+      .deny_hosts
+      .iter()
+      .any(|denied| host_matches(host, denied))
+    {
+      return Err(ResolveError::UriRead(format!(
+        "host `{host}` is denied by --deny-host"
+      )));
+    }
+    if !self.allow_hosts.is_empty()
+      && !self
+        .allow_hosts
+        .iter()
+        .any(|allowed| host_matches(host, allowed))
+    {
+      return Err(ResolveError::UriRead(format!(
+        "host `{host}` is not allowed by --allow-host"
+      )));
+    }
+    Ok(())
+  }
+}
+
+fn host(uri: &str) -> &str {
+  let rest = uri.split_once("://").map_or(uri, |(_, rest)| rest);
+  let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+  let host_port = authority
+    .rsplit_once('@')
+    .map_or(authority, |(_, host)| host);
+  match host_port.strip_prefix('[') {
+    // ipv6 literal, e.g. `[::1]:8080`
+    Some(ipv6) => ipv6.split(']').next().unwrap_or(ipv6),
+    None => host_port.split(':').next().unwrap_or(host_port),
+  }
+}
+
+fn host_matches(host: &str, pattern: &str) -> bool {
+  host.eq_ignore_ascii_case(pattern)
+    || (host.len() > pattern.len()
+      && host.as_bytes()[host.len() - pattern.len() - 1] == b'.'
+      && host[host.len() - pattern.len()..].eq_ignore_ascii_case(pattern))
+}
+
+/// stable across runs and platforms, unlike std's `DefaultHasher`
+fn cache_key(uri: &str) -> String {
+  let hash = uri.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  });
+  format!("{hash:016x}")
+}
+
+fn default_cache_dir() -> PathBuf {
+  let base = std::env::var_os("XDG_CACHE_HOME")
+    .map(PathBuf::from)
+    .or_else(|| std::env::home_dir().map(|home| home.join(".cache")))
+    .unwrap_or_else(std::env::temp_dir);
+  base.join("asciidork").join("uri")
+}
+
+#[test]
+fn test_host() {
+  let cases = [
+    ("https://example.com/a.adoc", "example.com"),
+    ("http://127.0.0.1:8080/a.adoc", "127.0.0.1"),
+    ("https://user:pw@docs.example.com?x=1", "docs.example.com"),
+    ("http://[::1]:80/a", "::1"),
+  ];
+  for (uri, expected) in cases {
+    assert_eq!(host(uri), expected);
+  }
+  assert!(host_matches("example.com", "example.com"));
+  assert!(host_matches("docs.Example.com", "example.com"));
+  assert!(!host_matches("badexample.com", "example.com"));
+  assert!(!host_matches("example.com", "docs.example.com"));
+}
@@ -1,10 +1,14 @@
+use std::cell::RefCell;
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::PathBuf;
+use std::rc::Rc;
 
 use asciidork_core::SafeMode;
 use asciidork_parser::includes::*;
 
+use crate::remote::UriOptions;
+
 use IncludeTarget as Target;
 use ResolveError::*;
 
@@ -12,6 +16,10 @@ use ResolveError::*;
 pub struct CliResolver {
   base_dir: Option<PathBuf>,
   strict: bool,
+  uri: UriOptions,
+  cache_uri: bool,
+  // shared with clones, so table cell resolutions are seen too
+  requested: Rc<RefCell<Vec<PathBuf>>>,
 }
 
 impl IncludeResolver for CliResolver {
@@ -23,19 +31,20 @@ impl IncludeResolver for CliResolver {
   ) -> std::result::Result<usize, ResolveError> {
     match target {
       Target::FilePath(target) => self.resolve_filepath(target, buffer, safe_mode),
-      Target::Uri(uri) => match minreq::get(uri).send() {
-        Ok(response) => {
-          let adoc = response.as_bytes();
-          buffer.initialize(adoc.len());
-          let bytes = buffer.as_bytes_mut();
-          bytes.copy_from_slice(adoc);
-          Ok(adoc.len())
-        }
-        Err(err) => Err(ResolveError::UriRead(err.to_string())),
-      },
+      Target::Uri(uri) => {
+        let adoc = self.uri.read(&uri, self.cache_uri)?;
+        buffer.initialize(adoc.len());
+        let bytes = buffer.as_bytes_mut();
+        bytes.copy_from_slice(&adoc);
+        Ok(adoc.len())
+      }
     }
   }
 
+  fn set_cache_uri(&mut self, cache_uri: bool) {
+    self.cache_uri = cache_uri;
+  }
+
   fn get_base_dir(&self) -> Option<String> {
     self
       .base_dir
@@ -49,8 +58,24 @@ impl IncludeResolver for CliResolver {
 }
 
 impl CliResolver {
-  pub const fn new(base_dir: Option<PathBuf>, strict: bool) -> Self {
-    Self { base_dir, strict }
+  pub fn new(base_dir: Option<PathBuf>, strict: bool) -> Self {
+    Self {
+      base_dir,
+      strict,
+      uri: UriOptions::default(),
+      cache_uri: false,
+      requested: Rc::new(RefCell::new(Vec::new())),
+    }
+  }
+
+  pub fn with_uri_options(mut self, uri: UriOptions) -> Self {
+    self.uri = uri;
+    self
+  }
+
+  /// every file path asked for, found or not: includes, docinfo, inline svgs
+  pub fn requested_files(&self) -> Vec<PathBuf> {
+    self.requested.borrow().clone()
   }
 
   fn resolve_filepath(
@@ -60,6 +85,7 @@ impl CliResolver {
     safe_mode: SafeMode,
   ) -> std::result::Result<usize, ResolveError> {
     let pathb = PathBuf::from(path);
+    self.requested.borrow_mut().push(pathb.clone());
     let Ok(pathc) = dunce::canonicalize(&pathb) else {
       return Err(NotFound);
     };
@@ -0,0 +1,132 @@
+use std::env;
+use std::path::Path as StdPath;
+
+use serde_json::{Value, json};
+
+use asciidork_core::Severity;
+use asciidork_parser::prelude::*;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SRCROOT: &str = "%SRCROOT%";
+
+/// a SARIF 2.1 log of `diagnostics`, paths under the current
+/// directory are made relative to `%SRCROOT%`
+pub fn log(diagnostics: &[Diagnostic]) -> Value {
+  let cwd = env::current_dir().ok();
+  let mut rules: Vec<DiagnosticCode> = Vec::new();
+  let results = diagnostics
+    .iter()
+    .map(|diagnostic| {
+      let code = diagnostic.code;
+      let rule_index = rules
+        .iter()
+        .position(|rule| *rule == code)
+        .unwrap_or_else(|| {
+          rules.push(code);
+          rules.len() - 1
+        });
+      let mut result = json!({
+        "ruleId": code.to_str(),
+        "ruleIndex": rule_index,
+        "level": level(diagnostic.severity),
+        "message": { "text": diagnostic.message },
+      });
+      if let SourceFile::Path(path) = &diagnostic.source_file {
+        result["locations"] = json!([{
+          "physicalLocation": {
+            "artifactLocation": artifact_location(StdPath::new(&path.to_string()), cwd.as_deref()),
+            "region": region(diagnostic),
+          }
+        }]);
+      }
+      result
+    })
+    .collect::<Vec<_>>();
+  let rules = rules
+    .iter()
+    .map(|code| {
+      json!({
+        "id": code.to_str(),
+        "defaultConfiguration": { "level": level(code.severity()) },
+      })
+    })
+    .collect::<Vec<_>>();
+  let mut run = json!({
+    "tool": {
+      "driver": {
+        "name": "asciidork",
+        "version": env!("CARGO_PKG_VERSION"),
+        "rules": rules,
+      }
+    },
+    "columnKind": "unicodeCodePoints",
+    "results": results,
+  });
+  if let Some(cwd) = &cwd {
+    let mut root = file_uri(cwd);
+    if !root.ends_with('/') {
+      root.push('/');
+    }
+    run["originalUriBaseIds"] = json!({ SRCROOT: { "uri": root } });
+  }
+  json!({
+    "$schema": SCHEMA,
+    "version": "2.1.0",
+    "runs": [run],
+  })
+}
+
+const fn level(severity: Severity) -> &'static str {
+  match severity {
+    Severity::Fatal | Severity::Error => "error",
+    Severity::Warn => "warning",
+    Severity::Info => "note",
+  }
+}
+
+fn artifact_location(path: &StdPath, cwd: Option<&StdPath>) -> Value {
+  match cwd.and_then(|cwd| path.strip_prefix(cwd).ok()) {
+    Some(rel) => json!({ "uri": encode(&rel.to_string_lossy()), "uriBaseId": SRCROOT }),
+    None => json!({ "uri": file_uri(path) }),
+  }
+}
+
+/// sarif columns are 1-based, with an exclusive end
+fn region(diagnostic: &Diagnostic) -> Value {
+  let line = &diagnostic.line;
+  let column = |byte_offset: u32| {
+    let mut offset = (byte_offset as usize).min(line.len());
+    while !line.is_char_boundary(offset) {
+      offset -= 1;
+    }
+    line[..offset].chars().count() + 1
+  };
+  json!({
+    "startLine": diagnostic.line_num,
+    "startColumn": column(diagnostic.underline_start),
+    "endColumn": column(diagnostic.underline_start + diagnostic.underline_width),
+    "snippet": { "text": line },
+  })
+}
+
+fn file_uri(path: &StdPath) -> String {
+  let path = encode(&path.to_string_lossy());
+  if path.starts_with('/') {
+    format!("file://{path}")
+  } else {
+    format!("file:///{path}")
+  }
+}
+
+fn encode(path: &str) -> String {
+  let mut encoded = String::with_capacity(path.len());
+  for byte in path.replace('\\', "/").bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+        encoded.push(byte as char)
+      }
+      _ => encoded.push_str(&format!("%{byte:02X}")),
+    }
+  }
+  encoded
+}
@@ -0,0 +1,271 @@
+use std::path::PathBuf;
+
+use bumpalo::Bump;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use asciidork_core::{DocumentMeta, ReadAttr, SafeMode};
+use asciidork_parser::base64;
+use asciidork_parser::includes::*;
+
+use crate::remote::UriOptions;
+use crate::resolver::CliResolver;
+
+lazy_static! {
+  static ref IMG_SRC: Regex = Regex::new(r#"(<img\b[^>]*?\bsrc=")([^"]*)""#).unwrap();
+  static ref LINK: Regex = Regex::new(r#"<link\b[^>]*>"#).unwrap();
+  static ref LINK_ATTR: Regex = Regex::new(r#"\b(rel|href)="([^"]*)""#).unwrap();
+  static ref STYLE: Regex = Regex::new(r#"(?s)(<style>)(.*?)(</style>)"#).unwrap();
+  static ref CSS_URL: Regex = Regex::new(r#"url\(\s*(['"]?)([^'")]*)['"]?\s*\)"#).unwrap();
+}
+
+/// where the relative references of an asset resolve from
+#[derive(Debug, Clone)]
+enum Base {
+  Dir(PathBuf),
+  Uri(String),
+}
+
+/// inlines the images, icons, stylesheets and fonts referenced
+/// by converted html, so the output is a single standalone file
+pub struct Embedder {
+  resolver: CliResolver,
+  safe_mode: SafeMode,
+  allow_uri_read: bool,
+  max_size: usize,
+  base_dir: PathBuf,
+  styles_dir: PathBuf,
+  bump: Bump,
+  /// assets which could not be embedded, left as external references
+  pub problems: Vec<String>,
+}
+
+impl Embedder {
+  pub fn new(
+    meta: &DocumentMeta,
+    base_dir: Option<PathBuf>,
+    strict: bool,
+    max_size: usize,
+    uri: UriOptions,
+  ) -> Result<Self, String> {
+    if meta.safe_mode == SafeMode::Secure {
+      return Err(
+        "--self-contained cannot read assets in secure mode, use --safe-mode safe".into(),
+      );
+    }
+    let cwd = std::env::current_dir().map_err(|err| err.to_string())?;
+    let base_dir = base_dir.unwrap_or_else(|| cwd.clone());
+    let attrs = meta.header_attrs();
+    // matches the resolution of the custom stylesheet in `css::resolve`
+    let styles_dir = cwd.join(attrs.str_or("stylesdir", "."));
+    let mut resolver = CliResolver::new(Some(base_dir.clone()), strict).with_uri_options(uri);
+    resolver.set_cache_uri(meta.is_true("cache-uri"));
+    Ok(Self {
+      resolver,
+      safe_mode: meta.safe_mode,
+      allow_uri_read: meta.is_true("allow-uri-read"),
+      max_size,
+      base_dir,
+      styles_dir,
+      bump: Bump::new(),
+      problems: Vec::new(),
+    })
+  }
+
+  pub fn embed(&mut self, html: &str) -> String {
+    let doc_base = Base::Dir(self.base_dir.clone());
+    let html = IMG_SRC.replace_all(html, |caps: &Captures| {
+      match self.data_uri(&doc_base, &caps[2]) {
+        Some(data_uri) => format!("{}{}\"", &caps[1], data_uri),
+        None => caps[0].to_string(),
+      }
+    });
+
+    let styles_base = Base::Dir(self.styles_dir.clone());
+    let html = STYLE.replace_all(&html, |caps: &Captures| {
+      let css = self.embed_css_urls(&styles_base, &caps[2]);
+      format!("{}{}{}", &caps[1], css, &caps[3])
+    });
+
+    LINK
+      .replace_all(&html, |caps: &Captures| {
+        self.embed_link(&doc_base, &caps[0])
+      })
+      .into_owned()
+  }
+
+  fn embed_link(&mut self, base: &Base, tag: &str) -> String {
+    let mut rel = "";
+    let mut href = None;
+    for caps in LINK_ATTR.captures_iter(tag) {
+      match &caps[1] {
+        "rel" => rel = caps.get(2).unwrap().as_str(),
+        _ => href = Some(caps.get(2).unwrap().as_str()),
+      }
+    }
+    let Some(href) = href else {
+      return tag.to_string();
+    };
+    if rel == "stylesheet" {
+      let Some((bytes, css_base)) = self.read(base, href) else {
+        return tag.to_string();
+      };
+      let css = String::from_utf8_lossy(&bytes);
+      format!("<style>{}</style>", self.embed_css_urls(&css_base, &css))
+    } else if rel.split_whitespace().any(|rel| rel == "icon") {
+      match self.data_uri(base, href) {
+        Some(data_uri) => tag.replacen(href, &data_uri, 1),
+        None => tag.to_string(),
+      }
+    } else {
+      tag.to_string()
+    }
+  }
+
+  fn embed_css_urls(&mut self, base: &Base, css: &str) -> String {
+    CSS_URL
+      .replace_all(css, |caps: &Captures| match self.data_uri(base, &caps[2]) {
+        Some(data_uri) => format!("url({0}{1}{0})", &caps[1], data_uri),
+        None => caps[0].to_string(),
+      })
+      .into_owned()
+  }
+
+  fn data_uri(&mut self, base: &Base, reference: &str) -> Option<String> {
+    let (bytes, _) = self.read(base, reference)?;
+    let encoded = base64::encode_in(&bytes, &self.bump);
+    let data_uri = format!("data:{};base64,{}", mime_type(reference), encoded);
+    drop(encoded);
+    self.bump.reset();
+    Some(data_uri)
+  }
+
+  fn read(&mut self, base: &Base, reference: &str) -> Option<(Vec<u8>, Base)> {
+    let reference = reference.trim().replace("&amp;", "&");
+    if reference.is_empty() || reference.starts_with("data:") || reference.starts_with('#') {
+      return None;
+    }
+    let target = resolve_target(base, &reference);
+    if target.is_uri() && !self.allow_uri_read {
+      self.problem(&reference, "remote assets require allow-uri-read");
+      return None;
+    }
+    let asset_base = match &target {
+      IncludeTarget::Uri(uri) => Base::Uri(uri.clone()),
+      IncludeTarget::FilePath(path) => {
+        let path = PathBuf::from(path);
+        Base::Dir(path.parent().map(|p| p.to_path_buf()).unwrap_or_default())
+      }
+    };
+    let mut bytes = Vec::new();
+    if let Err(err) = self.resolver.resolve(target, &mut bytes, self.safe_mode) {
+      self.problem(&reference, &err.to_string());
+      return None;
+    }
+    if bytes.len() > self.max_size {
+      self.problem(
+        &reference,
+        &format!(
+          "{} bytes exceeds --max-embed-size of {}",
+          bytes.len(),
+          self.max_size
+        ),
+      );
+      return None;
+    }
+    Some((bytes, asset_base))
+  }
+
+  fn problem(&mut self, reference: &str, reason: &str) {
+    self
+      .problems
+      .push(format!("could not embed `{reference}`: {reason}"));
+  }
+}
+
+fn resolve_target(base: &Base, reference: &str) -> IncludeTarget {
+  if reference.starts_with("http://") || reference.starts_with("https://") {
+    return IncludeTarget::Uri(reference.to_string());
+  } else if reference.starts_with("//") {
+    return IncludeTarget::Uri(format!("https:{reference}"));
+  }
+  match base {
+    Base::Dir(dir) => {
+      let path = reference.split(['?', '#']).next().unwrap_or(reference);
+      IncludeTarget::FilePath(dir.join(path).to_string_lossy().into())
+    }
+    Base::Uri(uri) => {
+      let after_scheme = uri.find("://").map(|idx| idx + 3).unwrap_or(0);
+      let origin_end = uri[after_scheme..]
+        .find('/')
+        .map(|idx| idx + after_scheme)
+        .unwrap_or(uri.len());
+      if reference.starts_with('/') {
+        IncludeTarget::Uri(format!("{}{reference}", &uri[..origin_end]))
+      } else {
+        let path = uri.split(['?', '#']).next().unwrap_or(uri);
+        let dir_end = path.rfind('/').filter(|idx| *idx >= origin_end);
+        let dir = dir_end.map(|idx| &path[..idx]).unwrap_or(path);
+        IncludeTarget::Uri(format!("{dir}/{reference}"))
+      }
+    }
+  }
+}
+
+fn mime_type(reference: &str) -> &'static str {
+  let path = reference.split(['?', '#']).next().unwrap_or(reference);
+  let ext = path
+    .rsplit_once('.')
+    .map(|(_, ext)| ext.to_ascii_lowercase());
+  match ext.as_deref() {
+    Some("png") => "image/png",
+    Some("jpg" | "jpeg") => "image/jpeg",
+    Some("gif") => "image/gif",
+    Some("svg") => "image/svg+xml",
+    Some("webp") => "image/webp",
+    Some("ico") => "image/x-icon",
+    Some("css") => "text/css",
+    Some("woff") => "font/woff",
+    Some("woff2") => "font/woff2",
+    Some("ttf") => "font/ttf",
+    Some("otf") => "font/otf",
+    Some("eot") => "application/vnd.ms-fontobject",
+    _ => "application/octet-stream",
+  }
+}
+
+#[test]
+fn test_resolve_target() {
+  let dir = Base::Dir(PathBuf::from("/docs"));
+  let css = Base::Uri("https://fonts.example.com/css/site.css?family=Foo".into());
+  let cases = [
+    (
+      &dir,
+      "images/a.png",
+      IncludeTarget::FilePath("/docs/images/a.png".into()),
+    ),
+    (
+      &dir,
+      "a.woff?v=2#x",
+      IncludeTarget::FilePath("/docs/a.woff".into()),
+    ),
+    (
+      &dir,
+      "//cdn.example.com/a.png",
+      IncludeTarget::Uri("https://cdn.example.com/a.png".into()),
+    ),
+    (
+      &css,
+      "/f/a.ttf",
+      IncludeTarget::Uri("https://fonts.example.com/f/a.ttf".into()),
+    ),
+    (
+      &css,
+      "a.ttf",
+      IncludeTarget::Uri("https://fonts.example.com/css/a.ttf".into()),
+    ),
+  ];
+  for (base, reference, expected) in cases {
+    assert_eq!(resolve_target(base, reference), expected);
+  }
+}
@@ -0,0 +1,38 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// runs `command` through the platform shell with `env` set and `input`
+/// on stdin, returning its stdout, or an error describing the failure
+pub fn pipe_through(command: &str, env: &[(&str, &str)], input: String) -> Result<String, String> {
+  let mut child = shell(command)
+    .envs(env.iter().copied())
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| format!("unable to run `{command}`: {err}"))?;
+  let mut stdin = child.stdin.take().unwrap();
+  // write from a thread, a command could fill its stdout pipe before reading all input
+  let writer = std::thread::spawn(move || {
+    _ = stdin.write_all(input.as_bytes());
+  });
+  let output = child.wait_with_output().map_err(|err| err.to_string())?;
+  _ = writer.join();
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(format!("`{command}` {}: {}", output.status, stderr.trim()));
+  }
+  String::from_utf8(output.stdout).map_err(|err| err.to_string())
+}
+
+fn shell(command: &str) -> Command {
+  if cfg!(windows) {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+  } else {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+  }
+}
@@ -0,0 +1,321 @@
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path as StdPath, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use bumpalo::Bump;
+
+use asciidork_parser::prelude::*;
+
+use crate::args::{Args, Output};
+use crate::{Input, css};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const EVENTS_PATH: &str = "/__asciidork/events";
+
+pub fn run(
+  args: &Args,
+  mut stdout: impl Write,
+  mut stderr: impl Write,
+) -> Result<(), Box<dyn Error>> {
+  if args.serve && !args.format.is_html() {
+    return Err("--serve requires an html output format".into());
+  }
+  let input = args.input.clone().ok_or("--watch requires --input")?;
+  let page = Arc::new(Page::default());
+  let mut last_output = None;
+  let mut watched: Vec<(PathBuf, Option<SystemTime>)> = Vec::new();
+  let mut serving = false;
+  loop {
+    let changed = watched.iter().any(|(path, mtime)| modified(path) != *mtime);
+    if watched.is_empty() || changed {
+      let build = build(args, &mut stderr);
+      if let Some(output) = build.output {
+        if let Some(file) = &args.output {
+          fs::write(file, &output)?;
+        } else if !args.serve {
+          writeln!(stdout, "{output}")?;
+        }
+        last_output = Some(output);
+      }
+      let mut files = build.files;
+      if !build.files_complete {
+        // a failed parse stops before reading every file, so keep
+        // watching the ones from the last good build as well
+        files.extend(watched.into_iter().map(|(path, _)| path));
+      }
+      if !files.contains(&input) {
+        files.push(input.clone());
+      }
+      files.sort();
+      files.dedup();
+      watched = files
+        .into_iter()
+        .map(|path| {
+          let mtime = modified(&path);
+          (path, mtime)
+        })
+        .collect();
+      page.publish(last_output.as_deref(), &build.problems);
+    }
+    if args.serve && !serving {
+      let listener = TcpListener::bind(("127.0.0.1", args.port))?;
+      writeln!(stderr, "Serving at http://{}", listener.local_addr()?)?;
+      let root = dunce::canonicalize(
+        args
+          .base_dir
+          .clone()
+          .or_else(|| input.parent().map(StdPath::to_path_buf))
+          .unwrap_or_default(),
+      )?;
+      let page = Arc::clone(&page);
+      thread::spawn(move || serve(listener, root, page));
+      serving = true;
+    }
+    thread::sleep(POLL_INTERVAL);
+  }
+}
+
+struct Build {
+  output: Option<String>,
+  /// rendered diagnostics and errors, shown over the page
+  problems: Vec<String>,
+  files: Vec<PathBuf>,
+  /// false if the build failed before knowing every file it depends on
+  files_complete: bool,
+}
+
+fn build(args: &Args, stderr: &mut impl Write) -> Build {
+  let mut build = Build {
+    output: None,
+    problems: Vec::new(),
+    files: Vec::new(),
+    files_complete: false,
+  };
+  match convert(args, &mut build, stderr) {
+    Ok(output) => build.output = output,
+    Err(err) => {
+      _ = writeln!(stderr, "ERROR: {err}");
+      build.problems.push(err.to_string());
+    }
+  }
+  build
+}
+
+fn convert(
+  args: &Args,
+  build: &mut Build,
+  stderr: &mut impl Write,
+) -> Result<Option<String>, Box<dyn Error>> {
+  let Input { src, src_file, base_dir, mtime } = crate::read_input(args, &mut io::empty())?;
+  let bump = &Bump::with_capacity(src.len() * 2);
+  // clones share requested files, so they're known even if parsing fails
+  let resolver = crate::resolver(args, base_dir.clone());
+  let parser = crate::parser(args, &src, src_file, resolver.clone(), mtime, bump)?;
+  build.files.push(args.input.clone().unwrap());
+  let parse_result = parser.parse();
+  build.files.extend(resolver.requested_files());
+  let mut parse_result = match parse_result {
+    Ok(parse_result) => parse_result,
+    Err(diagnostics) => {
+      build.problems = diagnostics.iter().map(Diagnostic::plain_text).collect();
+      crate::print_human_diagnostics(stderr, diagnostics);
+      return Ok(None);
+    }
+  };
+  let warnings = std::mem::take(&mut parse_result.warnings);
+  build.problems = warnings.iter().map(Diagnostic::plain_text).collect();
+  crate::print_human_diagnostics(stderr, warnings);
+
+  let meta = &parse_result.document.meta;
+  if let Some(base_dir) = &base_dir {
+    let included = meta.included_files.iter().filter(|f| !f.contains("://"));
+    build.files.extend(included.map(|file| base_dir.join(file)));
+  }
+  build.files.extend(css::stylesheet_path(meta));
+  build.files_complete = true;
+
+  let mut output = crate::convert(args, parse_result, &src, base_dir, stderr)?;
+  if args.format == Output::Html5Prettier || args.format == Output::DrHtmlPrettier {
+    output = crate::format_html(output);
+  }
+  Ok(Some(output))
+}
+
+fn modified(path: &StdPath) -> Option<SystemTime> {
+  fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// the page being served, bumping `version` wakes live-reload listeners
+#[derive(Default)]
+struct Page {
+  state: Mutex<PageState>,
+  changed: Condvar,
+}
+
+#[derive(Default)]
+struct PageState {
+  version: u64,
+  html: String,
+}
+
+impl Page {
+  fn publish(&self, output: Option<&str>, problems: &[String]) {
+    let mut state = self.state.lock().unwrap();
+    state.version += 1;
+    let mut html = output.unwrap_or(EMPTY_PAGE).to_string();
+    let mut snippet = String::new();
+    if !problems.is_empty() {
+      snippet.push_str(OVERLAY_OPEN);
+      snippet.push_str(&escape(&problems.join("\n")));
+      snippet.push_str("</div>");
+    }
+    snippet.push_str(&format!(
+      "<script>new EventSource(\"{EVENTS_PATH}?version={}\").onmessage = () => location.reload();</script>",
+      state.version
+    ));
+    match html.rfind("</body>") {
+      Some(idx) => html.insert_str(idx, &snippet),
+      None => html.push_str(&snippet),
+    }
+    state.html = html;
+    self.changed.notify_all();
+  }
+}
+
+const EMPTY_PAGE: &str =
+  "<!DOCTYPE html><html><head><meta charset=\"UTF-8\"></head><body></body></html>";
+
+const OVERLAY_OPEN: &str = "<div id=\"asciidork-overlay\" style=\"position:fixed;inset:0;z-index:9999;overflow:auto;margin:0;padding:1.5em;background:rgba(0,0,0,.85);color:#ff6b6b;font:14px/1.4 monospace;white-space:pre\">";
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+}
+
+fn serve(listener: TcpListener, root: PathBuf, page: Arc<Page>) {
+  for stream in listener.incoming().flatten() {
+    let root = root.clone();
+    let page = Arc::clone(&page);
+    thread::spawn(move || {
+      _ = handle(stream, &root, &page);
+    });
+  }
+}
+
+fn handle(mut stream: TcpStream, root: &StdPath, page: &Page) -> io::Result<()> {
+  let mut reader = BufReader::new(&stream);
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+  // drain the headers, closing with unread input resets the connection
+  let mut header = String::new();
+  while reader.read_line(&mut header)? > 2 {
+    header.clear();
+  }
+  let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+  let (path, query) = target.split_once('?').unwrap_or((target, ""));
+  match path {
+    EVENTS_PATH => {
+      let version = query
+        .strip_prefix("version=")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| page.state.lock().unwrap().version);
+      send_events(stream, page, version)
+    }
+    "/" => {
+      let html = page.state.lock().unwrap().html.clone();
+      respond(
+        &mut stream,
+        "200 OK",
+        "text/html; charset=utf-8",
+        html.as_bytes(),
+      )
+    }
+    _ => match static_file(root, path) {
+      Some((bytes, content_type)) => respond(&mut stream, "200 OK", content_type, &bytes),
+      None => respond(&mut stream, "404 Not Found", "text/plain", b"Not Found"),
+    },
+  }
+}
+
+fn respond(
+  stream: &mut TcpStream,
+  status: &str,
+  content_type: &str,
+  body: &[u8],
+) -> io::Result<()> {
+  write!(
+    stream,
+    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+    body.len()
+  )?;
+  stream.write_all(body)
+}
+
+/// sends a single `reload` event once the page is newer than `version`
+fn send_events(mut stream: TcpStream, page: &Page, version: u64) -> io::Result<()> {
+  stream.write_all(
+    b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\n\r\n",
+  )?;
+  loop {
+    let changed = {
+      let state = page.state.lock().unwrap();
+      let (state, _) = page
+        .changed
+        .wait_timeout_while(state, KEEPALIVE_INTERVAL, |state| state.version == version)
+        .unwrap();
+      state.version != version
+    };
+    if changed {
+      return stream.write_all(b"data: reload\n\n");
+    }
+    // comments keep the connection open, and notice closed ones
+    stream.write_all(b": keepalive\n\n")?;
+  }
+}
+
+fn static_file(root: &StdPath, path: &str) -> Option<(Vec<u8>, &'static str)> {
+  let path = dunce::canonicalize(root.join(percent_decode(path.trim_start_matches('/')))).ok()?;
+  if !path.starts_with(root) || !path.is_file() {
+    return None;
+  }
+  let content_type = match path.extension().and_then(|ext| ext.to_str()) {
+    Some("html") => "text/html; charset=utf-8",
+    Some("css") => "text/css",
+    Some("js") => "text/javascript",
+    Some("svg") => "image/svg+xml",
+    Some("png") => "image/png",
+    Some("jpg" | "jpeg") => "image/jpeg",
+    Some("gif") => "image/gif",
+    Some("webp") => "image/webp",
+    _ => "application/octet-stream",
+  };
+  Some((fs::read(path).ok()?, content_type))
+}
+
+fn percent_decode(input: &str) -> String {
+  let bytes = input.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] == b'%'
+      && let Some(byte) = input
+        .get(i + 1..i + 3)
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+    {
+      decoded.push(byte);
+      i += 3;
+    } else {
+      decoded.push(bytes[i]);
+      i += 1;
+    }
+  }
+  String::from_utf8_lossy(&decoded).into_owned()
+}
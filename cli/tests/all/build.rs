@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::helpers::*;
+
+#[test]
+fn build_site() {
+  let out_dir = out_dir("site");
+  run_input(&["build", "site", out_dir.to_str().unwrap()], "");
+  let index = fs::read_to_string(out_dir.join("index.html")).unwrap();
+  assert!(index.contains(r#"<a href="guide/setup.html#install">installing</a>"#));
+  assert!(index.contains(r#"<a href="guide/setup.html">the guide</a>"#));
+  assert!(index.contains("Text from a partial."));
+  let setup = fs::read_to_string(out_dir.join("guide/setup.html")).unwrap();
+  assert!(setup.contains(r#"<a href="../index.html">the index</a>"#));
+  assert!(out_dir.join("images/logo.svg").is_file());
+  assert!(out_dir.join("guide/diagram.svg").is_file());
+  assert!(!out_dir.join("_partial.html").exists());
+}
+
+#[test]
+fn build_broken_xrefs() {
+  let out_dir = out_dir("broken-site");
+  let stderr = run_input_expecting_err(&["build", "broken-site", out_dir.to_str().unwrap()], "");
+  assert!(stderr.contains("Invalid cross reference, no document found for `missing.adoc#intro`"));
+  assert!(stderr.contains("Invalid cross reference, no anchor found for `other.adoc#nope`"));
+  assert_eq!(stderr.matches("Invalid cross reference").count(), 2);
+  assert!(stderr.contains("2 broken cross reference(s)"));
+  // documents are still converted
+  assert!(out_dir.join("other.html").is_file());
+}
+
+#[test]
+fn build_skips_anchor_checks_into_failed_docs() {
+  let out_dir = out_dir("failing-site");
+  let stderr = run_input_expecting_err(
+    &[
+      "build",
+      "--strict",
+      "failing-site",
+      out_dir.to_str().unwrap(),
+    ],
+    "",
+  );
+  assert!(stderr.contains("bad.adoc: Parse error"));
+  assert!(stderr.contains("1 document(s) failed to build"));
+  assert!(!stderr.contains("`bad.adoc#real`"));
+}
+
+#[test]
+fn build_honors_outfilesuffix() {
+  let out_dir = out_dir("site-outfilesuffix");
+  run_input(
+    &[
+      "build",
+      "-a",
+      "outfilesuffix=.htm",
+      "site",
+      out_dir.to_str().unwrap(),
+    ],
+    "",
+  );
+  let index = fs::read_to_string(out_dir.join("index.htm")).unwrap();
+  assert!(index.contains(r#"<a href="guide/setup.htm#install">installing</a>"#));
+  assert!(out_dir.join("guide/setup.htm").is_file());
+  assert!(!out_dir.join("index.html").exists());
+}
+
+fn out_dir(name: &str) -> PathBuf {
+  let out_dir = std::env::temp_dir().join(format!("asciidork-build-{name}"));
+  _ = fs::remove_dir_all(&out_dir);
+  out_dir
+}
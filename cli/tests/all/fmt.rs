@@ -0,0 +1,18 @@
+use crate::helpers::*;
+
+#[test]
+fn fmt_stdin_to_stdout() {
+  let stdout = run_input(&["fmt"], "## Section\n- item\n");
+  assert_eq!(stdout, "== Section\n\n* item\n");
+}
+
+#[test]
+fn fmt_check_formatted() {
+  run_input(&["fmt", "--check"], "== Section\n\n* item\n");
+}
+
+#[test]
+fn fmt_check_unformatted() {
+  let stderr = run_input_expecting_err(&["fmt", "--check"], "## Section\n");
+  assert!(stderr.contains("stdin is not formatted"));
+}
@@ -638,3 +638,76 @@ fn test_iconsdir_url_secure_mode_blocks_data_uri_embed() {
     "#}
   );
 }
+
+#[cfg(unix)]
+#[test]
+fn test_stdin_relative_includes() {
+  let stdout = run_input(
+    &["--embedded", "--strict", "--safe-mode", "safe"],
+    "include::gen/parent-include.adoc[]",
+  );
+  expect_eq!(
+    stdout.trim(),
+    html! {r#"
+      <div class="paragraph"><p>first line of parent</p></div>
+      <div class="paragraph"><p>first line of child</p></div>
+      <div class="paragraph"><p>first line of grandchild</p></div>
+      <div class="paragraph"><p>last line of grandchild</p></div>
+      <div class="paragraph"><p>last line of child</p></div>
+      <div class="paragraph"><p>last line of parent</p></div>
+    "#}
+  );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_stdin_include_restricted_by_safe_mode() {
+  let stderr = run_input_expecting_err(
+    &["--embedded", "--strict", "--safe-mode", "safe"],
+    "include::../main.rs[]",
+  );
+  assert!(stderr.contains("Include error: Include path outside docdir restricted by safe mode"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_stdin_input_name() {
+  let adoc = adoc! {r#"
+    docname: {docname}
+
+    docfile: {docfile}
+
+    include::nope.adoc[]
+  "#};
+  let stdout = run_input(
+    &[
+      "--embedded",
+      "--safe-mode",
+      "safe",
+      "--input-name",
+      "report.adoc",
+    ],
+    adoc,
+  );
+  expect_eq!(
+    stdout.trim(),
+    html! {r#"
+      <div class="paragraph"><p>docname: report</p></div>
+      <div class="paragraph"><p>docfile: {cwd}/tests/all/fixtures/report.adoc</p></div>
+      <div class="paragraph"><p>Unresolved directive in report.adoc - include::nope.adoc[]</p></div>
+    "#}
+    .replace("{cwd}", &cwd())
+  );
+  let stderr = run_input_expecting_err(
+    &[
+      "--embedded",
+      "--strict",
+      "--safe-mode",
+      "safe",
+      "--input-name",
+      "report.adoc",
+    ],
+    "include::nope.adoc[]",
+  );
+  assert!(stderr.contains("--> report.adoc:1:10"));
+}
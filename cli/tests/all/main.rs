@@ -1,5 +1,10 @@
+mod build;
 mod css;
 mod docinfo;
+mod fmt;
 mod helpers;
 mod includes;
 mod opts;
+mod remote;
+mod self_contained;
+mod watch;
@@ -71,3 +71,150 @@ fn attr_precedence_soft_unset_allows_document_override() {
   let stdout = run_input(&["-a", "!sectids@", "-e"], input);
   assert!(stdout.contains("id=\"_section_title\""));
 }
+
+#[test]
+fn format_ast_json() {
+  let stdout = run_input(&["--format", "ast-json"], "Hello *world*.");
+  assert!(stdout.contains("\"context\": \"Paragraph\""));
+  assert!(stdout.contains("\"Text\": \"world\""));
+  assert!(stdout.contains("\"doctype\": \"Article\""));
+}
+
+#[test]
+fn format_asg_json_omits_included_locations() {
+  let stdout = run_file(
+    &["--format", "asg-json", "--safe-mode", "unsafe"],
+    "tests/all/fixtures/gen/a.adoc",
+  );
+  let asg: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+  let blocks = asg["blocks"].as_array().unwrap();
+  assert_eq!(blocks.len(), 4);
+  assert_eq!(blocks[1]["location"][0]["line"], 3);
+  assert_eq!(blocks[1]["inlines"][0]["location"][0]["line"], 3);
+  // offsets of included content aren't into the root source
+  assert!(blocks[3].get("location").is_none());
+  assert!(blocks[3]["inlines"][0].get("location").is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn block_processor_command() {
+  let input = "[shout]\n----\nhello\nworld\n----\n\nafter";
+  let stdout = run_input(&["--block-processor", "shout=tr a-z A-Z", "-e"], input);
+  assert!(stdout.contains("HELLO\nWORLD"));
+  assert!(stdout.contains("<p>after</p>"));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_processor_command_failure() {
+  let input = "[shout]\n----\na < b\n----";
+  let stderr = run_input_expecting_err(&["--strict", "--block-processor", "shout=exit 3"], input);
+  assert!(stderr.contains("Block processor `shout` failed"));
+}
+
+#[cfg(unix)]
+#[test]
+fn postprocessor_commands() {
+  let input = "= Guide\n\nhello";
+  let stdout = run_input(
+    &[
+      "--postprocessor",
+      "sed 's|</body>|<script>track()</script></body>|'",
+      "--postprocessor",
+      "sed \"s|<title>|<title>$ASCIIDORK_DOCTYPE: |\"",
+    ],
+    input,
+  );
+  assert!(stdout.contains("<script>track()</script></body>"));
+  assert!(stdout.contains("<title>article: Guide</title>"));
+}
+
+#[cfg(unix)]
+#[test]
+fn postprocessor_command_failure() {
+  let stderr = run_input_expecting_err(&["--postprocessor", "exit 3"], "hello");
+  assert!(stderr.contains("`exit 3` exit status: 3"));
+}
+
+#[test]
+fn failure_level() {
+  let input = "See <<nowhere>>.";
+  let stdout = run_input(&["-e"], input);
+  assert!(stdout.contains("nowhere"));
+
+  let stderr = run_input_expecting_err(&["-e", "--failure-level", "warn"], input);
+  assert!(stderr.contains("Invalid cross reference"));
+  assert!(stderr.contains("1 diagnostic(s) at or above failure level `warn`"));
+
+  run_input(&["-e", "--failure-level", "error"], input);
+  run_input(&["-e", "--strict", "--failure-level", "error"], input);
+}
+
+#[test]
+fn failure_level_below_log_level() {
+  let input = "See <<nowhere>>.";
+  let stderr = run_input_expecting_err(
+    &["-e", "--failure-level", "warn", "--log-level", "error"],
+    input,
+  );
+  assert!(!stderr.contains("Invalid cross reference"));
+  assert!(stderr.contains("diagnostic(s) below log level `error` reached failure level `warn`"));
+}
+
+#[test]
+fn failure_level_info() {
+  let input = "include::nope.adoc[opts=optional]";
+  run_input(&["-e", "--safe-mode", "safe"], input);
+  let stderr = run_input_expecting_err(
+    &["-e", "--safe-mode", "safe", "--failure-level", "info"],
+    input,
+  );
+  assert!(stderr.contains("diagnostic(s) below log level `warn` reached failure level `info`"));
+  let stderr = run_input_expecting_err(
+    &[
+      "-e",
+      "--safe-mode",
+      "safe",
+      "--failure-level",
+      "info",
+      "--log-level",
+      "info",
+    ],
+    input,
+  );
+  assert!(stderr.contains("1 diagnostic(s) at or above failure level `info`"));
+}
+
+#[test]
+fn sarif_diagnostics() {
+  let stderr = run_expecting_err(
+    &["-e", "--sarif", "--failure-level", "warn"],
+    "tests/all/fixtures/broken-site/index.adoc",
+  );
+  let json = &stderr[stderr.find("{\n").unwrap()..=stderr.rfind('}').unwrap()];
+  let log: serde_json::Value = serde_json::from_str(json).unwrap();
+  assert_eq!(log["version"], "2.1.0");
+  let run = &log["runs"][0];
+  assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "xref-invalid");
+  let result = run["results"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|result| {
+      let message = result["message"]["text"].as_str().unwrap();
+      message.contains("`other.adoc#nope`")
+    })
+    .unwrap();
+  assert_eq!(result["ruleId"], "xref-invalid");
+  assert_eq!(result["level"], "warning");
+  let location = &result["locations"][0]["physicalLocation"];
+  assert_eq!(
+    location["artifactLocation"]["uri"],
+    "tests/all/fixtures/broken-site/index.adoc"
+  );
+  assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+  assert_eq!(location["region"]["startLine"], 3);
+  assert_eq!(location["region"]["startColumn"], 34);
+  assert_eq!(location["region"]["endColumn"], 49);
+}
@@ -0,0 +1,192 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::helpers::*;
+
+/// a local stand-in for a remote host, sending the same response to every request
+struct StandIn {
+  addr: String,
+  hits: Arc<AtomicUsize>,
+}
+
+impl StandIn {
+  fn serve(body: &'static str) -> Self {
+    Self::respond(format!(
+      "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+      body.len()
+    ))
+  }
+
+  fn redirect(location: &str) -> Self {
+    Self::respond(format!(
+      "HTTP/1.1 302 Found\r\nLocation: {location}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+    ))
+  }
+
+  fn respond(response: String) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    thread::spawn(move || {
+      for mut stream in listener.incoming().flatten() {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let mut reader = BufReader::new(&stream);
+        let mut line = String::new();
+        while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+          line.clear();
+        }
+        _ = stream.write_all(response.as_bytes());
+      }
+    });
+    Self { addr, hits }
+  }
+
+  fn include(&self) -> String {
+    format!("include::http://{}/remote.adoc[]", self.addr)
+  }
+
+  fn hits(&self) -> usize {
+    self.hits.load(Ordering::SeqCst)
+  }
+}
+
+fn cache_dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("asciidork-uri-cache-{name}"));
+  _ = fs::remove_dir_all(&dir);
+  dir
+}
+
+fn remote_args<'a>(cache_dir: &'a Path, extra: &[&'a str]) -> Vec<&'a str> {
+  let mut args = vec![
+    "--embedded",
+    "--strict",
+    "--safe-mode",
+    "safe",
+    "--attribute",
+    "allow-uri-read",
+    "--uri-cache-dir",
+    cache_dir.to_str().unwrap(),
+  ];
+  args.extend(extra);
+  args
+}
+
+#[test]
+fn cache_uri_serves_repeat_reads_from_cache() {
+  let server = StandIn::serve("from the stand-in");
+  let dir = cache_dir("repeat");
+  let args = remote_args(&dir, &["--attribute", "cache-uri"]);
+  for _ in 0..2 {
+    let stdout = run_input(&args, &server.include());
+    assert!(stdout.contains("<p>from the stand-in</p>"));
+  }
+  assert_eq!(server.hits(), 1);
+
+  // without cache-uri, every read goes to the network
+  let stdout = run_input(&remote_args(&dir, &[]), &server.include());
+  assert!(stdout.contains("<p>from the stand-in</p>"));
+  assert_eq!(server.hits(), 2);
+
+  let stdout = run_input(&remote_args(&dir, &["--offline"]), &server.include());
+  assert!(stdout.contains("<p>from the stand-in</p>"));
+  assert_eq!(server.hits(), 2);
+}
+
+#[test]
+fn offline_without_cached_uri() {
+  let server = StandIn::serve("never read");
+  let dir = cache_dir("offline");
+  let stderr = run_input_expecting_err(&remote_args(&dir, &["--offline"]), &server.include());
+  assert!(stderr.contains(&format!(
+    "`http://{}/remote.adoc` is not cached and --offline is set",
+    server.addr
+  )));
+  assert_eq!(server.hits(), 0);
+}
+
+#[test]
+fn host_allow_and_deny_lists() {
+  let server = StandIn::serve("allowed");
+  let dir = cache_dir("hosts");
+  let stderr = run_input_expecting_err(
+    &remote_args(&dir, &["--deny-host", "127.0.0.1"]),
+    &server.include(),
+  );
+  assert!(stderr.contains("host `127.0.0.1` is denied by --deny-host"));
+
+  let stderr = run_input_expecting_err(
+    &remote_args(&dir, &["--allow-host", "example.com"]),
+    &server.include(),
+  );
+  assert!(stderr.contains("host `127.0.0.1` is not allowed by --allow-host"));
+  assert_eq!(server.hits(), 0);
+
+  let args = remote_args(&dir, &["--allow-host", "127.0.0.1"]);
+  let stdout = run_input(&args, &server.include());
+  assert!(stdout.contains("<p>allowed</p>"));
+}
+
+#[test]
+fn host_lists_refuse_redirects() {
+  let target = StandIn::serve("redirected");
+  let server = StandIn::redirect(&format!(
+    "http://{}/",
+    target.addr.replace("127.0.0.1", "localhost")
+  ));
+  let dir = cache_dir("redirects");
+  let stderr = run_input_expecting_err(
+    &remote_args(&dir, &["--allow-host", "127.0.0.1"]),
+    &server.include(),
+  );
+  assert!(stderr.contains("redirects are not followed when --allow-host or --deny-host is set"));
+  assert_eq!(server.hits(), 1);
+  assert_eq!(target.hits(), 0);
+
+  let stdout = run_input(&remote_args(&dir, &[]), &server.include());
+  assert!(stdout.contains("<p>redirected</p>"));
+  assert_eq!(target.hits(), 1);
+}
+
+#[test]
+fn uri_max_bytes() {
+  let server = StandIn::serve("more than ten bytes");
+  let dir = cache_dir("max-bytes");
+  let stderr = run_input_expecting_err(
+    &remote_args(&dir, &["--uri-max-bytes", "10", "--attribute", "cache-uri"]),
+    &server.include(),
+  );
+  assert!(stderr.contains("response exceeds --uri-max-bytes of 10"));
+  assert!(!dir.exists());
+}
+
+#[test]
+fn self_contained_honors_uri_options() {
+  let server = StandIn::serve("not an image");
+  let dir = cache_dir("self-contained");
+  let image = format!("image::http://{}/remote.png[]", server.addr);
+  let stderr = run_input_expecting_err(
+    &remote_args(&dir, &["--self-contained", "--offline"]),
+    &image,
+  );
+  assert!(stderr.contains(&format!(
+    "WARNING: could not embed `http://{}/remote.png`: Error reading URI: `http://{0}/remote.png` is not cached and --offline is set",
+    server.addr
+  )));
+  assert_eq!(server.hits(), 0);
+
+  let args = remote_args(&dir, &["--self-contained", "--attribute", "cache-uri"]);
+  let stdout = run_input(&args, &image);
+  assert!(stdout.contains(r#"<img src="data:image/png;base64,bm90IGFuIGltYWdl""#));
+  assert_eq!(server.hits(), 1);
+
+  let args = remote_args(&dir, &["--self-contained", "--offline"]);
+  let stdout = run_input(&args, &image);
+  assert!(stdout.contains(r#"<img src="data:image/png;base64,bm90IGFuIGltYWdl""#));
+  assert_eq!(server.hits(), 1);
+}
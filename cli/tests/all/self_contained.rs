@@ -0,0 +1,103 @@
+use crate::helpers::*;
+use test_utils::*;
+
+#[test]
+fn self_contained_embeds_images_and_icons() {
+  let adoc = adoc! {r#"
+    :icons:
+    :iconsdir: images/icons
+    :icontype: gif
+
+    image::mini.svg[mini]
+
+    NOTE: inline image:mini.svg[] too
+  "#};
+  let stdout = run_input(
+    &["--embedded", "--safe-mode", "safe", "--self-contained"],
+    adoc,
+  );
+  let svg = "data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciPjxwYXRoIGQ9Ik0wIDAiLz48L3N2Zz4K";
+  assert!(stdout.contains(&format!(r#"<img src="{svg}" alt="mini">"#)));
+  assert!(stdout.contains(&format!(r#"<img src="{svg}" alt="mini"></span>"#)));
+  assert!(stdout.contains(r#"<img src="data:image/gif;base64,"#));
+  assert!(!stdout.contains("images/icons/note"));
+}
+
+#[test]
+fn self_contained_embeds_docinfo_stylesheet_links() {
+  let stdout = run_file(
+    &["--safe-mode", "safe", "--self-contained", "-a", "webfonts!"],
+    "tests/all/fixtures/self-contained/doc.adoc",
+  );
+  assert!(stdout.contains(
+    "<style>@font-face { font-family: Mini; src: url(data:font/woff2;base64,bWluaQo=); }\n</style>"
+  ));
+  assert!(!stdout.contains("<link rel=\"stylesheet\" href=\"theme.css\""));
+}
+
+#[test]
+fn self_contained_warns_on_unembeddable_assets() {
+  let adoc = adoc! {r#"
+    image::nope.png[]
+
+    image::https://example.com/remote.png[]
+  "#};
+  let stderr = run_input_expecting_err(
+    &[
+      "--embedded",
+      "--safe-mode",
+      "safe",
+      "--self-contained",
+      "--strict",
+    ],
+    adoc,
+  );
+  assert!(stderr.contains("WARNING: could not embed `nope.png`: File not found"));
+  assert!(stderr.contains(
+    "WARNING: could not embed `https://example.com/remote.png`: remote assets require allow-uri-read"
+  ));
+}
+
+#[test]
+fn self_contained_max_embed_size() {
+  let stderr = run_input_expecting_err(
+    &[
+      "--embedded",
+      "--safe-mode",
+      "safe",
+      "--self-contained",
+      "--max-embed-size",
+      "10",
+      "--strict",
+    ],
+    "image::mini.svg[]",
+  );
+  assert!(
+    stderr.contains("WARNING: could not embed `mini.svg`: 63 bytes exceeds --max-embed-size of 10")
+  );
+}
+
+#[test]
+fn self_contained_not_in_secure_mode() {
+  let stderr = run_input_expecting_err(&["--self-contained"], "hello");
+  assert!(stderr.contains("--self-contained cannot read assets in secure mode"));
+}
+
+#[cfg(unix)]
+#[test]
+fn self_contained_postprocesses_embedded_output() {
+  let stdout = run_input(
+    &[
+      "--embedded",
+      "--safe-mode",
+      "safe",
+      "--self-contained",
+      "--postprocessor",
+      "sed 's|</div>$|<img src=\"mini.svg\" alt=\"added\"></div>|'",
+    ],
+    "image::mini.svg[mini]",
+  );
+  assert!(stdout.contains(r#"<img src="data:image/svg+xml;base64,"#));
+  // markup added by a postprocessor is left as-is
+  assert!(stdout.contains(r#"<img src="mini.svg" alt="added">"#));
+}
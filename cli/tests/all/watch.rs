@@ -0,0 +1,136 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn watch_serve_reloads_on_change() {
+  let dir = std::env::temp_dir().join("asciidork-watch-serve");
+  _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+  let input = dir.join("doc.adoc");
+  let partial = dir.join("partial.adoc");
+  fs::write(&input, "= Doc\n\ninclude::partial.adoc[]\n").unwrap();
+  fs::write(&partial, "first version\n").unwrap();
+
+  let (_server, addr) = serve(&input);
+
+  let page = get(&addr, "/");
+  assert!(page.contains("first version"));
+  let version = page.split("?version=").nth(1).unwrap();
+  let version = &version[..version.find('"').unwrap()];
+  let mut events = TcpStream::connect(&addr).unwrap();
+  events
+    .set_read_timeout(Some(Duration::from_secs(10)))
+    .unwrap();
+  write!(
+    events,
+    "GET /__asciidork/events?version={version} HTTP/1.1\r\n\r\n"
+  )
+  .unwrap();
+
+  // changing an included file triggers a reload
+  fs::write(&partial, "second version\n").unwrap();
+  let mut received = String::new();
+  let mut buf = [0; 256];
+  while !received.contains("data: reload") {
+    let len = events.read(&mut buf).unwrap();
+    assert!(len > 0, "events stream closed");
+    received.push_str(&String::from_utf8_lossy(&buf[..len]));
+  }
+  assert!(get(&addr, "/").contains("second version"));
+
+  // errors are shown over the last good page
+  fs::write(&partial, "see <<nope>>\n").unwrap();
+  let page = poll(&addr, "asciidork-overlay");
+  assert!(page.contains("second version"));
+  assert!(page.contains("Invalid cross reference, no anchor found for `nope`"));
+
+  fs::write(&partial, "third version\n").unwrap();
+  let page = poll(&addr, "third version");
+  assert!(!page.contains("asciidork-overlay"));
+}
+
+#[test]
+fn watch_serve_rebuilds_when_new_include_is_fixed() {
+  let dir = std::env::temp_dir().join("asciidork-watch-new-include");
+  _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+  let input = dir.join("doc.adoc");
+  fs::write(&input, "= Doc\n\nfirst version\n").unwrap();
+  let (_server, addr) = serve(&input);
+  poll(&addr, "first version");
+
+  // the parse fails on the new include, before a good build lists it
+  let added = dir.join("added.adoc");
+  fs::write(&added, "see <<nope>>\n").unwrap();
+  fs::write(&input, "= Doc\n\ninclude::added.adoc[]\n").unwrap();
+  poll(&addr, "asciidork-overlay");
+
+  fs::write(&added, "fixed version\n").unwrap();
+  let page = poll(&addr, "fixed version");
+  assert!(!page.contains("asciidork-overlay"));
+}
+
+/// starts `--watch --serve` for `input`, returning it and its address
+fn serve(input: &Path) -> (Server, String) {
+  let mut server = Server(
+    Command::new(env!("CARGO_BIN_EXE_asciidork"))
+      .args(["--input", input.to_str().unwrap()])
+      .args([
+        "--watch",
+        "--serve",
+        "--port",
+        "0",
+        "--strict",
+        "--safe-mode",
+        "safe",
+      ])
+      .stdout(Stdio::null())
+      .stderr(Stdio::piped())
+      .spawn()
+      .unwrap(),
+  );
+  // borrowed, a closed pipe would fail the server's later writes
+  let mut stderr = BufReader::new(server.0.stderr.as_mut().unwrap());
+  let addr = loop {
+    let mut line = String::new();
+    assert!(stderr.read_line(&mut line).unwrap() > 0, "server exited");
+    if let Some(addr) = line.trim().strip_prefix("Serving at http://") {
+      break addr.to_string();
+    }
+  };
+  (server, addr)
+}
+
+struct Server(Child);
+
+impl Drop for Server {
+  fn drop(&mut self) {
+    _ = self.0.kill();
+    _ = self.0.wait();
+  }
+}
+
+fn get(addr: &str, path: &str) -> String {
+  let mut stream = TcpStream::connect(addr).unwrap();
+  write!(stream, "GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n").unwrap();
+  let mut response = String::new();
+  stream.read_to_string(&mut response).unwrap();
+  response
+}
+
+fn poll(addr: &str, needle: &str) -> String {
+  let start = Instant::now();
+  loop {
+    let page = get(addr, "/");
+    if page.contains(needle) {
+      return page;
+    }
+    assert!(start.elapsed() < Duration::from_secs(10), "timed out");
+    thread::sleep(Duration::from_millis(50));
+  }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use crate::validate;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AttrValue {
   String(String),
   Bool(bool),
@@ -107,6 +108,7 @@ pub trait ReadAttr {
 }
 
 #[derive(Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Attrs(HashMap<String, AttrValue>);
 
 impl std::fmt::Debug for Attrs {
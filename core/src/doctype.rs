@@ -4,6 +4,7 @@ use std::str::FromStr;
 use crate::internal::*;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum DocType {
   #[default]
   Article,
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use crate::internal::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DocumentMeta {
   pub authors: Vec<Author>,
   doctype: DocType,
@@ -4,6 +4,7 @@ use crate::internal::*;
 use crate::validate;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct JobAttr {
   pub readonly: bool,
   pub value: AttrValue,
@@ -20,6 +21,7 @@ impl JobAttr {
 }
 
 #[derive(Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct JobAttrs(HashMap<String, JobAttr>);
 
 impl JobAttrs {
@@ -6,7 +6,11 @@ pub struct JobSettings {
   pub safe_mode: SafeMode,
   pub job_attrs: JobAttrs,
   pub embedded: bool, // TODO: not needed by parser, consider making backend-only
-  pub strict: bool,   // TODO: expand to log-level and failure-level
+  /// stop parsing at the first diagnostic at or above `failure_level`
+  pub strict: bool,
+  /// diagnostics less severe than this are dropped
+  pub log_level: Severity,
+  pub failure_level: Severity,
 }
 
 impl JobSettings {
@@ -51,6 +55,8 @@ impl Default for JobSettings {
       job_attrs: JobAttrs::default(),
       embedded: false,
       strict: true,
+      log_level: Severity::Info,
+      failure_level: Severity::Warn,
     }
   }
 }
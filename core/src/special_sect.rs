@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SpecialSection {
   Abstract,
   Colophon,
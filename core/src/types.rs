@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SafeMode {
   Unsafe,
   Safe,
@@ -22,6 +23,40 @@ impl FromStr for SafeMode {
   }
 }
 
+/// how serious a diagnostic is, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord, Hash)]
+pub enum Severity {
+  Info,
+  #[default]
+  Warn,
+  Error,
+  Fatal,
+}
+
+impl Severity {
+  pub const fn to_str(&self) -> &'static str {
+    match self {
+      Severity::Info => "info",
+      Severity::Warn => "warn",
+      Severity::Error => "error",
+      Severity::Fatal => "fatal",
+    }
+  }
+}
+
+impl FromStr for Severity {
+  type Err = &'static str;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_ascii_lowercase().as_str() {
+      "info" => Ok(Severity::Info),
+      "warn" | "warning" => Ok(Severity::Warn),
+      "error" => Ok(Severity::Error),
+      "fatal" => Ok(Severity::Fatal),
+      _ => Err("Invalid level: expected `info`, `warn`, `error`, or `fatal`"),
+    }
+  }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum IconMode {
   #[default]
@@ -31,6 +66,7 @@ pub enum IconMode {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Author {
   pub first_name: String,
   pub middle_name: Option<String>,
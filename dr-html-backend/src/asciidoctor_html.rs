@@ -42,6 +42,7 @@ impl Backend for AsciidoctorHtml {
     self.doc_meta = document.meta.clone();
     set_backend_attrs::<Self>(&mut self.doc_meta);
     self.state.section_num_levels = document.meta.isize("sectnumlevels").unwrap_or(3);
+    self.enable_index(document);
     if document.meta.is_true("hardbreaks-option") {
       self.default_newlines = Newlines::JoinWithBreak
     }
@@ -58,7 +59,9 @@ impl Backend for AsciidoctorHtml {
   }
 
   #[instrument(skip_all)]
-  fn exit_document(&mut self, _document: &Document) {}
+  fn exit_document(&mut self, _document: &Document) {
+    self.render_index();
+  }
 
   #[instrument(skip_all)]
   fn enter_body(&mut self, document: &Document) {
@@ -130,6 +133,9 @@ impl Backend for AsciidoctorHtml {
     if !self.doc_meta.embedded && !self.doc_meta.is_true("nofooter") {
       self.push_str("</div>")
     }
+    if self.standalone() && self.doc_meta.str("stem").is_some() {
+      self.push_str(&stem::mathjax_scripts(&self.doc_meta));
+    }
   }
 
   #[instrument(skip_all)]
@@ -282,6 +288,7 @@ impl Backend for AsciidoctorHtml {
 
   #[instrument(skip_all)]
   fn exit_section(&mut self, section: &Section) {
+    self.mark_index_section(section);
     if section.level == 1 {
       self.push_str("</div>");
     }
@@ -353,6 +360,10 @@ impl Backend for AsciidoctorHtml {
     self.render_buffered_block_title(block);
     self.push_str(r#"<div class="content"><pre"#);
     let doc_lang = self.doc_meta.string("source-language");
+    if (block.meta.attrs.is_source() || doc_lang.is_some()) && self.enter_highlighted_source(block)
+    {
+      return;
+    }
     if block.meta.attrs.is_source() || doc_lang.is_some() {
       self.push_str(r#" class="highlight"><code"#);
       if let Some(lang) = block.meta.attrs.source_language() {
@@ -370,6 +381,11 @@ impl Backend for AsciidoctorHtml {
 
   #[instrument(skip_all)]
   fn exit_listing_block(&mut self, _block: &Block) {
+    if self.state.ephemeral.remove(&IsHighlightedSourceBlock) {
+      self.swap_discard_alt_buffer();
+      self.push_str("</code></pre></div></div>");
+      return;
+    }
     if self.state.ephemeral.remove(&IsSourceBlock) {
       self.push_str("</code>");
     }
@@ -391,6 +407,23 @@ impl Backend for AsciidoctorHtml {
     self.newlines = self.default_newlines;
   }
 
+  #[instrument(skip_all)]
+  fn enter_stem_block(&mut self, block: &Block, _notation: StemNotation) {
+    self.open_element("div", &["stemblock"], &block.meta.attrs);
+    self.render_buffered_block_title(block);
+    self.push_str(r#"<div class="content">"#);
+    self.newlines = Newlines::Preserve;
+    self.start_buffering();
+  }
+
+  #[instrument(skip_all)]
+  fn exit_stem_block(&mut self, _block: &Block, notation: StemNotation) {
+    let equation = self.swap_take_buffer();
+    self.push_str(&stem::block_equation(notation, &equation));
+    self.push_str("</div></div>");
+    self.newlines = self.default_newlines;
+  }
+
   #[instrument(skip_all)]
   fn enter_quoted_paragraph(&mut self, block: &Block) {
     self.open_element("div", &["quoteblock"], &block.meta.attrs);
@@ -779,6 +812,7 @@ impl Backend for AsciidoctorHtml {
   fn asciidoc_table_cell_backend(&mut self) -> Self {
     let mut backend = Self::default();
     backend.state.footnotes = Rc::clone(&self.state.footnotes);
+    backend.state.index = Rc::clone(&self.state.index);
     backend.state.in_asciidoc_table_cell = true;
     backend
   }
@@ -916,6 +950,16 @@ impl Backend for AsciidoctorHtml {
     self.push_str("</code>");
   }
 
+  #[instrument(skip_all)]
+  fn enter_inline_stem(&mut self, notation: StemNotation) {
+    self.push_str(stem::inline_delimiters(notation).0);
+  }
+
+  #[instrument(skip_all)]
+  fn exit_inline_stem(&mut self, notation: StemNotation) {
+    self.push_str(stem::inline_delimiters(notation).1);
+  }
+
   #[instrument(skip_all)]
   fn enter_inline_highlight(&mut self, attrs: Option<&AttrList>) {
     self.open_element_opt("mark", &[], attrs);
@@ -1498,12 +1542,61 @@ impl Backend for AsciidoctorHtml {
 
   #[instrument(skip_all)]
   fn enter_visible_index_term(&mut self) -> bool {
+    if self.start_index_term() {
+      self.start_index_term_text();
+    }
     true
   }
 
+  #[instrument(skip_all)]
+  fn exit_visible_index_term(&mut self) {
+    self.end_index_term_text();
+    self.end_index_term(false);
+  }
+
   #[instrument(skip_all)]
   fn enter_concealed_index_term(&mut self, _num_terms: u8) -> bool {
-    false
+    self.start_index_term()
+  }
+
+  #[instrument(skip_all)]
+  fn exit_concealed_index_term(&mut self, _num_terms: u8) {
+    self.end_index_term(true);
+  }
+
+  #[instrument(skip_all)]
+  fn enter_concealed_index_term_primary(&mut self) {
+    self.start_index_term_text();
+  }
+
+  #[instrument(skip_all)]
+  fn exit_concealed_index_term_primary(&mut self) {
+    self.end_index_term_text();
+  }
+
+  #[instrument(skip_all)]
+  fn enter_concealed_index_term_secondary(&mut self) {
+    self.start_index_term_text();
+  }
+
+  #[instrument(skip_all)]
+  fn exit_concealed_index_term_secondary(&mut self) {
+    self.end_index_term_text();
+  }
+
+  #[instrument(skip_all)]
+  fn enter_concealed_index_term_tertiary(&mut self) {
+    self.start_index_term_text();
+  }
+
+  #[instrument(skip_all)]
+  fn exit_concealed_index_term_tertiary(&mut self) {
+    self.end_index_term_text();
+  }
+
+  #[instrument(skip_all)]
+  fn visit_index_term_reference(&mut self, term_ref: &IndexTermReference) {
+    self.set_index_term_reference(term_ref);
   }
 
   fn into_result(self) -> Result<Self::Output, Self::Error> {
@@ -1617,6 +1710,34 @@ impl AsciidoctorHtml {
     self.state.footnotes = footnotes;
   }
 
+  /// renders the entire content of a source block w/ the native highlighter,
+  /// returning false if the block can't be highlighted, in which case the
+  /// normal inline-by-inline rendering should proceed
+  fn enter_highlighted_source(&mut self, block: &Block) -> bool {
+    let Some(highlighter) = Highlighter::from_doc_meta(&self.doc_meta) else {
+      return false;
+    };
+    let BlockContent::Simple(nodes) = &block.content else {
+      return false;
+    };
+    let include_tucks = self.doc_meta.icon_mode() != IconMode::Font;
+    let Some(source) = HighlightSource::from_nodes(nodes, include_tucks) else {
+      return false;
+    };
+    let doc_lang = self.doc_meta.string("source-language");
+    let lang = block.meta.attrs.source_language().or(doc_lang.as_deref());
+    self.push([r#" class=""#, highlighter.name(), r#" highlight"><code"#]);
+    if let Some(lang) = lang {
+      self.push([r#" data-lang=""#, lang, "\""]);
+    }
+    self.push_ch('>');
+    self.render_highlighted_source(&source, lang, highlighter, block);
+    // inline content was rendered above, discard the normal eval output
+    self.state.ephemeral.insert(IsHighlightedSourceBlock);
+    self.start_buffering();
+    true
+  }
+
   fn render_styles(&mut self, meta: &DocumentMeta) {
     if meta.str("stylesheet") == Some("") {
       let family = match meta.str("webfonts") {
@@ -1633,6 +1754,11 @@ impl AsciidoctorHtml {
     }
 
     self.render_embedded_stylesheet(crate::css::DEFAULT);
+    if let Some(highlighter) = Highlighter::from_doc_meta(meta)
+      && !meta.is_true("linkcss")
+    {
+      self.push(["<style>", &highlighter.stylesheet(), "</style>"]);
+    }
   }
 
   fn render_checklist_item(&mut self, item: &ListItem) {
@@ -8,10 +8,18 @@ mod asciidoctor_html;
 pub mod css;
 
 pub use asciidoctor_html::AsciidoctorHtml;
-pub use backend::Backend;
+pub use backend::{Backend, Postprocessor};
 
 pub fn convert(document: ast::Document) -> Result<String, Box<dyn Error>> {
-  Ok(eval::eval(&document, AsciidoctorHtml::new())?)
+  convert_with_postprocessors(document, &mut [])
+}
+
+pub fn convert_with_postprocessors(
+  document: ast::Document,
+  postprocessors: &mut [Box<dyn Postprocessor>],
+) -> Result<String, Box<dyn Error>> {
+  let html = eval::eval(&document, AsciidoctorHtml::new())?;
+  Ok(backend::postprocess(html, &document.meta, postprocessors)?)
 }
 
 mod internal {
@@ -23,7 +31,9 @@ mod internal {
 
   pub use asciidork_core::*;
   pub use ast::prelude::*;
+  pub use backend::highlight::{HighlightSource, Highlighter};
   pub use backend::html::backend::*;
+  pub use backend::html::stem;
   pub use backend::html::{AltHtmlBuf, HtmlBuf, OpenTag};
   pub use backend::prelude::*;
   pub use backend::utils;
@@ -0,0 +1,174 @@
+use asciidork_ast::prelude::*;
+use asciidork_ast::{InlineNode, InlineNodes};
+use asciidork_core::JobSettings;
+use asciidork_parser::prelude::*;
+use asciidork_parser::{BlockProcessor, BlockProcessorInput, BlockProcessorOutput};
+use bumpalo::collections::{String as BumpString, Vec as BumpVec};
+use test_utils::*;
+
+/// passes lines through uppercased, wrapped in the style's 1st positional tag
+#[derive(Clone)]
+struct Shout;
+
+impl BlockProcessor for Shout {
+  fn process<'arena>(
+    &mut self,
+    input: BlockProcessorInput<'_, 'arena>,
+  ) -> Result<BlockProcessorOutput<'arena>, String> {
+    let tag = input.attrs.str_positional_at(1).unwrap_or("p");
+    let text = input.lines.join("\n").to_uppercase();
+    Ok(BlockProcessorOutput::Raw(format!("<{tag}>{text}</{tag}>")))
+  }
+
+  fn clone_box(&self) -> Box<dyn BlockProcessor> {
+    Box::new(self.clone())
+  }
+}
+
+/// replaces the block with a paragraph counting its lines
+#[derive(Clone)]
+struct CountLines;
+
+impl BlockProcessor for CountLines {
+  fn process<'arena>(
+    &mut self,
+    input: BlockProcessorInput<'_, 'arena>,
+  ) -> Result<BlockProcessorOutput<'arena>, String> {
+    let text = format!("{} lines in {:?} block", input.lines.len(), input.context);
+    let mut nodes = InlineNodes::new(input.bump);
+    nodes.push(InlineNode::new(
+      Inline::Text(BumpString::from_str_in(&text, input.bump)),
+      SourceLocation::default(),
+    ));
+    let mut blocks = BumpVec::new_in(input.bump);
+    blocks.push(Block {
+      meta: ChunkMeta::empty(SourceLocation::default(), input.bump),
+      context: BlockContext::Paragraph,
+      content: BlockContent::Simple(nodes),
+      loc: SourceLocation::default().into(),
+    });
+    Ok(BlockProcessorOutput::Blocks(blocks))
+  }
+
+  fn clone_box(&self) -> Box<dyn BlockProcessor> {
+    Box::new(self.clone())
+  }
+}
+
+#[derive(Clone)]
+struct Fail;
+
+impl BlockProcessor for Fail {
+  fn process<'arena>(
+    &mut self,
+    _: BlockProcessorInput<'_, 'arena>,
+  ) -> Result<BlockProcessorOutput<'arena>, String> {
+    Err("renderer not installed".to_string())
+  }
+
+  fn clone_box(&self) -> Box<dyn BlockProcessor> {
+    Box::new(self.clone())
+  }
+}
+
+fn processed_html(input: &str) -> (String, Vec<String>) {
+  let bump = &Bump::new();
+  let mut settings = JobSettings::embedded();
+  settings.strict = false;
+  let mut parser = Parser::from_str(input, SourceFile::Tmp, bump);
+  parser.apply_job_settings(settings);
+  parser.register_block_processor("shout", Box::new(Shout));
+  parser.register_block_processor("count", Box::new(CountLines));
+  parser.register_block_processor("fail", Box::new(Fail));
+  let result = parser.parse().unwrap();
+  let warnings = result.warnings.iter().map(|w| w.message.clone()).collect();
+  let html =
+    asciidork_eval::eval(&result.document, crate::helpers::test_backend_factory()).unwrap();
+  (html, warnings)
+}
+
+#[test]
+fn raw_output_from_listing_block() {
+  let (html, warnings) = processed_html(adoc! {r#"
+    [shout,h2]
+    ----
+    hello
+    world
+    ----
+
+    after
+  "#});
+  expect_eq!(
+    html,
+    "<h2>HELLO\nWORLD</h2><div class=\"paragraph\"><p>after</p></div>"
+  );
+  assert!(warnings.is_empty());
+}
+
+#[test]
+fn raw_output_from_paragraph() {
+  let (html, _) = processed_html("[shout]\nhello *world*\n");
+  expect_eq!(html, "<p>HELLO *WORLD*</p>");
+}
+
+#[test]
+fn unclaimed_styles_parse_normally() {
+  let (html, _) = processed_html("[verse]\nhello\n");
+  assert!(html.contains(r#"<div class="verseblock">"#));
+}
+
+#[test]
+fn replacement_block_takes_metadata() {
+  let (html, _) = processed_html(adoc! {r#"
+    .Counted
+    [count#counted.big]
+    --
+    one
+
+    three
+    --
+  "#});
+  expect_eq!(
+    html,
+    html! {r#"
+      <div id="counted" class="paragraph big">
+        <div class="title">Counted</div>
+        <p>3 lines in Open block</p>
+      </div>
+    "#}
+  );
+}
+
+#[test]
+fn claimed_block_content_not_parsed() {
+  let (html, warnings) = processed_html(adoc! {r#"
+    [count]
+    ====
+    |===
+    <<nowhere>>
+    ====
+  "#});
+  expect_eq!(
+    html,
+    r#"<div class="paragraph"><p>2 lines in Example block</p></div>"#
+  );
+  assert!(warnings.is_empty());
+}
+
+#[test]
+fn failed_processor_falls_back_to_listing() {
+  let (html, warnings) = processed_html(adoc! {r#"
+    [fail]
+    ....
+    a < b
+    ....
+  "#});
+  expect_eq!(
+    html,
+    r#"<div class="listingblock"><div class="content"><pre>a &lt; b</pre></div></div>"#
+  );
+  expect_eq!(
+    warnings,
+    vec!["Block processor `fail` failed: renderer not installed".to_string()]
+  );
+}
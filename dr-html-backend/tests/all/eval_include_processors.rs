@@ -0,0 +1,140 @@
+use asciidork_ast::{AttrData, AttrList};
+use asciidork_core::{DocumentMeta, JobSettings, SafeMode};
+use asciidork_parser::Preprocessor;
+use asciidork_parser::includes::{ConstResolver, IncludeProcessor, ProcessedInclude};
+use asciidork_parser::prelude::*;
+use test_utils::*;
+
+/// replaces `%VERSION%` everywhere, recording which files it saw
+#[derive(Clone)]
+struct Version;
+
+impl Preprocessor for Version {
+  fn process(&mut self, file: &SourceFile, src: &str, _: &DocumentMeta) -> Option<String> {
+    if !src.contains("%VERSION%") {
+      return None;
+    }
+    Some(src.replace("%VERSION%", &format!("1.2.3 ({})", file.file_name())))
+  }
+
+  fn clone_box(&self) -> Box<dyn Preprocessor> {
+    Box::new(self.clone())
+  }
+}
+
+/// generates asciidoc for `gen:<name>` targets
+#[derive(Clone)]
+struct Generator;
+
+impl IncludeProcessor for Generator {
+  fn handles(&self, target: &str) -> bool {
+    target.starts_with("gen:")
+  }
+
+  fn process(
+    &mut self,
+    target: &str,
+    attrs: &AttrList,
+    _: &DocumentMeta,
+  ) -> Result<ProcessedInclude, String> {
+    let name = target.trim_start_matches("gen:");
+    let asciidoc = match name {
+      "api-table" => {
+        let cols = attrs.named("cols").unwrap_or("2");
+        format!("|===\n|Endpoint |Version\n\n|/users |%VERSION%\n|===\n// cols={cols}")
+      }
+      "broken" => "para\n\n----\nnever closed".to_string(),
+      _ => return Err(format!("unknown generator `{name}`")),
+    };
+    Ok(ProcessedInclude {
+      file: SourceFile::Path(Path::new(format!("gen/{name}.adoc"))),
+      asciidoc,
+    })
+  }
+
+  fn clone_box(&self) -> Box<dyn IncludeProcessor> {
+    Box::new(self.clone())
+  }
+}
+
+fn processed(input: &str, settings: JobSettings) -> (String, Vec<Diagnostic>) {
+  let bump = &Bump::new();
+  let mut parser = Parser::from_str(input, SourceFile::Path(Path::new("doc.adoc")), bump);
+  parser.apply_job_settings(settings);
+  parser.set_resolver(Box::new(ConstResolver(Vec::from("included %VERSION%"))));
+  parser.register_preprocessor(Box::new(Version));
+  parser.register_include_processor(Box::new(Generator));
+  let result = parser.parse().unwrap();
+  let html =
+    asciidork_eval::eval(&result.document, crate::helpers::test_backend_factory()).unwrap();
+  (html, result.warnings)
+}
+
+fn processed_html(input: &str) -> (String, Vec<Diagnostic>) {
+  let mut settings = JobSettings::embedded();
+  settings.strict = false;
+  settings.safe_mode = SafeMode::Safe;
+  processed(input, settings)
+}
+
+#[test]
+fn preprocessor_runs_on_root_and_includes() {
+  let (html, warnings) = processed_html(adoc! {r#"
+    version %VERSION%
+
+    include::other.adoc[]
+  "#});
+  expect_eq!(
+    html,
+    html! {r#"
+      <div class="paragraph"><p>version 1.2.3 (doc.adoc)</p></div>
+      <div class="paragraph"><p>included 1.2.3 (other.adoc)</p></div>
+    "#}
+  );
+  assert!(warnings.is_empty());
+}
+
+#[test]
+fn include_processor_generates_asciidoc() {
+  let (html, warnings) = processed_html("include::gen:api-table[cols=3]\n");
+  assert!(html.contains(
+    "<td class=\"tableblock halign-left valign-top\"><p class=\"tableblock\">/users</p></td>"
+  ));
+  assert!(html.contains("1.2.3 (api-table.adoc)"));
+  assert!(warnings.is_empty());
+}
+
+#[test]
+fn claimed_includes_ignore_secure_mode() {
+  let mut settings = JobSettings::embedded();
+  settings.strict = false;
+  settings.safe_mode = SafeMode::Secure;
+  let (html, _) = processed("include::gen:api-table[]\n", settings);
+  assert!(html.contains("/users"));
+}
+
+#[test]
+fn generated_source_diagnostics_attributed() {
+  let (html, warnings) = processed_html("intro\n\ninclude::gen:broken[]\n");
+  assert!(html.contains("never closed"));
+  assert_eq!(warnings.len(), 1);
+  expect_eq!(warnings[0].message, "This delimiter was never closed");
+  expect_eq!(
+    warnings[0].source_file,
+    SourceFile::Path(Path::new("gen/broken.adoc"))
+  );
+  expect_eq!(warnings[0].line_num, 3);
+}
+
+#[test]
+fn failed_include_processor() {
+  let (html, warnings) = processed_html("include::gen:nope[]\n");
+  expect_eq!(
+    html,
+    r#"<div class="paragraph"><p>Unresolved directive in doc.adoc - include::gen:nope[]</p></div>"#
+  );
+  expect_eq!(
+    warnings[0].message,
+    "Include processor failed: unknown generator `nope`"
+  );
+}
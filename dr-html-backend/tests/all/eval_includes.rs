@@ -205,6 +205,34 @@ assert_html!(
   "#}
 );
 
+assert_html!(
+  include_honors_single_byte_encoding,
+  resolving: b"caf\xE9 \x93quoted\x94",
+  adoc! {r#"
+    include::some_file.adoc[encoding=windows-1252]
+  "#},
+  html! {r#"
+    <div class="paragraph">
+      <p>café “quoted”</p>
+    </div>
+  "#}
+);
+
+assert_html!(
+  include_defaults_to_document_encoding,
+  resolving: b"\x93\xFA\x96\x7B\x8C\xEA",
+  adoc! {r#"
+    :encoding: Shift_JIS
+
+    include::some_file.adoc[]
+  "#},
+  html! {r#"
+    <div class="paragraph">
+      <p>日本語</p>
+    </div>
+  "#}
+);
+
 assert_html!(
   include_err_on_missing_file,
   resolving_err: ResolveError::NotFound,
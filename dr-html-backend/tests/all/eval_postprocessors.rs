@@ -0,0 +1,45 @@
+use asciidork_core::{DocumentMeta, JobSettings, ReadAttr};
+use asciidork_dr_html_backend::{Postprocessor, convert_with_postprocessors};
+use asciidork_parser::prelude::*;
+use test_utils::*;
+
+/// adds a csp nonce to inline scripts
+struct Nonce;
+
+impl Postprocessor for Nonce {
+  fn process(&mut self, output: String, doc_meta: &DocumentMeta) -> Result<String, String> {
+    let nonce = doc_meta.str("nonce").ok_or("missing nonce")?;
+    Ok(output.replace("<script>", &format!(r#"<script nonce="{nonce}">"#)))
+  }
+}
+
+/// rewrites image urls to a cdn
+struct Cdn;
+
+impl Postprocessor for Cdn {
+  fn process(&mut self, output: String, _: &DocumentMeta) -> Result<String, String> {
+    Ok(output.replace(r#"src=""#, r#"src="https://cdn.test/"#))
+  }
+}
+
+fn convert(input: &str) -> Result<String, String> {
+  let bump = &Bump::new();
+  let mut parser = Parser::from_str(input, SourceFile::Tmp, bump);
+  parser.apply_job_settings(JobSettings::embedded());
+  let document = parser.parse().unwrap().document;
+  let mut postprocessors: Vec<Box<dyn Postprocessor>> = vec![Box::new(Nonce), Box::new(Cdn)];
+  convert_with_postprocessors(document, &mut postprocessors).map_err(|err| err.to_string())
+}
+
+#[test]
+fn postprocessors_run_in_order() {
+  let html =
+    convert(":nonce: abc123\n\nimage::cat.png[]\n\n++++\n<script>go()</script>\n++++").unwrap();
+  assert!(html.contains(r#"<img src="https://cdn.test/cat.png" alt="cat">"#));
+  assert!(html.contains(r#"<script nonce="abc123">go()</script>"#));
+}
+
+#[test]
+fn postprocessor_error() {
+  expect_eq!(convert("hello").unwrap_err(), "missing nonce");
+}
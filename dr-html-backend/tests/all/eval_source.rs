@@ -374,6 +374,139 @@ assert_html!(
   "#}
 );
 
+assert_html!(
+  native_highlighter_rouge,
+  adoc! {r#"
+    :source-highlighter: rouge
+
+    [source,ruby]
+    ----
+    require 'sinatra'
+    puts "hi" if x > 1
+    ----
+  "#},
+  source::wrap_listing(raw_html! {r#"
+    <pre class="rouge highlight"><code data-lang="ruby"><span class="nb">require</span> <span class="s">'sinatra'</span>
+    <span class="nb">puts</span> <span class="s">"hi"</span> <span class="k">if</span> x <span class="o">&gt;</span> <span class="m">1</span></code></pre>
+  "#})
+);
+
+assert_html!(
+  native_highlighter_no_lang,
+  adoc! {r#"
+    :source-highlighter: asciidork
+
+    [source]
+    ----
+    foo <bar>
+    ----
+  "#},
+  source::wrap_listing(r#"<pre class="asciidork highlight"><code>foo &lt;bar&gt;</code></pre>"#)
+);
+
+assert_html!(
+  native_highlighter_pygments_prefix,
+  adoc! {r#"
+    :source-highlighter: pygments
+
+    [source,python]
+    ----
+    x = None
+    ----
+  "#},
+  source::wrap_listing(
+    r#"<pre class="pygments highlight"><code data-lang="python">x <span class="tok-o">=</span> <span class="tok-kc">None</span></code></pre>"#
+  )
+);
+
+assert_html!(
+  native_highlighter_callouts,
+  adoc! {r#"
+    :source-highlighter: rouge
+
+    [source,ruby]
+    ----
+    require 'sinatra' <1>
+    get '/hi' do <2> <3>
+      "Hello World!" # <4>
+    end
+    ----
+  "#},
+  source::wrap_listing(raw_html! {r#"
+    <pre class="rouge highlight"><code data-lang="ruby"><span class="nb">require</span> <span class="s">'sinatra'</span> <b class="conum">(1)</b>
+    get <span class="s">'/hi'</span> <span class="k">do</span> <b class="conum">(2)</b> <b class="conum">(3)</b>
+      <span class="s">"Hello World!"</span> <span class="c1">#</span> <b class="conum">(4)</b>
+    <span class="k">end</span></code></pre>
+  "#})
+);
+
+assert_html!(
+  native_highlighter_font_callouts_drop_tuck,
+  adoc! {r#"
+    :source-highlighter: rouge
+    :icons: font
+
+    [source,ruby]
+    ----
+    puts "2" # <1>
+    ----
+  "#},
+  contains: r#"<span class="s">"2"</span> <i class="conum" data-value="1"></i><b>(1)</b></code>"#
+);
+
+assert_html!(
+  native_highlighter_linenums_and_highlight,
+  adoc! {r#"
+    :source-highlighter: rouge
+
+    [source%linenums,rust,start=4,highlight=5]
+    ----
+    let x = 1;
+    let y = 2;
+    ----
+  "#},
+  source::wrap_listing(raw_html! {r#"
+    <pre class="rouge highlight"><code data-lang="rust"><table class="linenotable"><tbody><tr><td class="linenos gl"><pre class="lineno">4
+    5</pre></td><td class="code"><pre><span class="k">let</span> x <span class="o">=</span> <span class="m">1</span><span class="p">;</span>
+    <span class="hll"><span class="k">let</span> y <span class="o">=</span> <span class="m">2</span><span class="p">;</span></span></pre></td></tr></tbody></table></code></pre>
+  "#})
+);
+
+assert_html!(
+  native_highlighter_skips_inline_subs,
+  adoc! {r#"
+    :source-highlighter: rouge
+
+    [source,ruby,subs=+quotes]
+    ----
+    puts *bold*
+    ----
+  "#},
+  source::wrap("ruby", "puts <strong>bold</strong>")
+);
+
+assert_html!(
+  unknown_highlighter_unchanged,
+  adoc! {r#"
+    :source-highlighter: highlight.js
+
+    [source,ruby]
+    ----
+    puts 1
+    ----
+  "#},
+  source::wrap("ruby", "puts 1")
+);
+
+test_non_embedded_contains!(
+  native_highlighter_stylesheet,
+  ":source-highlighter: rouge\n\n[source,ruby]\n----\nputs 1\n----",
+  [
+    r#"<style>pre.rouge table td{padding:5px}"#,
+    r#"pre.rouge .k,pre.rouge .kt{"#
+  ]
+);
+
 fn attach_listing(attrs: &str) -> String {
   format!("{attrs}\n----\nfoo\n----")
 }
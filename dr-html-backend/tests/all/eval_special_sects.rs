@@ -58,3 +58,97 @@ assert_html!(
     </div>
   "#}
 );
+
+assert_html!(
+  book_index_catalog,
+  adoc! {r#"
+    = Book Title
+    :doctype: book
+
+    == Fruit
+
+    The ((Apple)) is red. indexterm:[Fruit,Banana]
+    (((Citrus, Lemon))) ((apple)) pie.
+    indexterm:[Yellow fruit, see=Banana]
+    indexterm:[Zest, see-also="Citrus"]
+
+    [index]
+    == Index
+  "#},
+  html! {r##"
+    <div class="sect1">
+      <h2 id="_fruit">Fruit</h2>
+      <div class="sectionbody">
+        <div class="paragraph">
+          <p>The <a id="_indexterm_1"></a>Apple is red. <a id="_indexterm_2"></a> <a id="_indexterm_3"></a> <a id="_indexterm_4"></a>apple pie. <a id="_indexterm_5"></a> <a id="_indexterm_6"></a></p>
+        </div>
+      </div>
+    </div>
+    <div class="sect1">
+      <h2 id="_index">Index</h2>
+      <div class="sectionbody">
+        <div class="index">
+          <div class="index-category">
+            <h3>A</h3>
+            <ul class="index-terms">
+              <li id="_indexentry_1">
+                <span class="index-term">Apple</span>, <a href="#_indexterm_1">1</a>, <a href="#_indexterm_4">2</a>
+              </li>
+            </ul>
+          </div>
+          <div class="index-category">
+            <h3>C</h3>
+            <ul class="index-terms">
+              <li id="_indexentry_2">
+                <span class="index-term">Citrus</span>
+                <ul>
+                  <li><span class="index-term">Lemon</span>, <a href="#_indexterm_3">1</a></li>
+                </ul>
+              </li>
+            </ul>
+          </div>
+          <div class="index-category">
+            <h3>F</h3>
+            <ul class="index-terms">
+              <li id="_indexentry_3">
+                <span class="index-term">Fruit</span>
+                <ul>
+                  <li><span class="index-term">Banana</span>, <a href="#_indexterm_2">1</a></li>
+                </ul>
+              </li>
+            </ul>
+          </div>
+          <div class="index-category">
+            <h3>Y</h3>
+            <ul class="index-terms">
+              <li id="_indexentry_4">
+                <span class="index-term">Yellow fruit</span>, <em>see</em> Banana
+              </li>
+            </ul>
+          </div>
+          <div class="index-category">
+            <h3>Z</h3>
+            <ul class="index-terms">
+              <li id="_indexentry_5">
+                <span class="index-term">Zest</span>, <a href="#_indexterm_6">1</a>, <em>see also</em> <a href="#_indexentry_2">Citrus</a>
+              </li>
+            </ul>
+          </div>
+        </div>
+      </div>
+    </div>
+  "##}
+);
+
+assert_html!(
+  index_terms_without_index_section,
+  adoc! {r#"
+    = Book Title
+    :doctype: book
+
+    == Chapter
+
+    The ((Apple)) is red.
+  "#},
+  contains: r#"<p>The Apple is red.</p>"#
+);
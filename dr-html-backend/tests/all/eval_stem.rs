@@ -0,0 +1,138 @@
+use test_utils::*;
+
+assert_html!(
+  stem_block_defaults_to_asciimath,
+  adoc! {r#"
+    [stem]
+    ++++
+    sqrt(4) = 2
+    ++++
+  "#},
+  html! {r#"
+    <div class="stemblock">
+      <div class="content">\$sqrt(4) = 2\$</div>
+    </div>
+  "#}
+);
+
+assert_html!(
+  stem_block_resolves_stem_attr,
+  adoc! {r#"
+    :stem: latexmath
+
+    .Equation
+    [stem#eq.big]
+    ++++
+    C = \alpha + \beta Y^{\gamma} + \epsilon
+    ++++
+  "#},
+  html! {r#"
+    <div id="eq" class="stemblock big">
+      <div class="title">Equation</div>
+      <div class="content">\[C = \alpha + \beta Y^{\gamma} + \epsilon\]</div>
+    </div>
+  "#}
+);
+
+assert_html!(
+  latexmath_paragraph_escapes_special_chars,
+  adoc! {r#"
+    [latexmath]
+    a < *b*
+  "#},
+  html! {r#"
+    <div class="stemblock">
+      <div class="content">\[a &lt; *b*\]</div>
+    </div>
+  "#}
+);
+
+assert_html!(
+  stem_block_keeps_author_delimiters,
+  adoc! {r#"
+    [latexmath]
+    ++++
+    \[C = \alpha\]
+    ++++
+  "#},
+  html! {r#"
+    <div class="stemblock">
+      <div class="content">\[C = \alpha\]</div>
+    </div>
+  "#}
+);
+
+assert_html!(
+  asciimath_block_splits_equations,
+  adoc! {r#"
+    [asciimath]
+    ++++
+    a^2
+
+    b^2
+    ++++
+  "#},
+  contains: "<div class=\"content\">\\$a^2\\$\n<br>\n<br>\n\\$b^2\\$</div>"
+);
+
+assert_html!(
+  inline_stem_macros,
+  adoc! {r#"
+    :stem:
+
+    The formula stem:[sqrt(4) = 2] is true, as is latexmath:[C = \alpha]
+    and asciimath:[a < b].
+  "#},
+  html! {r#"
+    <div class="paragraph">
+      <p>The formula \$sqrt(4) = 2\$ is true, as is \(C = \alpha\) and \$a &lt; b\$.</p>
+    </div>
+  "#}
+);
+
+assert_html!(
+  inline_stem_resolves_stem_attr,
+  adoc! {r#"
+    :stem: latexmath
+
+    Where stem:[\sqrt{4} = 2].
+  "#},
+  html! {r#"
+    <div class="paragraph"><p>Where \(\sqrt{4} = 2\).</p></div>
+  "#}
+);
+
+assert_html!(
+  inline_stem_skips_inline_formatting,
+  adoc! {r#"
+    Where stem:[*a* + _b_ \] c].
+  "#},
+  html! {r#"
+    <div class="paragraph"><p>Where \$*a* + _b_ ] c\$.</p></div>
+  "#}
+);
+
+test_non_embedded_contains!(
+  stem_attr_adds_mathjax,
+  adoc! {r#"
+    :stem:
+
+    stem:[x]
+  "#},
+  [
+    r#"<script type="text/x-mathjax-config">"#,
+    r#"TeX: { equationNumbers: { autoNumber: "none" } }"#,
+    r#"/mathjax/2.7.9/MathJax.js?config=TeX-MML-AM_HTMLorMML"></script></body>"#
+  ]
+);
+
+test_non_embedded_contains!(
+  eqnums_attr_configures_mathjax,
+  adoc! {r#"
+    :stem: latexmath
+    :eqnums:
+
+    stem:[x]
+  "#},
+  [r#"TeX: { equationNumbers: { autoNumber: "AMS" } }"#]
+);
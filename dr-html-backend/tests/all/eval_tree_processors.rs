@@ -0,0 +1,204 @@
+use asciidork_ast::prelude::*;
+use asciidork_ast::{InlineNodes, MacroNode, VisitMut};
+use asciidork_core::JobSettings;
+use asciidork_parser::TreeProcessor;
+use asciidork_parser::prelude::*;
+use bumpalo::collections::{String as BumpString, Vec as BumpVec};
+use test_utils::*;
+
+fn text<'arena>(text: &str, bump: &'arena Bump) -> InlineNodes<'arena> {
+  let mut nodes = InlineNodes::new(bump);
+  nodes.push(InlineNode::new(
+    Inline::Text(BumpString::from_str_in(text, bump)),
+    SourceLocation::default(),
+  ));
+  nodes
+}
+
+/// prefixes paragraphs with the `req` role with `REQ-<n>: `
+struct NumberRequirements;
+
+impl TreeProcessor for NumberRequirements {
+  fn process<'arena>(&mut self, document: &mut Document<'arena>, bump: &'arena Bump) {
+    let mut num = 0;
+    document.for_each_block_mut(|block| {
+      if let BlockContent::Simple(inlines) = &mut block.content
+        && block.meta.attrs.has_role("req")
+      {
+        num += 1;
+        let label = text(&format!("REQ-{num}: "), bump);
+        inlines.splice(0..0, label.into_vec());
+      }
+    });
+  }
+}
+
+/// rewrites `.adoc` link targets to `.html`
+struct RewriteLinks;
+
+impl<'arena> VisitMut<'arena> for RewriteLinks {
+  fn visit_inlines(&mut self, inlines: &mut InlineNodes<'arena>) {
+    for node in inlines.iter_mut() {
+      if let Inline::Macro(MacroNode::Link { target, .. }) = &mut node.content
+        && let Some(stem) = target.src.strip_suffix(".adoc")
+      {
+        let mut rewritten = BumpString::from_str_in(stem, target.src.bump());
+        rewritten.push_str(".html");
+        target.src = rewritten;
+      }
+    }
+  }
+}
+
+impl TreeProcessor for RewriteLinks {
+  fn process<'arena>(&mut self, document: &mut Document<'arena>, _: &'arena Bump) {
+    document.walk_mut(self);
+  }
+}
+
+/// replaces every occurrence of `from` in text nodes with `to`
+struct ReplaceText {
+  from: &'static str,
+  to: &'static str,
+}
+
+impl<'arena> VisitMut<'arena> for ReplaceText {
+  fn visit_inlines(&mut self, inlines: &mut InlineNodes<'arena>) {
+    for node in inlines.iter_mut() {
+      if let Inline::Text(text) = &mut node.content
+        && text.contains(self.from)
+      {
+        let replaced = text.replace(self.from, self.to);
+        *text = BumpString::from_str_in(&replaced, text.bump());
+      }
+    }
+  }
+}
+
+impl TreeProcessor for ReplaceText {
+  fn process<'arena>(&mut self, document: &mut Document<'arena>, _: &'arena Bump) {
+    document.walk_mut(self);
+  }
+}
+
+/// appends a generated section to the document
+struct AppendSection;
+
+impl TreeProcessor for AppendSection {
+  fn process<'arena>(&mut self, document: &mut Document<'arena>, bump: &'arena Bump) {
+    let DocContent::Sections(sectioned) = &mut document.content else {
+      return;
+    };
+    let mut blocks = BumpVec::new_in(bump);
+    blocks.push(Block {
+      meta: ChunkMeta::empty(SourceLocation::default(), bump),
+      context: BlockContext::Paragraph,
+      content: BlockContent::Simple(text("Generated content.", bump)),
+      loc: SourceLocation::default().into(),
+    });
+    sectioned.sections.push(Section {
+      meta: ChunkMeta::empty(SourceLocation::default(), bump),
+      level: 1,
+      id: Some(BumpString::from_str_in("_generated", bump)),
+      heading: text("Generated", bump),
+      blocks,
+      loc: SourceLocation::default().into(),
+    });
+  }
+}
+
+fn processed_html(input: &str, processors: Vec<Box<dyn TreeProcessor>>) -> String {
+  let bump = &Bump::new();
+  let mut parser = Parser::from_str(input, SourceFile::Tmp, bump);
+  parser.apply_job_settings(JobSettings::embedded());
+  for processor in processors {
+    parser.register_tree_processor(processor);
+  }
+  let document = parser.parse().unwrap().document;
+  asciidork_eval::eval(&document, crate::helpers::test_backend_factory()).unwrap()
+}
+
+#[test]
+fn numbers_nested_blocks() {
+  let html = processed_html(
+    adoc! {r#"
+      [.req]
+      Must parse.
+
+      ====
+      [.req]
+      Must render.
+      ====
+
+      * item
+      +
+      [.req]
+      Must nest.
+    "#},
+    vec![Box::new(NumberRequirements)],
+  );
+  assert!(html.contains("<p>REQ-1: Must parse.</p>"));
+  assert!(html.contains("<p>REQ-2: Must render.</p>"));
+  assert!(html.contains("<p>REQ-3: Must nest.</p>"));
+}
+
+#[test]
+fn rewrites_inlines_in_nested_content() {
+  let html = processed_html(
+    adoc! {r#"
+      .See link:guide.adoc[the guide]
+      ****
+      Read *link:intro.adoc[]* first.
+      ****
+
+      |===
+      a|link:table.adoc[]
+      |===
+    "#},
+    vec![Box::new(RewriteLinks)],
+  );
+  assert!(html.contains(r#"<a href="guide.html">the guide</a>"#));
+  assert!(html.contains(r#"<a href="intro.html""#));
+  assert!(html.contains(r#"<a href="table.html""#));
+  assert!(!html.contains(".adoc"));
+}
+
+#[test]
+fn rewrites_stem_and_link_text() {
+  let html = processed_html(
+    "Solve stem:[x_old + 1] per link:https://x.test[the x_old docs].",
+    vec![Box::new(ReplaceText { from: "x_old", to: "x_new" })],
+  );
+  assert!(html.contains(r#"\$x_new + 1\$"#));
+  assert!(html.contains(r#"<a href="https://x.test">the x_new docs</a>"#));
+}
+
+#[test]
+fn processors_run_in_order() {
+  let html = processed_html(
+    adoc! {r#"
+      == Intro
+
+      [.req]
+      Must exist.
+    "#},
+    vec![Box::new(AppendSection), Box::new(NumberRequirements)],
+  );
+  expect_eq!(
+    html,
+    html! {r#"
+      <div class="sect1">
+        <h2 id="_intro">Intro</h2>
+        <div class="sectionbody">
+          <div class="paragraph req"><p>REQ-1: Must exist.</p></div>
+        </div>
+      </div>
+      <div class="sect1">
+        <h2 id="_generated">Generated</h2>
+        <div class="sectionbody">
+          <div class="paragraph"><p>Generated content.</p></div>
+        </div>
+      </div>
+    "#}
+  );
+}
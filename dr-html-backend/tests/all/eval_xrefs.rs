@@ -609,3 +609,91 @@ assert_html!(
   "#},
   contains: r##"<h2 id="a">See <a href="#b">Consult Google</a></h2>"##,
 );
+
+assert_html!(
+  xrefstyle_full_short_basic,
+  adoc! {r#"
+    = Doc
+    :sectnums:
+    :xrefstyle: full
+
+    == Intro
+
+    See <<fig-tiger>>, <<tbl-data>> and <<_details>>.
+
+    Also xref:fig-tiger[xrefstyle=short], xref:_details[xrefstyle=short]
+    and xref:fig-tiger[xrefstyle=basic].
+
+    Linktext wins: <<fig-tiger,the tiger>>.
+
+    === Details
+
+    image::tiger.png[]
+
+    [#fig-tiger]
+    .A Tiger
+    image::tiger.png[Tiger]
+
+    [#tbl-data]
+    .Some Data
+    |===
+    | a
+    |===
+  "#},
+  contains:
+    r##"See <a href="#fig-tiger">Figure 1, &#8220;A Tiger&#8221;</a>, <a href="#tbl-data">Table 1, &#8220;Some Data&#8221;</a> and <a href="#_details">Section 1.1, &#8220;Details&#8221;</a>."##,
+    r##"Also <a href="#fig-tiger">Figure 1</a>, <a href="#_details">Section 1.1</a>"##,
+    r##"and <a href="#fig-tiger">A Tiger</a>."##,
+    r##"Linktext wins: <a href="#fig-tiger">the tiger</a>."##,
+    r##"<div class="title">Figure 1. A Tiger</div>"##,
+);
+
+assert_html!(
+  xrefstyle_chapters_and_appendices,
+  adoc! {r#"
+    = Book
+    :doctype: book
+    :sectnums:
+    :xrefstyle: short
+
+    == Chapter One
+
+    See <<_chapter_two>>, xref:_chapter_two[xrefstyle=full],
+    xref:_chapter_two[xrefstyle=basic], <<_extras>> and <<_more>>.
+
+    == Chapter Two
+
+    [appendix]
+    == Extras
+
+    === More
+  "#},
+  contains:
+    r##"See <a href="#_chapter_two">Chapter 2</a>, <a href="#_chapter_two">Chapter 2, <em>Chapter Two</em></a>,"##,
+    r##"<a href="#_chapter_two"><em>Chapter Two</em></a>, <a href="#_extras">Appendix A</a> and <a href="#_more">Section A.1</a>."##,
+);
+
+assert_html!(
+  xrefstyle_unnumbered_and_custom_caption,
+  adoc! {r#"
+    :xrefstyle: full
+
+    == Intro
+
+    See <<_intro>>, <<ex>> and <<custom>>.
+
+    [#ex]
+    .Sample
+    ====
+    content
+    ====
+
+    [#custom,caption="Exhibit A. "]
+    .Odd
+    ====
+    content
+    ====
+  "#},
+  contains:
+    r##"See <a href="#_intro">Intro</a>, <a href="#ex">Example 1, &#8220;Sample&#8221;</a> and <a href="#custom">Exhibit A, &#8220;Odd&#8221;</a>."##,
+);
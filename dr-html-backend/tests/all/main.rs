@@ -6,6 +6,7 @@ mod helpers;
 mod eval_adoc;
 mod eval_adoc_cells;
 mod eval_audio_macros;
+mod eval_block_processors;
 mod eval_blocks;
 mod eval_books;
 mod eval_breaks;
@@ -18,21 +19,25 @@ mod eval_delimited;
 mod eval_desc_lists;
 mod eval_footnotes;
 mod eval_image_macros;
+mod eval_include_processors;
 mod eval_includes;
 mod eval_inline_svg;
 mod eval_links;
 mod eval_lists;
 mod eval_macros;
 mod eval_markdown;
+mod eval_postprocessors;
 mod eval_quotes;
 mod eval_sections;
 mod eval_source;
 mod eval_special_sects;
 mod eval_standalone;
+mod eval_stem;
 mod eval_tables_csv;
 mod eval_tables_dsv;
 mod eval_tables_psv;
 mod eval_toc;
+mod eval_tree_processors;
 mod eval_verses;
 mod eval_video_macros;
 mod eval_win_crlf;
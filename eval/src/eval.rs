@@ -1,7 +1,9 @@
-use std::cell::RefCell;
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
 
 use crate::internal::*;
-use asciidork_ast::InlineNodes;
+use crate::xref_labels::{self, XrefLabel};
+use asciidork_ast::{InlineNodes, ReadAttr};
 use asciidork_backend::utils;
 
 pub fn eval<B: Backend>(document: &Document, mut backend: B) -> Result<B::Output, B::Error> {
@@ -12,12 +14,14 @@ pub fn eval<B: Backend>(document: &Document, mut backend: B) -> Result<B::Output
 struct Ctx<'a, 'b> {
   doc: &'a Document<'b>,
   resolving_xref: RefCell<bool>,
+  xref_labels: OnceCell<HashMap<String, XrefLabel>>,
 }
 
 pub fn visit<B: Backend>(doc: &Document, backend: &mut B) {
   let ctx = Ctx {
     doc,
     resolving_xref: RefCell::new(false),
+    xref_labels: OnceCell::new(),
   };
   let render_docinfo = !ctx.doc.meta.embedded;
   backend.enter_document(ctx.doc);
@@ -391,6 +395,20 @@ fn eval_block(block: &Block, ctx: &Ctx, backend: &mut impl Backend) {
       backend.exit_simple_block_content(block);
       backend.exit_passthrough_block(block);
     }
+    (Context::Stem, Content::Simple(children)) => {
+      let stem_attr = ctx.doc.meta.str("stem");
+      let notation = block
+        .meta
+        .attrs
+        .str_positional_at(0)
+        .and_then(|style| StemNotation::from_name(style, stem_attr))
+        .unwrap_or_else(|| StemNotation::from_stem_attr(stem_attr));
+      backend.enter_stem_block(block, notation);
+      backend.enter_simple_block_content(block);
+      children.iter().for_each(|n| eval_inline(n, ctx, backend));
+      backend.exit_simple_block_content(block);
+      backend.exit_stem_block(block, notation);
+    }
     (Context::Table, Content::Table(table)) => {
       backend.enter_table(table, block);
       if let Some(header_row) = &table.header_row {
@@ -525,13 +543,25 @@ fn eval_inline(inline: &InlineNode, ctx: &Ctx, backend: &mut impl Backend) {
       backend.visit_keyboard_macro(&keys.iter().map(|s| s.as_str()).collect::<Vec<&str>>())
     }
     Macro(Menu(items)) => backend.visit_menu_macro(items.as_slice()),
-    Macro(Xref { target, linktext, kind }) => {
+    Macro(Xref { target, linktext, kind, xrefstyle }) => {
       let anchors = ctx.doc.anchors.borrow();
-      let anchor = anchors.get(utils::xref::get_id(&target.src));
+      let id = utils::xref::get_id(&target.src);
+      let anchor = anchors.get(id);
       let is_biblio = anchor.map(|a| a.is_biblio).unwrap_or(false);
       backend.enter_xref(target, linktext.is_some(), *kind);
       if ctx.resolving_xref.replace(true) {
         backend.visit_missing_xref(target, *kind, ctx.doc.title());
+      } else if let Some(anchor) = anchor.filter(|a| a.reftext.is_none() && linktext.is_none())
+        && !anchor.title.is_empty()
+        && let Some(style) = xrefstyle.or_else(|| ctx.doc.meta.str("xrefstyle")?.parse().ok())
+        && let Some(label) = ctx
+          .xref_labels
+          .get_or_init(|| xref_labels::collect(ctx.doc))
+          .get(id)
+      {
+        backend.enter_xref_text(is_biblio);
+        eval_styled_xref_text(style, label, &anchor.title, ctx, backend);
+        backend.exit_xref_text(is_biblio);
       } else if let Some(text) = anchor
         .map(|anchor| {
           anchor
@@ -556,6 +586,11 @@ fn eval_inline(inline: &InlineNode, ctx: &Ctx, backend: &mut impl Backend) {
     }
     Macro(Icon { target, attrs }) => backend.visit_icon_macro(target, attrs),
     Macro(Plugin(plugin_macro)) => backend.visit_plugin_macro(plugin_macro),
+    Macro(Stem { notation, content }) => {
+      backend.enter_inline_stem(*notation);
+      content.iter().for_each(|n| eval_inline(n, ctx, backend));
+      backend.exit_inline_stem(*notation);
+    }
     Macro(Mailto {
       address,
       linktext,
@@ -603,11 +638,53 @@ fn eval_inline(inline: &InlineNode, ctx: &Ctx, backend: &mut impl Backend) {
   }
 }
 
+/// https://docs.asciidoctor.org/asciidoc/latest/macros/xref-text-and-style/
+fn eval_styled_xref_text(
+  style: XrefStyle,
+  label: &XrefLabel,
+  title: &InlineNodes,
+  ctx: &Ctx,
+  backend: &mut impl Backend,
+) {
+  match (style, label.prefix()) {
+    (XrefStyle::Short, Some(prefix)) => backend.visit_inline_text(&prefix),
+    (XrefStyle::Full, Some(prefix)) => {
+      backend.visit_inline_text(&format!("{prefix}, "));
+      if label.emphasized {
+        backend.enter_inline_italic(None);
+        title
+          .iter()
+          .for_each(|node| eval_inline(node, ctx, backend));
+        backend.exit_inline_italic(None);
+      } else {
+        // NB: named for their side of the backtick, "` opens and `" closes
+        backend.visit_curly_quote(CurlyKind::RightDouble);
+        title
+          .iter()
+          .for_each(|node| eval_inline(node, ctx, backend));
+        backend.visit_curly_quote(CurlyKind::LeftDouble);
+      }
+    }
+    (XrefStyle::Basic, _) if label.emphasized && label.number.is_some() => {
+      backend.enter_inline_italic(None);
+      title
+        .iter()
+        .for_each(|node| eval_inline(node, ctx, backend));
+      backend.exit_inline_italic(None);
+    }
+    _ => title
+      .iter()
+      .for_each(|node| eval_inline(node, ctx, backend)),
+  }
+}
+
 fn eval_index_term(index_term: &asciidork_ast::IndexTerm, ctx: &Ctx, backend: &mut impl Backend) {
   match &index_term.term_type {
     asciidork_ast::IndexTermType::Visible { term } => {
       if backend.enter_visible_index_term() {
         term.iter().for_each(|n| eval_inline(n, ctx, backend));
+        backend.visit_index_term_reference(&index_term.term_ref);
+        backend.exit_visible_index_term();
       }
     }
     asciidork_ast::IndexTermType::Concealed { primary, secondary, tertiary } => {
@@ -627,6 +704,7 @@ fn eval_index_term(index_term: &asciidork_ast::IndexTerm, ctx: &Ctx, backend: &m
           tertiary.iter().for_each(|n| eval_inline(n, ctx, backend));
           backend.exit_concealed_index_term_tertiary();
         }
+        backend.visit_index_term_reference(&index_term.term_ref);
       }
       backend.exit_concealed_index_term(num_terms);
     }
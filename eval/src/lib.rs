@@ -1,4 +1,5 @@
 mod eval;
+mod xref_labels;
 
 pub use eval::*;
 
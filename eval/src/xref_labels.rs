@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use crate::internal::*;
+use asciidork_ast::{ReadAttr, SpecialSection};
+
+/// what an `xrefstyle` other than the bare title draws on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XrefLabel {
+  /// `Section`, `Chapter`, `Figure`, etc., unset refsigs are omitted
+  pub signifier: Option<String>,
+  /// `3.1`, `A`, `2`, etc., none for unnumbered targets
+  pub number: Option<String>,
+  /// a block's custom `caption` attr, replaces signifier and number
+  pub caption: Option<String>,
+  /// chapter and appendix titles are emphasized, others quoted
+  pub emphasized: bool,
+}
+
+impl XrefLabel {
+  /// the `short` text, and the lead-in of the `full` text
+  pub fn prefix(&self) -> Option<String> {
+    if let Some(caption) = &self.caption {
+      return Some(caption.strip_suffix(". ").unwrap_or(caption).to_string());
+    }
+    let number = self.number.as_ref()?;
+    match &self.signifier {
+      Some(signifier) => Some(format!("{signifier} {number}")),
+      None => Some(number.clone()),
+    }
+  }
+}
+
+/// numbers sections and captioned blocks the way the html backends
+/// do, so xrefs can be styled before (or after) their target renders
+pub fn collect(doc: &Document) -> HashMap<String, XrefLabel> {
+  let mut collector = Collector {
+    meta: doc.meta.clone(),
+    labels: HashMap::new(),
+    section_nums: [0; 5],
+    appendix_num: 0,
+    in_appendix: false,
+    caption_nums: [0; 4],
+  };
+  match &doc.content {
+    DocContent::Blocks(blocks) => collector.blocks(blocks),
+    DocContent::Sections(Sectioned { preamble, sections }) => {
+      collector.blocks(preamble.iter().flatten());
+      sections
+        .iter()
+        .for_each(|section| collector.section(section));
+    }
+    DocContent::Parts(MultiPartBook {
+      preamble,
+      opening_special_sects,
+      parts,
+      closing_special_sects,
+    }) => {
+      collector.blocks(preamble.iter().flatten());
+      opening_special_sects
+        .iter()
+        .for_each(|section| collector.section(section));
+      for part in parts {
+        collector.blocks(part.intro.iter().flatten());
+        part
+          .sections
+          .iter()
+          .for_each(|section| collector.section(section));
+      }
+      closing_special_sects
+        .iter()
+        .for_each(|section| collector.section(section));
+    }
+  }
+  collector.labels
+}
+
+struct Collector {
+  meta: DocumentMeta,
+  labels: HashMap<String, XrefLabel>,
+  section_nums: [u16; 5],
+  appendix_num: u8,
+  in_appendix: bool,
+  /// table, figure, example and listing caption counters
+  caption_nums: [usize; 4],
+}
+
+impl Collector {
+  fn section(&mut self, section: &Section) {
+    let special_sect = section.meta.attrs.special_sect();
+    let book = self.meta.get_doctype() == DocType::Book;
+    let label = if special_sect == Some(SpecialSection::Appendix) {
+      self.section_nums = [0; 5];
+      self.in_appendix = true;
+      let letter = (self.appendix_num + b'A') as char;
+      self.appendix_num += 1;
+      XrefLabel {
+        signifier: self.meta.string("appendix-refsig"),
+        number: Some(letter.to_string()),
+        caption: None,
+        emphasized: true,
+      }
+    } else {
+      let chapter = book && section.level == 1;
+      let refsig = if chapter { "chapter-refsig" } else { "section-refsig" };
+      XrefLabel {
+        signifier: self.meta.string(refsig),
+        number: self.section_number(section.level, special_sect),
+        caption: None,
+        emphasized: chapter,
+      }
+    };
+    if let Some(id) = &section.id {
+      self.labels.insert(id.to_string(), label);
+    }
+    self.blocks(&section.blocks);
+    if special_sect == Some(SpecialSection::Appendix) {
+      self.section_nums = [0; 5];
+      self.in_appendix = false;
+    }
+  }
+
+  fn section_number(&mut self, level: u8, special_sect: Option<SpecialSection>) -> Option<String> {
+    if !(1..=5).contains(&level) || !self.should_number_section(level, special_sect) {
+      return None;
+    }
+    let level_idx = (level - 1) as usize;
+    self.section_nums[level_idx] += 1;
+    self
+      .section_nums
+      .iter_mut()
+      .skip(level_idx + 1)
+      .for_each(|n| *n = 0);
+    let number = self
+      .section_nums
+      .iter()
+      .take(level_idx + 1)
+      .enumerate()
+      .map(|(idx, num)| match idx {
+        0 if self.in_appendix => ((self.appendix_num - 1 + b'A') as char).to_string(),
+        _ => num.to_string(),
+      })
+      .collect::<Vec<_>>()
+      .join(".");
+    Some(number)
+  }
+
+  fn should_number_section(&self, level: u8, special_sect: Option<SpecialSection>) -> bool {
+    let Some(sectnums) = self.meta.get("sectnums") else {
+      return false;
+    };
+    if self.meta.isize("sectnumlevels").unwrap_or(3) < level as isize {
+      return false;
+    }
+    match sectnums {
+      AttrValue::String(val) if val == "all" => true,
+      AttrValue::Bool(true) => special_sect.is_none_or(|special_sect| {
+        self
+          .meta
+          .get_doctype()
+          .supports_special_section(special_sect)
+      }),
+      _ => false,
+    }
+  }
+
+  fn blocks<'a, 'b: 'a>(&mut self, blocks: impl IntoIterator<Item = &'a Block<'b>>) {
+    blocks.into_iter().for_each(|block| self.block(block));
+  }
+
+  fn block(&mut self, block: &Block) {
+    if let Some(label) = self.block_label(block)
+      && let Some(id) = block.meta.attrs.id()
+    {
+      self.labels.insert(id.to_string(), label);
+    }
+    match &block.content {
+      BlockContent::Compound(blocks) => self.blocks(blocks),
+      BlockContent::Section(section) => self.section(section),
+      BlockContent::List { items, .. } => {
+        items.iter().for_each(|item| self.blocks(&item.blocks));
+      }
+      BlockContent::DocumentAttribute(name, value) => {
+        _ = self.meta.insert_doc_attr(name, value.clone());
+      }
+      _ => {}
+    }
+  }
+
+  fn block_label(&mut self, block: &Block) -> Option<XrefLabel> {
+    let titled = match &block.content {
+      BlockContent::Empty(EmptyMetadata::Image { attrs, .. }) => {
+        block.has_title() || attrs.named("title").is_some()
+      }
+      _ => block.has_title(),
+    };
+    if !titled {
+      return None;
+    }
+    if let Some(caption) = block.meta.attrs.named("caption") {
+      return Some(XrefLabel {
+        signifier: None,
+        number: None,
+        caption: Some(caption.to_string()),
+        emphasized: false,
+      });
+    }
+    let signifier = block
+      .context
+      .caption_attr_name()
+      .and_then(|attr_name| self.meta.string(attr_name))?;
+    let num = match block.context {
+      BlockContext::Table => &mut self.caption_nums[0],
+      BlockContext::Image => &mut self.caption_nums[1],
+      BlockContext::Example => &mut self.caption_nums[2],
+      _ => &mut self.caption_nums[3],
+    };
+    *num += 1;
+    Some(XrefLabel {
+      signifier: Some(signifier),
+      number: Some(num.to_string()),
+      caption: None,
+      emphasized: false,
+    })
+  }
+}
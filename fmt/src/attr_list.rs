@@ -0,0 +1,65 @@
+/// normalizes the spacing of a block attribute list line, eg:
+/// `[source , rust]` -> `[source,rust]`, `[cols = "1,2"]` -> `[cols="1,2"]`
+/// returns `None` if the line isn't an attribute list we can safely rewrite
+pub fn normalize(line: &str) -> Option<String> {
+  let line = line.trim();
+  if !line.starts_with('[') || !line.ends_with(']') || line.starts_with("[[") {
+    return None;
+  }
+  let inner = &line[1..line.len() - 1];
+  let mut entries = Vec::new();
+  for entry in split_entries(inner)? {
+    entries.push(normalize_entry(entry.trim()));
+  }
+  Some(format!("[{}]", entries.join(",")))
+}
+
+fn normalize_entry(entry: &str) -> String {
+  match entry.split_once('=') {
+    Some((key, value)) if is_attr_name(key.trim()) && !key.contains(['"', '\'']) => {
+      format!("{}={}", key.trim(), value.trim())
+    }
+    _ => entry.to_string(),
+  }
+}
+
+fn is_attr_name(name: &str) -> bool {
+  !name.is_empty()
+    && name
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// splits on commas outside of quoted values,
+/// `None` signals an unterminated quote
+fn split_entries(inner: &str) -> Option<Vec<&str>> {
+  let mut entries = Vec::new();
+  let mut quote: Option<char> = None;
+  let mut start = 0;
+  let mut prev = None;
+  for (idx, c) in inner.char_indices() {
+    match (c, quote) {
+      ('"' | '\'', None) if starts_value(inner, idx) => quote = Some(c),
+      (c, Some(q)) if c == q && prev != Some('\\') => quote = None,
+      (',', None) => {
+        entries.push(&inner[start..idx]);
+        start = idx + 1;
+      }
+      _ => {}
+    }
+    prev = Some(c);
+  }
+  if quote.is_some() {
+    return None;
+  }
+  entries.push(&inner[start..]);
+  Some(entries)
+}
+
+/// quotes only delimit a value at the start of an entry or after `=`
+fn starts_value(inner: &str, idx: usize) -> bool {
+  matches!(
+    inner[..idx].trim_end().chars().last(),
+    None | Some(',') | Some('=')
+  )
+}
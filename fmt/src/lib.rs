@@ -0,0 +1,33 @@
+use asciidork_ast::prelude::*;
+use asciidork_core::{JobSettings, SafeMode};
+use asciidork_parser::prelude::*;
+
+mod attr_list;
+mod printer;
+mod resolver;
+mod table;
+
+use crate::printer::Printer;
+use crate::resolver::UnexpandedIncludes;
+
+/// parses and formats asciidoc source, include directives are not
+/// expanded, so the result only ever contains the source's own content
+pub fn format(src: &str, file: SourceFile) -> Result<String, Vec<Diagnostic>> {
+  let bump = &Bump::with_capacity(src.len() * 2);
+  let mut parser = Parser::from_str(src, file, bump);
+  parser.apply_job_settings(JobSettings {
+    safe_mode: SafeMode::Unsafe,
+    // xrefs into unexpanded includes can't be resolved
+    strict: false,
+    ..JobSettings::default()
+  });
+  parser.set_resolver(Box::new(UnexpandedIncludes));
+  let result = parser.parse()?;
+  Ok(format_document(&result.document, src))
+}
+
+/// formats a document parsed from `src`, which must have been parsed
+/// without expanding includes, see `format`
+pub fn format_document(document: &Document, src: &str) -> String {
+  Printer::new(src).print_document(document)
+}
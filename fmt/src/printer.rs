@@ -0,0 +1,372 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use asciidork_ast::prelude::*;
+
+use crate::{attr_list, table};
+
+lazy_static! {
+  static ref HEADING_RE: Regex = Regex::new(r"^(=+|#+)[ \t]+(.*?)\s*$").unwrap();
+}
+
+/// how blank lines between two printed constructs are normalized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spacing {
+  /// runs of blank lines are collapsed to a single blank line
+  Collapse,
+  /// like `Collapse`, but adjacent nodes are separated by a blank line
+  Separate,
+  /// blank lines are removed entirely
+  Strip,
+}
+
+/// prints a document back to asciidoc, by walking the AST and copying
+/// source not represented in it (comments, directives) verbatim
+pub struct Printer<'src> {
+  src: &'src str,
+  out: String,
+  pos: usize,
+  unordered_depth: usize,
+  ordered_depth: usize,
+}
+
+impl<'src> Printer<'src> {
+  pub fn new(src: &'src str) -> Self {
+    Self {
+      src,
+      out: String::with_capacity(src.len()),
+      pos: 0,
+      unordered_depth: 0,
+      ordered_depth: 0,
+    }
+  }
+
+  pub fn print_document(mut self, document: &Document) -> String {
+    if let Some(header) = &document.header {
+      if let Some(loc) = header.title.as_ref().and_then(|t| t.main.first_loc()) {
+        let line_start = self.line_start(loc.start as usize);
+        self.gap_to(line_start, Spacing::Collapse);
+        self.heading(line_start);
+      }
+      // author, revision, and attribute entry lines, up to the first blank line
+      let mut header_end = self.pos.max(header.loc.end as usize);
+      while header_end < self.src.len() {
+        let next_end = self.line_end(header_end + 1);
+        if self.src[header_end + 1..next_end].trim().is_empty() {
+          break;
+        }
+        header_end = next_end;
+      }
+      self.verbatim(header_end, false);
+    }
+
+    match &document.content {
+      DocContent::Blocks(blocks) => self.blocks(blocks, Spacing::Separate),
+      DocContent::Sections(Sectioned { preamble, sections }) => {
+        if let Some(preamble) = preamble {
+          self.blocks(preamble, Spacing::Separate);
+        }
+        sections.iter().for_each(|section| self.section(section));
+      }
+      DocContent::Parts(book) => {
+        if let Some(preamble) = &book.preamble {
+          self.blocks(preamble, Spacing::Separate);
+        }
+        book
+          .opening_special_sects
+          .iter()
+          .for_each(|s| self.section(s));
+        book.parts.iter().for_each(|part| self.part(part));
+        book
+          .closing_special_sects
+          .iter()
+          .for_each(|s| self.section(s));
+      }
+    }
+
+    self.gap_to(self.src.len(), Spacing::Collapse);
+    let mut out = self.out.trim_start_matches('\n').trim_end().to_string();
+    out.push('\n');
+    out
+  }
+
+  fn blocks(&mut self, blocks: &[Block], first_spacing: Spacing) {
+    let mut prev: Option<&Block> = None;
+    for block in blocks {
+      let spacing = match prev {
+        None => first_spacing,
+        Some(prev) if is_attr_decl(prev) && is_attr_decl(block) => Spacing::Collapse,
+        Some(_) => Spacing::Separate,
+      };
+      self.block(block, spacing);
+      prev = Some(block);
+    }
+  }
+
+  fn block(&mut self, block: &Block, spacing: Spacing) {
+    let start = block.meta.start_loc.start as usize;
+    let end = block.loc.end_pos as usize;
+    let content_start = match &block.content {
+      // loc of a discrete heading includes its metadata
+      BlockContent::Empty(EmptyMetadata::DiscreteHeading { content, .. }) => content
+        .first_loc()
+        .map_or(block.loc.start_pos as usize, |loc| {
+          self.line_start(loc.start as usize)
+        }),
+      _ => block.loc.start_pos as usize,
+    };
+    // nothing to print, or source already printed by an ancestor
+    if start < self.pos || end <= content_start {
+      return;
+    }
+    self.gap_to(start, spacing);
+    self.metadata(content_start);
+    match &block.content {
+      BlockContent::Section(section) => {
+        self.heading(content_start);
+        self.blocks(&section.blocks, Spacing::Separate);
+      }
+      BlockContent::Empty(EmptyMetadata::DiscreteHeading { .. }) => self.heading(content_start),
+      BlockContent::Compound(blocks) => self.compound(block, blocks),
+      BlockContent::List { variant, items, .. } => self.list(block, *variant, items),
+      BlockContent::Table(table) => self.table(block, table),
+      _ => self.verbatim(end, preserves_whitespace(block.context)),
+    }
+    if self.pos < end {
+      self.verbatim(end, preserves_whitespace(block.context));
+    }
+  }
+
+  fn section(&mut self, section: &Section) {
+    let start = section.meta.start_loc.start as usize;
+    let heading_start = section.loc.start_pos as usize;
+    if start < self.pos {
+      return;
+    }
+    self.gap_to(start, Spacing::Separate);
+    self.metadata(heading_start);
+    self.heading(heading_start);
+    self.blocks(&section.blocks, Spacing::Separate);
+  }
+
+  fn part(&mut self, part: &Part) {
+    let Some(loc) = part.title.text.first_loc() else {
+      return;
+    };
+    self.gap_to(part.title.meta.start_loc.start as usize, Spacing::Separate);
+    let heading_start = self.line_start(loc.start as usize);
+    self.metadata(heading_start);
+    self.heading(heading_start);
+    if let Some(intro) = &part.intro {
+      self.blocks(intro, Spacing::Separate);
+    }
+    part
+      .sections
+      .iter()
+      .for_each(|section| self.section(section));
+  }
+
+  /// prints a delimited block's delimiters, and its children between them
+  fn compound(&mut self, block: &Block, blocks: &[Block]) {
+    let end = block.loc.end_pos as usize;
+    let open_end = self.line_end(self.pos);
+    let close_start = self.line_start(end.saturating_sub(1));
+    let delimited = blocks
+      .first()
+      .is_none_or(|first| first.meta.start_loc.start as usize > open_end)
+      && close_start > open_end;
+    if !delimited {
+      return self.verbatim(end, false);
+    }
+    self.verbatim(open_end, false);
+    // list marker depth starts over inside a delimited block
+    let depths = (self.unordered_depth, self.ordered_depth);
+    (self.unordered_depth, self.ordered_depth) = (0, 0);
+    self.blocks(blocks, Spacing::Strip);
+    (self.unordered_depth, self.ordered_depth) = depths;
+    self.gap_to(close_start, Spacing::Strip);
+    self.verbatim(end, false);
+  }
+
+  fn list(&mut self, block: &Block, variant: ListVariant, items: &[ListItem]) {
+    let depth = match variant {
+      ListVariant::Unordered => &mut self.unordered_depth,
+      ListVariant::Ordered => &mut self.ordered_depth,
+      _ => return self.verbatim(block.loc.end_pos as usize, false),
+    };
+    *depth += 1;
+    let marker = match variant {
+      ListVariant::Ordered if !implicitly_numbered(items) => None,
+      ListVariant::Ordered => Some(".".repeat(*depth)),
+      _ => Some("*".repeat(*depth)),
+    };
+
+    for item in items {
+      self.gap_to(item.loc_start() as usize, Spacing::Collapse);
+      match &marker {
+        Some(marker) => {
+          self.out.push_str(marker);
+          self.pos = item.loc().end as usize;
+        }
+        None => self.verbatim(item.loc().end as usize, false),
+      }
+      if let Some(end) = item.principle.last_loc_end() {
+        self.verbatim(end as usize, false);
+      }
+      for block in item.blocks.iter() {
+        self.block(block, Spacing::Collapse);
+      }
+    }
+
+    match variant {
+      ListVariant::Unordered => self.unordered_depth -= 1,
+      _ => self.ordered_depth -= 1,
+    }
+  }
+
+  fn table(&mut self, block: &Block, table: &Table) {
+    let end = block.loc.end_pos as usize;
+    match table::align(&self.src[self.pos..end], table, &block.meta.attrs) {
+      Some(aligned) => {
+        self.out.push_str(&aligned);
+        self.pos = end;
+      }
+      None => self.verbatim(end, false),
+    }
+  }
+
+  /// prints the attribute list, anchor, and title lines preceding a block
+  fn metadata(&mut self, content_start: usize) {
+    if content_start <= self.pos {
+      return;
+    }
+    let lines = &self.src[self.pos..content_start];
+    for line in lines.split_inclusive('\n') {
+      // partial line preceding content, eg: `NOTE: ` or indentation
+      if !line.ends_with('\n') {
+        self.out.push_str(line);
+        continue;
+      }
+      match attr_list::normalize(line) {
+        Some(normalized) => self.out.push_str(&normalized),
+        None => self.out.push_str(line.trim_end()),
+      }
+      self.out.push('\n');
+    }
+    self.pos = content_start;
+  }
+
+  /// prints a section or document title line, normalizing
+  /// markdown-style `#` markers to `=`
+  fn heading(&mut self, line_start: usize) {
+    let line_end = self.line_end(line_start);
+    let line = &self.src[line_start..line_end];
+    match HEADING_RE.captures(line) {
+      Some(captures) => {
+        self.out.push_str(&"=".repeat(captures[1].len()));
+        self.out.push(' ');
+        self.out.push_str(&captures[2]);
+        self.pos = line_end;
+      }
+      None => self.verbatim(line_end, false),
+    }
+  }
+
+  /// copies source up to `end` as-is, except for trailing whitespace
+  fn verbatim(&mut self, end: usize, preserve_whitespace: bool) {
+    if end <= self.pos {
+      return;
+    }
+    let text = &self.src[self.pos..end];
+    if preserve_whitespace {
+      self.out.push_str(text);
+    } else {
+      for (idx, line) in text.split('\n').enumerate() {
+        if idx > 0 {
+          self.out.push('\n');
+        }
+        self.out.push_str(line.trim_end());
+      }
+    }
+    self.pos = end;
+  }
+
+  /// copies source between printed nodes (blank lines, comments,
+  /// directives, etc.), normalizing blank lines per `spacing`
+  fn gap_to(&mut self, end: usize, spacing: Spacing) {
+    if end <= self.pos {
+      return;
+    }
+    let gap = &self.src[self.pos..end];
+    self.pos = end;
+    let mut segments = gap.split('\n').collect::<Vec<_>>();
+    // partial line preceding `end`, usually empty, or indentation
+    let last = segments.pop().unwrap_or("");
+    if segments.is_empty() {
+      self.out.push_str(last);
+      return;
+    }
+    // remainder of the line the previous node ended on
+    self.out.push_str(segments.remove(0).trim_end());
+    self.trim_line_end();
+
+    let mut lines = Vec::with_capacity(segments.len() + 1);
+    for segment in segments {
+      let line = segment.trim_end();
+      if line.is_empty() && (spacing == Spacing::Strip || lines.last() == Some(&"")) {
+        continue;
+      }
+      lines.push(line);
+    }
+    if spacing == Spacing::Separate && lines.is_empty() {
+      lines.insert(0, "");
+    }
+    for line in lines {
+      self.out.push('\n');
+      self.out.push_str(line);
+    }
+    self.out.push('\n');
+    self.out.push_str(last);
+  }
+
+  fn trim_line_end(&mut self) {
+    let len = self.out.trim_end_matches([' ', '\t']).len();
+    self.out.truncate(len);
+  }
+
+  fn line_start(&self, pos: usize) -> usize {
+    self.src[..pos].rfind('\n').map_or(0, |idx| idx + 1)
+  }
+
+  fn line_end(&self, pos: usize) -> usize {
+    self.src[pos..]
+      .find('\n')
+      .map_or(self.src.len(), |idx| pos + idx)
+  }
+}
+
+/// ordered lists numbered `1.`, `2.`, `3.`... can use implicit `.` markers
+fn implicitly_numbered(items: &[ListItem]) -> bool {
+  items
+    .iter()
+    .enumerate()
+    .all(|(idx, item)| match item.marker {
+      ListMarker::Digits(n) => n as usize == idx + 1,
+      _ => true,
+    })
+}
+
+fn is_attr_decl(block: &Block) -> bool {
+  block.context == BlockContext::DocumentAttributeDecl
+}
+
+const fn preserves_whitespace(context: BlockContext) -> bool {
+  matches!(
+    context,
+    BlockContext::Listing
+      | BlockContext::Literal
+      | BlockContext::Passthrough
+      | BlockContext::Stem
+      | BlockContext::Comment
+  )
+}
@@ -0,0 +1,27 @@
+use asciidork_core::SafeMode;
+use asciidork_parser::includes::*;
+
+/// resolves every include to empty content, leaving the
+/// directive line in the source to be printed as-is
+#[derive(Clone)]
+pub struct UnexpandedIncludes;
+
+impl IncludeResolver for UnexpandedIncludes {
+  fn resolve(
+    &mut self,
+    _target: IncludeTarget,
+    buffer: &mut dyn IncludeBuffer,
+    _safe_mode: SafeMode,
+  ) -> std::result::Result<usize, ResolveError> {
+    buffer.initialize(0);
+    Ok(0)
+  }
+
+  fn get_base_dir(&self) -> Option<String> {
+    Some(".".to_string())
+  }
+
+  fn clone_box(&self) -> Box<dyn IncludeResolver> {
+    Box::new(self.clone())
+  }
+}
@@ -0,0 +1,110 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use asciidork_ast::prelude::*;
+
+lazy_static! {
+  static ref CELL_SPEC_RE: Regex =
+    Regex::new(r"^(\d+\*)?(\d+(\.\d+)?\+|\.\d+\+)?([<^>])?(\.[<^>])?[adehlmsv]?$").unwrap();
+}
+
+/// aligns the columns of a simple psv table, where each row is written
+/// on a single line and no cell has a spec, returns `None` otherwise
+pub fn align(src: &str, table: &Table, attrs: &MultiAttrList) -> Option<String> {
+  if attrs.named("format").is_some() || attrs.named("separator").is_some() {
+    return None;
+  }
+  let lines = src.lines().collect::<Vec<_>>();
+  if lines.len() < 2 || lines[0] != "|===" || lines[lines.len() - 1] != "|===" {
+    return None;
+  }
+
+  let num_cols = table.col_widths.len();
+  let rows = table
+    .header_row
+    .iter()
+    .chain(table.rows.iter())
+    .chain(table.footer_row.iter())
+    .collect::<Vec<_>>();
+  if rows.iter().any(|row| {
+    row.cells.len() != num_cols || row.cells.iter().any(|c| c.col_span != 1 || c.row_span != 1)
+  }) {
+    return None;
+  }
+
+  let body = &lines[1..lines.len() - 1];
+  let mut row_lines = Vec::with_capacity(body.len());
+  for line in body {
+    if line.trim().is_empty() {
+      row_lines.push(None);
+      continue;
+    }
+    let cells = split_cells(line)?;
+    if cells.len() != num_cols {
+      return None;
+    }
+    row_lines.push(Some(cells));
+  }
+  if row_lines.iter().flatten().count() != rows.len() {
+    return None;
+  }
+
+  let mut widths = vec![0; num_cols];
+  for cells in row_lines.iter().flatten() {
+    for (width, cell) in widths.iter_mut().zip(cells) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+
+  let mut aligned = String::with_capacity(src.len());
+  aligned.push_str("|===\n");
+  for cells in &row_lines {
+    if let Some(cells) = cells {
+      let mut line = String::new();
+      for (idx, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+        if idx > 0 {
+          line.push(' ');
+        }
+        line.push('|');
+        if !cell.is_empty() {
+          line.push(' ');
+          line.push_str(cell);
+        }
+        if idx < num_cols - 1 {
+          let padding = width - cell.chars().count() + usize::from(cell.is_empty());
+          line.extend(std::iter::repeat_n(' ', padding));
+        }
+      }
+      aligned.push_str(line.trim_end());
+    }
+    aligned.push('\n');
+  }
+  aligned.push_str("|===");
+  Some(aligned)
+}
+
+/// splits a row line into trimmed cell contents, bailing if the line
+/// doesn't start with a separator, or any cell has a spec
+fn split_cells(line: &str) -> Option<Vec<&str>> {
+  let line = line.trim();
+  if !line.starts_with('|') {
+    return None;
+  }
+  let mut cells = Vec::new();
+  let mut start = 1;
+  let bytes = line.as_bytes();
+  for idx in 1..bytes.len() {
+    if bytes[idx] == b'|' && bytes[idx - 1] != b'\\' {
+      cells.push(&line[start..idx]);
+      start = idx + 1;
+    }
+  }
+  cells.push(&line[start..]);
+  for cell in &cells[..cells.len() - 1] {
+    let last_word = cell.rsplit(char::is_whitespace).next().unwrap_or("");
+    if !last_word.is_empty() && CELL_SPEC_RE.is_match(last_word) {
+      return None;
+    }
+  }
+  Some(cells.into_iter().map(str::trim).collect())
+}
@@ -0,0 +1,270 @@
+use asciidork_parser::prelude::*;
+use indoc::indoc;
+
+assert_fmt!(
+  markdown_headings,
+  indoc! {"
+    # Document Title
+    :toc:
+
+    ## Section   Title
+    text
+
+    ### Subsection ###
+    more
+  "},
+  indoc! {"
+    = Document Title
+    :toc:
+
+    == Section   Title
+
+    text
+
+    === Subsection ###
+
+    more
+  "}
+);
+
+assert_fmt!(
+  attr_list_spacing,
+  indoc! {r#"
+    [source , rust ]
+    ----
+    fn main() {}
+    ----
+
+    [quote, "Mark, Twain" , Book]
+    ____
+    Quote text
+    ____
+
+    [cols = "1,2" ]
+    |===
+    |one |two
+    |===
+  "#},
+  indoc! {r#"
+    [source,rust]
+    ----
+    fn main() {}
+    ----
+
+    [quote,"Mark, Twain",Book]
+    ____
+    Quote text
+    ____
+
+    [cols="1,2"]
+    |===
+    | one | two
+    |===
+  "#}
+);
+
+assert_fmt!(
+  list_markers,
+  indoc! {"
+    - one
+    ** nested
+    - two
+
+    1. first
+    2. second
+    .. sub
+  "},
+  indoc! {"
+    * one
+    ** nested
+    * two
+
+    . first
+    . second
+    .. sub
+  "}
+);
+
+assert_fmt!(
+  explicit_list_numbering_kept,
+  indoc! {"
+    4. four
+    5. five
+  "},
+  indoc! {"
+    4. four
+    5. five
+  "}
+);
+
+assert_fmt!(
+  list_markers_restart_in_delimited_block,
+  indoc! {"
+    * outer
+    +
+    ====
+    - inner
+    ====
+  "},
+  indoc! {"
+    * outer
+    +
+    ====
+    * inner
+    ====
+  "}
+);
+
+assert_fmt!(
+  table_alignment,
+  indoc! {"
+    |===
+    |Name|Value
+
+    |longer name | v
+    | x |
+    |===
+  "},
+  indoc! {"
+    |===
+    | Name        | Value
+
+    | longer name | v
+    | x           |
+    |===
+  "}
+);
+
+assert_fmt!(
+  table_with_cell_specs_untouched,
+  indoc! {"
+    |===
+    2+|spans
+    |x |y
+    |===
+  "},
+  indoc! {"
+    |===
+    2+|spans
+    |x |y
+    |===
+  "}
+);
+
+assert_fmt!(
+  blank_lines,
+  "\n\nfirst   \n\n\n\nsecond\n----\ncode  \n----\n\n\n",
+  indoc! {"
+    first
+
+    second
+
+    ----
+    code  \n\
+    ----
+  "}
+);
+
+assert_fmt!(
+  delimited_block_inner_blank_lines,
+  indoc! {"
+    ****
+
+    inside
+
+
+    ****
+  "},
+  indoc! {"
+    ****
+    inside
+    ****
+  "}
+);
+
+assert_fmt!(
+  comments_preserved,
+  indoc! {"
+    // leading comment
+    para one
+    // line comment
+    continued
+
+    ////
+    block comment
+    ////
+    // attached to next
+    para two
+  "},
+  indoc! {"
+    // leading comment
+    para one
+    // line comment
+    continued
+
+    ////
+    block comment
+    ////
+    // attached to next
+    para two
+  "}
+);
+
+assert_fmt!(
+  directives_preserved,
+  indoc! {"
+    == Section
+    include::partial.adoc[]
+
+    ifdef::foo[]
+    Only with foo.
+    endif::[]
+    ifndef::foo[]
+    Without foo.
+    endif::[]
+  "},
+  indoc! {"
+    == Section
+    include::partial.adoc[]
+
+    ifdef::foo[]
+    Only with foo.
+    endif::[]
+    ifndef::foo[]
+    Without foo.
+    endif::[]
+  "}
+);
+
+assert_fmt!(
+  xref_into_include,
+  indoc! {"
+    See <<partial-anchor>>.
+    include::partial.adoc[]
+  "},
+  indoc! {"
+    See <<partial-anchor>>.
+    include::partial.adoc[]
+  "}
+);
+
+assert_fmt!(
+  header_preserved,
+  indoc! {"
+    = Title
+    Author Name
+    v1.0, 2024-01-01
+    :toc:
+
+
+    Preamble.
+  "},
+  indoc! {"
+    = Title
+    Author Name
+    v1.0, 2024-01-01
+    :toc:
+
+    Preamble.
+  "}
+);
@@ -0,0 +1,13 @@
+macro_rules! assert_fmt {
+  ($name:ident, $input:expr, $expected:expr$(,)?) => {
+    #[test]
+    fn $name() {
+      let actual = asciidork_fmt::format($input, SourceFile::Tmp).unwrap();
+      ::test_utils::expect_eq!(actual, $expected.to_string(), from: $input);
+      let reformatted = asciidork_fmt::format(&actual, SourceFile::Tmp).unwrap();
+      ::test_utils::expect_eq!(reformatted, actual, from: actual);
+    }
+  };
+}
+
+mod format;
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use asciidork_ast::MacroNode;
+use asciidork_ast::prelude::*;
+use asciidork_core::{AttrValue, Attrs, JobSettings, SafeMode, Severity};
+use asciidork_parser::prelude::*;
+
+use crate::resolver::LspResolver;
+use crate::walk;
+
+/// owned facts about a parsed document, kept between requests
+#[derive(Debug, Default)]
+pub struct Analysis {
+  pub problems: Vec<Problem>,
+  pub anchors: Vec<AnchorDef>,
+  pub xrefs: Vec<XrefUse>,
+  pub symbols: Vec<Symbol>,
+  pub attrs: Vec<AttrDef>,
+}
+
+/// a diagnostic in the analyzed document, `line` is 0-based, columns are bytes
+#[derive(Debug)]
+pub struct Problem {
+  pub line: u32,
+  pub start_col: u32,
+  pub end_col: u32,
+  pub message: String,
+  pub code: &'static str,
+  pub severity: Severity,
+}
+
+#[derive(Debug)]
+pub struct AnchorDef {
+  pub id: String,
+  pub title: String,
+  /// `None` when defined in the analyzed document itself
+  pub path: Option<PathBuf>,
+  pub line: u32,
+  pub col: u32,
+}
+
+#[derive(Debug)]
+pub struct XrefUse {
+  pub target: String,
+  pub start: usize,
+  pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+  Part,
+  Section,
+}
+
+#[derive(Debug)]
+pub struct Symbol {
+  pub name: String,
+  pub kind: SymbolKind,
+  pub start: usize,
+  pub end: usize,
+  pub heading_start: usize,
+  pub heading_end: usize,
+  pub children: Vec<Symbol>,
+}
+
+#[derive(Debug)]
+pub struct AttrDef {
+  pub name: String,
+  pub value: String,
+}
+
+impl Analysis {
+  pub fn new(text: &str, path: Option<&Path>, open_files: Rc<HashMap<String, String>>) -> Self {
+    let bump = &Bump::with_capacity(text.len() * 2);
+    let (file, base_dir) = match path {
+      Some(path) => (
+        SourceFile::Path(asciidork_core::Path::new(path.to_string_lossy())),
+        path.parent().map(|dir| dir.to_string_lossy().to_string()),
+      ),
+      None => (SourceFile::Tmp, None),
+    };
+    let mut parser = Parser::from_str(text, file.clone(), bump);
+    parser.apply_job_settings(JobSettings {
+      safe_mode: SafeMode::Unsafe,
+      strict: false,
+      ..JobSettings::default()
+    });
+    parser.set_resolver(Box::new(LspResolver {
+      base_dir: base_dir.unwrap_or_else(|| ".".to_string()),
+      open_files,
+    }));
+
+    match parser.parse() {
+      Ok(result) => {
+        let mut analysis = Analysis::default();
+        analysis.push_problems(result.warnings.iter(), &file);
+        analysis.collect_anchors(&result);
+        analysis.collect_xrefs(&result.document);
+        analysis.collect_symbols(&result.document);
+        analysis.collect_attrs(&result.document);
+        analysis
+      }
+      Err(diagnostics) => {
+        let mut analysis = Analysis::default();
+        analysis.push_problems(diagnostics.iter(), &file);
+        analysis
+      }
+    }
+  }
+
+  pub fn xref_at(&self, offset: usize) -> Option<&XrefUse> {
+    self
+      .xrefs
+      .iter()
+      .find(|xref| xref.start <= offset && offset <= xref.end)
+  }
+
+  pub fn anchor(&self, id: &str) -> Option<&AnchorDef> {
+    self.anchors.iter().find(|anchor| anchor.id == id)
+  }
+
+  pub fn attr(&self, name: &str) -> Option<&AttrDef> {
+    self.attrs.iter().rev().find(|attr| attr.name == name)
+  }
+
+  fn push_problems<'a>(
+    &mut self,
+    diagnostics: impl Iterator<Item = &'a Diagnostic>,
+    file: &SourceFile,
+  ) {
+    for diagnostic in diagnostics.filter(|d| &d.source_file == file) {
+      self.problems.push(Problem {
+        line: diagnostic.line_num.saturating_sub(1),
+        start_col: diagnostic.underline_start,
+        end_col: diagnostic.underline_start + diagnostic.underline_width,
+        message: diagnostic.message.clone(),
+        code: diagnostic.code.to_str(),
+        severity: diagnostic.severity,
+      });
+    }
+  }
+
+  fn collect_anchors(&mut self, result: &ParseResult) {
+    for (id, anchor) in result.document.anchors.borrow().iter() {
+      // section anchors are located by their heading
+      let Some(loc) = anchor.source_loc.or_else(|| anchor.title.first_loc()) else {
+        continue;
+      };
+      let path = match (anchor.source_idx, result.source_file_at(anchor.source_idx)) {
+        (0, _) => None,
+        (_, SourceFile::Path(path)) => Some(PathBuf::from(path.to_string())),
+        _ => continue,
+      };
+      let (line, col) = result.line_number_with_offset(loc);
+      let title = anchor.reftext.as_ref().unwrap_or(&anchor.title);
+      self.anchors.push(AnchorDef {
+        id: id.to_string(),
+        title: title.plain_text().join(""),
+        path,
+        line: line.saturating_sub(1),
+        col,
+      });
+    }
+    self.anchors.sort_by(|a, b| a.id.cmp(&b.id));
+  }
+
+  fn collect_xrefs(&mut self, document: &Document) {
+    walk::each_inline(document, &mut |node| {
+      if let Inline::Macro(MacroNode::Xref { target, .. }) = &node.content
+        && node.loc.include_depth == 0
+      {
+        self.xrefs.push(XrefUse {
+          target: target.to_string(),
+          start: node.loc.start as usize,
+          end: node.loc.end as usize,
+        });
+      }
+    });
+  }
+
+  fn collect_symbols(&mut self, document: &Document) {
+    match &document.content {
+      DocContent::Blocks(_) => {}
+      DocContent::Sections(sectioned) => {
+        self.symbols = sectioned
+          .sections
+          .iter()
+          .filter_map(section_symbol)
+          .collect();
+      }
+      DocContent::Parts(book) => {
+        let opening = book.opening_special_sects.iter().filter_map(section_symbol);
+        let parts = book.parts.iter().filter_map(part_symbol);
+        let closing = book.closing_special_sects.iter().filter_map(section_symbol);
+        self.symbols = opening.chain(parts).chain(closing).collect();
+      }
+    }
+  }
+
+  fn collect_attrs(&mut self, document: &Document) {
+    let defaults = Attrs::defaults();
+    let header = document.meta.header_attrs();
+    for (name, value) in defaults.iter().chain(header.iter()) {
+      if !name.starts_with("_asciidork") {
+        self.push_attr(name, value);
+      }
+    }
+    let mut push_decls = |blocks: &[Block]| {
+      for block in blocks {
+        if let BlockContent::DocumentAttribute(name, value) = &block.content {
+          self.push_attr(name, value);
+        }
+      }
+    };
+    match &document.content {
+      DocContent::Blocks(blocks) => push_decls(blocks),
+      DocContent::Sections(sectioned) => {
+        sectioned
+          .preamble
+          .iter()
+          .for_each(|blocks| push_decls(blocks));
+        each_section(&sectioned.sections, &mut |section| {
+          push_decls(&section.blocks)
+        });
+      }
+      DocContent::Parts(book) => {
+        book.preamble.iter().for_each(|blocks| push_decls(blocks));
+        for part in book.parts.iter() {
+          each_section(&part.sections, &mut |section| push_decls(&section.blocks));
+        }
+      }
+    }
+  }
+
+  fn push_attr(&mut self, name: &str, value: &AttrValue) {
+    let value = match value {
+      AttrValue::String(value) => value.clone(),
+      AttrValue::Bool(true) => String::new(),
+      AttrValue::Bool(false) => "(unset)".to_string(),
+    };
+    self.attrs.push(AttrDef { name: name.to_string(), value });
+  }
+}
+
+fn each_section(sections: &[Section], f: &mut impl FnMut(&Section)) {
+  for section in sections {
+    f(section);
+    for block in section.blocks.iter() {
+      if let BlockContent::Section(nested) = &block.content {
+        each_section(std::slice::from_ref(nested), f);
+      }
+    }
+  }
+}
+
+fn section_symbol(section: &Section) -> Option<Symbol> {
+  if section.loc.start_depth != 0 {
+    return None;
+  }
+  let children = section
+    .blocks
+    .iter()
+    .filter_map(|block| match &block.content {
+      BlockContent::Section(nested) => section_symbol(nested),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  let heading_start = section.loc.start_pos as usize;
+  let heading_end = section
+    .heading
+    .last_loc()
+    .filter(|loc| loc.include_depth == 0)
+    .map_or(heading_start, |loc| loc.end as usize);
+  let end = match section.loc.end_depth {
+    0 => section.loc.end_pos as usize,
+    _ => children.last().map_or(heading_end, |child| child.end),
+  };
+  Some(Symbol {
+    name: section.heading.plain_text().join(""),
+    kind: SymbolKind::Section,
+    start: section.meta.start_loc.start as usize,
+    end: end.max(heading_end),
+    heading_start,
+    heading_end,
+    children,
+  })
+}
+
+fn part_symbol(part: &Part) -> Option<Symbol> {
+  let first = part.title.text.first_loc()?;
+  let last = part.title.text.last_loc()?;
+  if first.include_depth != 0 {
+    return None;
+  }
+  let children = part
+    .sections
+    .iter()
+    .filter_map(section_symbol)
+    .collect::<Vec<_>>();
+  let heading_end = last.end as usize;
+  Some(Symbol {
+    name: part.title.text.plain_text().join(""),
+    kind: SymbolKind::Part,
+    start: part.title.meta.start_loc.start as usize,
+    end: children.last().map_or(heading_end, |child| child.end),
+    heading_start: first.start as usize,
+    heading_end,
+    children,
+  })
+}
@@ -0,0 +1,17 @@
+use std::io::{self, BufRead, Write};
+
+mod analysis;
+mod resolver;
+mod server;
+mod source_text;
+mod transport;
+mod uri;
+mod walk;
+
+pub use crate::server::Server;
+
+/// runs the language server over the given streams until an `exit`
+/// notification, returning the process exit code
+pub fn run(input: impl BufRead, output: impl Write) -> io::Result<i32> {
+  Server::new(input, output).run()
+}
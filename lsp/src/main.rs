@@ -0,0 +1,11 @@
+fn main() {
+  let stdin = std::io::stdin();
+  let stdout = std::io::stdout();
+  match asciidork_lsp::run(stdin.lock(), stdout.lock()) {
+    Ok(code) => std::process::exit(code),
+    Err(err) => {
+      eprintln!("asciidork-lsp: {err}");
+      std::process::exit(1);
+    }
+  }
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::fs;
+use std::rc::Rc;
+
+use asciidork_core::SafeMode;
+use asciidork_parser::includes::*;
+
+/// resolves includes from open editor buffers, falling back to the filesystem
+#[derive(Clone)]
+pub struct LspResolver {
+  pub base_dir: String,
+  pub open_files: Rc<HashMap<String, String>>,
+}
+
+impl IncludeResolver for LspResolver {
+  fn resolve(
+    &mut self,
+    target: IncludeTarget,
+    buffer: &mut dyn IncludeBuffer,
+    _safe_mode: SafeMode,
+  ) -> std::result::Result<usize, ResolveError> {
+    let IncludeTarget::FilePath(path) = target else {
+      return Err(ResolveError::UriReadNotSupported);
+    };
+    let contents = match self.open_files.get(&path) {
+      Some(text) => text.as_bytes().to_vec(),
+      None => fs::read(&path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => ResolveError::NotFound,
+        _ => ResolveError::Io(err.to_string()),
+      })?,
+    };
+    buffer.initialize(contents.len());
+    buffer.as_bytes_mut().copy_from_slice(&contents);
+    Ok(contents.len())
+  }
+
+  fn get_base_dir(&self) -> Option<String> {
+    Some(self.base_dir.clone())
+  }
+
+  fn clone_box(&self) -> Box<dyn IncludeResolver> {
+    Box::new(self.clone())
+  }
+}
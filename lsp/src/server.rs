@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::{Value, json};
+
+use asciidork_core::Severity;
+
+use crate::analysis::{Analysis, AnchorDef, Symbol, SymbolKind};
+use crate::source_text::SourceText;
+use crate::{transport, uri};
+
+lazy_static! {
+  static ref INCLUDE_RE: Regex = Regex::new(r"^include::([^\[\s]+)\[").unwrap();
+  static ref ATTR_REF_RE: Regex = Regex::new(r"\{([\w-]+)\}").unwrap();
+  static ref XREF_PREFIX_RE: Regex = Regex::new(r"(?:<<|xref:)([\w:.#/-]*)$").unwrap();
+  static ref ATTR_REF_PREFIX_RE: Regex = Regex::new(r"\{([\w-]*)$").unwrap();
+  static ref ATTR_ENTRY_PREFIX_RE: Regex = Regex::new(r"^:!?([\w-]*)$").unwrap();
+}
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+const PARSE_ERROR: i64 = -32700;
+
+/// number of source lines shown in hover previews
+const PREVIEW_LINES: usize = 8;
+
+struct OpenDocument {
+  source: SourceText,
+  path: Option<PathBuf>,
+  analysis: Analysis,
+}
+
+pub struct Server<R, W> {
+  input: R,
+  output: W,
+  documents: HashMap<String, OpenDocument>,
+  shutdown_requested: bool,
+}
+
+impl<R: BufRead, W: Write> Server<R, W> {
+  pub fn new(input: R, output: W) -> Self {
+    Self {
+      input,
+      output,
+      documents: HashMap::new(),
+      shutdown_requested: false,
+    }
+  }
+
+  pub fn run(mut self) -> io::Result<i32> {
+    while let Some(message) = transport::read_message(&mut self.input)? {
+      let message = match message {
+        Ok(message) => message,
+        Err(err) => {
+          self.respond_err(Value::Null, PARSE_ERROR, &format!("Parse error: {err}"))?;
+          continue;
+        }
+      };
+      let method = message["method"].as_str().unwrap_or_default().to_string();
+      if method == "exit" {
+        return Ok(if self.shutdown_requested { 0 } else { 1 });
+      }
+      match message.get("id").cloned() {
+        Some(id) => self.handle_request(id, &method, &message["params"])?,
+        None => self.handle_notification(&method, &message["params"])?,
+      }
+    }
+    Ok(1)
+  }
+
+  fn handle_request(&mut self, id: Value, method: &str, params: &Value) -> io::Result<()> {
+    if self.shutdown_requested && method != "shutdown" {
+      return self.respond_err(id, INVALID_REQUEST, "Server is shutting down");
+    }
+    let result = match method {
+      "initialize" => initialize_result(),
+      "shutdown" => {
+        self.shutdown_requested = true;
+        Value::Null
+      }
+      "textDocument/definition" => self.definition(params),
+      "textDocument/completion" => self.completion(params),
+      "textDocument/documentSymbol" => self.document_symbols(params),
+      "textDocument/hover" => self.hover(params),
+      _ => return self.respond_err(id, METHOD_NOT_FOUND, &format!("Unknown method: {method}")),
+    };
+    self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+  }
+
+  fn handle_notification(&mut self, method: &str, params: &Value) -> io::Result<()> {
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+    match method {
+      "textDocument/didOpen" => {
+        let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+        self.update(uri, text.to_string())
+      }
+      // we only advertise full document sync
+      "textDocument/didChange" => {
+        match params["contentChanges"].as_array().and_then(|c| c.last()) {
+          Some(change) => {
+            let text = change["text"].as_str().unwrap_or_default();
+            self.update(uri, text.to_string())
+          }
+          None => Ok(()),
+        }
+      }
+      "textDocument/didClose" => {
+        self.documents.remove(uri);
+        self.publish_diagnostics(uri, Vec::new())
+      }
+      _ => Ok(()),
+    }
+  }
+
+  fn update(&mut self, uri: &str, text: String) -> io::Result<()> {
+    let path = uri::to_path(uri);
+    let mut open_files = self
+      .documents
+      .values()
+      .filter_map(|doc| {
+        Some((
+          doc.path.as_ref()?.to_string_lossy().to_string(),
+          doc.source.text.clone(),
+        ))
+      })
+      .collect::<HashMap<_, _>>();
+    if let Some(path) = &path {
+      open_files.remove(&*path.to_string_lossy());
+    }
+    let analysis = Analysis::new(&text, path.as_deref(), Rc::new(open_files));
+    let source = SourceText::new(text);
+    let diagnostics = analysis
+      .problems
+      .iter()
+      .map(|problem| {
+        let start = source.line_col_offset(problem.line, problem.start_col);
+        let end = source.line_col_offset(problem.line, problem.end_col);
+        json!({
+          "range": source.range(start, end),
+          "severity": match problem.severity {
+            Severity::Fatal | Severity::Error => 1,
+            Severity::Warn => 2,
+            Severity::Info => 3,
+          },
+          "code": problem.code,
+          "source": "asciidork",
+          "message": problem.message,
+        })
+      })
+      .collect();
+    self
+      .documents
+      .insert(uri.to_string(), OpenDocument { source, path, analysis });
+    self.publish_diagnostics(uri, diagnostics)
+  }
+
+  fn definition(&self, params: &Value) -> Value {
+    let Some((doc, offset)) = self.document_at(params) else {
+      return Value::Null;
+    };
+    if let Some(xref) = doc.analysis.xref_at(offset) {
+      return self.xref_location(doc, &xref.target).unwrap_or(Value::Null);
+    }
+    match doc.include_at(offset) {
+      Some(path) if path.exists() => json!({
+        "uri": uri::from_path(&path),
+        "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+      }),
+      _ => Value::Null,
+    }
+  }
+
+  fn completion(&self, params: &Value) -> Value {
+    let Some((doc, offset)) = self.document_at(params) else {
+      return Value::Null;
+    };
+    let (line_start, _) = doc.source.line_at(offset);
+    let prefix = &doc.source.text[line_start..offset];
+    if XREF_PREFIX_RE.is_match(prefix) {
+      let items = doc.analysis.anchors.iter().map(|anchor| {
+        json!({
+          "label": anchor.id,
+          "kind": 18, // reference
+          "detail": anchor.title,
+        })
+      });
+      return Value::Array(items.collect());
+    }
+    if ATTR_REF_PREFIX_RE.is_match(prefix) || ATTR_ENTRY_PREFIX_RE.is_match(prefix) {
+      let mut names = doc
+        .analysis
+        .attrs
+        .iter()
+        .map(|attr| attr.name.as_str())
+        .collect::<Vec<_>>();
+      names.sort_unstable();
+      names.dedup();
+      let items = names.into_iter().map(|name| {
+        let value = doc.analysis.attr(name).map(|attr| attr.value.as_str());
+        json!({
+          "label": name,
+          "kind": 6, // variable
+          "detail": value,
+        })
+      });
+      return Value::Array(items.collect());
+    }
+    Value::Array(Vec::new())
+  }
+
+  fn document_symbols(&self, params: &Value) -> Value {
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+    let Some(doc) = self.documents.get(uri) else {
+      return Value::Null;
+    };
+    Value::Array(
+      doc
+        .analysis
+        .symbols
+        .iter()
+        .map(|s| symbol_json(&doc.source, s))
+        .collect(),
+    )
+  }
+
+  fn hover(&self, params: &Value) -> Value {
+    let Some((doc, offset)) = self.document_at(params) else {
+      return Value::Null;
+    };
+    if let Some(xref) = doc.analysis.xref_at(offset) {
+      let Some(anchor) = doc.analysis.anchor(xref_id(&xref.target)) else {
+        return Value::Null;
+      };
+      let mut contents = format!("**{}**", anchor.title);
+      if let Some(preview) = self.anchor_preview(doc, anchor) {
+        contents.push_str(&format!("\n\n```asciidoc\n{preview}\n```"));
+      }
+      return hover_json(contents, doc.source.range(xref.start, xref.end));
+    }
+    if let Some(path) = doc.include_at(offset) {
+      let Ok(text) = fs::read_to_string(&path) else {
+        return Value::Null;
+      };
+      let contents = format!(
+        "`{}`\n\n```asciidoc\n{}\n```",
+        path.display(),
+        preview(&text, 0)
+      );
+      let (line_start, line) = doc.source.line_at(offset);
+      return hover_json(
+        contents,
+        doc.source.range(line_start, line_start + line.len()),
+      );
+    }
+    let (line_start, line) = doc.source.line_at(offset);
+    for captures in ATTR_REF_RE.captures_iter(line) {
+      let whole = captures.get(0).unwrap();
+      let (start, end) = (line_start + whole.start(), line_start + whole.end());
+      if start <= offset && offset <= end {
+        let name = &captures[1];
+        let contents = match doc.analysis.attr(name) {
+          Some(attr) => format!("`{name}`: {}", attr.value),
+          None => format!("`{name}` is not defined"),
+        };
+        return hover_json(contents, doc.source.range(start, end));
+      }
+    }
+    Value::Null
+  }
+
+  fn xref_location(&self, doc: &OpenDocument, target: &str) -> Option<Value> {
+    let (file, id) = match target.split_once('#') {
+      Some((file, id)) => (file, id),
+      None if target.ends_with(".adoc") => (target, ""),
+      None => ("", target),
+    };
+    if file.is_empty() {
+      let anchor = doc.analysis.anchor(id)?;
+      let (uri, range) = self.anchor_range(doc, anchor)?;
+      return Some(json!({ "uri": uri, "range": range }));
+    }
+    let mut path = doc.path.as_ref()?.parent()?.join(file);
+    if path.extension().is_none() {
+      path.set_extension("adoc");
+    }
+    let text = self.read_text(&path)?;
+    let source = SourceText::new(text);
+    let offset = find_anchor_def(&source.text, id).unwrap_or(0);
+    Some(json!({ "uri": uri::from_path(&path), "range": source.range(offset, offset) }))
+  }
+
+  fn anchor_range(&self, doc: &OpenDocument, anchor: &AnchorDef) -> Option<(String, Value)> {
+    match &anchor.path {
+      None => {
+        let offset = doc.source.line_col_offset(anchor.line, anchor.col);
+        let uri = doc.path.as_deref().map(uri::from_path)?;
+        Some((uri, doc.source.range(offset, offset)))
+      }
+      Some(path) => {
+        let source = SourceText::new(self.read_text(path)?);
+        let offset = source.line_col_offset(anchor.line, anchor.col);
+        Some((uri::from_path(path), source.range(offset, offset)))
+      }
+    }
+  }
+
+  fn anchor_preview(&self, doc: &OpenDocument, anchor: &AnchorDef) -> Option<String> {
+    match &anchor.path {
+      None => Some(preview(&doc.source.text, anchor.line as usize)),
+      Some(path) => Some(preview(&self.read_text(path)?, anchor.line as usize)),
+    }
+  }
+
+  /// reads a file, preferring the contents of an open editor buffer
+  fn read_text(&self, path: &Path) -> Option<String> {
+    self
+      .documents
+      .values()
+      .find(|doc| doc.path.as_deref() == Some(path))
+      .map(|doc| doc.source.text.clone())
+      .or_else(|| fs::read_to_string(path).ok())
+  }
+
+  fn document_at(&self, params: &Value) -> Option<(&OpenDocument, usize)> {
+    let uri = params["textDocument"]["uri"].as_str()?;
+    let doc = self.documents.get(uri)?;
+    let line = params["position"]["line"].as_u64()? as u32;
+    let character = params["position"]["character"].as_u64()? as u32;
+    Some((doc, doc.source.offset(line, character)))
+  }
+
+  fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> io::Result<()> {
+    self.send(json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": { "uri": uri, "diagnostics": diagnostics },
+    }))
+  }
+
+  fn respond_err(&mut self, id: Value, code: i64, message: &str) -> io::Result<()> {
+    self.send(json!({
+      "jsonrpc": "2.0",
+      "id": id,
+      "error": { "code": code, "message": message },
+    }))
+  }
+
+  fn send(&mut self, message: Value) -> io::Result<()> {
+    transport::write_message(&mut self.output, &message)
+  }
+}
+
+impl OpenDocument {
+  /// the resolved target of an include directive on the line at `offset`
+  fn include_at(&self, offset: usize) -> Option<PathBuf> {
+    let (_, line) = self.source.line_at(offset);
+    let target = INCLUDE_RE.captures(line)?.get(1)?.as_str();
+    if target.contains('{') || target.contains("://") {
+      return None;
+    }
+    let dir = self.path.as_ref()?.parent()?;
+    Some(dir.join(target))
+  }
+}
+
+fn initialize_result() -> Value {
+  json!({
+    "capabilities": {
+      "textDocumentSync": 1, // full
+      "definitionProvider": true,
+      "completionProvider": { "triggerCharacters": ["<", "{", ":"] },
+      "documentSymbolProvider": true,
+      "hoverProvider": true,
+    },
+    "serverInfo": { "name": "asciidork-lsp", "version": env!("CARGO_PKG_VERSION") },
+  })
+}
+
+fn symbol_json(source: &SourceText, symbol: &Symbol) -> Value {
+  let children = symbol
+    .children
+    .iter()
+    .map(|child| symbol_json(source, child));
+  json!({
+    "name": symbol.name,
+    "kind": match symbol.kind {
+      SymbolKind::Part => 2, // module
+      SymbolKind::Section => 15, // string
+    },
+    "range": source.range(symbol.start, symbol.end),
+    "selectionRange": source.range(symbol.heading_start, symbol.heading_end),
+    "children": children.collect::<Vec<_>>(),
+  })
+}
+
+fn hover_json(markdown: String, range: Value) -> Value {
+  json!({
+    "contents": { "kind": "markdown", "value": markdown },
+    "range": range,
+  })
+}
+
+fn xref_id(target: &str) -> &str {
+  target.rsplit_once('#').map_or(target, |(_, id)| id)
+}
+
+fn preview(text: &str, first_line: usize) -> String {
+  let lines = text.lines().skip(first_line).take(PREVIEW_LINES);
+  lines.collect::<Vec<_>>().join("\n")
+}
+
+/// finds an explicit anchor definition for `id` in asciidoc source
+fn find_anchor_def(text: &str, id: &str) -> Option<usize> {
+  if id.is_empty() {
+    return None;
+  }
+  [
+    format!("[[{id}]]"),
+    format!("[[{id},"),
+    format!("[#{id}"),
+    format!("anchor:{id}["),
+  ]
+  .iter()
+  .filter_map(|needle| text.find(needle.as_str()))
+  .min()
+}
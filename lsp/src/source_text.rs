@@ -0,0 +1,70 @@
+use serde_json::{Value, json};
+
+/// document text, with conversions between byte offsets
+/// and lsp positions, which count utf-16 code units
+pub struct SourceText {
+  pub text: String,
+  line_starts: Vec<usize>,
+}
+
+impl SourceText {
+  pub fn new(text: String) -> Self {
+    let mut line_starts = vec![0];
+    line_starts.extend(text.match_indices('\n').map(|(idx, _)| idx + 1));
+    Self { text, line_starts }
+  }
+
+  pub fn position(&self, offset: usize) -> (u32, u32) {
+    let offset = offset.min(self.text.len());
+    let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+    let line_start = self.line_starts[line];
+    let character = self.text[line_start..offset].encode_utf16().count();
+    (line as u32, character as u32)
+  }
+
+  pub fn offset(&self, line: u32, character: u32) -> usize {
+    let Some(&line_start) = self.line_starts.get(line as usize) else {
+      return self.text.len();
+    };
+    let line_text = &self.text[line_start..self.line_end(line_start)];
+    let mut units = 0;
+    for (idx, c) in line_text.char_indices() {
+      if units >= character as usize {
+        return line_start + idx;
+      }
+      units += c.len_utf16();
+    }
+    line_start + line_text.len()
+  }
+
+  /// the byte offset of the given 0-based line and byte column
+  pub fn line_col_offset(&self, line: u32, col: u32) -> usize {
+    self
+      .line_starts
+      .get(line as usize)
+      .map_or(self.text.len(), |start| {
+        (start + col as usize).min(self.text.len())
+      })
+  }
+
+  pub fn line_at(&self, offset: usize) -> (usize, &str) {
+    let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+    let start = self.line_starts[line];
+    (start, &self.text[start..self.line_end(start)])
+  }
+
+  pub fn range(&self, start: usize, end: usize) -> Value {
+    let (start_line, start_char) = self.position(start);
+    let (end_line, end_char) = self.position(end);
+    json!({
+      "start": { "line": start_line, "character": start_char },
+      "end": { "line": end_line, "character": end_char },
+    })
+  }
+
+  fn line_end(&self, line_start: usize) -> usize {
+    self.text[line_start..]
+      .find('\n')
+      .map_or(self.text.len(), |idx| line_start + idx)
+  }
+}
@@ -0,0 +1,41 @@
+use std::io::{self, BufRead, Write};
+
+use serde_json::Value;
+
+/// reads a single `Content-Length` framed json-rpc message,
+/// returning `None` when the input is exhausted, a body that isn't
+/// valid json is an inner `Err`, as the next message is still readable
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<serde_json::Result<Value>>> {
+  let mut content_length = None;
+  loop {
+    let mut header = String::new();
+    if input.read_line(&mut header)? == 0 {
+      return Ok(None);
+    }
+    let header = header.trim_end();
+    if header.is_empty() {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':')
+      && name.eq_ignore_ascii_case("content-length")
+    {
+      content_length = value.trim().parse::<usize>().ok();
+    }
+  }
+  let Some(len) = content_length else {
+    return Err(invalid_data("missing Content-Length header"));
+  };
+  let mut body = vec![0; len];
+  input.read_exact(&mut body)?;
+  Ok(Some(serde_json::from_slice(&body)))
+}
+
+pub fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+  let body = message.to_string();
+  write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+  output.flush()
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
@@ -0,0 +1,41 @@
+use std::path::{Path, PathBuf};
+
+/// converts a `file://` uri to a filesystem path
+pub fn to_path(uri: &str) -> Option<PathBuf> {
+  let path = uri.strip_prefix("file://")?;
+  let mut bytes = Vec::with_capacity(path.len());
+  let mut chars = path.bytes();
+  while let Some(byte) = chars.next() {
+    if byte == b'%' {
+      let hex = [chars.next()?, chars.next()?];
+      let hex = std::str::from_utf8(&hex).ok()?;
+      bytes.push(u8::from_str_radix(hex, 16).ok()?);
+    } else {
+      bytes.push(byte);
+    }
+  }
+  let path = String::from_utf8(bytes).ok()?;
+  // windows paths are written as `file:///C:/...`
+  match path.strip_prefix('/') {
+    Some(rest) if rest.get(1..2) == Some(":") => Some(PathBuf::from(rest)),
+    _ => Some(PathBuf::from(path)),
+  }
+}
+
+/// converts a filesystem path to a `file://` uri
+pub fn from_path(path: &Path) -> String {
+  let path = path.to_string_lossy().replace('\\', "/");
+  let mut uri = String::from("file://");
+  if !path.starts_with('/') {
+    uri.push('/');
+  }
+  for byte in path.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+        uri.push(byte as char)
+      }
+      _ => uri.push_str(&format!("%{byte:02X}")),
+    }
+  }
+  uri
+}
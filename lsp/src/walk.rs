@@ -0,0 +1,97 @@
+use asciidork_ast::prelude::*;
+use asciidork_ast::{InlineNodes, MacroNode};
+
+/// calls `f` with every inline node in the document, depth-first
+pub fn each_inline(document: &Document, f: &mut impl FnMut(&InlineNode)) {
+  if let Some(title) = document.header.as_ref().and_then(|h| h.title.as_ref()) {
+    inlines(&title.main, f);
+  }
+  match &document.content {
+    DocContent::Blocks(blocks) => blocks.iter().for_each(|b| block(b, f)),
+    DocContent::Sections(Sectioned { preamble, sections }) => {
+      preamble.iter().flatten().for_each(|b| block(b, f));
+      sections.iter().for_each(|s| section(s, f));
+    }
+    DocContent::Parts(book) => {
+      book.preamble.iter().flatten().for_each(|b| block(b, f));
+      book
+        .opening_special_sects
+        .iter()
+        .for_each(|s| section(s, f));
+      for part in book.parts.iter() {
+        inlines(&part.title.text, f);
+        part.intro.iter().flatten().for_each(|b| block(b, f));
+        part.sections.iter().for_each(|s| section(s, f));
+      }
+      book
+        .closing_special_sects
+        .iter()
+        .for_each(|s| section(s, f));
+    }
+  }
+}
+
+fn section(section: &Section, f: &mut impl FnMut(&InlineNode)) {
+  inlines(&section.heading, f);
+  section.blocks.iter().for_each(|b| block(b, f));
+}
+
+fn block(block: &Block, f: &mut impl FnMut(&InlineNode)) {
+  if let Some(title) = block.meta.title() {
+    inlines(title, f);
+  }
+  match &block.content {
+    BlockContent::Compound(blocks) => blocks.iter().for_each(|b| self::block(b, f)),
+    BlockContent::Simple(nodes) => inlines(nodes, f),
+    BlockContent::Section(s) => section(s, f),
+    BlockContent::Empty(EmptyMetadata::DiscreteHeading { content, .. }) => inlines(content, f),
+    BlockContent::QuotedParagraph { quote, attr, cite } => {
+      inlines(quote, f);
+      inlines(attr, f);
+      cite.iter().for_each(|cite| inlines(cite, f));
+    }
+    BlockContent::List { items, .. } => {
+      for item in items.iter() {
+        inlines(&item.principle, f);
+        if let ListItemTypeMeta::DescList { description, extra_terms } = &item.type_meta {
+          extra_terms.iter().for_each(|(term, _)| inlines(term, f));
+          description.iter().for_each(|b| self::block(b, f));
+        }
+        item.blocks.iter().for_each(|b| self::block(b, f));
+      }
+    }
+    BlockContent::Table(table) => {
+      let rows = table.header_row.iter().chain(table.rows.iter());
+      for cell in rows
+        .chain(table.footer_row.iter())
+        .flat_map(|row| row.cells.iter())
+      {
+        match &cell.content {
+          CellContent::AsciiDoc(document) => each_inline(document, f),
+          CellContent::Literal(nodes) => inlines(nodes, f),
+          CellContent::Default(paras)
+          | CellContent::Emphasis(paras)
+          | CellContent::Header(paras)
+          | CellContent::Monospace(paras)
+          | CellContent::Strong(paras) => paras.iter().for_each(|p| inlines(p, f)),
+        }
+      }
+    }
+    BlockContent::Empty(_) | BlockContent::DocumentAttribute(..) => {}
+  }
+}
+
+fn inlines(nodes: &InlineNodes, f: &mut impl FnMut(&InlineNode)) {
+  for node in nodes.iter() {
+    f(node);
+    match &node.content {
+      Inline::Span(_, _, nodes) | Inline::Quote(_, nodes) | Inline::InlinePassthru(nodes) => {
+        inlines(nodes, f)
+      }
+      Inline::Macro(MacroNode::Xref { linktext: Some(nodes), .. })
+      | Inline::Macro(MacroNode::Footnote { text: Some(nodes), .. })
+      | Inline::Macro(MacroNode::Mailto { linktext: Some(nodes), .. }) => inlines(nodes, f),
+      _ => {}
+    }
+  }
+}
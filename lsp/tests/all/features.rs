@@ -0,0 +1,182 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use serde_json::{Value, json};
+
+use crate::*;
+
+const URI: &str = "file:///tmp/asciidork-lsp-doc.adoc";
+
+const DOC: &str = indoc! {"
+  = Document Title
+  :product: Asciidork
+
+  == Intro
+
+  See <<details>> for {product}.
+
+  [#details]
+  == Details
+
+  === Nested
+
+  The end.
+"};
+
+#[test]
+fn diagnostics_published_on_open_and_change() {
+  let (_, sent) = session(&[
+    did_open(URI, "== Intro\n\nSee <<nowhere>>.\n"),
+    notification(
+      "textDocument/didChange",
+      json!({
+        "textDocument": { "uri": URI, "version": 2 },
+        "contentChanges": [{ "text": "== Intro\n\nFixed.\n" }],
+      }),
+    ),
+  ]);
+  assert_eq!(sent[0]["method"], "textDocument/publishDiagnostics");
+  let diagnostics = &sent[0]["params"]["diagnostics"];
+  assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+  assert_eq!(diagnostics[0]["severity"], 2);
+  assert_eq!(diagnostics[0]["code"], "xref-invalid");
+  assert_eq!(diagnostics[0]["range"]["start"]["line"], 2);
+  assert!(
+    diagnostics[0]["message"]
+      .as_str()
+      .unwrap()
+      .contains("nowhere")
+  );
+  assert_eq!(sent[1]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn document_symbols() {
+  let (_, sent) = session(&[
+    did_open(URI, DOC),
+    request(
+      1,
+      "textDocument/documentSymbol",
+      json!({ "textDocument": { "uri": URI } }),
+    ),
+  ]);
+  let symbols = response(&sent, 1);
+  let outline = |symbol: &Value| {
+    let children = symbol["children"].as_array().unwrap();
+    (
+      symbol["name"].as_str().unwrap().to_string(),
+      symbol["selectionRange"]["start"]["line"].as_u64().unwrap(),
+      children
+        .iter()
+        .map(|c| c["name"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>(),
+    )
+  };
+  let symbols = symbols
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(outline)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    symbols,
+    vec![
+      ("Intro".to_string(), 3, vec![]),
+      ("Details".to_string(), 8, vec!["Nested".to_string()]),
+    ]
+  );
+}
+
+#[test]
+fn definition_of_local_xref() {
+  let (_, sent) = session(&[
+    did_open(URI, DOC),
+    at(1, "textDocument/definition", URI, 5, 7),
+  ]);
+  let location = response(&sent, 1);
+  assert_eq!(location["uri"], URI);
+  assert_eq!(location["range"]["start"]["line"], 8);
+}
+
+#[test]
+fn definition_of_include_and_cross_document_xref() {
+  let dir = tmp_dir("definition");
+  std::fs::write(
+    dir.join("chapter.adoc"),
+    "== Chapter\n\n[[deep]]\nDeep paragraph.\n",
+  )
+  .unwrap();
+  let main = dir.join("main.adoc");
+  let uri = file_uri(&main);
+  let chapter_uri = file_uri(&dir.join("chapter.adoc"));
+  let (_, sent) = session(&[
+    did_open(
+      &uri,
+      "= Main\n\ninclude::chapter.adoc[]\n\nSee xref:chapter.adoc#deep[].\n",
+    ),
+    at(1, "textDocument/definition", &uri, 2, 12),
+    at(2, "textDocument/definition", &uri, 4, 10),
+  ]);
+  assert_eq!(response(&sent, 1)["uri"], chapter_uri.as_str());
+  let xref = response(&sent, 2);
+  assert_eq!(xref["uri"], chapter_uri.as_str());
+  assert_eq!(xref["range"]["start"]["line"], 2);
+}
+
+#[test]
+fn complete_anchor_ids() {
+  let text = "== Intro\n\n[#details]\n== Details\n\nSee <<\n";
+  let (_, sent) = session(&[
+    did_open(URI, text),
+    at(1, "textDocument/completion", URI, 5, 6),
+  ]);
+  let labels = labels(&response(&sent, 1));
+  assert!(labels.contains(&"details".to_string()));
+  assert!(labels.contains(&"_intro".to_string()));
+}
+
+#[test]
+fn complete_attribute_names() {
+  let text = "= Title\n:product: Asciidork\n\nUsing {pro\n";
+  let (_, sent) = session(&[
+    did_open(URI, text),
+    at(1, "textDocument/completion", URI, 3, 10),
+    at(2, "textDocument/completion", URI, 3, 2),
+  ]);
+  let items = response(&sent, 1);
+  let product = items
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|item| item["label"] == "product")
+    .unwrap();
+  assert_eq!(product["detail"], "Asciidork");
+  assert!(labels(&items).contains(&"toc-title".to_string()));
+  assert_eq!(response(&sent, 2), json!([]));
+}
+
+#[test]
+fn hover_previews() {
+  let (_, sent) = session(&[
+    did_open(URI, DOC),
+    at(1, "textDocument/hover", URI, 5, 7),
+    at(2, "textDocument/hover", URI, 5, 24),
+    at(3, "textDocument/hover", URI, 11, 2),
+  ]);
+  let xref = response(&sent, 1)["contents"]["value"]
+    .as_str()
+    .unwrap()
+    .to_string();
+  assert!(xref.starts_with("**Details**\n\n```asciidoc\n== Details\n"));
+  assert_eq!(
+    response(&sent, 2)["contents"]["value"],
+    "`product`: Asciidork"
+  );
+  assert_eq!(response(&sent, 3), json!(null));
+}
+
+fn labels(items: &Value) -> Vec<String> {
+  let items = items.as_array().unwrap().iter();
+  items
+    .map(|item| item["label"].as_str().unwrap().to_string())
+    .collect()
+}
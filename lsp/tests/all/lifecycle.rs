@@ -0,0 +1,33 @@
+use serde_json::json;
+
+use crate::*;
+
+#[test]
+fn initialize_and_shutdown() {
+  let (code, sent) = session(&[
+    request(1, "initialize", json!({ "capabilities": {} })),
+    notification("initialized", json!({})),
+    request(2, "shutdown", json!(null)),
+    notification("exit", json!(null)),
+  ]);
+  assert_eq!(code, 0);
+  let capabilities = &response(&sent, 1)["capabilities"];
+  assert_eq!(capabilities["textDocumentSync"], 1);
+  assert_eq!(capabilities["hoverProvider"], true);
+  assert_eq!(capabilities["definitionProvider"], true);
+  assert_eq!(capabilities["documentSymbolProvider"], true);
+  assert_eq!(response(&sent, 2), json!(null));
+}
+
+#[test]
+fn exit_without_shutdown() {
+  let (code, _) = session(&[notification("exit", json!(null))]);
+  assert_eq!(code, 1);
+}
+
+#[test]
+fn unknown_request() {
+  let (_, sent) = session(&[request(7, "textDocument/frobnicate", json!({}))]);
+  assert_eq!(sent[0]["id"], 7);
+  assert_eq!(sent[0]["error"]["code"], -32601);
+}
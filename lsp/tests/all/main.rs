@@ -0,0 +1,94 @@
+use std::io::{BufReader, Cursor};
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+mod features;
+mod lifecycle;
+mod transport;
+
+/// runs the server over `messages`, returning its exit code and every message it sent
+pub fn session(messages: &[Value]) -> (i32, Vec<Value>) {
+  let bodies = messages.iter().map(Value::to_string).collect::<Vec<_>>();
+  raw_session(&bodies)
+}
+
+/// like `session`, but framing message `bodies` as-is, valid json or not
+pub fn raw_session(bodies: &[String]) -> (i32, Vec<Value>) {
+  let mut input = Vec::new();
+  for body in bodies {
+    input.extend(format!("Content-Length: {}\r\n\r\n{body}", body.len()).bytes());
+  }
+  let mut output = Vec::new();
+  let code = asciidork_lsp::run(BufReader::new(Cursor::new(input)), &mut output).unwrap();
+  let mut reader = BufReader::new(Cursor::new(output));
+  let mut sent = Vec::new();
+  while let Some(message) = read_frame(&mut reader) {
+    sent.push(message);
+  }
+  (code, sent)
+}
+
+fn read_frame(reader: &mut impl std::io::BufRead) -> Option<Value> {
+  let mut len = 0;
+  loop {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+      return None;
+    }
+    let line = line.trim_end();
+    if line.is_empty() {
+      break;
+    }
+    if let Some(value) = line.strip_prefix("Content-Length: ") {
+      len = value.parse().ok()?;
+    }
+  }
+  let mut body = vec![0; len];
+  std::io::Read::read_exact(reader, &mut body).ok()?;
+  serde_json::from_slice(&body).ok()
+}
+
+pub fn request(id: u64, method: &str, params: Value) -> Value {
+  json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+pub fn notification(method: &str, params: Value) -> Value {
+  json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+pub fn did_open(uri: &str, text: &str) -> Value {
+  notification(
+    "textDocument/didOpen",
+    json!({ "textDocument": { "uri": uri, "languageId": "asciidoc", "version": 1, "text": text } }),
+  )
+}
+
+pub fn at(id: u64, method: &str, uri: &str, line: u32, character: u32) -> Value {
+  request(
+    id,
+    method,
+    json!({ "textDocument": { "uri": uri }, "position": { "line": line, "character": character } }),
+  )
+}
+
+/// the result of the response to request `id`
+pub fn response(messages: &[Value], id: u64) -> Value {
+  messages
+    .iter()
+    .find(|message| message["id"] == id)
+    .map(|message| message["result"].clone())
+    .unwrap_or_else(|| panic!("no response to request {id}"))
+}
+
+/// a fresh, empty directory for fixture files
+pub fn tmp_dir(name: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join(format!("asciidork-lsp-{name}-{}", std::process::id()));
+  let _ = std::fs::remove_dir_all(&dir);
+  std::fs::create_dir_all(&dir).unwrap();
+  dir
+}
+
+pub fn file_uri(path: &Path) -> String {
+  format!("file://{}", path.display())
+}
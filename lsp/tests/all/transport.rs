@@ -0,0 +1,18 @@
+use serde_json::json;
+
+use crate::*;
+
+#[test]
+fn malformed_message_does_not_stop_server() {
+  let (code, sent) = raw_session(&[
+    "{ not json".to_string(),
+    request(1, "initialize", json!({ "capabilities": {} })).to_string(),
+    request(2, "shutdown", json!(null)).to_string(),
+    notification("exit", json!(null)).to_string(),
+  ]);
+  assert_eq!(code, 0);
+  assert_eq!(sent[0]["id"], json!(null));
+  assert_eq!(sent[0]["error"]["code"], -32700);
+  assert_eq!(response(&sent, 1)["capabilities"]["hoverProvider"], true);
+  assert_eq!(response(&sent, 2), json!(null));
+}
@@ -8,10 +8,116 @@ pub struct Diagnostic {
   pub underline_start: u32,
   pub underline_width: u32,
   pub source_file: SourceFile,
+  pub code: DiagnosticCode,
+  pub severity: Severity,
+}
+
+/// stable identifiers for the kinds of problems the parser reports,
+/// usable to suppress them with the `suppress-diagnostics` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+  AttrDefInvalid,
+  AttrListInvalid,
+  AttributeMissing,
+  AuthorLineInvalid,
+  BlockMetadataUnattached,
+  BlockProcessorFailed,
+  BlockUnclosed,
+  BookPartEmpty,
+  CalloutInvalid,
+  ConditionalInvalid,
+  ConditionalUnclosed,
+  FootnoteEmpty,
+  IdDuplicate,
+  IdInvalid,
+  ImageReadFailed,
+  IncludeDepthExceeded,
+  IncludeFailed,
+  IncludeNotFound,
+  IncludeOptionalNotFound,
+  IncludeProcessorFailed,
+  IncludeRestricted,
+  IncludeTagInvalid,
+  IndexTermInvalid,
+  ListContinuationDangling,
+  ManpageNonconforming,
+  NoResolver,
+  SectionInvalid,
+  SectionOutOfSequence,
+  TableInvalid,
+  TocInvalid,
+  UriReadNotAllowed,
+  XrefInvalid,
+}
+
+impl DiagnosticCode {
+  pub const fn to_str(&self) -> &'static str {
+    match self {
+      DiagnosticCode::AttrDefInvalid => "attr-def-invalid",
+      DiagnosticCode::AttrListInvalid => "attr-list-invalid",
+      DiagnosticCode::AttributeMissing => "attribute-missing",
+      DiagnosticCode::AuthorLineInvalid => "author-line-invalid",
+      DiagnosticCode::BlockMetadataUnattached => "block-metadata-unattached",
+      DiagnosticCode::BlockProcessorFailed => "block-processor-failed",
+      DiagnosticCode::BlockUnclosed => "block-unclosed",
+      DiagnosticCode::BookPartEmpty => "book-part-empty",
+      DiagnosticCode::CalloutInvalid => "callout-invalid",
+      DiagnosticCode::ConditionalInvalid => "conditional-invalid",
+      DiagnosticCode::ConditionalUnclosed => "conditional-unclosed",
+      DiagnosticCode::FootnoteEmpty => "footnote-empty",
+      DiagnosticCode::IdDuplicate => "id-duplicate",
+      DiagnosticCode::IdInvalid => "id-invalid",
+      DiagnosticCode::ImageReadFailed => "image-read-failed",
+      DiagnosticCode::IncludeDepthExceeded => "include-depth-exceeded",
+      DiagnosticCode::IncludeFailed => "include-failed",
+      DiagnosticCode::IncludeNotFound => "include-not-found",
+      DiagnosticCode::IncludeOptionalNotFound => "include-optional-not-found",
+      DiagnosticCode::IncludeProcessorFailed => "include-processor-failed",
+      DiagnosticCode::IncludeRestricted => "include-restricted",
+      DiagnosticCode::IncludeTagInvalid => "include-tag-invalid",
+      DiagnosticCode::IndexTermInvalid => "index-term-invalid",
+      DiagnosticCode::ListContinuationDangling => "list-continuation-dangling",
+      DiagnosticCode::ManpageNonconforming => "manpage-nonconforming",
+      DiagnosticCode::NoResolver => "no-resolver",
+      DiagnosticCode::SectionInvalid => "section-invalid",
+      DiagnosticCode::SectionOutOfSequence => "section-out-of-sequence",
+      DiagnosticCode::TableInvalid => "table-invalid",
+      DiagnosticCode::TocInvalid => "toc-invalid",
+      DiagnosticCode::UriReadNotAllowed => "uri-read-not-allowed",
+      DiagnosticCode::XrefInvalid => "xref-invalid",
+    }
+  }
+
+  pub const fn severity(&self) -> Severity {
+    match self {
+      DiagnosticCode::IncludeOptionalNotFound => Severity::Info,
+      DiagnosticCode::AttributeMissing
+      | DiagnosticCode::BlockMetadataUnattached
+      | DiagnosticCode::BlockUnclosed
+      | DiagnosticCode::CalloutInvalid
+      | DiagnosticCode::ConditionalUnclosed
+      | DiagnosticCode::FootnoteEmpty
+      | DiagnosticCode::IdDuplicate
+      | DiagnosticCode::IncludeRestricted
+      | DiagnosticCode::IncludeTagInvalid
+      | DiagnosticCode::ListContinuationDangling
+      | DiagnosticCode::SectionOutOfSequence
+      | DiagnosticCode::TocInvalid
+      | DiagnosticCode::UriReadNotAllowed
+      | DiagnosticCode::XrefInvalid => Severity::Warn,
+      DiagnosticCode::IncludeDepthExceeded | DiagnosticCode::NoResolver => Severity::Fatal,
+      _ => Severity::Error,
+    }
+  }
 }
 
 impl Parser<'_> {
-  pub(crate) fn err_line(&self, message: impl Into<String>, line: &Line) -> Result<()> {
+  pub(crate) fn err_line(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    line: &Line,
+  ) -> Result<()> {
     let loc = line.first_loc().expect("non empty line for `err_line`");
     let (line_num, offset) = self.lexer.line_number_with_offset(loc);
     let line = line.reassemble_src().to_string();
@@ -21,12 +127,15 @@ impl Parser<'_> {
       underline_start: offset,
       underline_width: line.len() as u32,
       line,
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(loc.include_depth).clone(),
     })
   }
 
   pub(crate) fn err_line_starting(
     &self,
+    code: DiagnosticCode,
     message: impl Into<String>,
     loc: SourceLocation,
   ) -> Result<()> {
@@ -38,11 +147,18 @@ impl Parser<'_> {
       underline_start: offset,
       underline_width: line.len() as u32,
       line: String::from(line.as_str()),
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(loc.include_depth).clone(),
     })
   }
 
-  pub(crate) fn err_line_of(&self, message: impl Into<String>, loc: SourceLocation) -> Result<()> {
+  pub(crate) fn err_line_of(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    loc: SourceLocation,
+  ) -> Result<()> {
     let (line_num, _) = self.lexer.line_number_with_offset(loc);
     let line = self.lexer.line_of(loc);
     self.handle_err(Diagnostic {
@@ -51,12 +167,15 @@ impl Parser<'_> {
       underline_start: 0,
       underline_width: line.len() as u32,
       line: String::from(line.as_str()),
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(loc.include_depth).clone(),
     })
   }
 
   pub(crate) fn err_doc_attr(
     &self,
+    code: DiagnosticCode,
     key: impl Into<String>,
     message: impl Into<String>,
   ) -> Result<()> {
@@ -68,11 +187,12 @@ impl Parser<'_> {
       debug_assert!(false, "doc attr not found");
       return Ok(());
     };
-    self.err_line_of(message, loc)
+    self.err_line_of(code, message, loc)
   }
 
   pub(crate) fn err_at_pattern(
     &self,
+    code: DiagnosticCode,
     message: impl Into<String>,
     line_start: SourceLocation,
     pattern: &str,
@@ -86,6 +206,8 @@ impl Parser<'_> {
         message: message.into(),
         underline_start: idx as u32,
         underline_width: pattern.len() as u32,
+        code,
+        severity: code.severity(),
         source_file: self.lexer.source_file_at(line_start.include_depth).clone(),
       });
     }
@@ -95,11 +217,18 @@ impl Parser<'_> {
       message: message.into(),
       underline_start: 0,
       underline_width: line.len() as u32,
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(line_start.include_depth).clone(),
     })
   }
 
-  pub(crate) fn err_at(&self, message: impl Into<String>, loc: SourceLocation) -> Result<()> {
+  pub(crate) fn err_at(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    loc: SourceLocation,
+  ) -> Result<()> {
     let (line_num, offset) = self.lexer.line_number_with_offset(loc);
     self.handle_err(Diagnostic {
       line_num,
@@ -107,11 +236,18 @@ impl Parser<'_> {
       message: message.into(),
       underline_start: offset,
       underline_width: loc.end - loc.start,
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(loc.include_depth).clone(),
     })
   }
 
-  pub(crate) fn err_token_full(&self, message: impl Into<String>, token: &Token) -> Result<()> {
+  pub(crate) fn err_token_full(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    token: &Token,
+  ) -> Result<()> {
     let (line_num, offset) = self.lexer.line_number_with_offset(token.loc);
     self.handle_err(Diagnostic {
       line_num,
@@ -119,11 +255,18 @@ impl Parser<'_> {
       message: message.into(),
       underline_start: offset,
       underline_width: token.lexeme.len() as u32,
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(token.loc.include_depth).clone(),
     })
   }
 
-  pub(crate) fn err_token_start(&self, message: impl Into<String>, token: &Token) -> Result<()> {
+  pub(crate) fn err_token_start(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    token: &Token,
+  ) -> Result<()> {
     let (line_num, offset) = self.lexer.line_number_with_offset(token.loc);
     self.handle_err(Diagnostic {
       line_num,
@@ -131,11 +274,18 @@ impl Parser<'_> {
       message: message.into(),
       underline_start: offset,
       underline_width: 1,
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(token.loc.include_depth).clone(),
     })
   }
 
-  pub(crate) fn err_token(&self, message: impl Into<String>, token: Option<&Token>) -> Result<()> {
+  pub(crate) fn err_token(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    token: Option<&Token>,
+  ) -> Result<()> {
     let location = token.map_or_else(|| self.lexer.loc(), |t| t.loc);
     let (line_num, offset) = self.lexer.line_number_with_offset(location);
     self.handle_err(Diagnostic {
@@ -144,6 +294,8 @@ impl Parser<'_> {
       message: message.into(),
       underline_start: offset,
       underline_width: 1,
+      code,
+      severity: code.severity(),
       source_file: self.lexer.source_file_at(location.include_depth).clone(),
     })
   }
@@ -153,13 +305,39 @@ impl Parser<'_> {
   }
 
   fn handle_err(&self, err: Diagnostic) -> Result<()> {
-    if self.strict {
+    if self.is_suppressed(err.code) {
+      return Ok(());
+    }
+    self.record_severity(err.severity);
+    if self.strict && err.severity >= self.failure_level {
       Err(err)
     } else {
-      self.errors.borrow_mut().push(err);
+      if err.severity >= self.log_level {
+        self.errors.borrow_mut().push(err);
+      }
       Ok(())
     }
   }
+
+  /// tracked apart from the logged diagnostics, so reaching a failure
+  /// level isn't missed for diagnostics below the `log_level`
+  pub(crate) fn record_severity(&self, severity: Severity) {
+    let highest = self.highest_severity.get().max(Some(severity));
+    self.highest_severity.set(highest);
+  }
+
+  /// codes listed in the `suppress-diagnostics` attribute are never reported
+  fn is_suppressed(&self, code: DiagnosticCode) -> bool {
+    self
+      .document
+      .meta
+      .str("suppress-diagnostics")
+      .is_some_and(|codes| {
+        codes
+          .split(|c: char| c == ',' || c.is_whitespace())
+          .any(|suppressed| suppressed == code.to_str())
+      })
+  }
 }
 
 pub trait DiagnosticColor {
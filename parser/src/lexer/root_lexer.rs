@@ -71,6 +71,9 @@ impl<'arena> RootLexer<'arena> {
         | b"kbd"
         | b"link"
         | b"pass"
+        | b"stem"
+        | b"latexmath"
+        | b"asciimath"
         | b"btn"
         | b"menu"
         | b"mailto"
@@ -85,6 +88,7 @@ impl<'arena> RootLexer<'arena> {
     src_file: SourceFile,
     leveloffset: i8,
     max_include_depth: Option<u16>,
+    included_at: SourceLocation,
     mut src_bytes: BumpVec<'arena, u8>,
   ) {
     // match asciidoctor - its include processor returns an array of lines
@@ -93,18 +97,33 @@ impl<'arena> RootLexer<'arena> {
     if src_bytes.last() != Some(&b'\n') {
       src_bytes.push(b'\n');
     }
-    self.sources.push(SourceLexer::new(
+    let mut source = SourceLexer::new(
       src_bytes,
       src_file,
       leveloffset,
       max_include_depth,
       Rc::clone(&self.plugin_macros),
       self.bump,
-    ));
+    );
+    source.included_at = Some(included_at);
+    self.sources.push(source);
     let next_idx = self.sources.len() as u16 - 1;
     self.next_idx = Some(next_idx);
   }
 
+  /// NB: only valid before lexing has begun
+  pub fn take_primary_src(&mut self) -> BumpVec<'arena, u8> {
+    std::mem::replace(&mut self.sources[0].src, BumpVec::new_in(self.bump))
+  }
+
+  pub fn replace_primary_src(&mut self, src: BumpVec<'arena, u8>) {
+    self.sources[0].src = src;
+  }
+
+  pub fn primary_src(&self) -> &[u8] {
+    &self.sources[0].src
+  }
+
   pub fn set_tmp_buf(&mut self, buf: &str, loc: BufLoc) {
     self.tmp_buf = Some((SourceLexer::from_str(buf, SourceFile::Tmp, self.bump), loc));
   }
@@ -326,6 +345,19 @@ impl<'arena> RootLexer<'arena> {
     self.sources[self.idx as usize].truncate();
   }
 
+  /// `loc` if it is in the primary source, otherwise the location
+  /// of the primary source include directive it was included by
+  pub fn root_loc(&self, mut loc: SourceLocation) -> SourceLocation {
+    while let Some(included_at) = self
+      .sources
+      .get(loc.include_depth as usize)
+      .and_then(|source| source.included_at)
+    {
+      loc = included_at;
+    }
+    loc
+  }
+
   pub const fn include_depth(&self) -> u16 {
     self.idx
   }
@@ -350,6 +382,13 @@ impl<'arena> RootLexer<'arena> {
       lexer.plugin_macros = Rc::clone(&self.plugin_macros);
     }
   }
+
+  pub fn share_plugin_macros(&mut self, other: &RootLexer<'arena>) {
+    self.plugin_macros = Rc::clone(&other.plugin_macros);
+    for lexer in self.sources.iter_mut() {
+      lexer.plugin_macros = Rc::clone(&self.plugin_macros);
+    }
+  }
 }
 
 impl<'arena> HasArena<'arena> for RootLexer<'arena> {
@@ -13,6 +13,8 @@ pub struct SourceLexer<'arena> {
   pub leveloffset: i8,
   pub plugin_macros: Rc<BumpVec<'arena, BumpString<'arena>>>,
   pub max_include_depth: Option<u16>,
+  /// location of the include directive that pushed this source
+  pub included_at: Option<SourceLocation>,
 }
 
 impl<'arena> SourceLexer<'arena> {
@@ -33,6 +35,7 @@ impl<'arena> SourceLexer<'arena> {
       file,
       max_include_depth,
       plugin_macros,
+      included_at: None,
     }
   }
 
@@ -50,6 +53,7 @@ impl<'arena> SourceLexer<'arena> {
       file,
       max_include_depth: None,
       plugin_macros: Rc::new(bvec![in bump]),
+      included_at: None,
     }
   }
 
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-mod base64;
+pub mod base64;
 mod chunk;
 mod contiguous_lines;
 mod delimiter;
@@ -26,7 +26,7 @@ pub mod includes {
 }
 
 pub mod prelude {
-  pub use crate::diagnostic::{Diagnostic, DiagnosticColor};
+  pub use crate::diagnostic::{Diagnostic, DiagnosticCode, DiagnosticColor};
   pub use crate::parse_result::ParseResult;
   pub use crate::parser::{Parser, SourceFile};
   pub use asciidork_core::Path;
@@ -34,7 +34,13 @@ pub mod prelude {
 }
 
 pub use crate::tasks::attr_refs::AttrRefObserver;
-pub use diagnostic::{Diagnostic, DiagnosticColor};
+pub use crate::tasks::block_processors::{
+  BlockProcessor, BlockProcessorInput, BlockProcessorOutput,
+};
+pub use crate::tasks::preprocessors::Preprocessor;
+pub use crate::tasks::reparse::TextEdit;
+pub use crate::tasks::tree_processors::TreeProcessor;
+pub use diagnostic::{Diagnostic, DiagnosticCode, DiagnosticColor};
 pub use parser::Parser;
 
 mod internal {
@@ -52,14 +58,19 @@ mod internal {
   pub use crate::substitutions::*;
   #[cfg(feature = "attr_ref_observation")]
   pub use crate::tasks::attr_refs::AttrRefObserver;
+  pub use crate::tasks::block_processors::*;
   pub use crate::tasks::collect_text::*;
   pub use crate::tasks::customize_subs;
   pub use crate::tasks::directives::includes::*;
   pub use crate::tasks::parse_section::PeekedSection;
+  pub use crate::tasks::preprocessors::*;
+  pub use crate::tasks::tree_processors::*;
   pub use crate::token::*;
   pub use crate::utils::bump::*;
   pub use asciidork_core::regx;
-  pub use asciidork_core::{Author, DocType, JobAttr, JobSettings, Path, ReadAttr, SafeMode};
+  pub use asciidork_core::{
+    Author, DocType, JobAttr, JobSettings, Path, ReadAttr, SafeMode, Severity,
+  };
   pub use asciidork_core::{file, iff};
   pub use ast::*;
   pub use smallvec::SmallVec;
@@ -59,7 +59,7 @@ impl<'arena> Line<'arena> {
 
   pub fn push(&mut self, token: Token<'arena>) {
     match token.kind {
-      MacroName if token.lexeme == "pass:" => self.pass_macro = true,
+      MacroName if is_pass_macro_name(&token.lexeme) => self.pass_macro = true,
       TermDelimiter => self.term_delim = true,
       MaybeEmail => return self.finalize_email(token),
       Plus if self.tokens.last().not_kind(Backtick) && token.len() < 4 => {
@@ -1034,6 +1034,11 @@ impl<'arena> Line<'arena> {
   }
 }
 
+/// macros whose content is extracted before inline parsing, like asciidoctor
+pub fn is_pass_macro_name(lexeme: &str) -> bool {
+  matches!(lexeme, "pass:" | "stem:" | "latexmath:" | "asciimath:")
+}
+
 #[cfg(test)]
 mod tests {
   use crate::internal::*;
@@ -19,7 +19,7 @@ pub struct ParseContext<'arena> {
   pub bibliography_ctx: BiblioContext,
   pub table_cell_ctx: TableCellContext,
   pub inline_ctx: InlineCtx,
-  pub passthrus: BumpVec<'arena, Option<InlineNodes<'arena>>>,
+  pub passthrus: BumpVec<'arena, Option<(InlineNodes<'arena>, Option<StemNotation>)>>,
   pub max_include_depth: u16,
   pub ifdef_stack: BumpVec<'arena, BumpString<'arena>>,
   pub comment_delim_in_lines: bool,
@@ -183,6 +183,9 @@ impl<'arena> ParseContext<'arena> {
       BlockContext::Passthrough => {
         self.subs = Substitutions::none();
       }
+      BlockContext::Stem => {
+        self.subs = Substitutions::only_special_chars();
+      }
       BlockContext::Listing | BlockContext::Literal => {
         self.subs = Substitutions::verbatim();
       }
@@ -1,15 +1,20 @@
 use std::fmt::{Debug, Formatter};
+use std::ops::Range;
 
 use crate::internal::*;
 
 pub struct ParseResult<'arena> {
   pub document: Document<'arena>,
   pub warnings: Vec<Diagnostic>,
+  /// the most severe diagnostic found, including any not
+  /// in `warnings` because they were below the `log_level`
+  pub highest_severity: Option<Severity>,
   pub(crate) attr_defs: BumpVec<'arena, AttrDef>,
   pub include_resolver: Option<Box<dyn IncludeResolver>>,
   #[cfg(feature = "attr_ref_observation")]
   pub attr_ref_observer: Option<Box<dyn AttrRefObserver>>,
-  lexer: Lexer<'arena>,
+  pub(crate) lexer: Lexer<'arena>,
+  pub(crate) reparsed_range: Option<Range<u32>>,
 }
 
 impl ParseResult<'_> {
@@ -21,6 +26,33 @@ impl ParseResult<'_> {
     self.lexer.source_file_at(idx)
   }
 
+  /// builds a diagnostic pointing at `loc`, for problems found
+  /// after parsing, such as by tools checking several documents
+  pub fn diagnostic_at(
+    &self,
+    code: DiagnosticCode,
+    message: impl Into<String>,
+    loc: SourceLocation,
+  ) -> Diagnostic {
+    let (line_num, offset) = self.lexer.line_number_with_offset(loc);
+    Diagnostic {
+      line_num,
+      line: self.lexer.line_of(loc).to_string(),
+      message: message.into(),
+      underline_start: offset,
+      underline_width: loc.end - loc.start,
+      source_file: self.lexer.source_file_at(loc.include_depth).clone(),
+      code,
+      severity: code.severity(),
+    }
+  }
+
+  /// the byte range of the source re-parsed by `Parser::reparse`,
+  /// `None` if the whole document was parsed
+  pub fn reparsed_range(&self) -> Option<Range<u32>> {
+    self.reparsed_range.clone()
+  }
+
   pub fn take_include_resolver_as<T: 'static>(&mut self) -> Option<T> {
     let resolver = self.include_resolver.take()?;
     let any_resolver = resolver as Box<dyn std::any::Any>;
@@ -40,11 +72,13 @@ impl<'arena> From<Parser<'arena>> for ParseResult<'arena> {
     ParseResult {
       document: parser.document,
       warnings: parser.errors.into_inner(),
+      highest_severity: parser.highest_severity.get(),
       attr_defs: parser.ctx.attr_defs,
       include_resolver: parser.include_resolver,
       #[cfg(feature = "attr_ref_observation")]
       attr_ref_observer: parser.attr_ref_observer,
       lexer: parser.lexer,
+      reparsed_range: None,
     }
   }
 }
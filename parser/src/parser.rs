@@ -1,5 +1,7 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::{cell::RefCell, rc::Rc};
+use std::rc::Rc;
 
 use crate::internal::*;
 
@@ -11,8 +13,16 @@ pub struct Parser<'arena> {
   pub(super) peeked_meta: Option<ChunkMeta<'arena>>,
   pub(super) ctx: ParseContext<'arena>,
   pub(super) errors: RefCell<Vec<Diagnostic>>,
+  pub(super) highest_severity: Cell<Option<Severity>>,
   pub(super) strict: bool, // todo: naming...
+  pub(super) log_level: Severity,
+  pub(super) failure_level: Severity,
+  pub(super) check_interdoc_xrefs: bool,
   pub(super) include_resolver: Option<Box<dyn IncludeResolver>>,
+  pub(super) block_processors: HashMap<String, Box<dyn BlockProcessor>>,
+  pub(super) tree_processors: Vec<Box<dyn TreeProcessor>>,
+  pub(super) preprocessors: Vec<Box<dyn Preprocessor>>,
+  pub(super) include_processors: Vec<Box<dyn IncludeProcessor>>,
   #[cfg(feature = "attr_ref_observation")]
   pub(super) attr_ref_observer: Option<Box<dyn AttrRefObserver>>,
 }
@@ -34,8 +44,16 @@ impl<'arena> Parser<'arena> {
       peeked_meta: None,
       ctx: ParseContext::new(lexer.bump),
       errors: RefCell::new(Vec::new()),
+      highest_severity: Cell::new(None),
       strict: true,
+      log_level: Severity::Info,
+      failure_level: Severity::Warn,
+      check_interdoc_xrefs: true,
       include_resolver: None,
+      block_processors: HashMap::new(),
+      tree_processors: Vec::new(),
+      preprocessors: Vec::new(),
+      include_processors: Vec::new(),
       lexer,
       #[cfg(feature = "attr_ref_observation")]
       attr_ref_observer: None,
@@ -49,6 +67,8 @@ impl<'arena> Parser<'arena> {
       Parser::adjust_leveloffset(&mut self.ctx.leveloffset, &leveloffset.value);
     }
     self.strict = settings.strict;
+    self.log_level = settings.log_level;
+    self.failure_level = settings.failure_level;
     self.ctx.max_include_depth = settings.job_attrs.u16("max-include-depth").unwrap_or(64);
     self.document.meta = settings.into();
     self.set_source_file_attrs();
@@ -71,6 +91,13 @@ impl<'arena> Parser<'arena> {
     self.include_resolver = Some(resolver);
   }
 
+  /// when `false`, xrefs to documents other than this one (or its
+  /// includes) are not diagnosed, for callers that validate them
+  /// against a whole tree of documents
+  pub const fn set_check_interdoc_xrefs(&mut self, check: bool) {
+    self.check_interdoc_xrefs = check;
+  }
+
   #[cfg(feature = "attr_ref_observation")]
   pub fn set_attr_ref_observer(&mut self, observer: Box<dyn AttrRefObserver>) {
     self.attr_ref_observer = Some(observer);
@@ -79,7 +106,20 @@ impl<'arena> Parser<'arena> {
   pub fn cell_parser(&mut self, src: BumpVec<'arena, u8>, offset: u32) -> Parser<'arena> {
     let mut cell_parser = Parser::new(src, self.lexer.source_file().clone(), self.bump);
     cell_parser.include_resolver = self.include_resolver.as_ref().map(|r| r.clone_box());
+    cell_parser.block_processors = self
+      .block_processors
+      .iter()
+      .map(|(name, processor)| (name.clone(), processor.clone_box()))
+      .collect();
+    cell_parser.preprocessors = self.preprocessors.iter().map(|p| p.clone_box()).collect();
+    cell_parser.include_processors = self
+      .include_processors
+      .iter()
+      .map(|p| p.clone_box())
+      .collect();
     cell_parser.strict = self.strict;
+    cell_parser.log_level = self.log_level;
+    cell_parser.failure_level = self.failure_level;
     cell_parser.lexer.adjust_offset(offset);
     cell_parser.ctx = self.ctx.clone_for_cell(self.bump);
     cell_parser.document.meta = self.document.meta.clone_for_cell();
@@ -256,7 +296,13 @@ impl<'arena> Parser<'arena> {
 
   pub fn parse(mut self) -> std::result::Result<ParseResult<'arena>, Vec<Diagnostic>> {
     self.lockdown_secure_mode();
+    if self.ctx.table_cell_ctx != TableCellContext::AsciiDocCell {
+      self.preprocess_root_source();
+    }
     self.parse_document_header()?;
+    if self.document.meta.get_doctype() == DocType::Manpage {
+      self.parse_manpage_title()?;
+    }
     self.prepare_toc();
 
     // ensure we only read a single "paragraph" for `inline` doc_type
@@ -281,6 +327,7 @@ impl<'arena> Parser<'arena> {
     // so the backend can see them replayed in decl order
     self.document.meta.clear_doc_attrs();
     self.diagnose_document()?;
+    self.run_tree_processors();
     Ok(self.into())
   }
 
@@ -328,7 +375,7 @@ impl<'arena> Parser<'arena> {
           let first = line.discard_assert(TokenKind::OpenBracket);
           line.discard_assert(TokenKind::OpenBracket);
           let Some(anchor) = self.parse_block_anchor(&mut line)? else {
-            self.err_line_starting("Invalid block anchor", first.loc)?;
+            self.err_line_starting(DiagnosticCode::IdInvalid, "Invalid block anchor", first.loc)?;
             return Ok(ChunkMeta::new(attrs, title, start_loc));
           };
           let mut anchor_attrs = AttrList::new(anchor.loc, self.bump);
@@ -390,7 +437,11 @@ pub enum DirectiveAction<'arena> {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SourceFile {
-  Stdin { cwd: Path },
+  /// `name` is an optional virtual filename, relative to `cwd`
+  Stdin {
+    cwd: Path,
+    name: Option<String>,
+  },
   Path(Path),
   Tmp,
 }
@@ -398,7 +449,8 @@ pub enum SourceFile {
 impl SourceFile {
   pub fn file_name(&self) -> &str {
     match self {
-      SourceFile::Stdin { .. } => "<stdin>",
+      SourceFile::Stdin { name: Some(name), .. } => file::basename(name),
+      SourceFile::Stdin { name: None, .. } => "<stdin>",
       SourceFile::Path(path) => path.file_name(),
       SourceFile::Tmp => "<temp-buffer>",
     }
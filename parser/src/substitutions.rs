@@ -128,6 +128,12 @@ impl Substitutions {
     self.flags & Subs::CALLOUTS != 0
   }
 
+  /// backslash escapes only have meaning to substitutions which
+  /// could otherwise recognize the escaped text
+  pub const fn unescapes(&self) -> bool {
+    self.flags & !Subs::SPECIAL_CHARS != 0
+  }
+
   pub fn insert(&mut self, sub: Subs) {
     if self.contains(sub) {
       return;
@@ -14,6 +14,7 @@ impl<'arena> Parser<'arena> {
       .is_some()
     {
       self.err_at(
+        DiagnosticCode::IdDuplicate,
         if anchor.is_biblio {
           "Duplicate bibliography id"
         } else {
@@ -41,4 +42,35 @@ impl<'arena> Parser<'arena> {
       is_biblio,
     }
   }
+
+  /// blocks with an id are xref targets, titled by their block title
+  pub(crate) fn insert_block_anchor(&mut self, block: &Block<'arena>) -> Result<()> {
+    // sections insert their own anchor
+    if block.context == BlockContext::Section {
+      return Ok(());
+    }
+    let Some(id) = block.meta.attrs.iter().find_map(|attrs| attrs.id.clone()) else {
+      return Ok(());
+    };
+    let title = match &block.content {
+      BlockContent::Empty(EmptyMetadata::DiscreteHeading { content, .. }) => Some(content),
+      _ => block
+        .meta
+        .attrs
+        .iter()
+        .find_map(|attrs| attrs.named.get("title"))
+        .or(block.meta.dot_line_title.as_ref()),
+    };
+    let reftext = block
+      .meta
+      .attrs
+      .iter()
+      .find_map(|attrs| attrs.named.get("reftext"))
+      .cloned();
+    let mut anchor = self.anchor_from(reftext, Some(id.loc), false);
+    if let Some(title) = title {
+      anchor.title = title.clone();
+    }
+    self.insert_anchor(&id, anchor)
+  }
 }
@@ -12,33 +12,39 @@ impl<'arena> Parser<'arena> {
   pub(crate) fn set_source_file_attrs(&mut self) {
     let source_file = self.lexer.source_file().clone();
     match source_file {
-      SourceFile::Stdin { .. } => {}
-      SourceFile::Path(path) => {
-        let file_stem = path.file_stem();
-        let ext = path.extension();
-        let filename = format!("{file_stem}{ext}");
-        self.insert_job_attr("docfilesuffix", ext.to_string());
-        self.insert_job_attr("docname", file_stem.to_string());
-        self.insert_job_attr("asciidork-docfilename", filename.clone());
-        match self.document.meta.safe_mode {
-          SafeMode::Server | SafeMode::Secure => {
-            self.insert_job_attr("docdir", "");
-            self.insert_job_attr("docfile", filename);
-            self.insert_job_attr("user-home", ".");
-          }
-          SafeMode::Safe | SafeMode::Unsafe => {
-            self.insert_job_attr("docfile", path.to_string());
-            self.insert_job_attr("docdir", path.dirname().to_string());
-            if let Some(home) = std::env::home_dir() {
-              self.insert_job_attr("user-home", home.to_str().unwrap_or("."));
-            }
-          }
-        }
-      }
+      SourceFile::Stdin { cwd, name: None } => match self.document.meta.safe_mode {
+        SafeMode::Server | SafeMode::Secure => self.insert_job_attr("docdir", ""),
+        SafeMode::Safe | SafeMode::Unsafe => self.insert_job_attr("docdir", cwd.to_string()),
+      },
+      SourceFile::Stdin { cwd, name: Some(name) } => self.set_path_attrs(cwd.join(name.as_str())),
+      SourceFile::Path(path) => self.set_path_attrs(path),
       SourceFile::Tmp => {}
     };
   }
 
+  fn set_path_attrs(&mut self, path: Path) {
+    let file_stem = path.file_stem();
+    let ext = path.extension();
+    let filename = format!("{file_stem}{ext}");
+    self.insert_job_attr("docfilesuffix", ext.to_string());
+    self.insert_job_attr("docname", file_stem.to_string());
+    self.insert_job_attr("asciidork-docfilename", filename.clone());
+    match self.document.meta.safe_mode {
+      SafeMode::Server | SafeMode::Secure => {
+        self.insert_job_attr("docdir", "");
+        self.insert_job_attr("docfile", filename);
+        self.insert_job_attr("user-home", ".");
+      }
+      SafeMode::Safe | SafeMode::Unsafe => {
+        self.insert_job_attr("docfile", path.to_string());
+        self.insert_job_attr("docdir", path.dirname().to_string());
+        if let Some(home) = std::env::home_dir() {
+          self.insert_job_attr("user-home", home.to_str().unwrap_or("."));
+        }
+      }
+    }
+  }
+
   pub(crate) fn push_token_replacing_attr_ref(
     &mut self,
     mut token: Token<'arena>,
@@ -87,7 +93,11 @@ impl<'arena> Parser<'arena> {
             val => {
               token.kind = TokenKind::Word;
               if val == Some("warn") {
-                self.err_token_full("Skipping reference to missing attribute", &token)?;
+                self.err_token_full(
+                  DiagnosticCode::AttributeMissing,
+                  "Skipping reference to missing attribute",
+                  &token,
+                )?;
               }
               line.push(token);
             }
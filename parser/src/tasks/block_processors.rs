@@ -0,0 +1,192 @@
+use std::any::Any;
+
+use crate::internal::*;
+use ast::short::block::*;
+
+/// a callback that claims blocks with a custom style, e.g. `[plantuml]`
+pub trait BlockProcessor: Any {
+  fn process<'arena>(
+    &mut self,
+    input: BlockProcessorInput<'_, 'arena>,
+  ) -> std::result::Result<BlockProcessorOutput<'arena>, String>;
+
+  fn clone_box(&self) -> Box<dyn BlockProcessor>;
+}
+
+pub struct BlockProcessorInput<'a, 'arena> {
+  /// the claimed block style
+  pub name: &'a str,
+  /// the context the block would otherwise have, e.g. `Listing` or `Open`
+  pub context: BlockContext,
+  /// the block's lines, excluding delimiters
+  pub lines: &'a [&'a str],
+  /// the attribute list naming the style
+  pub attrs: &'a AttrList<'arena>,
+  pub doc_meta: &'a DocumentMeta,
+  pub bump: &'arena Bump,
+}
+
+pub enum BlockProcessorOutput<'arena> {
+  /// replacement blocks, a single block with no metadata takes
+  /// the metadata (id, title, roles) of the processed block
+  Blocks(BumpVec<'arena, Block<'arena>>),
+  /// output passed through to the backend as-is
+  Raw(String),
+}
+
+impl<'arena> Parser<'arena> {
+  pub fn register_block_processor(
+    &mut self,
+    name: impl Into<String>,
+    processor: Box<dyn BlockProcessor>,
+  ) {
+    self.block_processors.insert(name.into(), processor);
+  }
+
+  pub(crate) fn claimed_block_style(&self, meta: &ChunkMeta<'arena>) -> Option<BumpString<'arena>> {
+    if self.block_processors.is_empty() {
+      return None;
+    }
+    let style = meta.attrs.str_positional_at(0)?;
+    if self.block_processors.contains_key(style) {
+      Some(self.string(style))
+    } else {
+      None
+    }
+  }
+
+  pub(crate) fn parse_processed_paragraph(
+    &mut self,
+    name: &str,
+    mut lines: ContiguousLines<'arena>,
+    meta: ChunkMeta<'arena>,
+  ) -> Result<Option<Block<'arena>>> {
+    let mut src_lines = Vec::with_capacity(lines.len());
+    while let Some(line) = lines.consume_current() {
+      src_lines.push(line);
+    }
+    self.restore_lines(lines);
+    let first_loc = src_lines.first().and_then(|l| l.loc()).unwrap();
+    let last_loc = src_lines.last().and_then(|l| l.last_loc()).unwrap();
+    let loc = MultiSourceLocation::spanning(meta.start_loc, last_loc);
+    self.process_block(name, Context::Paragraph, &src_lines, meta, loc, first_loc)
+  }
+
+  pub(crate) fn parse_processed_delimited_block(
+    &mut self,
+    name: &str,
+    delimiter: Delimiter,
+    open_token: Token<'arena>,
+    meta: ChunkMeta<'arena>,
+  ) -> Result<Option<Block<'arena>>> {
+    let mut lines = self
+      .read_lines_until(delimiter)?
+      .unwrap_or_else(|| ContiguousLines::new(Deq::new(self.bump)));
+    let mut src_lines = Vec::with_capacity(lines.len());
+    while let Some(line) = lines.consume_current() {
+      if line.is_delimiter(delimiter) {
+        lines.restore_if_nonempty(line);
+        break;
+      }
+      src_lines.push(line);
+    }
+    self.restore_lines(lines);
+
+    let mut end_loc = None;
+    if let Some(mut lines) = self.read_lines()?
+      && lines.current_satisfies(|l| l.is_delimiter(delimiter))
+    {
+      let token = lines.consume_current_line_with_token().unwrap();
+      self.restore_lines(lines);
+      end_loc = Some(token.loc);
+    }
+    if end_loc.is_none() {
+      self.err_token_full(
+        DiagnosticCode::BlockUnclosed,
+        "This delimiter was never closed",
+        &open_token,
+      )?;
+      end_loc = Some(self.lexer.loc());
+    };
+
+    let context = Context::from(delimiter.kind);
+    let loc = MultiSourceLocation::spanning(open_token.loc, end_loc.unwrap());
+    self.process_block(name, context, &src_lines, meta, loc, open_token.loc)
+  }
+
+  fn process_block(
+    &mut self,
+    name: &str,
+    context: BlockContext,
+    lines: &[Line<'arena>],
+    meta: ChunkMeta<'arena>,
+    loc: MultiSourceLocation,
+    err_loc: SourceLocation,
+  ) -> Result<Option<Block<'arena>>> {
+    let src = lines.iter().map(Line::reassemble_src).collect::<Vec<_>>();
+    let src = src.iter().map(|line| line.as_str()).collect::<Vec<_>>();
+    let empty_attrs = AttrList::new(meta.start_loc, self.bump);
+    let attrs = meta
+      .attrs
+      .iter()
+      .find(|attrs| attrs.str_positional_at(0) == Some(name))
+      .unwrap_or(&empty_attrs);
+    let processor = self.block_processors.get_mut(name).unwrap();
+    let output = processor.process(BlockProcessorInput {
+      name,
+      context,
+      lines: &src,
+      attrs,
+      doc_meta: &self.document.meta,
+      bump: self.bump,
+    });
+
+    match output {
+      Ok(BlockProcessorOutput::Raw(raw)) => {
+        let mut nodes = InlineNodes::new(self.bump);
+        nodes.push(InlineNode {
+          content: Inline::Text(self.string(&raw)),
+          loc: err_loc,
+        });
+        Ok(Some(Block {
+          meta,
+          context: Context::Passthrough,
+          content: Content::Simple(nodes),
+          loc,
+        }))
+      }
+      Ok(BlockProcessorOutput::Blocks(mut blocks)) => {
+        if blocks.len() == 1 && blocks[0].meta.is_empty() {
+          let mut block = blocks.pop().unwrap();
+          block.meta = meta;
+          return Ok(Some(block));
+        }
+        Ok(Some(Block {
+          meta,
+          context: Context::Open,
+          content: Content::Compound(blocks),
+          loc,
+        }))
+      }
+      Err(message) => {
+        self.err_at(
+          DiagnosticCode::BlockProcessorFailed,
+          format!("Block processor `{name}` failed: {message}"),
+          err_loc,
+        )?;
+        // fall back to showing the unprocessed lines verbatim
+        let restore_subs = self.ctx.set_subs_for(Context::Listing, &meta);
+        let mut verbatim = ContiguousLines::with_capacity(lines.len(), self.bump);
+        lines.iter().for_each(|line| verbatim.push(line.clone()));
+        let nodes = self.parse_inlines(&mut verbatim)?;
+        self.ctx.subs = restore_subs;
+        Ok(Some(Block {
+          meta,
+          context: Context::Listing,
+          content: Content::Simple(nodes),
+          loc,
+        }))
+      }
+    }
+  }
+}
@@ -7,14 +7,16 @@ impl Parser<'_> {
     Ok(())
   }
 
-  fn diagnose_invalid_xrefs(&self) -> Result<()> {
+  pub(crate) fn diagnose_invalid_xrefs(&self) -> Result<()> {
     if self.ctx.table_cell_ctx != TableCellContext::None {
       return Ok(());
     }
     for (ref_target, ref_loc) in self.ctx.xrefs.borrow().iter() {
       let Some((idx, id)) = self.target_data(ref_target, ref_loc.include_depth) else {
         // couldn't find source idx
-        self.invalid_xref(ref_target, *ref_loc)?;
+        if self.check_interdoc_xrefs {
+          self.invalid_xref(ref_target, *ref_loc)?;
+        }
         continue;
       };
       if id == "__self__" {
@@ -36,6 +38,7 @@ impl Parser<'_> {
 
   fn invalid_xref(&self, target: &str, loc: SourceLocation) -> Result<()> {
     self.err_at(
+      DiagnosticCode::XrefInvalid,
       format!("Invalid cross reference, no anchor found for `{target}`"),
       loc,
     )
@@ -71,6 +74,7 @@ impl Parser<'_> {
     match toc_pos {
       Some(TocPosition::Macro) if !self.ctx.saw_toc_macro => {
         self.err_doc_attr(
+          DiagnosticCode::TocInvalid,
           "toc",
           "Table of Contents set to `macro` but macro (`toc::[]`) not found",
         )?;
@@ -78,6 +82,7 @@ impl Parser<'_> {
       Some(TocPosition::Preamble) => match &self.document.content {
         DocContent::Blocks(_) | DocContent::Sections(Sectioned { preamble: None, .. }) => {
           self.err_doc_attr(
+            DiagnosticCode::TocInvalid,
             "toc",
             "Table of Contents set to `preamble` but no preamble found",
           )?;
@@ -82,7 +82,11 @@ impl<'arena> Parser<'arena> {
         depth += 1;
       } else if self.lexer.is_eof() {
         // TODO: should probably not be a skippable/non-strict error
-        self.err_line("This ifdef directive was never closed", start_line)?;
+        self.err_line(
+          DiagnosticCode::ConditionalUnclosed,
+          "This ifdef directive was never closed",
+          start_line,
+        )?;
         break Ok(None);
       }
     }
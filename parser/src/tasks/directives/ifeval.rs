@@ -13,6 +13,7 @@ impl<'arena> Parser<'arena> {
           pattern = &err_cap[1];
         }
         self.err_at_pattern(
+          DiagnosticCode::ConditionalInvalid,
           "Invalid ifeval directive expression",
           line.first_loc().unwrap(),
           pattern,
@@ -23,6 +24,7 @@ impl<'arena> Parser<'arena> {
 
     if !&captures[1].is_empty() {
       self.err_at_pattern(
+        DiagnosticCode::ConditionalInvalid,
         "ifeval directive may not include a target",
         line.first_loc().unwrap(),
         &captures[1],
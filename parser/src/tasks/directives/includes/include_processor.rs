@@ -0,0 +1,38 @@
+use std::any::Any;
+
+use crate::internal::*;
+
+/// a callback that claims include targets, e.g. `include::gen:api-table[]`,
+/// and supplies generated asciidoc in place of reading a file
+pub trait IncludeProcessor: Any {
+  fn handles(&self, target: &str) -> bool;
+
+  fn process(
+    &mut self,
+    target: &str,
+    attrs: &AttrList,
+    doc_meta: &DocumentMeta,
+  ) -> std::result::Result<ProcessedInclude, String>;
+
+  fn clone_box(&self) -> Box<dyn IncludeProcessor>;
+}
+
+pub struct ProcessedInclude {
+  /// the file diagnostics within the generated source are attributed to
+  pub file: SourceFile,
+  pub asciidoc: String,
+}
+
+impl<'arena> Parser<'arena> {
+  /// the first registered processor that handles a target claims it
+  pub fn register_include_processor(&mut self, processor: Box<dyn IncludeProcessor>) {
+    self.include_processors.push(processor);
+  }
+
+  pub(crate) fn include_processor_for(&self, target: &str) -> Option<usize> {
+    self
+      .include_processors
+      .iter()
+      .position(|p| p.handles(target))
+  }
+}
@@ -46,6 +46,11 @@ pub trait IncludeResolver: Any {
   fn get_base_dir(&self) -> Option<String> {
     None
   }
+
+  /// called before resolving a uri target, with whether
+  /// the document has enabled the `cache-uri` attribute
+  fn set_cache_uri(&mut self, _cache_uri: bool) {}
+
   fn clone_box(&self) -> Box<dyn IncludeResolver>;
 }
 
@@ -87,6 +92,19 @@ pub enum ResolveError {
   CaseMismatch(Option<String>),
 }
 
+impl ResolveError {
+  pub const fn code(&self) -> DiagnosticCode {
+    match self {
+      ResolveError::NotFound | ResolveError::CaseMismatch(_) => DiagnosticCode::IncludeNotFound,
+      ResolveError::RestrictedPath => DiagnosticCode::IncludeRestricted,
+      ResolveError::UriReadNotSupported => DiagnosticCode::UriReadNotAllowed,
+      ResolveError::Io(_) | ResolveError::UriRead(_) | ResolveError::BaseDirRequired => {
+        DiagnosticCode::IncludeFailed
+      }
+    }
+  }
+}
+
 impl fmt::Display for ResolveError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
@@ -1,6 +1,7 @@
 use std::ops::Range;
 
 use bumpalo::collections::CollectIn;
+use encoding_rs::{DecoderResult, Encoding};
 
 use crate::internal::*;
 
@@ -10,8 +11,11 @@ impl<'arena> Parser<'arena> {
     path: &Path,
     include_attrs: &AttrList,
     bytes: &mut BumpVec<'arena, u8>,
-  ) -> std::result::Result<(), &'static str> {
-    self.normalize_encoding(include_attrs.named("encoding"), bytes)?;
+  ) -> std::result::Result<(), String> {
+    // an include without its own `encoding` is assumed to share the document's
+    let doc_encoding = self.document.meta.string("encoding");
+    let encoding = include_attrs.named("encoding").or(doc_encoding.as_deref());
+    self.normalize_encoding(encoding, bytes)?;
     self.normalize_asciidoc(path, bytes);
     Ok(())
   }
@@ -72,9 +76,9 @@ impl<'arena> Parser<'arena> {
     &mut self,
     encoding: Option<&str>,
     bytes: &mut BumpVec<u8>,
-  ) -> std::result::Result<(), &'static str> {
+  ) -> std::result::Result<(), String> {
     if let Some("utf-16" | "utf16" | "UTF-16" | "UTF16") = encoding {
-      return self.convert_utf16_le(bytes);
+      return Ok(self.convert_utf16_le(bytes)?);
     }
 
     // UTF-8 BOM
@@ -86,7 +90,7 @@ impl<'arena> Parser<'arena> {
     // UTF-16 BOM, little endian
     if bytes.len() >= 2 && bytes[0..2] == [0xFF, 0xFE] {
       bytes.drain(0..2);
-      return self.convert_utf16_le(bytes);
+      return Ok(self.convert_utf16_le(bytes)?);
     }
 
     // UTF-16 BOM, big endian
@@ -104,14 +108,24 @@ impl<'arena> Parser<'arena> {
       if from_utf16_in(utf16, bytes, self.bump) {
         return Ok(());
       } else {
-        return Err("Invalid UTF-16 (BE)");
+        return Err("Invalid UTF-16 (BE)".into());
       }
     }
 
-    // TODO: doctor supports iso-8859-1, + all encodings ruby supports
-    // we could use encoding_rs for this, but might not be any demand
-    if std::str::from_utf8(bytes).is_err() {
-      return Err("Invalid UTF-8");
+    if let Some(label) = encoding {
+      let Some(encoding) = Encoding::for_label(label.trim().as_bytes()) else {
+        return Err(format!("Unknown encoding `{label}`"));
+      };
+      if encoding != encoding_rs::UTF_8 {
+        return decode(encoding, bytes);
+      }
+    }
+
+    if let Err(err) = std::str::from_utf8(bytes) {
+      return Err(format!(
+        "Invalid UTF-8 at byte {}, set the `encoding` attribute for other encodings",
+        err.valid_up_to()
+      ));
     }
 
     Ok(())
@@ -142,6 +156,27 @@ fn trim_trailing_whitespace(bytes: &mut BumpVec<u8>) {
   bytes.truncate(i);
 }
 
+fn decode(encoding: &'static Encoding, bytes: &mut BumpVec<u8>) -> std::result::Result<(), String> {
+  let mut decoder = encoding.new_decoder_without_bom_handling();
+  let capacity = decoder
+    .max_utf8_buffer_length_without_replacement(bytes.len())
+    .unwrap_or(bytes.len() * 3);
+  let mut decoded = String::with_capacity(capacity);
+  match decoder.decode_to_string_without_replacement(bytes, &mut decoded, true) {
+    (DecoderResult::InputEmpty, _) => {
+      bytes.clear();
+      bytes.extend_from_slice(decoded.as_bytes());
+      Ok(())
+    }
+    (DecoderResult::Malformed(malformed, consumed_after), read) => Err(format!(
+      "Invalid {} at byte {}",
+      encoding.name(),
+      read - malformed as usize - consumed_after as usize
+    )),
+    (DecoderResult::OutputFull, _) => unreachable!("decode buffer sized for worst case"),
+  }
+}
+
 fn from_utf16_in(utf16: BumpVec<u16>, dest: &mut BumpVec<u8>, bump: &Bump) -> bool {
   match BumpString::from_utf16_in(&utf16, bump) {
     Ok(string) => {
@@ -208,6 +243,44 @@ mod tests {
     assert_eq!(bytes.as_slice(), b"hi");
   }
 
+  #[test]
+  fn decodes_declared_encodings() {
+    let cases: [(&str, &[u8], &str); 5] = [
+      ("windows-1252", b"caf\xE9 \x93q\x94", "café “q”"),
+      ("iso-8859-1", b"na\xEFve", "naïve"),
+      ("Shift_JIS", b"\x93\xFA\x96\x7B", "日本"),
+      ("gbk", b"\xD6\xD0\xCE\xC4", "中文"),
+      ("utf-8", b"caf\xC3\xA9", "café"),
+    ];
+    for (encoding, input, expected) in cases {
+      let mut parser = test_parser!("");
+      let mut bytes = BumpVec::from_iter_in(input.iter().copied(), parser.bump);
+      parser
+        .normalize_encoding(Some(encoding), &mut bytes)
+        .unwrap();
+      assert_eq!(std::str::from_utf8(&bytes).unwrap(), expected);
+    }
+  }
+
+  #[test]
+  fn encoding_errors() {
+    let cases: [(Option<&str>, &[u8], &str); 3] = [
+      (
+        None,
+        b"caf\xE9",
+        "Invalid UTF-8 at byte 3, set the `encoding` attribute for other encodings",
+      ),
+      (Some("klingon"), b"hi", "Unknown encoding `klingon`"),
+      (Some("Shift_JIS"), b"ok \x93", "Invalid Shift_JIS at byte 3"),
+    ];
+    for (encoding, input, expected) in cases {
+      let mut parser = test_parser!("");
+      let mut bytes = BumpVec::from_iter_in(input.iter().copied(), parser.bump);
+      let err = parser.normalize_encoding(encoding, &mut bytes).unwrap_err();
+      assert_eq!(err, expected);
+    }
+  }
+
   #[test]
   fn test_parse_line_ranges() {
     assert_eq!(parse_line_ranges("1"), vec![1..2]);
@@ -23,6 +23,11 @@ impl<'arena> Parser<'arena> {
       return Ok(DirectiveAction::Passthrough);
     };
 
+    // NB: claimed targets are generated, so safe mode doesn't apply
+    if let Some(idx) = self.include_processor_for(&directive.target) {
+      return self.process_claimed_include(idx, &directive);
+    }
+
     if self.document.meta.safe_mode == SafeMode::Secure {
       // TODO: maybe warn?
       return Ok(DirectiveAction::SubstituteLine(
@@ -35,6 +40,7 @@ impl<'arena> Parser<'arena> {
         || !self.document.meta.is_true("allow-uri-read"))
     {
       self.target_err(
+        DiagnosticCode::UriReadNotAllowed,
         "Cannot include URL contents (allow-uri-read not enabled)",
         &directive,
       )?;
@@ -45,6 +51,7 @@ impl<'arena> Parser<'arena> {
 
     if let Some(max_depth) = self.exceeded_max_include_depth() {
       self.err_line_starting(
+        DiagnosticCode::IncludeDepthExceeded,
         format!("Maximum include depth of {max_depth} exceeded"),
         directive.first_token.loc,
       )?;
@@ -53,6 +60,7 @@ impl<'arena> Parser<'arena> {
 
     let Some(resolver) = self.include_resolver.as_mut() else {
       self.err_token_full(
+        DiagnosticCode::NoResolver,
         "No resolver supplied for include directive",
         &directive.first_token,
       )?;
@@ -68,7 +76,11 @@ impl<'arena> Parser<'arena> {
     ) {
       Ok(target) => target,
       Err(err) => {
-        self.target_err(format!("Error preparing target: {err}"), &directive)?;
+        self.target_err(
+          DiagnosticCode::IncludeFailed,
+          format!("Error preparing target: {err}"),
+          &directive,
+        )?;
         return Ok(DirectiveAction::SubstituteLine(
           self.substitute_link_for_include(&directive),
         ));
@@ -76,66 +88,117 @@ impl<'arena> Parser<'arena> {
     };
 
     let target_abspath = target.path();
+    if target.is_uri() {
+      resolver.set_cache_uri(self.document.meta.is_true("cache-uri"));
+    }
     let mut buffer = BumpVec::new_in(self.bump);
     match resolver.resolve(target, &mut buffer, self.document.meta.safe_mode) {
       Ok(_) => {
         if let Err(msg) =
           self.normalize_include_bytes(&target_abspath, &directive.attrs, &mut buffer)
         {
-          self.target_err(format!("Error resolving file contents: {msg}"), &directive)?;
+          self.target_err(
+            DiagnosticCode::IncludeFailed,
+            format!("Error resolving file contents: {msg}"),
+            &directive,
+          )?;
           return Ok(DirectiveAction::SubstituteLine(
             self.substitute_link_for_include(&directive),
           ));
         }
+        let file = SourceFile::Path(target_abspath.clone());
+        self.preprocess(&file, &mut buffer);
         self.select_lines(&directive.attrs, &target_abspath, &mut buffer)?;
         self.set_include_indentation(&directive.attrs, &mut buffer);
-        let mut leveloffset = 0;
-        if let Some(offset_attr) = directive
-          .attrs
-          .named("leveloffset")
-          .map(|s| AttrValue::String(s.to_string()))
-        {
-          Parser::adjust_leveloffset(&mut leveloffset, &offset_attr);
-        }
-        let include_depth = directive
-          .attrs
-          .named("depth")
-          .and_then(|s| s.parse::<u16>().ok());
-        self.lexer.push_source(
-          SourceFile::Path(target_abspath),
-          leveloffset,
-          include_depth,
-          buffer,
-        );
-        self
-          .document
-          .meta
-          .included_files
-          .insert(directive.target.as_str().to_string());
+        self.push_include_source(file, &directive, buffer);
         Ok(DirectiveAction::ReadNextLine)
       }
       Err(ResolveError::NotFound) if directive.attrs.has_option("optional") => {
-        // TODO: when we have info/trace logging, emit a log
+        self.target_err(
+          DiagnosticCode::IncludeOptionalNotFound,
+          "Optional include dropped, file not found",
+          &directive,
+        )?;
         Ok(DirectiveAction::ReadNextLine)
       }
       Err(err @ ResolveError::NotFound | err @ ResolveError::Io(..)) => {
-        self.target_err(format!("Include error: {err}"), &directive)?;
-        let mut msg = self.string("+++Unresolved directive in ");
-        msg.push_str(self.lexer.source_file().file_name());
-        msg.push_str(" - ");
-        let offset = directive.first_token.loc.start + msg.len() as u32;
-        msg.push_str(directive.line_src.as_str());
-        msg.push_str("+++\n");
-        self.lexer.set_tmp_buf(&msg, BufLoc::Offset(offset));
+        self.target_err(err.code(), format!("Include error: {err}"), &directive)?;
+        self.substitute_unresolved_directive(&directive);
         Ok(DirectiveAction::ReadNextLine)
       }
       Err(error) => {
-        self.target_err(format!("Include error: {error}"), &directive)?;
+        self.target_err(error.code(), format!("Include error: {error}"), &directive)?;
         Ok(DirectiveAction::Passthrough)
       }
     }
   }
 
+  fn process_claimed_include(
+    &mut self,
+    idx: usize,
+    directive: &IncludeDirective<'arena>,
+  ) -> Result<DirectiveAction<'arena>> {
+    if let Some(max_depth) = self.exceeded_max_include_depth() {
+      self.err_line_starting(
+        DiagnosticCode::IncludeDepthExceeded,
+        format!("Maximum include depth of {max_depth} exceeded"),
+        directive.first_token.loc,
+      )?;
+      return Ok(DirectiveAction::Passthrough);
+    }
+    let processor = &mut self.include_processors[idx];
+    match processor.process(&directive.target, &directive.attrs, &self.document.meta) {
+      Ok(ProcessedInclude { file, asciidoc }) => {
+        let mut buffer = BumpVec::from_iter_in(asciidoc.bytes(), self.bump);
+        self.preprocess(&file, &mut buffer);
+        self.set_include_indentation(&directive.attrs, &mut buffer);
+        self.push_include_source(file, directive, buffer);
+        Ok(DirectiveAction::ReadNextLine)
+      }
+      Err(msg) => {
+        self.target_err(
+          DiagnosticCode::IncludeProcessorFailed,
+          format!("Include processor failed: {msg}"),
+          directive,
+        )?;
+        self.substitute_unresolved_directive(directive);
+        Ok(DirectiveAction::ReadNextLine)
+      }
+    }
+  }
+
+  fn push_include_source(
+    &mut self,
+    file: SourceFile,
+    directive: &IncludeDirective<'arena>,
+    buffer: BumpVec<'arena, u8>,
+  ) {
+    let mut leveloffset = 0;
+    if let Some(offset_attr) = directive
+      .attrs
+      .named("leveloffset")
+      .map(|s| AttrValue::String(s.to_string()))
+    {
+      Parser::adjust_leveloffset(&mut leveloffset, &offset_attr);
+    }
+    let include_depth = directive
+      .attrs
+      .named("depth")
+      .and_then(|s| s.parse::<u16>().ok());
+    self.lexer.push_source(
+      file,
+      leveloffset,
+      include_depth,
+      directive.first_token.loc,
+      buffer,
+    );
+    self
+      .document
+      .meta
+      .included_files
+      .insert(directive.target.as_str().to_string());
+  }
+
   fn valid_include_directive(
     &mut self,
     line: &mut Line<'arena>,
@@ -168,6 +231,16 @@ impl<'arena> Parser<'arena> {
     }
   }
 
+  fn substitute_unresolved_directive(&mut self, directive: &IncludeDirective<'arena>) {
+    let mut msg = self.string("+++Unresolved directive in ");
+    msg.push_str(self.lexer.source_file().file_name());
+    msg.push_str(" - ");
+    let offset = directive.first_token.loc.start + msg.len() as u32;
+    msg.push_str(directive.line_src.as_str());
+    msg.push_str("+++\n");
+    self.lexer.set_tmp_buf(&msg, BufLoc::Offset(offset));
+  }
+
   fn substitute_link_for_include(&mut self, directive: &IncludeDirective<'arena>) -> Line<'arena> {
     let mut link_src = directive.line_src.replace("include::", "link:");
     link_src.push('\n');
@@ -204,10 +277,12 @@ impl<'arena> Parser<'arena> {
 
   fn target_err(
     &mut self,
+    code: DiagnosticCode,
     msg: impl Into<String>,
     directive: &IncludeDirective<'arena>,
   ) -> Result<()> {
     self.err_at(
+      code,
       msg,
       SourceLocation::new(
         directive.first_token.loc.end,
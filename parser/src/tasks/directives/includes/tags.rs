@@ -220,6 +220,8 @@ impl<'arena> Parser<'arena> {
               underline_start,
               underline_width: tag.len() as u32,
               source_file: SourceFile::Path(src_path.clone()),
+              code: DiagnosticCode::IncludeTagInvalid,
+              severity: DiagnosticCode::IncludeTagInvalid.severity(),
             })?;
           }
         }
@@ -253,6 +255,8 @@ impl<'arena> Parser<'arena> {
         underline_start,
         underline_width: tag.len() as u32,
         source_file: SourceFile::Path(src_path.clone()),
+        code: DiagnosticCode::IncludeTagInvalid,
+        severity: DiagnosticCode::IncludeTagInvalid.severity(),
       })?;
     }
 
@@ -260,6 +264,7 @@ impl<'arena> Parser<'arena> {
       let mut tags = expected_tags.into_iter().collect::<Vec<_>>();
       tags.sort_unstable();
       self.err_at(
+        DiagnosticCode::IncludeTagInvalid,
         format!(
           "Tag{} `{}` not found in included file",
           if tags.len() > 1 { "s" } else { "" },
@@ -15,8 +15,10 @@ pub fn prepare(
   }
   let target = Path::new(target_str);
   if src_is_primary && target.is_relative() {
-    let Some(base_dir) = base_dir else {
-      return Err(Err::BaseDirRequired);
+    let base_dir = match (base_dir, src_file) {
+      (Some(base_dir), _) => base_dir,
+      (None, Src::Stdin { cwd, .. }) => cwd.clone(),
+      (None, _) => return Err(Err::BaseDirRequired),
     };
     let abspath = base_dir.join(target);
     return Ok(Target::FilePath(abspath.to_string()));
@@ -31,7 +33,7 @@ pub fn prepare(
       };
       Ok(abspath.into())
     }
-    Src::Stdin { .. } => unimplemented!("include from stdin not implemented yet"),
+    Src::Stdin { .. } => Ok(target.into()),
     Src::Tmp => unreachable!(),
   }
 }
@@ -106,6 +108,29 @@ mod tests {
         expected: Ok(FilePath("/d1/d2/../other.adoc".to_string())),
         ..TestCase::default()
       },
+      TestCase {
+        name: "relative include from stdin without basedir resolved from cwd",
+        base_dir: None,
+        target_str: "sub/other.adoc",
+        src_file: SourceFile::Stdin { cwd: Path::new("/cwd"), name: None },
+        expected: Ok(FilePath("/cwd/sub/other.adoc".to_string())),
+        ..TestCase::default()
+      },
+      TestCase {
+        name: "relative include from stdin prefers basedir",
+        target_str: "other.adoc",
+        src_file: SourceFile::Stdin { cwd: Path::new("/cwd"), name: None },
+        expected: Ok(FilePath("/basedir/other.adoc".to_string())),
+        ..TestCase::default()
+      },
+      TestCase {
+        name: "absolute include from stdin",
+        base_dir: None,
+        target_str: "/abs/other.adoc",
+        src_file: SourceFile::Stdin { cwd: Path::new("/cwd"), name: None },
+        expected: Ok(FilePath("/abs/other.adoc".to_string())),
+        ..TestCase::default()
+      },
     ];
 
     for case in cases {
@@ -28,11 +28,16 @@ impl<'arena> Parser<'arena> {
       return Ok(DirectiveAction::Passthrough);
     };
     let Some(expected) = self.ctx.ifdef_stack.last() else {
-      self.err_line("This endif directive has no previous ifdef/ifndef", line)?;
+      self.err_line(
+        DiagnosticCode::ConditionalInvalid,
+        "This endif directive has no previous ifdef/ifndef",
+        line,
+      )?;
       return Ok(DirectiveAction::Passthrough);
     };
     if !endif_attrs.is_empty() && expected != &endif_attrs {
       self.err_at_pattern(
+        DiagnosticCode::ConditionalInvalid,
         format!("Mismatched endif directive, expected `{}`", &expected),
         line.first_loc().unwrap(),
         &endif_attrs,
@@ -1,5 +1,6 @@
 mod anchors;
 pub(crate) mod attr_refs;
+pub(crate) mod block_processors;
 pub(crate) mod collect_text;
 pub mod customize_subs;
 mod diagnose_document;
@@ -15,8 +16,12 @@ mod parse_doc_header;
 mod parse_image_block;
 mod parse_inlines;
 mod parse_list;
+mod parse_manpage_header;
 mod parse_revision_line;
 pub mod parse_section;
+pub(crate) mod preprocessors;
+pub(crate) mod reparse;
 mod section_id;
 mod table;
 mod time;
+pub(crate) mod tree_processors;
@@ -227,6 +227,7 @@ impl<'arena> Parser<'arena> {
     match self.attr_ir(tokens, supports_shorthand) {
       AttrIr::Positional(inner, _) if formatted_text && !attr_list.roles.is_empty() => {
         self.err_at(
+          DiagnosticCode::AttrListInvalid,
           ONLY_SHORTHAND_ERR,
           SourceLocation::spanning(inner.first().unwrap().loc, inner.last().unwrap().loc),
         )?;
@@ -234,6 +235,7 @@ impl<'arena> Parser<'arena> {
       }
       AttrIr::Id(inner) if formatted_text => {
         self.err_at(
+          DiagnosticCode::AttrListInvalid,
           ONLY_SHORTHAND_ERR,
           SourceLocation::spanning(inner.first().unwrap().loc, inner.last().unwrap().loc),
         )?;
@@ -241,6 +243,7 @@ impl<'arena> Parser<'arena> {
       }
       AttrIr::Options(groups) | AttrIr::Roles(groups) if formatted_text => {
         self.err_at(
+          DiagnosticCode::AttrListInvalid,
           ONLY_SHORTHAND_ERR,
           SourceLocation::spanning(
             groups.first().unwrap().first().unwrap().loc,
@@ -251,6 +254,7 @@ impl<'arena> Parser<'arena> {
       }
       AttrIr::Named(name, tokens) if formatted_text => {
         self.err_at(
+          DiagnosticCode::AttrListInvalid,
           ONLY_SHORTHAND_ERR,
           SourceLocation::spanning(
             name.loc,
@@ -278,7 +282,11 @@ impl<'arena> Parser<'arena> {
             pos_tokens.push_non_attr(tokens.pop_front().unwrap());
           }
           if pos_tokens.is_empty() {
-            self.err_at("Invalid attr list", tokens.first().unwrap().loc)?;
+            self.err_at(
+              DiagnosticCode::AttrListInvalid,
+              "Invalid attr list",
+              tokens.first().unwrap().loc,
+            )?;
             return Ok(());
           }
           attr_list
@@ -305,13 +313,21 @@ impl<'arena> Parser<'arena> {
       AttrIr::Roles(groups) => self.push_attr_groups(groups, &mut attr_list.roles),
       AttrIr::Id(tokens) => {
         if tokens.is_empty() {
-          self.err_at("Invalid empty id attribute", attr_list.loc)?;
+          self.err_at(
+            DiagnosticCode::IdInvalid,
+            "Invalid empty id attribute",
+            attr_list.loc,
+          )?;
           return Ok(());
         }
         let mut line = Line::new(tokens);
         let src = line.consume_to_string(self.bump);
         if attr_list.id.is_some() {
-          self.err_at("More than one id attribute", src.loc)?;
+          self.err_at(
+            DiagnosticCode::IdInvalid,
+            "More than one id attribute",
+            src.loc,
+          )?;
           return Ok(());
         } else {
           attr_list.id = Some(src);
@@ -327,7 +343,7 @@ impl<'arena> Parser<'arena> {
           };
           match token.kind {
             _ if line.is_empty() => {
-              self.err_token_start("Invalid id attribute", &token)?;
+              self.err_token_start(DiagnosticCode::IdInvalid, "Invalid id attribute", &token)?;
               return Ok(());
             }
             Dots if token.len() == 1 => attr_list
@@ -335,7 +351,11 @@ impl<'arena> Parser<'arena> {
               .push(line.consume_to_string_until_one_of(stop, self.bump)),
             Hash => {
               if attr_list.id.is_some() {
-                self.err_token_start("More than one id attribute", &token)?;
+                self.err_token_start(
+                  DiagnosticCode::IdInvalid,
+                  "More than one id attribute",
+                  &token,
+                )?;
                 return Ok(());
               } else {
                 let src = line.consume_to_string_until_one_of(stop, self.bump);
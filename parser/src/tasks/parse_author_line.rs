@@ -40,15 +40,27 @@ impl<'arena> Parser<'arena> {
 
     let num_bytes = src.len();
     if first_start == usize::MAX {
-      self.err_token("invalid author line", line.current_token())
+      self.err_token(
+        DiagnosticCode::AuthorLineInvalid,
+        "invalid author line",
+        line.current_token(),
+      )
     } else if first_start > 0 {
       let loc = line.current_token().unwrap().loc;
-      self.err_at("invalid author line", loc.adding_to_end(first_start as u32))
+      self.err_at(
+        DiagnosticCode::AuthorLineInvalid,
+        "invalid author line",
+        loc.adding_to_end(first_start as u32),
+      )
     } else if last_end < num_bytes {
       let mut loc = line.current_token().unwrap().loc;
       loc.start += last_end as u32;
       loc.end += num_bytes as u32;
-      self.err_at("invalid author line", loc)
+      self.err_at(
+        DiagnosticCode::AuthorLineInvalid,
+        "invalid author line",
+        loc,
+      )
     } else {
       Ok(())
     }
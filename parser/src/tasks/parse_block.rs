@@ -4,6 +4,14 @@ use ast::short::block::*;
 
 impl<'arena> Parser<'arena> {
   pub(crate) fn parse_block(&mut self) -> Result<Option<Block<'arena>>> {
+    let block = self.read_block()?;
+    if let Some(block) = &block {
+      self.insert_block_anchor(block)?;
+    }
+    Ok(block)
+  }
+
+  fn read_block(&mut self) -> Result<Option<Block<'arena>>> {
     let Some(mut lines) = self.read_lines()? else {
       return Ok(None);
     };
@@ -18,7 +26,11 @@ impl<'arena> Parser<'arena> {
 
     let meta = self.parse_chunk_meta(&mut lines)?;
     if lines.is_empty() {
-      self.err_line_starting("Unattached block metadata", meta.start_loc)?;
+      self.err_line_starting(
+        DiagnosticCode::BlockMetadataUnattached,
+        "Unattached block metadata",
+        meta.start_loc,
+      )?;
       return self.parse_block();
     }
 
@@ -109,7 +121,7 @@ impl<'arena> Parser<'arena> {
           .meta
           .insert_doc_attr(def.name.as_str(), def.value.clone())
         {
-          self.err_at(err, def.loc)?;
+          self.err_at(DiagnosticCode::AttrDefInvalid, err, def.loc)?;
         }
         lines.consume_current(); // attr def
         if !lines.is_empty() {
@@ -184,6 +196,10 @@ impl<'arena> Parser<'arena> {
       _ => {}
     }
 
+    if let Some(name) = self.claimed_block_style(&meta) {
+      return self.parse_processed_paragraph(&name, lines, meta);
+    }
+
     if lines.is_quoted_paragraph(self.ctx.in_markdown_blockquote) {
       self.parse_quoted_paragraph(lines, meta)
     } else {
@@ -337,6 +353,15 @@ impl<'arena> Parser<'arena> {
     let context = meta.block_style_or(Context::from(delimiter.kind));
     let restore_subs = self.ctx.set_subs_for(context, &meta);
 
+    if context != Context::Comment
+      && let Some(name) = self.claimed_block_style(&meta)
+    {
+      let block = self.parse_processed_delimited_block(&name, delimiter, open_token, meta);
+      self.ctx.subs = restore_subs;
+      self.ctx.delimiter = prev;
+      return block;
+    }
+
     // newlines have a different meaning in a these contexts, so we have to
     // manually gather all (including empty) lines until the end delimiter
     let content = if matches!(
@@ -344,6 +369,7 @@ impl<'arena> Parser<'arena> {
       Context::Listing
         | Context::Literal
         | Context::Passthrough
+        | Context::Stem
         | Context::Comment
         | Context::Verse
     ) {
@@ -407,7 +433,11 @@ impl<'arena> Parser<'arena> {
     }
 
     if end_loc.is_none() {
-      self.err_token_full("This delimiter was never closed", &open_token)?;
+      self.err_token_full(
+        DiagnosticCode::BlockUnclosed,
+        "This delimiter was never closed",
+        &open_token,
+      )?;
       end_loc = Some(self.lexer.loc());
     };
 
@@ -556,6 +586,7 @@ impl<'arena> Parser<'arena> {
     self.restore_lines(lines);
     if self.document.toc.is_none() {
       self.err_line(
+        DiagnosticCode::TocInvalid,
         "Found macro placing Table of Contents, but TOC not enabled",
         &line,
       )?;
@@ -50,6 +50,7 @@ impl<'arena> Parser<'arena> {
 
     if sections.is_empty() {
       self.err_line_starting(
+        DiagnosticCode::BookPartEmpty,
         "Invalid empty book part, must have at least one section",
         equals.loc,
       )?;
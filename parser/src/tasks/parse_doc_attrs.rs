@@ -140,7 +140,11 @@ impl<'arena> Parser<'arena> {
       AttrValue::Bool(negated == Negation::None)
     } else {
       if negated != Negation::None {
-        self.err_line_starting("Cannot unset attr with `!` AND provide value", token.loc)?;
+        self.err_line_starting(
+          DiagnosticCode::AttrDefInvalid,
+          "Cannot unset attr with `!` AND provide value",
+          token.loc,
+        )?;
       }
       let mut value_string = if has_lbrace && self.ctx.in_header {
         self.replace_attr_vals(value_str).into_owned()
@@ -185,7 +189,7 @@ impl<'arena> Parser<'arena> {
         author.email = value.str().map(|s| s.to_string());
       }
       if let Err(err) = self.document.meta.insert_header_attr(name, value) {
-        self.err_at(err, attr_def_loc)?;
+        self.err_at(DiagnosticCode::AttrDefInvalid, err, attr_def_loc)?;
       }
       if let Some(author) = attr_author {
         self.document.meta.add_author(author);
@@ -74,7 +74,11 @@ impl<'arena> Parser<'arena> {
 
     loop {
       let Some(mut next_lines) = self.read_lines()? else {
-        self.err_line("Unclosed comment block, started here", &block_start)?;
+        self.err_line(
+          DiagnosticCode::BlockUnclosed,
+          "Unclosed comment block, started here",
+          &block_start,
+        )?;
         return Ok(None);
       };
       if next_lines.discard_until(Line::is_comment_block_delimiter) {
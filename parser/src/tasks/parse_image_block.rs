@@ -161,7 +161,11 @@ impl<'arena> Parser<'arena> {
       BumpString::from_utf8_lossy_in(&modified, self.bump)
     };
     if image.is_empty() {
-      self.err_at("Empty svg file", target.loc)?;
+      self.err_at(
+        DiagnosticCode::ImageReadFailed,
+        "Empty svg file",
+        target.loc,
+      )?;
       return Ok(ImageKind::InlineSvg(None));
     }
     Ok(ImageKind::InlineSvg(Some(image)))
@@ -211,6 +215,7 @@ impl<'arena> Parser<'arena> {
     let err_str = kind.err_str();
     let Some(resolver) = self.include_resolver.as_mut() else {
       self.err_at(
+        DiagnosticCode::NoResolver,
         format!("No include resolver supplied for {err_str}"),
         err_loc,
       )?;
@@ -219,6 +224,7 @@ impl<'arena> Parser<'arena> {
 
     if target_is_uri && !self.document.meta.is_true("allow-uri-read") {
       self.err_at(
+        DiagnosticCode::UriReadNotAllowed,
         "Cannot include URL contents (allow-uri-read not enabled)",
         err_loc,
       )?;
@@ -233,6 +239,7 @@ impl<'arena> Parser<'arena> {
         let dir_is_uri = regx::URI_SNIFF.is_match(imagesdir);
         if dir_is_uri && !self.document.meta.is_true("allow-uri-read") {
           self.err_at(
+            DiagnosticCode::UriReadNotAllowed,
             "Cannot include URL contents (allow-uri-read not enabled)",
             err_loc,
           )?;
@@ -251,12 +258,16 @@ impl<'arena> Parser<'arena> {
       }
     } else {
       self.err_at(
+        DiagnosticCode::ImageReadFailed,
         format!("Base dir required to resolve relative-path {err_str} for include"),
         err_loc,
       )?;
       return Ok(None);
     };
 
+    if include_target.is_uri() {
+      resolver.set_cache_uri(self.document.meta.is_true("cache-uri"));
+    }
     let mut buffer = BumpVec::new_in(self.bump);
     match resolver.resolve(include_target, &mut buffer, self.document.meta.safe_mode) {
       Ok(_) => {
@@ -264,6 +275,7 @@ impl<'arena> Parser<'arena> {
           && let Err(msg) = self.normalize_encoding(None, &mut buffer)
         {
           self.err_at(
+            DiagnosticCode::ImageReadFailed,
             format!("Error resolving file contents for {err_str}: {msg}"),
             err_loc,
           )?;
@@ -271,7 +283,11 @@ impl<'arena> Parser<'arena> {
         }
       }
       Err(msg) => {
-        self.err_at(format!("Error including {err_str}: {msg}"), err_loc)?;
+        self.err_at(
+          DiagnosticCode::ImageReadFailed,
+          format!("Error including {err_str}: {msg}"),
+          err_loc,
+        )?;
         return Ok(None);
       }
     }
@@ -14,7 +14,11 @@ impl<'arena> Parser<'arena> {
     let mut attr_list = self.parse_inline_macro_attr_list(line)?;
     loc.end = attr_list.loc.end;
     let Some(term) = attr_list.positional.get_mut(0).and_then(Option::take) else {
-      self.err_at("invalid index term macro", attr_list.loc)?;
+      self.err_at(
+        DiagnosticCode::IndexTermInvalid,
+        "invalid index term macro",
+        attr_list.loc,
+      )?;
       let invalid_macro = self.lexer.str_from_loc(loc);
       return Ok((Inline::Text(self.string(invalid_macro)), loc));
     };
@@ -124,6 +128,7 @@ impl<'arena> Parser<'arena> {
     let mut terms = lines.consume_splitting_csv_until(Len(end_len as u8, CloseParens), self.bump);
     if terms.len() > 3 {
       self.err_at(
+        DiagnosticCode::IndexTermInvalid,
         "too many terms in concealed indexterm, max 3",
         open_parens_token.loc,
       )?;
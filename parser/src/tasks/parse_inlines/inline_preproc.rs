@@ -20,10 +20,16 @@ impl<'arena> Parser<'arena> {
       }
       let kind = token.kind;
       match token.kind {
-        MacroName if token.lexeme == "pass:" => {
-          if let Some((n, subs)) = self.terminates_valid_pass_macro(line, lines) {
-            let placeholder = self.pass_placeholder(&token, 0, line, lines, n, subs)?;
-            replaced.push_nonpass(placeholder);
+        MacroName if is_pass_macro_name(&token.lexeme) => {
+          let name = &token.lexeme[..token.lexeme.len() - 1];
+          let stem = StemNotation::from_name(name, self.document.meta.str("stem"));
+          let default_subs = match stem {
+            Some(_) => Substitutions::only_special_chars(),
+            None => Substitutions::none(),
+          };
+          if let Some((n, subs)) = self.terminates_valid_pass_macro(line, lines, default_subs) {
+            let (loc, nodes) = self.pass_placeholder(&token, 0, line, lines, n, subs)?;
+            replaced.push_nonpass(self.push_passthru(loc, nodes, stem));
           } else {
             replaced.push_nonpass(token);
           };
@@ -32,9 +38,9 @@ impl<'arena> Parser<'arena> {
           let count = line.iter().take_while(|t| t.kind(Plus)).count() + 1;
           if let Some(n) = self.terminates_plus(count, line, lines) {
             let subs = Substitutions::from_pass_plus_len(count);
-            let placeholder = self.pass_placeholder(&token, count, line, lines, n, subs)?;
+            let (loc, nodes) = self.pass_placeholder(&token, count, line, lines, n, subs)?;
             skip_tokens = count;
-            replaced.push_nonpass(placeholder);
+            replaced.push_nonpass(self.push_passthru(loc, nodes, None));
           } else {
             replaced.push_nonpass(token);
           }
@@ -55,13 +61,15 @@ impl<'arena> Parser<'arena> {
     lines: &mut ContiguousLines<'arena>,
     mut num_passthru_tokens: usize,
     subs: Substitutions,
-  ) -> Result<Token<'arena>> {
+  ) -> Result<(SourceLocation, InlineNodes<'arena>)> {
     let mut loc = start_token.loc;
     if plus_count > 1 {
       line.discard(plus_count - 1);
       num_passthru_tokens -= plus_count - 1;
     }
-    let mut passlines = self.passthru_parse_lines(num_passthru_tokens, line, lines);
+    let unescape_brackets = start_token.kind == MacroName;
+    let mut passlines =
+      self.passthru_parse_lines(num_passthru_tokens, unescape_brackets, line, lines);
     if plus_count > 1 {
       line.discard(plus_count - 1);
     }
@@ -71,26 +79,37 @@ impl<'arena> Parser<'arena> {
     self.ctx.subs = subs;
     let nodes = self.parse_inlines(&mut passlines)?;
     self.ctx.subs = restore_subs;
-    self.ctx.passthrus.push(Some(nodes));
+    Ok((loc, nodes))
+  }
+
+  fn push_passthru(
+    &mut self,
+    loc: SourceLocation,
+    nodes: InlineNodes<'arena>,
+    stem: Option<StemNotation>,
+  ) -> Token<'arena> {
+    self.ctx.passthrus.push(Some((nodes, stem)));
     let index = self.ctx.passthrus.len() - 1;
     let mut lexeme = BumpString::with_capacity_in(6, self.bump);
     write!(lexeme, "^{index:05}").unwrap();
-    Ok(Token::new(PreprocPassthru, loc, lexeme))
+    Token::new(PreprocPassthru, loc, lexeme)
   }
 
   fn passthru_parse_lines(
     &self,
     num_tokens: usize,
+    unescape_brackets: bool,
     line: &mut Line<'arena>,
     lines: &mut ContiguousLines<'arena>,
   ) -> ContiguousLines<'arena> {
     let passthru = ContiguousLines::with_capacity(1, self.bump);
-    self.accum_passthru(num_tokens, line, lines, passthru)
+    self.accum_passthru(num_tokens, unescape_brackets, line, lines, passthru)
   }
 
   fn accum_passthru(
     &self,
     mut num_tokens: usize,
+    unescape_brackets: bool,
     line: &mut Line<'arena>,
     source: &mut ContiguousLines<'arena>,
     mut passthru: ContiguousLines<'arena>,
@@ -103,7 +122,11 @@ impl<'arena> Parser<'arena> {
         return passthru;
       }
       if let Some(token) = line.consume_current() {
-        passthru_line.push_nonpass(token);
+        // the macro syntax requires escaping closing brackets, so they
+        // are unescaped regardless of the substitutions that apply
+        if !(unescape_brackets && token.kind(Backslash) && line.current_is(CloseBracket)) {
+          passthru_line.push_nonpass(token);
+        }
         num_tokens -= 1;
       } else {
         if !passthru_line.is_empty() {
@@ -112,7 +135,7 @@ impl<'arena> Parser<'arena> {
         // NB: we know there is a next line because we found the end further on
         let mut next_line = source.consume_current().unwrap();
         std::mem::swap(line, &mut next_line);
-        return self.accum_passthru(num_tokens, line, source, passthru);
+        return self.accum_passthru(num_tokens, unescape_brackets, line, source, passthru);
       }
     }
   }
@@ -121,6 +144,7 @@ impl<'arena> Parser<'arena> {
     &self,
     line: &mut Line<'arena>,
     lines: &mut ContiguousLines<'arena>,
+    default_subs: Substitutions,
   ) -> Option<(usize, Substitutions)> {
     if line.is_empty() {
       return None;
@@ -143,7 +167,7 @@ impl<'arena> Parser<'arena> {
       if token.kind == CloseBracket && last != Backslash {
         unclosed_open_brackets -= 1;
         if unclosed_open_brackets == 0 {
-          let subs = pass_macro_subs(line, num_target_tokens, self.bump);
+          let subs = pass_macro_subs(line, num_target_tokens, default_subs, self.bump);
           return Some((n - 1, subs));
         }
       } else if token.kind == OpenBracket && last != Backslash {
@@ -157,7 +181,7 @@ impl<'arena> Parser<'arena> {
     for next_line in lines.iter() {
       for token in next_line.iter() {
         if token.kind == CloseBracket && last != Backslash {
-          let subs = pass_macro_subs(line, num_target_tokens, self.bump);
+          let subs = pass_macro_subs(line, num_target_tokens, default_subs, self.bump);
           return Some((n - 1, subs));
         }
         last = token.kind;
@@ -230,6 +254,7 @@ impl<'arena> Parser<'arena> {
 fn pass_macro_subs<'arena>(
   line: &mut Line<'arena>,
   n_target_tokens: usize,
+  default_subs: Substitutions,
   bump: &'arena Bump,
 ) -> Substitutions {
   let mut target = BumpString::with_capacity_in(8, bump);
@@ -237,7 +262,11 @@ fn pass_macro_subs<'arena>(
     target.push_str(&line.consume_current().unwrap().lexeme);
   }
   line.discard_assert(OpenBracket);
-  Substitutions::from_pass_macro_target(&target)
+  if target.is_empty() {
+    default_subs
+  } else {
+    Substitutions::from_pass_macro_target(&target)
+  }
 }
 
 #[inline(always)]
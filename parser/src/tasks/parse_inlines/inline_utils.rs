@@ -14,7 +14,7 @@ impl<'arena> Parser<'arena> {
       let index: usize = placeholder.lexeme[1..6].parse().unwrap();
       let src_len = placeholder.loc.size();
       let mut restored = BumpString::with_capacity_in(src_len as usize, self.bump);
-      let content = self.ctx.passthrus[index].take().unwrap();
+      let (content, _) = self.ctx.passthrus[index].take().unwrap();
       for text in content.plain_text().iter() {
         restored.push_str(text);
       }
@@ -181,7 +181,11 @@ impl<'arena> Parser<'arena> {
                 if id.is_none() && note.is_none() {
                   let mut err_loc = macro_loc.incr_end();
                   err_loc.start += 9;
-                  self.err_at("Empty footnote content", err_loc)?;
+                  self.err_at(
+                    DiagnosticCode::FootnoteEmpty,
+                    "Empty footnote content",
+                    err_loc,
+                  )?;
                 }
                 acc.push_node(Macro(Footnote { id, text: note }), macro_loc);
                 break;
@@ -191,18 +195,23 @@ impl<'arena> Parser<'arena> {
                 self.push_xref(&target);
                 lines.restore_if_nonempty(line);
                 let nodes = self.parse_inlines_until(lines, &[Kind(CloseBracket)])?;
+                let xrefstyle = nodes
+                  .single_text()
+                  .and_then(|text| text.strip_prefix("xrefstyle="))
+                  .and_then(|style| style.parse().ok());
                 let linktext = if nodes.is_empty() {
                   macro_loc.end = target.loc.end + 2;
                   None
                 } else {
                   extend(&mut macro_loc, &nodes, 1);
-                  Some(nodes)
+                  xrefstyle.is_none().then_some(nodes)
                 };
                 acc.push_node(
                   Macro(Xref {
                     target,
                     linktext,
                     kind: XrefKind::Macro,
+                    xrefstyle,
                   }),
                   macro_loc,
                 );
@@ -461,6 +470,7 @@ impl<'arena> Parser<'arena> {
                 target,
                 linktext,
                 kind: XrefKind::Shorthand,
+                xrefstyle: None,
               }),
               loc,
             );
@@ -820,6 +830,8 @@ impl<'arena> Parser<'arena> {
 
           AttrRef => {}
 
+          Backslash if !subs.unescapes() => acc.push_text_token(&token),
+
           Backslash => {
             match line.current_token().map(|t| (t.kind, t.len())) {
               Some((Word, _)) | None => acc.push_text_token(&token),
@@ -870,8 +882,12 @@ impl<'arena> Parser<'arena> {
 
           PreprocPassthru => {
             let index: usize = token.lexeme[1..6].parse().unwrap();
-            let content = self.ctx.passthrus[index].take().unwrap();
-            acc.push_node(InlinePassthru(content), token.loc);
+            match self.ctx.passthrus[index].take().unwrap() {
+              (content, Some(notation)) => {
+                acc.push_node(Macro(Stem { notation, content }), token.loc);
+              }
+              (content, None) => acc.push_node(InlinePassthru(content), token.loc),
+            }
           }
 
           _ => {
@@ -86,6 +86,7 @@ impl<'arena> Parser<'arena> {
       let conum = marker.callout_num().unwrap_or(*autogen_conum);
       if conum != *autogen_conum {
         self.err_at(
+          DiagnosticCode::CalloutInvalid,
           format!("Unexpected callout number, expected `<{autogen_conum}>`"),
           marker_src.loc,
         )?;
@@ -93,6 +94,7 @@ impl<'arena> Parser<'arena> {
       let callouts = self.ctx.get_callouts(conum);
       if callouts.is_empty() {
         self.err_at(
+          DiagnosticCode::CalloutInvalid,
           format!("No callout found for number `{conum}`"),
           marker_src.loc,
         )?;
@@ -186,7 +188,11 @@ impl<'arena> Parser<'arena> {
     if let Some(marker) = lines.nth(1).and_then(|line| line.list_marker())
       && self.ctx.list.stack.continues_current_list(marker)
     {
-      self.err_line("Dangling list continuation", lines.current().unwrap())?;
+      self.err_line(
+        DiagnosticCode::ListContinuationDangling,
+        "Dangling list continuation",
+        lines.current().unwrap(),
+      )?;
       lines.consume_current();
       self.restore_lines(lines);
       return Ok(accum);
@@ -0,0 +1,137 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::internal::*;
+
+impl<'arena> Parser<'arena> {
+  /// for `doctype: manpage`, derive `mantitle` and `manvolnum`
+  /// from a doc title in the form `name(volnum)`
+  pub(crate) fn parse_manpage_title(&mut self) -> Result<()> {
+    let Some(DocHeader { title: Some(title), loc }) = &self.document.header else {
+      return Ok(());
+    };
+    let loc = *loc;
+    let text = title.main.plain_text().join("");
+    let (mantitle, manvolnum) = match MANTITLE_RE.captures(text.trim()) {
+      Some(captures) => (captures[1].to_string(), captures[2].to_string()),
+      None => {
+        if self.targets_manpage_backend() {
+          self.err_line_of(
+            DiagnosticCode::ManpageNonconforming,
+            "Non-conforming manpage title, expected `= name(volnum)`",
+            loc,
+          )?;
+        }
+        (text.trim().to_string(), "1".to_string())
+      }
+    };
+    let meta = &mut self.document.meta;
+    if !meta.is_set("mantitle") {
+      _ = meta.insert_header_attr("mantitle", mantitle);
+    }
+    if !meta.is_set("manvolnum") {
+      _ = meta.insert_header_attr("manvolnum", manvolnum);
+    }
+    Ok(())
+  }
+
+  /// the first section of a manpage must be the NAME section, whose
+  /// first paragraph provides `manname` and `manpurpose`
+  pub(super) fn parse_manpage_name_section(
+    &mut self,
+    blocks: &[Block<'arena>],
+    heading_loc: SourceLocation,
+  ) -> Result<()> {
+    let name_line = blocks.first().and_then(|block| match &block.content {
+      BlockContent::Simple(inlines) if block.context == BlockContext::Paragraph => {
+        Some(inlines.plain_text().join(""))
+      }
+      _ => None,
+    });
+    let captures = name_line
+      .as_deref()
+      .and_then(|line| MANNAME_RE.captures(line.trim()));
+    let meta = &mut self.document.meta;
+    let Some(captures) = captures else {
+      // fallback so that we only check the first section
+      let mantitle = meta.string_or("mantitle", "");
+      _ = meta.insert_header_attr("manname", mantitle);
+      if !self.targets_manpage_backend() {
+        return Ok(());
+      }
+      return self.err_line_of(
+        DiagnosticCode::ManpageNonconforming,
+        "Non-conforming NAME section body, expected `name - purpose`",
+        heading_loc,
+      );
+    };
+    let mannames = captures[1].split(',').map(str::trim).collect::<Vec<_>>();
+    _ = meta.insert_header_attr("manname", mannames[0]);
+    _ = meta.insert_header_attr("mannames", mannames.join(", "));
+    _ = meta.insert_header_attr("manpurpose", captures[2].trim());
+    Ok(())
+  }
+
+  /// other backends render `doctype: manpage` documents leniently,
+  /// so we only complain about manpage structure when emitting roff
+  fn targets_manpage_backend(&self) -> bool {
+    self.document.meta.str("backend") == Some("manpage")
+  }
+}
+
+lazy_static! {
+  // e.g. `git-commit(1)`
+  static ref MANTITLE_RE: Regex = Regex::new(r"^(.+?) *\((\S+)\)$").unwrap();
+  // e.g. `git-commit, git-ci - Record changes to the repository`
+  static ref MANNAME_RE: Regex =
+    Regex::new(r"^(\S+?(?:, *\S+?)*) +(?:-|—|&#8212;) +(.+)$").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use test_utils::*;
+
+  #[test]
+  fn test_parse_manpage_header() {
+    let input = adoc! {"
+      = git-foo(1)
+      :doctype: manpage
+
+      == NAME
+
+      git-foo, git-bar - do foo things
+
+      == SYNOPSIS
+
+      git foo [--bar]
+    "};
+    let parser = test_parser!(input);
+    let document = parser.parse().unwrap().document;
+    let meta = &document.meta;
+    expect_eq!(meta.str("mantitle"), Some("git-foo"));
+    expect_eq!(meta.str("manvolnum"), Some("1"));
+    expect_eq!(meta.str("manname"), Some("git-foo"));
+    expect_eq!(meta.str("mannames"), Some("git-foo, git-bar"));
+    expect_eq!(meta.str("manpurpose"), Some("do foo things"));
+  }
+
+  #[test]
+  fn test_non_conforming_manpage_title() {
+    let input = adoc! {"
+      = Not a manpage title
+      :doctype: manpage
+      :backend: manpage
+
+      == NAME
+
+      foo - bar
+    "};
+    let parser = test_parser!(input);
+    let diagnostics = parser.parse().err().unwrap();
+    expect_eq!(
+      diagnostics[0].message,
+      "Non-conforming manpage title, expected `= name(volnum)`"
+    );
+  }
+}
@@ -8,6 +8,7 @@ impl<'arena> Parser<'arena> {
 
     if peeked.semantic_level == 0 && self.document.meta.get_doctype() != DocType::Book {
       self.err_line(
+        DiagnosticCode::SectionInvalid,
         "Level 0 section allowed only in doctype=book",
         peeked.lines.current().unwrap(),
       )?;
@@ -32,7 +33,11 @@ impl<'arena> Parser<'arena> {
 
     let Some(line) = lines.current() else {
       if !meta.is_empty() {
-        self.err_line_starting("Unattached block metadata", meta.start_loc)?;
+        self.err_line_starting(
+          DiagnosticCode::BlockMetadataUnattached,
+          "Unattached block metadata",
+          meta.start_loc,
+        )?;
       }
       self.restore_peeked_meta(meta);
       return Ok(None);
@@ -84,6 +89,7 @@ impl<'arena> Parser<'arena> {
     let out_of_sequence = semantic_level > last_level && semantic_level - last_level > 1;
     if out_of_sequence {
       self.err_at(
+        DiagnosticCode::SectionOutOfSequence,
         format!(
           "Section title out of sequence: expected level {} `{}`",
           last_level + 1,
@@ -93,6 +99,7 @@ impl<'arena> Parser<'arena> {
       )?;
     }
 
+    let heading_loc = heading_line.first_loc().unwrap();
     let heading = self.parse_inlines(&mut heading_line.into_lines())?;
     if !out_of_sequence {
       self.push_toc_node(
@@ -139,6 +146,7 @@ impl<'arena> Parser<'arena> {
       for block in &blocks {
         if let BlockContent::Section(subsection) = &block.content {
           self.err_line_starting(
+            DiagnosticCode::SectionInvalid,
             format!(
               "{} sections do not support nested sections",
               special_sect.to_str()
@@ -149,6 +157,13 @@ impl<'arena> Parser<'arena> {
       }
     }
 
+    if semantic_level == 1
+      && self.document.meta.get_doctype() == DocType::Manpage
+      && !self.document.meta.is_set("manname")
+    {
+      self.parse_manpage_name_section(&blocks, heading_loc)?;
+    }
+
     self.ctx.bibliography_ctx = BiblioContext::None;
     self.ctx.section_level = last_level;
     Ok(Section {
@@ -0,0 +1,38 @@
+use crate::internal::*;
+
+/// a callback that rewrites raw source before it is lexed, it runs on
+/// the root document and again on the contents of every include
+pub trait Preprocessor {
+  /// returns `None` to leave the source unchanged
+  fn process(&mut self, file: &SourceFile, src: &str, doc_meta: &DocumentMeta) -> Option<String>;
+
+  fn clone_box(&self) -> Box<dyn Preprocessor>;
+}
+
+impl<'arena> Parser<'arena> {
+  /// processors run in the order registered, each seeing the output of the last
+  pub fn register_preprocessor(&mut self, processor: Box<dyn Preprocessor>) {
+    self.preprocessors.push(processor);
+  }
+
+  pub(crate) fn preprocess_root_source(&mut self) {
+    if self.preprocessors.is_empty() {
+      return;
+    }
+    let file = self.lexer.source_file().clone();
+    let mut src = self.lexer.take_primary_src();
+    self.preprocess(&file, &mut src);
+    self.lexer.replace_primary_src(src);
+  }
+
+  pub(crate) fn preprocess(&mut self, file: &SourceFile, buffer: &mut BumpVec<'arena, u8>) {
+    for processor in self.preprocessors.iter_mut() {
+      let Ok(src) = std::str::from_utf8(buffer) else {
+        return;
+      };
+      if let Some(processed) = processor.process(file, src, &self.document.meta) {
+        *buffer = BumpVec::from_iter_in(processed.bytes(), self.bump);
+      }
+    }
+  }
+}
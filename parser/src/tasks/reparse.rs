@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::{cell::RefCell, rc::Rc};
+
+use lazy_static::lazy_static;
+use regex::bytes::Regex;
+
+use crate::internal::*;
+
+/// a single edit to a document's source, as reported by an editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+  /// byte range of the previous source that was replaced
+  pub range: Range<usize>,
+  pub replacement: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum Unit<'arena> {
+  Block(Block<'arena>),
+  Section(Section<'arena>),
+}
+
+impl<'arena> Parser<'arena> {
+  /// parses the (already edited) source of this parser by re-parsing only
+  /// the top-level blocks or sections touched by `edit`, reusing the rest of
+  /// the `previous` result, which must come from a parser configured the
+  /// same way as this one
+  ///
+  /// falls back to a full parse whenever the edit could affect anything
+  /// outside of the re-parsed region, so the result is always equivalent
+  /// to calling `parse()`, see `ParseResult::reparsed_range`. Content
+  /// included from outside the region is reused without re-reading it,
+  /// so a full parse is needed when an included file changes
+  pub fn reparse(
+    mut self,
+    mut previous: ParseResult<'arena>,
+    edit: &TextEdit,
+  ) -> std::result::Result<ParseResult<'arena>, Vec<Diagnostic>> {
+    let Some(reparsed_range) = self.try_reparse(&mut previous, edit) else {
+      return self.parse();
+    };
+    previous
+      .lexer
+      .replace_primary_src(self.lexer.take_primary_src());
+    previous.include_resolver = self.include_resolver.take();
+    previous.reparsed_range = Some(reparsed_range);
+    Ok(previous)
+  }
+
+  fn try_reparse(
+    &mut self,
+    previous: &mut ParseResult<'arena>,
+    edit: &TextEdit,
+  ) -> Option<Range<u32>> {
+    if !self.incremental_supported(previous) {
+      return None;
+    }
+    let old_src = previous.lexer.primary_src();
+    let new_src = self.lexer.primary_src();
+    if !edit_matches(old_src, new_src, edit) || CONDITIONAL_RE.is_match(new_src) {
+      return None;
+    }
+    let delta = new_src.len() as i64 - old_src.len() as i64;
+    let shift = |pos: u32| (pos as i64 + delta) as u32;
+
+    let empty = DocContent::Blocks(BumpVec::new_in(self.bump));
+    let mut units = match std::mem::replace(&mut previous.document.content, empty) {
+      DocContent::Blocks(blocks) => blocks.into_iter().map(Unit::Block).collect::<Vec<_>>(),
+      DocContent::Sections(Sectioned { preamble, sections }) => {
+        let blocks = preamble.into_iter().flatten().map(Unit::Block);
+        blocks
+          .chain(sections.into_iter().map(Unit::Section))
+          .collect()
+      }
+      DocContent::Parts(_) => return None,
+    };
+    let starts = unit_starts(&units, &previous.lexer)?;
+    let first_start = *starts.first()?;
+    if edit.range.start < first_start as usize
+      || previous
+        .attr_defs
+        .iter()
+        .any(|def| previous.lexer.root_loc(def.loc).end > first_start)
+    {
+      return None;
+    }
+
+    // the edited units, plus one before and one after, whose starts
+    // must stay put for the rest of the document to be reusable
+    let k_lo = starts.partition_point(|&s| s as usize <= edit.range.start) - 1;
+    let k_hi = starts.partition_point(|&s| s as usize <= edit.range.end) - 1;
+    let a = k_lo.saturating_sub(1);
+    let b = k_hi + 1;
+    let region_start = starts[a];
+    let old_end = starts.get(b).copied().unwrap_or(old_src.len() as u32);
+    let resync = starts.get(b).map(|&s| shift(s));
+    let region_end = starts
+      .get(b + 1)
+      .map_or(new_src.len() as u32, |&s| shift(s));
+    let new_end = resync.unwrap_or(new_src.len() as u32);
+
+    let old_region = &old_src[region_start as usize..old_end as usize];
+    let new_region = &new_src[region_start as usize..region_end as usize];
+    if UNSUPPORTED_RE.is_match(old_region)
+      || UNSUPPORTED_RE.is_match(new_region)
+      || !follows_empty_line(new_src, region_start as usize)
+      || resync.is_some_and(|pos| !follows_empty_line(new_src, pos as usize))
+    {
+      return None;
+    }
+    let new_region = std::str::from_utf8(new_region).ok()?;
+
+    let num_sections = |units: &[Unit]| units.iter().filter(|u| u.is_section()).count();
+    if let Some(toc) = &previous.document.toc
+      && toc.nodes.len() != num_sections(&units)
+    {
+      return None;
+    }
+
+    // auto-generated ids are de-duplicated by sequence, so any id that
+    // looks sequenced could change when an earlier section changes
+    if section_ids(&mut units).iter().any(|id| is_sequenced(id)) {
+      return None;
+    }
+    let prefix_ids = section_ids(&mut units[..a]);
+    let suffix_ids = section_ids(&mut units[b..]);
+    let mut removed_ids = section_ids(&mut units[a..b]);
+
+    // outside anchors are seeded into the sub-parser, which re-creates the region's own
+    let mut sub = self.region_parser(new_region, region_start, &previous.document.meta);
+    sub.document.anchors.swap(&previous.document.anchors);
+    sub.document.anchors.borrow_mut().retain(|id, anchor| {
+      let in_region = anchor.source_loc.is_some_and(|loc| {
+        loc.include_depth == 0 && loc.start >= region_start && loc.start < old_end
+      });
+      if in_region {
+        removed_ids.push(id.clone());
+      }
+      !in_region && !removed_ids.contains(id)
+    });
+    let mut relocate = |pos: &mut u32, depth: u16| {
+      if depth == 0 && *pos >= old_end {
+        *pos = shift(*pos);
+      }
+    };
+    sub
+      .document
+      .anchors
+      .borrow_mut()
+      .values_mut()
+      .for_each(|anchor| anchor.relocate(&mut relocate));
+    units[b..]
+      .iter_mut()
+      .for_each(|unit| unit.relocate(&mut relocate));
+    // the resync unit is re-parsed too, so its own ids must not be seen
+    let after_resync = (b + 1).min(units.len());
+    let outside_ids = prefix_ids
+      .into_iter()
+      .chain(section_ids(&mut units[after_resync..]));
+    sub.ctx.anchor_ids = Rc::new(RefCell::new(outside_ids.collect()));
+    let sectioned = sub.parse_sectioned().ok()?;
+    sub.diagnose_invalid_xrefs().ok()?;
+    if sub.highest_severity.get().is_some() {
+      return None;
+    }
+
+    let mut reparsed = sectioned
+      .preamble
+      .into_iter()
+      .flatten()
+      .map(Unit::Block)
+      .chain(sectioned.sections.into_iter().map(Unit::Section))
+      .collect::<Vec<_>>();
+    let reparsed_starts = unit_starts(&reparsed, &sub.lexer)?;
+    let accepted = match resync {
+      Some(resync) => {
+        let idx = reparsed_starts.iter().position(|&s| s == resync)?;
+        units[b].walk_mut(&mut ShareAnchors(Rc::clone(&sub.document.anchors)));
+        if reparsed[idx] != units[b] {
+          return None;
+        }
+        idx
+      }
+      None => reparsed.len(),
+    };
+
+    let mut new_toc_nodes = None;
+    if let Some(toc) = sub.document.toc.take() {
+      if toc.nodes.len() != num_sections(&reparsed) {
+        return None;
+      }
+      new_toc_nodes = Some(toc.nodes);
+    }
+    reparsed.truncate(accepted);
+
+    let added_ids = section_ids(&mut reparsed);
+    if added_ids
+      .iter()
+      .any(|id| is_sequenced(id) || suffix_ids.contains(id))
+    {
+      return None;
+    }
+    // xrefs outside the region may still point at removed anchors
+    if removed_ids
+      .iter()
+      .any(|id| !sub.document.anchors.borrow().contains_key(id))
+    {
+      return None;
+    }
+
+    let num_prefix_sections = num_sections(&units[..a]);
+    let num_removed_sections = num_sections(&units[a..b]);
+    let num_added_sections = num_sections(&reparsed);
+    let suffix = units.split_off(b);
+    units.truncate(a);
+    units.extend(reparsed);
+    units.extend(suffix);
+    if units
+      .windows(2)
+      .any(|pair| pair[0].is_section() && !pair[1].is_section())
+    {
+      return None;
+    }
+
+    if let (Some(toc), Some(new_nodes)) = (previous.document.toc.as_mut(), new_toc_nodes) {
+      let old_nodes = std::mem::replace(&mut toc.nodes, BumpVec::new_in(self.bump));
+      let mut old_nodes = old_nodes.into_iter();
+      toc
+        .nodes
+        .extend(old_nodes.by_ref().take(num_prefix_sections));
+      toc
+        .nodes
+        .extend(new_nodes.into_iter().take(num_added_sections));
+      toc
+        .nodes
+        .extend(old_nodes.skip(num_removed_sections).map(|mut node| {
+          node.relocate(&mut relocate);
+          node
+        }));
+    }
+
+    previous.document.anchors.swap(&sub.document.anchors);
+    let mut share = ShareAnchors(Rc::clone(&previous.document.anchors));
+    let mut blocks = BumpVec::new_in(self.bump);
+    let mut sections = BumpVec::new_in(self.bump);
+    for (idx, mut unit) in units.into_iter().enumerate() {
+      // the re-parsed units and the resync unit point at the sub-parser's anchors
+      if idx >= a && idx <= a + accepted {
+        unit.walk_mut(&mut share);
+      }
+      match unit {
+        Unit::Block(block) => blocks.push(block),
+        Unit::Section(section) => sections.push(section),
+      }
+    }
+    let preamble = if blocks.is_empty() { None } else { Some(blocks) };
+    previous.document.content = Sectioned { preamble, sections }.into_doc_content(self.bump);
+    Some(region_start..new_end)
+  }
+
+  fn incremental_supported(&self, previous: &ParseResult<'arena>) -> bool {
+    #[cfg(feature = "attr_ref_observation")]
+    if self.attr_ref_observer.is_some() {
+      return false;
+    }
+    let meta = &previous.document.meta;
+    self.preprocessors.is_empty()
+      && self.tree_processors.is_empty()
+      && previous.highest_severity.is_none()
+      && meta.get_doctype() == DocType::Article
+      && meta.get("leveloffset").is_none()
+      && previous
+        .document
+        .toc
+        .as_ref()
+        .is_none_or(|toc| !matches!(toc.position, TocPosition::Macro | TocPosition::Preamble))
+  }
+
+  fn region_parser(&self, src: &str, offset: u32, meta: &DocumentMeta) -> Parser<'arena> {
+    let mut parser = Parser::from_str(src, self.lexer.source_file().clone(), self.bump);
+    parser.lexer.adjust_offset(offset);
+    parser.lexer.share_plugin_macros(&self.lexer);
+    parser.include_resolver = self.include_resolver.as_ref().map(|r| r.clone_box());
+    parser.block_processors = self
+      .block_processors
+      .iter()
+      .map(|(name, processor)| (name.clone(), processor.clone_box()))
+      .collect();
+    parser.strict = false;
+    parser.log_level = self.log_level;
+    parser.check_interdoc_xrefs = self.check_interdoc_xrefs;
+    parser.ctx.leveloffset = self.ctx.leveloffset;
+    parser.ctx.max_include_depth = self.ctx.max_include_depth;
+    parser.document.meta = meta.clone();
+    parser.prepare_toc();
+    parser
+  }
+}
+
+impl<'arena> Unit<'arena> {
+  const fn is_section(&self) -> bool {
+    matches!(self, Unit::Section(_))
+  }
+
+  const fn meta_and_loc(&self) -> (&ChunkMeta<'arena>, &MultiSourceLocation) {
+    match self {
+      Unit::Block(block) => (&block.meta, &block.loc),
+      Unit::Section(section) => (&section.meta, &section.loc),
+    }
+  }
+
+  /// where the unit starts in the root source, units from
+  /// an included source start at their include directive
+  fn start(&self, lexer: &Lexer) -> u32 {
+    let (meta, loc) = self.meta_and_loc();
+    let start = SourceLocation::new(loc.start_pos, loc.start_pos, loc.start_depth);
+    let start = lexer.root_loc(start).start;
+    if meta.is_empty() {
+      return start;
+    }
+    lexer.root_loc(meta.start_loc).start.min(start)
+  }
+
+  fn relocate(&mut self, f: &mut dyn FnMut(&mut u32, u16)) {
+    match self {
+      Unit::Block(block) => block.relocate(f),
+      Unit::Section(section) => section.relocate(f),
+    }
+  }
+
+  fn walk_mut(&mut self, visitor: &mut impl VisitMut<'arena>) {
+    match self {
+      Unit::Block(block) => block.walk_mut(visitor),
+      Unit::Section(section) => section.walk_mut(visitor),
+    }
+  }
+}
+
+/// units from the same include share a start, the `include::` check
+/// on the re-parsed region keeps their directive from being re-parsed
+fn unit_starts(units: &[Unit], lexer: &Lexer) -> Option<Vec<u32>> {
+  let starts = units
+    .iter()
+    .map(|unit| unit.start(lexer))
+    .collect::<Vec<_>>();
+  if starts.windows(2).any(|pair| pair[0] > pair[1]) {
+    return None;
+  }
+  Some(starts)
+}
+
+fn section_ids<'arena>(units: &mut [Unit<'arena>]) -> Vec<BumpString<'arena>> {
+  let mut ids = SectionIds(Vec::new());
+  units.iter_mut().for_each(|unit| unit.walk_mut(&mut ids));
+  ids.0
+}
+
+struct SectionIds<'arena>(Vec<BumpString<'arena>>);
+
+impl<'arena> VisitMut<'arena> for SectionIds<'arena> {
+  fn visit_section(&mut self, section: &mut Section<'arena>) {
+    if let Some(id) = &section.id {
+      self.0.push(id.clone());
+    }
+  }
+}
+
+/// asciidoc table cells share the anchors of the enclosing document
+struct ShareAnchors<'arena>(Rc<RefCell<HashMap<BumpString<'arena>, Anchor<'arena>>>>);
+
+impl<'arena> VisitMut<'arena> for ShareAnchors<'arena> {
+  fn visit_block(&mut self, block: &mut Block<'arena>) {
+    let BlockContent::Table(table) = &mut block.content else {
+      return;
+    };
+    let rows = table.header_row.iter_mut().chain(table.rows.iter_mut());
+    for cell in rows
+      .chain(table.footer_row.iter_mut())
+      .flat_map(|row| row.cells.iter_mut())
+    {
+      if let CellContent::AsciiDoc(document) = &mut cell.content {
+        document.anchors = Rc::clone(&self.0);
+      }
+    }
+  }
+}
+
+fn edit_matches(old_src: &[u8], new_src: &[u8], edit: &TextEdit) -> bool {
+  let Range { start, end } = edit.range;
+  let replacement = edit.replacement.as_bytes();
+  start <= end
+    && end <= old_src.len()
+    && new_src.len() == old_src.len() - (end - start) + replacement.len()
+    && old_src[..start] == new_src[..start]
+    && new_src[start..start + replacement.len()] == *replacement
+    && old_src[end..] == new_src[start + replacement.len()..]
+}
+
+fn follows_empty_line(src: &[u8], pos: usize) -> bool {
+  if pos == 0 {
+    return true;
+  }
+  let Some(before) = src[..pos].strip_suffix(b"\n") else {
+    return false;
+  };
+  let line_start = before
+    .iter()
+    .rposition(|&b| b == b'\n')
+    .map_or(0, |i| i + 1);
+  before[line_start..].iter().all(u8::is_ascii_whitespace)
+}
+
+fn is_sequenced(id: &str) -> bool {
+  id.ends_with(|c: char| c.is_ascii_digit())
+}
+
+lazy_static! {
+  // conditionals anywhere can span the region being re-parsed
+  static ref CONDITIONAL_RE: Regex = Regex::new(r"(?m)^(ifn?def|ifeval|endif)::").unwrap();
+  // includes, attribute entries, counters and callouts all carry
+  // state from one block to the next
+  static ref UNSUPPORTED_RE: Regex = Regex::new(
+    r"(?m)^include::|^:!?[\w-]+!?:|\{counter2?:|<(\d+|\.)>|<!--(\d+|\.)-->"
+  )
+  .unwrap();
+}
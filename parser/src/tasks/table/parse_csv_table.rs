@@ -31,7 +31,11 @@ impl<'arena> Parser<'arena> {
       if tokens.current().is_none() || (quote.is_none() && self.consume_dsv_delimiter(tokens, ctx))
       {
         if let Some(loc) = quote {
-          self.err_at("Unclosed CSV quote", loc.clamp_start().incr_end())?;
+          self.err_at(
+            DiagnosticCode::TableInvalid,
+            "Unclosed CSV quote",
+            loc.clamp_start().incr_end(),
+          )?;
         }
         return self
           .finish_cell(CellSpec::default(), cell_tokens, col_index, ctx, start..end)
@@ -50,6 +54,7 @@ impl<'arena> Parser<'arena> {
       match token.kind {
         DoubleQuote if quote.is_none() => {
           self.err_at(
+            DiagnosticCode::TableInvalid,
             "Double quote not allowed here, entire field must be quoted",
             token.loc,
           )?;
@@ -88,7 +88,11 @@ impl<'arena> Parser<'arena> {
       Some((spec, start)) => (spec, start),
       None => {
         let sep = ctx.format.separator();
-        self.err_token(format!("Expected cell separator `{sep}`"), tokens.nth(0))?;
+        self.err_token(
+          DiagnosticCode::TableInvalid,
+          format!("Expected cell separator `{sep}`"),
+          tokens.nth(0),
+        )?;
         (CellSpec::default(), tokens.current().unwrap().loc.start)
       }
     };
@@ -96,6 +100,7 @@ impl<'arena> Parser<'arena> {
     let mut drop_invalid_cell = false;
     if !ctx.counting_cols && spec.col_span.unwrap_or(0) > ctx.num_cols as u8 {
       self.err_at(
+        DiagnosticCode::TableInvalid,
         format!(
           "Cell column span ({}) exceeds number of columns ({})",
           spec.col_span.unwrap(),
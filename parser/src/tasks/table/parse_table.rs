@@ -41,11 +41,16 @@ impl<'arena> Parser<'arena> {
       let msg = "Cell separator must be exactly one character";
       let mut chars = sep.chars();
       match chars.next() {
-        None => self.err_at_pattern(msg, meta.start_loc, "separator")?,
+        None => self.err_at_pattern(
+          DiagnosticCode::TableInvalid,
+          msg,
+          meta.start_loc,
+          "separator",
+        )?,
         Some(ch) => {
           format.replace_separator(ch);
           if chars.next().is_some() {
-            self.err_at_pattern(msg, meta.start_loc, sep)?;
+            self.err_at_pattern(DiagnosticCode::TableInvalid, msg, meta.start_loc, sep)?;
           }
         }
       }
@@ -220,12 +225,16 @@ impl<'arena> Parser<'arena> {
         Ok(ParseResult {
           document,
           mut warnings,
+          highest_severity,
           attr_defs,
           #[cfg(feature = "attr_ref_observation")]
           attr_ref_observer,
           ..
         }) => {
           self.ctx.attr_defs.extend(attr_defs);
+          if let Some(severity) = highest_severity {
+            self.record_severity(severity);
+          }
           if !warnings.is_empty() {
             self.lexer.reline_diagnostics(loc.start, &mut warnings);
             self.errors.borrow_mut().extend(warnings);
@@ -242,6 +251,9 @@ impl<'arena> Parser<'arena> {
         }
         Err(mut diagnostics) => {
           self.lexer.reline_diagnostics(loc.start, &mut diagnostics);
+          diagnostics
+            .iter()
+            .for_each(|diagnostic| self.record_severity(diagnostic.severity));
           if !diagnostics.is_empty() && self.strict {
             Err(diagnostics.remove(0))
           } else {
@@ -394,7 +406,11 @@ impl<'arena> Parser<'arena> {
       }
       next_line.drain_into(&mut tokens);
     }
-    self.err_line("Table never closed, started here", start_delim)?;
+    self.err_line(
+      DiagnosticCode::BlockUnclosed,
+      "Table never closed, started here",
+      start_delim,
+    )?;
     Ok((TableTokens::new(tokens), self.lexer.loc()))
   }
 }
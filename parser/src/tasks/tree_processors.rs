@@ -0,0 +1,23 @@
+use crate::internal::*;
+
+/// a callback that rewrites the document after parsing, before conversion,
+/// see `Document::walk_mut` for visiting nested blocks and inlines
+///
+/// NB: the table of contents is built while parsing, so processors
+/// that add or rename sections must update `document.toc` themselves
+pub trait TreeProcessor {
+  fn process<'arena>(&mut self, document: &mut Document<'arena>, bump: &'arena Bump);
+}
+
+impl<'arena> Parser<'arena> {
+  /// processors run in the order registered
+  pub fn register_tree_processor(&mut self, processor: Box<dyn TreeProcessor>) {
+    self.tree_processors.push(processor);
+  }
+
+  pub(crate) fn run_tree_processors(&mut self) {
+    for processor in self.tree_processors.iter_mut() {
+      processor.process(&mut self.document, self.bump);
+    }
+  }
+}
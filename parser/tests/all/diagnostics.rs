@@ -0,0 +1,68 @@
+use asciidork_core::{JobSettings, Severity};
+use asciidork_parser::prelude::*;
+use test_utils::*;
+
+const BROKEN_XREF: &str = "See <<nowhere>>.";
+
+#[test]
+fn diagnostics_carry_code_and_severity() {
+  let warnings = parse_warnings!(BROKEN_XREF);
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].code, DiagnosticCode::XrefInvalid);
+  assert_eq!(warnings[0].code.to_str(), "xref-invalid");
+  assert_eq!(warnings[0].severity, Severity::Warn);
+}
+
+#[test]
+fn strict_stops_at_failure_level() {
+  let mut parser = test_parser!(BROKEN_XREF);
+  parser.apply_job_settings(JobSettings::default());
+  let errors = parser.parse().unwrap_err();
+  assert_eq!(errors[0].code, DiagnosticCode::XrefInvalid);
+
+  let mut parser = test_parser!(BROKEN_XREF);
+  parser.apply_job_settings(JobSettings {
+    failure_level: Severity::Error,
+    ..JobSettings::default()
+  });
+  let warnings = parser.parse().unwrap().warnings;
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].code, DiagnosticCode::XrefInvalid);
+}
+
+#[test]
+fn log_level_drops_less_severe_diagnostics() {
+  let mut parser = test_parser!(BROKEN_XREF);
+  parser.apply_job_settings(JobSettings {
+    strict: false,
+    log_level: Severity::Error,
+    ..JobSettings::default()
+  });
+  let result = parser.parse().unwrap();
+  assert!(result.warnings.is_empty());
+  // still tracked, so a failure level below the log level is reachable
+  assert_eq!(result.highest_severity, Some(Severity::Warn));
+}
+
+#[test]
+fn suppress_diagnostics_attr() {
+  let input = adoc! {"
+    = Document Title
+    :suppress-diagnostics: footnote-empty, xref-invalid
+
+    See <<nowhere>>.
+  "};
+  let mut parser = test_parser!(input);
+  parser.apply_job_settings(JobSettings::default());
+  assert!(parser.parse().unwrap().warnings.is_empty());
+
+  let input = adoc! {"
+    = Document Title
+    :suppress-diagnostics: footnote-empty
+
+    See <<nowhere>>.
+  "};
+  let warnings = parse_warnings!(input);
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].code, DiagnosticCode::XrefInvalid);
+}
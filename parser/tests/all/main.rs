@@ -1,4 +1,5 @@
 mod attrs;
+mod diagnostics;
 mod fuzz_cases;
 mod parse_blocks;
 mod parse_breaks;
@@ -19,3 +20,4 @@ mod parse_sections;
 mod parse_toc;
 mod parse_unordered_lists;
 mod parse_verbatim_blocks;
+mod reparse;
@@ -217,6 +217,27 @@ fn test_parse_delimited_passthrough_block() {
   assert_block!(input, expected);
 }
 
+#[test]
+fn test_parse_delimited_stem_block() {
+  let input = adoc! {"
+    [stem]
+    ++++
+    x < \\[y\\]
+    ++++
+  "};
+  let expected = Block {
+    meta: ChunkMeta::new(vecb![attrs::pos("stem", 1..5)], None, loc!(0..1)),
+    context: Context::Stem,
+    content: Content::Simple(nodes![
+      node!("x "; 12..14),
+      node!(SpecialChar(SpecialCharKind::LessThan), 14..15),
+      node!(" \\[y\\]"; 15..21),
+    ]),
+    loc: (7..26).into(),
+  };
+  assert_block!(input, expected);
+}
+
 #[test]
 fn test_parse_delimited_passthrough_block_subs_normal() {
   let input = adoc! {"
@@ -1,6 +1,6 @@
 use asciidork_ast::prelude::*;
-use asciidork_core::SafeMode;
 use asciidork_core::{JobAttr, JobSettings};
+use asciidork_core::{SafeMode, Severity};
 use asciidork_parser::includes::*;
 use asciidork_parser::prelude::*;
 use test_utils::*;
@@ -186,7 +186,10 @@ fn optional_include_not_found() {
   let mut parser = test_parser!("include::nope.adoc[opts=optional]");
   parser.apply_job_settings(JobSettings::r#unsafe());
   parser.set_resolver(Box::new(ErrorResolver(ResolveError::NotFound)));
-  assert!(parser.parse().is_ok());
+  let warnings = parser.parse().unwrap().warnings;
+  assert_eq!(warnings.len(), 1);
+  assert_eq!(warnings[0].code, DiagnosticCode::IncludeOptionalNotFound);
+  assert_eq!(warnings[0].severity, Severity::Info);
 }
 
 assert_error!(
@@ -376,7 +379,18 @@ fn include_resolver_error_bad_encoding() {
      --> test.adoc:1:10
       |
     1 | include::file.adoc[]
-      |          ^^^^^^^^^ Error resolving file contents: Invalid UTF-8
+      |          ^^^^^^^^^ Error resolving file contents: Invalid UTF-8 at byte 2, set the `encoding` attribute for other encodings
+  "};
+  expect_eq!(parser.parse().err().unwrap()[0].plain_text(), expected_err);
+
+  let mut parser = test_parser!("include::file.adoc[encoding=euc-jp]");
+  parser.apply_job_settings(JobSettings::r#unsafe());
+  parser.set_resolver(Box::new(ConstResolver(vec![0x68, 0x69, 0xA4])));
+  let expected_err = error! {"
+     --> test.adoc:1:10
+      |
+    1 | include::file.adoc[encoding=euc-jp]
+      |          ^^^^^^^^^ Error resolving file contents: Invalid EUC-JP at byte 2
   "};
   expect_eq!(parser.parse().err().unwrap()[0].plain_text(), expected_err);
 }
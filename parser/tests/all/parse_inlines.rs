@@ -315,6 +315,39 @@ fn test_inline_passthrus() {
   ]);
 }
 
+#[test]
+fn test_stem_macros() {
+  run(vec![
+    (
+      "stem:[a < b]",
+      nodes![node!(
+        Macro(Stem {
+          notation: StemNotation::Asciimath,
+          content: nodes![
+            node!("a "; 6..8),
+            node!(SpecialChar(SpecialCharKind::LessThan), 8..9),
+            node!(" b"; 9..11),
+          ],
+        }),
+        0..12
+      )],
+    ),
+    (
+      "latexmath:[*x*\\]] y",
+      nodes![
+        node!(
+          Macro(Stem {
+            notation: StemNotation::Latexmath,
+            content: just!("*x*]", 11..16),
+          }),
+          0..17
+        ),
+        node!(" y"; 17..19),
+      ],
+    ),
+  ]);
+}
+
 #[test]
 fn test_line_comments() {
   run(vec![(
@@ -53,7 +53,8 @@ test_inlines_loose!(
     Macro(Xref {
       target: src!("foo", 5..8),
       linktext: None,
-      kind: XrefKind::Macro
+      kind: XrefKind::Macro,
+      xrefstyle: None,
     }),
     0..10
   )]
@@ -67,7 +68,8 @@ test_inlines_loose!(
       Macro(Xref {
         target: src!(":/c", 5..8),
         linktext: None,
-        kind: XrefKind::Macro
+        kind: XrefKind::Macro,
+        xrefstyle: None,
       }),
       0..10
     ),
@@ -76,7 +78,8 @@ test_inlines_loose!(
       Macro(Xref {
         target: src!(":/d", 20..23),
         linktext: None,
-        kind: XrefKind::Macro
+        kind: XrefKind::Macro,
+        xrefstyle: None,
       }),
       15..25
     )
@@ -147,7 +150,8 @@ test_inlines_loose!(
           13..18
         )
       ]),
-      kind: XrefKind::Macro
+      kind: XrefKind::Macro,
+      xrefstyle: None,
     }),
     0..19
   )]
@@ -160,7 +164,8 @@ test_inlines_loose!(
     Macro(Xref {
       target: src!("f-o", 5..8),
       linktext: Some(just!(" ", 9..10)),
-      kind: XrefKind::Macro
+      kind: XrefKind::Macro,
+      xrefstyle: None,
     }),
     0..11
   )]
@@ -175,7 +180,8 @@ test_inlines_loose!(
       Macro(Xref {
         target: src!("bar", 9..12),
         linktext: None,
-        kind: XrefKind::Macro
+        kind: XrefKind::Macro,
+        xrefstyle: None,
       }),
       4..14
     ),
@@ -183,6 +189,20 @@ test_inlines_loose!(
   ]
 );
 
+test_inlines_loose!(
+  xref_macro_xrefstyle,
+  "xref:foo[xrefstyle=short]",
+  nodes![node!(
+    Macro(Xref {
+      target: src!("foo", 5..8),
+      linktext: None,
+      kind: XrefKind::Macro,
+      xrefstyle: Some(XrefStyle::Short),
+    }),
+    0..25
+  )]
+);
+
 test_inlines_loose!(
   test_xref_shorthand,
   "<<foo>>",
@@ -190,7 +210,8 @@ test_inlines_loose!(
     Macro(Xref {
       target: src!("foo", 2..5),
       linktext: None,
-      kind: XrefKind::Shorthand
+      kind: XrefKind::Shorthand,
+      xrefstyle: None,
     }),
     0..7
   )]
@@ -203,7 +224,8 @@ test_inlines_loose!(
     Macro(Xref {
       target: src!("#foo", 2..6),
       linktext: None,
-      kind: XrefKind::Shorthand
+      kind: XrefKind::Shorthand,
+      xrefstyle: None,
     }),
     0..8
   )]
@@ -218,7 +240,8 @@ test_inlines_loose!(
       Macro(Xref {
         target: src!("foo", 3..6),
         linktext: None,
-        kind: XrefKind::Shorthand
+        kind: XrefKind::Shorthand,
+        xrefstyle: None,
       }),
       1..8
     )
@@ -241,7 +264,8 @@ test_inlines_loose!(
           ),
           node!(" wow"; 24..28)
         ]),
-        kind: XrefKind::Shorthand
+        kind: XrefKind::Shorthand,
+        xrefstyle: None,
       }),
       4..30
     ),
@@ -276,6 +300,24 @@ assert_error!(
   "}
 );
 
+#[test]
+fn xref_other_doc_unchecked() {
+  let input = "See <<other.adoc#foo>> and <<bar>>.";
+  let mut parser = test_parser!(input);
+  parser.set_check_interdoc_xrefs(false);
+  let err = parser.parse().unwrap_err().remove(0);
+  expect_eq!(
+    err.plain_text(),
+    error! {"
+       --> test.adoc:1:30
+        |
+      1 | See <<other.adoc#foo>> and <<bar>>.
+        |                              ^^^ Invalid cross reference, no anchor found for `bar`
+    "},
+    from: input
+  );
+}
+
 assert_error!(
   inline_svg_no_resolver,
   "image::cat.svg[opts=inline]",
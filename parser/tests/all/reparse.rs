@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use asciidork_core::{JobSettings, SafeMode};
+use asciidork_parser::TextEdit;
+use asciidork_parser::includes::*;
+use asciidork_parser::prelude::*;
+use test_utils::*;
+
+const DOC: &str = adoc! {r#"
+  = Document Title
+  :toc:
+
+  Preamble with a link:https://example.com[link].
+
+  [#first]
+  == First Section
+
+  Some _emphasis_ and a ref to <<second>>.
+
+  * one
+  * two
+
+  * three
+
+  === Nested
+
+  ----
+  code block
+  ----
+
+  [[second]]
+  == Second Section
+
+  A [[inline]]inline anchor, and <<first,first>>.
+
+  |===
+  a| cell with *strong* text
+  | plain
+  |===
+
+  .Titled
+  ****
+  sidebar content
+  ****
+
+  == Third Section
+
+  Last paragraph.
+  "#};
+
+const BLOCKS_DOC: &str = adoc! {r#"
+  First paragraph
+  spanning lines.
+
+  . one
+  . two
+
+  [quote,someone]
+  ____
+  A quote.
+  ____
+
+  term:: definition
+
+  Last [[anchor]]paragraph, see <<anchor>>.
+  "#};
+
+const INCLUDES_DOC: &str = adoc! {r#"
+  = Book
+  include::attrs.adoc[]
+
+  Intro with {product}.
+
+  Another intro paragraph.
+
+  [#third]
+  Third intro paragraph.
+
+  include::chapter.adoc[]
+
+  Trailing chapter paragraph, see <<third>>.
+
+  == Middle
+
+  Middle paragraph.
+
+  * one
+  * two
+
+  == Last
+
+  Last paragraph with _emphasis_.
+
+  ----
+  code block
+  ----
+
+  .Titled
+  ****
+  sidebar content
+  ****
+
+  Final paragraph.
+  "#};
+
+#[test]
+fn test_reparse_equivalent_to_full_parse() {
+  for src in [DOC, BLOCKS_DOC] {
+    let (incremental, total) = assert_all_edits_equivalent(src);
+    // most edits land in the body and should not need a full parse
+    assert!(
+      incremental * 2 > total,
+      "{incremental} of {total} incremental"
+    );
+  }
+  // edits next to an include directive need a full parse
+  let (incremental, _) = assert_all_edits_equivalent(INCLUDES_DOC);
+  assert!(incremental > 0);
+}
+
+/// returns the number of edits handled incrementally, and the total
+fn assert_all_edits_equivalent(src: &str) -> (usize, usize) {
+  let insertions = [
+    "x",
+    " ",
+    "\n",
+    "\n\n",
+    "== Added\n\n",
+    "=== Added\n\n",
+    "* item\n",
+    "----\n",
+    "[[new]]",
+    "<<first>>",
+    "<<missing>>",
+    ".Title\n",
+  ];
+  let mut incremental = 0;
+  let mut total = 0;
+  for pos in 0..=src.len() {
+    for text in insertions {
+      total += 1;
+      incremental += assert_equivalent(src, pos..pos, text) as usize;
+    }
+    for len in [1, 2, 7] {
+      if pos + len <= src.len() {
+        total += 2;
+        incremental += assert_equivalent(src, pos..pos + len, "") as usize;
+        incremental += assert_equivalent(src, pos..pos + len, "z") as usize;
+      }
+    }
+  }
+  (incremental, total)
+}
+
+#[test]
+fn test_reparse_only_touches_edited_blocks() {
+  let pos = DOC.find("Last paragraph").unwrap();
+  let (result, _) = reparse(DOC, pos..pos, "Very ");
+  let range = result.unwrap().reparsed_range().unwrap();
+  assert!(range.start as usize >= DOC.find("[[second]]").unwrap());
+  assert!((range.start as usize) <= pos);
+}
+
+#[test]
+fn test_reparse_reuses_included_chapter() {
+  for (target, text) in [("Intro", "New "), ("Last paragraph", "Very ")] {
+    let pos = INCLUDES_DOC.find(target).unwrap();
+    let (result, full) = reparse(INCLUDES_DOC, pos..pos, text);
+    let result = result.unwrap();
+    assert!(result.reparsed_range().is_some(), "{target:?}");
+    assert_eq!(result.document, full.unwrap().document);
+  }
+  // the included chapter's directive falls in the re-parsed region
+  let pos = INCLUDES_DOC.find("Middle paragraph").unwrap();
+  let (result, _) = reparse(INCLUDES_DOC, pos..pos, "New ");
+  assert!(result.unwrap().reparsed_range().is_none());
+}
+
+#[test]
+fn test_reparse_falls_back_to_full_parse() {
+  let cases = [
+    // header
+    (DOC.find("Title").unwrap(), "New "),
+    // attribute entry in the body
+    (DOC.find("Last").unwrap(), ":foo: bar\n\n"),
+    // include directive
+    (DOC.find("Last").unwrap(), "include::other.adoc[]\n\n"),
+    // conditional
+    (DOC.find("Last").unwrap(), "ifdef::foo[]\n"),
+  ];
+  for (pos, text) in cases {
+    let (result, full) = reparse(DOC, pos..pos, text);
+    match (result, full) {
+      (Ok(result), Ok(full)) => {
+        assert!(result.reparsed_range().is_none(), "{text:?}");
+        assert_eq!(result.document, full.document);
+      }
+      (Err(_), Err(_)) => {}
+      _ => panic!("reparse and full parse disagree for {text:?}"),
+    }
+  }
+}
+
+#[test]
+fn test_reparse_invalidated_xref_target() {
+  // renaming a section another section refers to needs a full parse
+  let pos = DOC.find("[[second]]").unwrap();
+  assert!(!assert_equivalent(DOC, pos + 2..pos + 8, "renamed"));
+}
+
+#[test]
+fn test_reparse_chained_edits() {
+  let bump = Bump::new();
+  let mut src = DOC.to_string();
+  let mut previous = parse(&src, &bump).unwrap();
+  let pos = src.find("Some").unwrap();
+  for word in ["alpha ", "beta ", "gamma "] {
+    let edit = TextEdit {
+      range: pos..pos,
+      replacement: word.to_string(),
+    };
+    src.insert_str(pos, word);
+    previous = parser(&src, &bump).reparse(previous, &edit).unwrap();
+    assert!(previous.reparsed_range().is_some());
+    assert_eq!(previous.document, parse(&src, &bump).unwrap().document);
+  }
+}
+
+fn parser<'arena>(src: &str, bump: &'arena Bump) -> Parser<'arena> {
+  let mut parser = Parser::from_str(src, SourceFile::Path(Path::new("test.adoc")), bump);
+  parser.apply_job_settings(JobSettings {
+    safe_mode: SafeMode::Safe,
+    ..JobSettings::default()
+  });
+  let files = [
+    ("attrs.adoc", ":product: Dork\n"),
+    (
+      "chapter.adoc",
+      "[#chapter]\n== Chapter\n\nChapter text.\n\n=== Nested\n\nMore text.\n",
+    ),
+  ];
+  parser.set_resolver(Box::new(MapResolver(
+    files
+      .into_iter()
+      .map(|(path, src)| (path.to_string(), src.as_bytes().to_vec()))
+      .collect(),
+  )));
+  parser
+}
+
+#[derive(Clone)]
+struct MapResolver(HashMap<String, Vec<u8>>);
+
+impl IncludeResolver for MapResolver {
+  fn resolve(
+    &mut self,
+    target: IncludeTarget,
+    buffer: &mut dyn IncludeBuffer,
+    _: SafeMode,
+  ) -> std::result::Result<usize, ResolveError> {
+    let path = target.path().to_string();
+    let Some(bytes) = self.0.get(path.rsplit('/').next().unwrap()) else {
+      return Err(ResolveError::NotFound);
+    };
+    buffer.initialize(bytes.len());
+    buffer.as_bytes_mut().copy_from_slice(bytes);
+    Ok(bytes.len())
+  }
+
+  fn get_base_dir(&self) -> Option<String> {
+    Some("/docs".to_string())
+  }
+
+  fn clone_box(&self) -> Box<dyn IncludeResolver> {
+    Box::new(self.clone())
+  }
+}
+
+fn parse<'arena>(src: &str, bump: &'arena Bump) -> Result<ParseResult<'arena>, Vec<Diagnostic>> {
+  parser(src, bump).parse()
+}
+
+type Results<'arena> = Result<ParseResult<'arena>, Vec<Diagnostic>>;
+
+fn reparse(
+  src: &str,
+  range: std::ops::Range<usize>,
+  replacement: &str,
+) -> (Results<'static>, Results<'static>) {
+  let bump = leaked_bump();
+  let previous = parse(src, bump).unwrap();
+  let mut edited = src.to_string();
+  edited.replace_range(range.clone(), replacement);
+  let edit = TextEdit {
+    range,
+    replacement: replacement.to_string(),
+  };
+  let result = parser(&edited, bump).reparse(previous, &edit);
+  (result, parse(&edited, bump))
+}
+
+/// returns true if the edit was handled incrementally
+fn assert_equivalent(src: &str, range: std::ops::Range<usize>, replacement: &str) -> bool {
+  if !src.is_char_boundary(range.start) || !src.is_char_boundary(range.end) {
+    return false;
+  }
+  let bump = Bump::new();
+  let previous = parse(src, &bump).unwrap();
+  let mut edited = src.to_string();
+  edited.replace_range(range.clone(), replacement);
+  let edit = TextEdit {
+    range,
+    replacement: replacement.to_string(),
+  };
+  let result = parser(&edited, &bump).reparse(previous, &edit);
+  match (result, parse(&edited, &bump)) {
+    (Ok(result), Ok(full)) => {
+      expect_eq!(result.document, full.document, from: &edited);
+      assert_eq!(result.warnings, full.warnings);
+      result.reparsed_range().is_some()
+    }
+    // NB: which error a strict parse stops at can vary between parses
+    (Err(_), Err(_)) => false,
+    (result, full) => panic!(
+      "reparse and full parse disagree for:\n{edited}\n\nreparse: {:?}\n\nfull: {:?}",
+      result.map(|r| r.warnings),
+      full.map(|r| r.warnings)
+    ),
+  }
+}
@@ -1,2 +1 @@
-mod loc;
 pub mod tck;
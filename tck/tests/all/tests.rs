@@ -59,3 +59,43 @@ assert_asg_doc!(
   block_sidebar_containing_unordered_list,
   "block/sidebar/containing-unordered-list"
 );
+
+assert_asg_inline!(
+  inline_span_emphasis_unconstrained,
+  "inline/span/emphasis/unconstrained"
+);
+
+assert_asg_inline!(
+  inline_ref_link_with_text, //
+  "inline/ref/link-with-text"
+);
+
+assert_asg_doc!(
+  block_admonition_paragraph_form,
+  "block/admonition/paragraph-form"
+);
+
+assert_asg_doc!(
+  block_break_thematic, //
+  "block/break/thematic"
+);
+
+assert_asg_doc!(
+  block_dlist_multiple_items, //
+  "block/dlist/multiple-items"
+);
+
+assert_asg_doc!(
+  block_list_ordered_multiple_items,
+  "block/list/ordered/multiple-items"
+);
+
+assert_asg_doc!(
+  block_part_part_with_chapter, //
+  "block/part/part-with-chapter"
+);
+
+assert_asg_doc!(
+  block_quote_delimited_attribution,
+  "block/quote/delimited-attribution"
+);
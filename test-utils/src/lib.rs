@@ -391,6 +391,14 @@ macro_rules! html {
   }};
 }
 
+/// xml output compared like `html!`, e.g. for docbook
+#[macro_export]
+macro_rules! xml {
+  ($($args:expr),+$(,)?) => {
+    $crate::html!($($args),+)
+  };
+}
+
 #[macro_export]
 macro_rules! adoc {
   ($s:expr) => {
@@ -536,6 +544,20 @@ macro_rules! expect_eq {
   }};
 }
 
+/// asserts that `$convert`, a backend's `fn(&str) -> String`,
+/// converts the asciidoc `$input` to exactly `$expected`
+#[macro_export]
+macro_rules! assert_converted {
+  ($name:ident, $convert:expr, $input:expr, $expected:expr$(,)?) => {
+    #[test]
+    fn $name() {
+      #[allow(clippy::redundant_closure_call)]
+      let actual = ($convert)($input);
+      $crate::expect_eq!(actual, $expected.to_string(), from: $input);
+    }
+  };
+}
+
 #[macro_export]
 macro_rules! assert_html_contains {
   ($html:expr, $needle:expr, from: $adoc:expr$(,)?) => {{